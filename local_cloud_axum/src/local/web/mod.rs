@@ -1,5 +1,7 @@
 pub use aws_query::AwsQueryBody;
 pub use response::XmlResponse;
+pub use sigv4::{CallerIdentity, CredentialsResolver, Principal, ResolvedCredentials, SigV4Auth, SignatureScope};
 
 pub mod aws_query;
 pub mod response;
+pub mod sigv4;
@@ -0,0 +1,672 @@
+use std::fmt::{Display, Formatter};
+
+use aws_smithy_xml::encode::XmlWriter;
+use axum::async_trait;
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderMap, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+const TERMINATION_STRING: &str = "aws4_request";
+const AMZ_DATE_HEADER: &str = "x-amz-date";
+const DATE_HEADER: &str = "date";
+const CONTENT_SHA256_HEADER: &str = "x-amz-content-sha256";
+const SECURITY_TOKEN_HEADER: &str = "x-amz-security-token";
+const CONTENT_TYPE_HEADER_VALUE: &str = "text/xml; charset=utf-8";
+const MAX_BODY_SIZE: usize = 2 * 1024 * 1024;
+const AMZ_DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+const SCOPE_DATE_FORMAT: &str = "%Y%m%d";
+/// Signed requests are accepted within 15 minutes of the server time, as AWS does, so that they can't be replayed.
+const MAX_REQUEST_TIME_SKEW_MINUTES: i64 = 15;
+
+/// Account used for requests signed with unknown access keys when `trust any key` mode is enabled.
+pub const DEFAULT_ACCOUNT_ID: i64 = 1;
+
+/// Identity of the caller resolved from the access key which was used to sign the request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallerIdentity {
+    pub access_key_id: String,
+    pub account_id: i64,
+    /// Principal owning the access key. `None` when the key is not bound to a principal (e.g. unknown key accepted
+    /// in `trust any key` mode).
+    pub principal: Option<Principal>,
+    /// Region and service of the signature credential scope. `None` for unsigned requests.
+    pub scope: Option<SignatureScope>,
+}
+
+impl CallerIdentity {
+    pub fn new(access_key_id: impl Into<String>, account_id: i64, principal: Option<Principal>) -> Self {
+        CallerIdentity {
+            access_key_id: access_key_id.into(),
+            account_id,
+            principal,
+            scope: None,
+        }
+    }
+
+    pub fn with_scope(mut self, scope: SignatureScope) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignatureScope {
+    pub region: String,
+    pub service: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Principal {
+    /// Unique ID of the principal, e.g. `AIDA...` of an IAM user or `AROA...:<session name>` of an assumed role.
+    pub id: String,
+    pub arn: String,
+}
+
+/// Credentials stored by the service for the access key ID.
+#[derive(Debug, Clone)]
+pub struct ResolvedCredentials {
+    pub secret_access_key: String,
+    /// Session token which must accompany temporary credentials.
+    pub session_token: Option<String>,
+    pub identity: CallerIdentity,
+}
+
+#[async_trait]
+pub trait CredentialsResolver: Clone + Send + Sync + 'static {
+    type Error: Display + Send;
+
+    /// Looks up active credentials by access key ID. Returns `None` if the key is unknown or disabled.
+    async fn resolve(&self, access_key_id: &str) -> Result<Option<ResolvedCredentials>, Self::Error>;
+}
+
+/// State of the [`authenticate`] middleware.
+#[derive(Debug, Clone)]
+pub struct SigV4Auth<R> {
+    resolver: R,
+    trust_any_key: bool,
+    xml_namespace: &'static str,
+}
+
+impl<R> SigV4Auth<R>
+where
+    R: CredentialsResolver,
+{
+    pub fn new(resolver: R, xml_namespace: &'static str) -> Self {
+        SigV4Auth {
+            resolver,
+            trust_any_key: false,
+            xml_namespace,
+        }
+    }
+
+    /// When enabled, signatures are not verified and requests signed with unknown access keys are accepted
+    /// on behalf of the [`DEFAULT_ACCOUNT_ID`] account.
+    pub fn trust_any_key(mut self, trust_any_key: bool) -> Self {
+        self.trust_any_key = trust_any_key;
+        self
+    }
+
+    async fn authenticate(
+        &self, parts: &axum::http::request::Parts, body: &[u8],
+    ) -> Result<CallerIdentity, SigV4Error> {
+        let authorization = match parts.headers.get(AUTHORIZATION) {
+            None if self.trust_any_key => return Ok(CallerIdentity::new("", DEFAULT_ACCOUNT_ID, None)),
+            None => return Err(SigV4Error::MissingAuthenticationToken),
+            Some(value) => value.to_str().map_err(|_err| {
+                SigV4Error::IncompleteSignature("Authorization header is not a valid string.".into())
+            })?,
+        };
+        let authorization = AuthorizationHeader::parse(authorization)?;
+
+        let credentials = self
+            .resolver
+            .resolve(&authorization.access_key_id)
+            .await
+            .map_err(|err| SigV4Error::InternalFailure(err.to_string()))?;
+
+        if self.trust_any_key {
+            let identity = credentials
+                .map(|credentials| credentials.identity)
+                .unwrap_or_else(|| CallerIdentity::new(&authorization.access_key_id, DEFAULT_ACCOUNT_ID, None));
+            return Ok(identity.with_scope(authorization.scope()));
+        }
+
+        let credentials = credentials.ok_or(SigV4Error::InvalidClientTokenId)?;
+        if let Some(session_token) = &credentials.session_token {
+            let provided_token = parts
+                .headers
+                .get(SECURITY_TOKEN_HEADER)
+                .and_then(|value| value.to_str().ok());
+            if provided_token != Some(session_token.as_str()) {
+                return Err(SigV4Error::InvalidClientTokenId);
+            }
+        }
+
+        let payload_hash = payload_hash(&parts.headers, body)?;
+        let canonical_request = canonical_request(parts, &authorization.signed_headers, &payload_hash);
+        let amz_date = header_value(&parts.headers, AMZ_DATE_HEADER)
+            .or_else(|| header_value(&parts.headers, DATE_HEADER))
+            .ok_or_else(|| {
+                SigV4Error::IncompleteSignature(
+                    "Authorization header requires existence of either a 'X-Amz-Date' or a 'Date' header.".into(),
+                )
+            })?;
+        verify_request_time(&amz_date, &authorization.date, Utc::now())?;
+        let string_to_sign = string_to_sign(&amz_date, &authorization.credential_scope(), &canonical_request);
+        let signature = hex::decode(&authorization.signature).map_err(|_err| SigV4Error::SignatureDoesNotMatch)?;
+
+        let signing_key = signing_key(
+            &credentials.secret_access_key,
+            &authorization.date,
+            &authorization.region,
+            &authorization.service,
+        );
+        let mut mac = HmacSha256::new_from_slice(&signing_key).expect("HMAC accepts keys of any size");
+        mac.update(string_to_sign.as_bytes());
+        mac.verify_slice(&signature)
+            .map_err(|_err| SigV4Error::SignatureDoesNotMatch)?;
+
+        Ok(credentials.identity.with_scope(authorization.scope()))
+    }
+}
+
+/// Middleware which verifies AWS Signature Version 4 of the request and puts [`CallerIdentity`] into request extensions.
+pub async fn authenticate<R>(State(auth): State<SigV4Auth<R>>, request: Request, next: Next) -> Response
+where
+    R: CredentialsResolver,
+{
+    let (mut parts, body) = request.into_parts();
+    let body = match axum::body::to_bytes(body, MAX_BODY_SIZE).await {
+        Ok(body) => body,
+        Err(err) => return SigV4Error::InternalFailure(err.to_string()).into_xml_response(auth.xml_namespace),
+    };
+
+    match auth.authenticate(&parts, &body).await {
+        Ok(identity) => {
+            parts.extensions.insert(identity);
+            next.run(Request::from_parts(parts, Body::from(body))).await
+        }
+        Err(err) => {
+            log::warn!("Request authentication failed: {}", err);
+            err.into_xml_response(auth.xml_namespace)
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct AuthorizationHeader {
+    access_key_id: String,
+    date: String,
+    region: String,
+    service: String,
+    signed_headers: Vec<String>,
+    signature: String,
+}
+
+impl AuthorizationHeader {
+    /// Parses value of the header like
+    /// `AWS4-HMAC-SHA256 Credential=AKID/20240101/us-east-1/iam/aws4_request, SignedHeaders=host;x-amz-date, Signature=...`
+    fn parse(value: &str) -> Result<Self, SigV4Error> {
+        let components = value.strip_prefix(ALGORITHM).ok_or_else(|| {
+            SigV4Error::IncompleteSignature(format!("Unsupported signing algorithm. Expected '{}'.", ALGORITHM))
+        })?;
+
+        let mut credential = None;
+        let mut signed_headers = None;
+        let mut signature = None;
+        for component in components.split(',') {
+            match component.trim().split_once('=') {
+                Some(("Credential", value)) => credential = Some(value),
+                Some(("SignedHeaders", value)) => signed_headers = Some(value),
+                Some(("Signature", value)) => signature = Some(value),
+                _ => {}
+            }
+        }
+        let (credential, signed_headers, signature) = match (credential, signed_headers, signature) {
+            (Some(credential), Some(signed_headers), Some(signature)) => (credential, signed_headers, signature),
+            _ => {
+                return Err(SigV4Error::IncompleteSignature(
+                    "Authorization header requires 'Credential', 'SignedHeaders' and 'Signature' parameters.".into(),
+                ))
+            }
+        };
+
+        let scope: Vec<&str> = credential.split('/').collect();
+        if scope.len() != 5 || scope[4] != TERMINATION_STRING || scope[0].is_empty() {
+            return Err(SigV4Error::IncompleteSignature(format!(
+                "Credential should be scoped to a valid region, service and terminated with '{}'.",
+                TERMINATION_STRING
+            )));
+        }
+
+        Ok(AuthorizationHeader {
+            access_key_id: scope[0].to_owned(),
+            date: scope[1].to_owned(),
+            region: scope[2].to_owned(),
+            service: scope[3].to_owned(),
+            signed_headers: signed_headers
+                .split(';')
+                .map(|header| header.to_ascii_lowercase())
+                .collect(),
+            signature: signature.to_owned(),
+        })
+    }
+
+    fn scope(&self) -> SignatureScope {
+        SignatureScope {
+            region: self.region.to_owned(),
+            service: self.service.to_owned(),
+        }
+    }
+
+    fn credential_scope(&self) -> String {
+        format!("{}/{}/{}/{}", self.date, self.region, self.service, TERMINATION_STRING)
+    }
+}
+
+/// Checks that the request is signed within the allowed time window around `now` and that the credential scope is bound
+/// to the date of the request.
+fn verify_request_time(request_date: &str, scope_date: &str, now: DateTime<Utc>) -> Result<(), SigV4Error> {
+    let request_time = NaiveDateTime::parse_from_str(request_date, AMZ_DATE_FORMAT)
+        .map(|time| time.and_utc())
+        .or_else(|_err| DateTime::parse_from_rfc2822(request_date).map(|time| time.with_timezone(&Utc)))
+        .map_err(|_err| {
+            SigV4Error::IncompleteSignature(format!(
+                "Date must be in ISO-8601 'basic format'. Got '{}'. See http://en.wikipedia.org/wiki/ISO_8601",
+                request_date
+            ))
+        })?;
+
+    let request_day = request_time.format(SCOPE_DATE_FORMAT).to_string();
+    if scope_date != request_day {
+        return Err(SigV4Error::CredentialScopeMismatch(format!(
+            "Date in Credential scope does not match YYYYMMDD from ISO-8601 version of date from HTTP: \
+            '{}' != '{}', from '{}'.",
+            scope_date, request_day, request_date
+        )));
+    }
+
+    let max_skew = Duration::minutes(MAX_REQUEST_TIME_SKEW_MINUTES);
+    let format = |time: DateTime<Utc>| time.format(AMZ_DATE_FORMAT).to_string();
+    if request_time < now - max_skew {
+        return Err(SigV4Error::SignatureExpired(format!(
+            "Signature expired: {} is now earlier than {} ({} - {} min.)",
+            format(request_time),
+            format(now - max_skew),
+            format(now),
+            MAX_REQUEST_TIME_SKEW_MINUTES
+        )));
+    }
+    if request_time > now + max_skew {
+        return Err(SigV4Error::SignatureExpired(format!(
+            "Signature not yet current: {} is still later than {} ({} + {} min.)",
+            format(request_time),
+            format(now + max_skew),
+            format(now),
+            MAX_REQUEST_TIME_SKEW_MINUTES
+        )));
+    }
+    Ok(())
+}
+
+fn header_value(headers: &HeaderMap, name: &str) -> Option<String> {
+    let values: Vec<String> = headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .map(|value| value.split_whitespace().collect::<Vec<&str>>().join(" "))
+        .collect();
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.join(","))
+    }
+}
+
+/// Hashes the request body. The `x-amz-content-sha256` header must match the hash if it is present, so that signed
+/// headers of a request can't be replayed with another body.
+fn payload_hash(headers: &HeaderMap, body: &[u8]) -> Result<String, SigV4Error> {
+    let payload_hash = hex::encode(Sha256::digest(body));
+    match header_value(headers, CONTENT_SHA256_HEADER) {
+        Some(provided_hash) if provided_hash != payload_hash => Err(SigV4Error::ContentSha256Mismatch),
+        _ => Ok(payload_hash),
+    }
+}
+
+fn canonical_request(parts: &axum::http::request::Parts, signed_headers: &[String], payload_hash: &str) -> String {
+    let canonical_uri = canonical_uri(parts.uri.path());
+    let canonical_query = canonical_query_string(parts.uri.query().unwrap_or_default());
+    let canonical_headers: String = signed_headers
+        .iter()
+        .map(|name| format!("{}:{}\n", name, header_value(&parts.headers, name).unwrap_or_default()))
+        .collect();
+
+    format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        parts.method.as_str(),
+        canonical_uri,
+        canonical_query,
+        canonical_headers,
+        signed_headers.join(";"),
+        payload_hash
+    )
+}
+
+fn canonical_uri(path: &str) -> String {
+    if path.is_empty() {
+        return String::from("/");
+    }
+    path.split('/')
+        .map(|segment| urlencoding::encode(segment).into_owned())
+        .collect::<Vec<String>>()
+        .join("/")
+}
+
+fn canonical_query_string(query: &str) -> String {
+    let mut params: Vec<(String, String)> = query
+        .split('&')
+        .filter(|param| !param.is_empty())
+        .map(|param| {
+            let (key, value) = param.split_once('=').unwrap_or((param, ""));
+            (uri_encode(key), uri_encode(value))
+        })
+        .collect();
+    params.sort();
+    params
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<String>>()
+        .join("&")
+}
+
+fn uri_encode(value: &str) -> String {
+    let decoded = urlencoding::decode(value)
+        .map(|value| value.into_owned())
+        .unwrap_or_else(|_err| value.to_owned());
+    urlencoding::encode(&decoded).into_owned()
+}
+
+fn string_to_sign(amz_date: &str, credential_scope: &str, canonical_request: &str) -> String {
+    format!(
+        "{}\n{}\n{}\n{}",
+        ALGORITHM,
+        amz_date,
+        credential_scope,
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    )
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn signing_key(secret_access_key: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let date_key = hmac_sha256(format!("AWS4{}", secret_access_key).as_bytes(), date);
+    let region_key = hmac_sha256(&date_key, region);
+    let service_key = hmac_sha256(&region_key, service);
+    hmac_sha256(&service_key, TERMINATION_STRING)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SigV4Error {
+    MissingAuthenticationToken,
+    IncompleteSignature(String),
+    InvalidClientTokenId,
+    SignatureDoesNotMatch,
+    /// The request is signed too long before or after the current time.
+    SignatureExpired(String),
+    /// The date of the credential scope differs from the date of the request.
+    CredentialScopeMismatch(String),
+    /// The `x-amz-content-sha256` header doesn't match the hash of the request body.
+    ContentSha256Mismatch,
+    InternalFailure(String),
+}
+
+impl SigV4Error {
+    pub fn code(&self) -> &'static str {
+        match self {
+            SigV4Error::MissingAuthenticationToken => "MissingAuthenticationToken",
+            SigV4Error::IncompleteSignature(_) => "IncompleteSignature",
+            SigV4Error::InvalidClientTokenId => "InvalidClientTokenId",
+            SigV4Error::SignatureDoesNotMatch => "SignatureDoesNotMatch",
+            SigV4Error::SignatureExpired(_) => "SignatureDoesNotMatch",
+            SigV4Error::CredentialScopeMismatch(_) => "SignatureDoesNotMatch",
+            SigV4Error::ContentSha256Mismatch => "XAmzContentSHA256Mismatch",
+            SigV4Error::InternalFailure(_) => "InternalFailure",
+        }
+    }
+
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            SigV4Error::IncompleteSignature(_) => StatusCode::BAD_REQUEST,
+            SigV4Error::ContentSha256Mismatch => StatusCode::BAD_REQUEST,
+            SigV4Error::InternalFailure(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            _ => StatusCode::FORBIDDEN,
+        }
+    }
+
+    /// Writes the error in AWS Query XML format.
+    pub fn into_xml_response(self, xml_namespace: &str) -> Response {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+        let mut doc = XmlWriter::new(&mut out);
+        let mut error_response_tag = doc.start_el("ErrorResponse").write_ns(xml_namespace, None).finish();
+        let mut error_tag = error_response_tag.start_el("Error").finish();
+        local_cloud_xml::write_tag_with_value(&mut error_tag, "Code", Some(self.code()));
+        local_cloud_xml::write_tag_with_value(&mut error_tag, "Message", Some(self.to_string()));
+        error_tag.finish();
+        local_cloud_xml::write_request_metadata_tag(
+            &mut error_response_tag,
+            "ResponseMetadata",
+            "RequestId",
+            Uuid::new_v4().to_string(),
+        );
+        error_response_tag.finish();
+
+        let mut response = (self.status_code(), out).into_response();
+        response
+            .headers_mut()
+            .insert(axum::http::header::CONTENT_TYPE, axum::http::HeaderValue::from_static(CONTENT_TYPE_HEADER_VALUE));
+        response
+    }
+}
+
+impl Display for SigV4Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SigV4Error::MissingAuthenticationToken => write!(f, "Request is missing Authentication Token"),
+            SigV4Error::IncompleteSignature(msg) => write!(f, "{}", msg),
+            SigV4Error::InvalidClientTokenId => write!(f, "The security token included in the request is invalid."),
+            SigV4Error::SignatureDoesNotMatch => write!(
+                f,
+                "The request signature we calculated does not match the signature you provided. \
+                Check your AWS Secret Access Key and signing method."
+            ),
+            SigV4Error::SignatureExpired(msg) => write!(f, "{}", msg),
+            SigV4Error::CredentialScopeMismatch(msg) => write!(f, "{}", msg),
+            SigV4Error::ContentSha256Mismatch => {
+                write!(f, "The provided 'x-amz-content-sha256' header does not match what was computed.")
+            }
+            SigV4Error::InternalFailure(msg) => write!(f, "Failed to authenticate request: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SigV4Error {}
+
+#[cfg(test)]
+mod tests {
+    use axum::async_trait;
+    use axum::body::Body;
+    use axum::extract::Request;
+    use chrono::{TimeZone, Utc};
+    use sha2::{Digest, Sha256};
+
+    use super::{
+        canonical_request, payload_hash, signing_key, string_to_sign, verify_request_time, AuthorizationHeader,
+        CallerIdentity, CredentialsResolver, HmacSha256, ResolvedCredentials, SigV4Auth, SigV4Error,
+    };
+    use hmac::Mac;
+
+    // "get-vanilla" case from the AWS Signature Version 4 test suite
+    const SECRET_ACCESS_KEY: &str = "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY";
+    const AUTHORIZATION: &str = "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+        SignedHeaders=host;x-amz-date, \
+        Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31";
+
+    #[test]
+    fn parse_authorization_header() {
+        let header = AuthorizationHeader::parse(AUTHORIZATION).unwrap();
+
+        assert_eq!(header.access_key_id, "AKIDEXAMPLE");
+        assert_eq!(header.credential_scope(), "20150830/us-east-1/service/aws4_request");
+        assert_eq!(header.signed_headers, vec!["host", "x-amz-date"]);
+    }
+
+    #[test]
+    fn parse_incomplete_authorization_header() {
+        assert!(AuthorizationHeader::parse("AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1").is_err());
+        assert!(AuthorizationHeader::parse("AWS3 Credential=AKIDEXAMPLE").is_err());
+    }
+
+    #[test]
+    fn calculate_signature() {
+        let header = AuthorizationHeader::parse(AUTHORIZATION).unwrap();
+        let request = Request::builder()
+            .method("GET")
+            .uri("/")
+            .header("Host", "example.amazonaws.com")
+            .header("X-Amz-Date", "20150830T123600Z")
+            .body(Body::empty())
+            .unwrap();
+        let (parts, _body) = request.into_parts();
+
+        let payload_hash = payload_hash(&parts.headers, b"").unwrap();
+        let canonical_request = canonical_request(&parts, &header.signed_headers, &payload_hash);
+        let string_to_sign = string_to_sign("20150830T123600Z", &header.credential_scope(), &canonical_request);
+        let key = signing_key(SECRET_ACCESS_KEY, &header.date, &header.region, &header.service);
+        let mut mac = HmacSha256::new_from_slice(&key).unwrap();
+        mac.update(string_to_sign.as_bytes());
+
+        assert_eq!(hex::encode(mac.finalize().into_bytes()), header.signature);
+    }
+
+    #[test]
+    fn verify_request_time_within_window() {
+        let now = Utc.with_ymd_and_hms(2015, 8, 30, 12, 36, 0).unwrap();
+
+        assert_eq!(Ok(()), verify_request_time("20150830T123600Z", "20150830", now));
+        assert_eq!(Ok(()), verify_request_time("20150830T122200Z", "20150830", now));
+        assert_eq!(Ok(()), verify_request_time("20150830T125000Z", "20150830", now));
+        assert_eq!(Ok(()), verify_request_time("Sun, 30 Aug 2015 12:36:00 GMT", "20150830", now));
+    }
+
+    #[test]
+    fn verify_expired_request_time() {
+        let now = Utc.with_ymd_and_hms(2015, 8, 30, 12, 36, 0).unwrap();
+
+        assert_eq!(
+            Err(SigV4Error::SignatureExpired(
+                "Signature expired: 20150830T122000Z is now earlier than 20150830T122100Z (20150830T123600Z - 15 min.)"
+                    .into()
+            )),
+            verify_request_time("20150830T122000Z", "20150830", now)
+        );
+        assert!(matches!(
+            verify_request_time("20150830T130000Z", "20150830", now),
+            Err(SigV4Error::SignatureExpired(_))
+        ));
+    }
+
+    #[test]
+    fn verify_request_time_of_other_scope_date() {
+        let now = Utc.with_ymd_and_hms(2015, 8, 30, 12, 36, 0).unwrap();
+
+        assert!(matches!(
+            verify_request_time("20150830T123600Z", "20150829", now),
+            Err(SigV4Error::CredentialScopeMismatch(_))
+        ));
+        assert!(matches!(verify_request_time("2015-08-30", "20150830", now), Err(SigV4Error::IncompleteSignature(_))));
+    }
+
+    #[derive(Debug, Clone)]
+    struct StaticResolver;
+
+    #[async_trait]
+    impl CredentialsResolver for StaticResolver {
+        type Error = String;
+
+        async fn resolve(&self, access_key_id: &str) -> Result<Option<ResolvedCredentials>, Self::Error> {
+            Ok(Some(ResolvedCredentials {
+                secret_access_key: SECRET_ACCESS_KEY.to_owned(),
+                session_token: None,
+                identity: CallerIdentity::new(access_key_id, 1, None),
+            }))
+        }
+    }
+
+    /// Signs a request with the body and the `x-amz-content-sha256` header of `signed_body`, then sends `body` instead.
+    async fn authenticate_with_body(signed_body: &str, body: &str) -> Result<CallerIdentity, SigV4Error> {
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let content_sha256 = hex::encode(Sha256::digest(signed_body.as_bytes()));
+        let signed_headers = vec![
+            "host".to_owned(),
+            "x-amz-content-sha256".to_owned(),
+            "x-amz-date".to_owned(),
+        ];
+        let request = |body: &str, authorization: &str| {
+            Request::builder()
+                .method("POST")
+                .uri("/")
+                .header("Host", "localhost")
+                .header("X-Amz-Date", &amz_date)
+                .header("X-Amz-Content-Sha256", &content_sha256)
+                .header("Authorization", authorization)
+                .body(Body::from(body.to_owned()))
+                .unwrap()
+                .into_parts()
+                .0
+        };
+
+        let canonical_request = canonical_request(&request(signed_body, ""), &signed_headers, &content_sha256);
+        let credential_scope = format!("{date}/us-east-1/iam/aws4_request");
+        let string_to_sign = string_to_sign(&amz_date, &credential_scope, &canonical_request);
+        let mut mac = HmacSha256::new_from_slice(&signing_key(SECRET_ACCESS_KEY, &date, "us-east-1", "iam")).unwrap();
+        mac.update(string_to_sign.as_bytes());
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/{credential_scope}, SignedHeaders={}, Signature={}",
+            signed_headers.join(";"),
+            hex::encode(mac.finalize().into_bytes())
+        );
+
+        let auth = SigV4Auth::new(StaticResolver, "");
+        auth.authenticate(&request(body, &authorization), body.as_bytes()).await
+    }
+
+    #[tokio::test]
+    async fn authenticate_signed_body() {
+        let result = authenticate_with_body("Action=ListUsers", "Action=ListUsers").await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn authenticate_replayed_signature_with_other_body() {
+        let result = authenticate_with_body("Action=ListUsers", "Action=DeleteUser&UserName=admin").await;
+
+        assert_eq!(Err(SigV4Error::ContentSha256Mismatch), result);
+    }
+}
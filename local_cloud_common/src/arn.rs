@@ -0,0 +1,46 @@
+/// Builds ARN of a role session like `arn:aws:sts::123456789012:assumed-role/name/session` from ARN of the role.
+/// Returns `None` if the ARN doesn't identify a role.
+pub fn assumed_role_arn(role_arn: &str, session_name: &str) -> Option<String> {
+    let parts: Vec<&str> = role_arn.splitn(6, ':').collect();
+    let (partition, account_id, resource) = match parts[..] {
+        ["arn", partition, _service, "", account_id, resource] => (partition, account_id, resource),
+        _ => return None,
+    };
+    let role_name = resource.strip_prefix("role/")?.rsplit('/').next()?;
+    Some(format!("arn:{partition}:sts::{account_id}:assumed-role/{role_name}/{session_name}"))
+}
+
+/// Extracts the role name from ARN of a role session like `arn:aws:sts::123456789012:assumed-role/name/session`.
+pub fn assumed_role_name(arn: &str) -> Option<&str> {
+    let resource = arn.splitn(6, ':').nth(5)?;
+    let mut parts = resource.split('/');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some("assumed-role"), Some(role_name), Some(_session_name)) => Some(role_name),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assumed_role_arn() {
+        assert_eq!(
+            assumed_role_arn("arn:aws:iam::000000000001:role/app", "session-1").as_deref(),
+            Some("arn:aws:sts::000000000001:assumed-role/app/session-1")
+        );
+        assert_eq!(
+            assumed_role_arn("arn:aws:iam::000000000001:role/division/app", "session-1").as_deref(),
+            Some("arn:aws:sts::000000000001:assumed-role/app/session-1")
+        );
+        assert_eq!(assumed_role_arn("arn:aws:iam::000000000001:user/app", "session-1"), None);
+    }
+
+    #[test]
+    fn test_assumed_role_name() {
+        assert_eq!(assumed_role_name("arn:aws:sts::000000000001:assumed-role/app/session-1"), Some("app"));
+        assert_eq!(assumed_role_name("arn:aws:iam::000000000001:user/app"), None);
+        assert_eq!(assumed_role_name("arn:aws:sts::000000000001:assumed-role/app"), None);
+    }
+}
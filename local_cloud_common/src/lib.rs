@@ -1,5 +1,7 @@
+pub mod arn;
 pub mod config;
 pub mod naming;
 pub mod network;
 pub mod random;
+pub mod totp;
 pub mod types;
@@ -0,0 +1,82 @@
+//! Time-based one-time passwords (RFC 6238) as they are generated by virtual MFA devices: HMAC-SHA1, 30 seconds time
+//! step and 6 digits codes.
+
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+
+pub const TIME_STEP_SECONDS: i64 = 30;
+pub const CODE_DIGITS: u32 = 6;
+
+/// Returns the number of the time step which contains the given UNIX time.
+pub fn time_step(unix_seconds: i64) -> i64 {
+    unix_seconds.div_euclid(TIME_STEP_SECONDS)
+}
+
+/// Generates the code of the device with the given seed for the time step (RFC 4226 HOTP with the time step counter).
+pub fn generate(seed: &[u8], time_step: i64) -> String {
+    let mut mac = Hmac::<Sha1>::new_from_slice(seed).expect("HMAC accepts keys of any length");
+    mac.update(&time_step.to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        hash[offset] & 0x7f,
+        hash[offset + 1],
+        hash[offset + 2],
+        hash[offset + 3],
+    ]);
+    format!("{:0width$}", binary % 10u32.pow(CODE_DIGITS), width = CODE_DIGITS as usize)
+}
+
+/// Looks for the time step of the code in the window of `skew_steps` steps around `time_step`. Returns `None` if the
+/// code doesn't match any time step of the window.
+pub fn find_time_step(seed: &[u8], code: &str, time_step: i64, skew_steps: i64) -> Option<i64> {
+    (time_step - skew_steps..=time_step + skew_steps).find(|step| generate(seed, *step) == code)
+}
+
+/// Verifies two consecutive codes of the device, as it is required to enable or to resynchronize a device. Returns
+/// the time step of the second code.
+pub fn verify_consecutive(seed: &[u8], code1: &str, code2: &str, time_step: i64, skew_steps: i64) -> Option<i64> {
+    let first_step = find_time_step(seed, code1, time_step, skew_steps)?;
+    if generate(seed, first_step + 1) == code2 {
+        Some(first_step + 1)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Seed of RFC 6238 test vectors for SHA1.
+    const RFC_SEED: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn test_generate_rfc_vectors() {
+        // RFC 6238 test vectors are 8 digits codes, so only the last 6 digits are compared
+        assert_eq!(generate(RFC_SEED, time_step(59)), "287082");
+        assert_eq!(generate(RFC_SEED, time_step(1111111109)), "081804");
+        assert_eq!(generate(RFC_SEED, time_step(1234567890)), "005924");
+        assert_eq!(generate(RFC_SEED, time_step(2000000000)), "279037");
+    }
+
+    #[test]
+    fn test_find_time_step_within_skew() {
+        let step = time_step(1234567890);
+        let code = generate(RFC_SEED, step - 1);
+
+        assert_eq!(find_time_step(RFC_SEED, &code, step, 1), Some(step - 1));
+        assert_eq!(find_time_step(RFC_SEED, &code, step + 1, 1), None);
+    }
+
+    #[test]
+    fn test_verify_consecutive() {
+        let step = time_step(1234567890);
+        let code1 = generate(RFC_SEED, step);
+        let code2 = generate(RFC_SEED, step + 1);
+
+        assert_eq!(verify_consecutive(RFC_SEED, &code1, &code2, step, 1), Some(step + 1));
+        assert_eq!(verify_consecutive(RFC_SEED, &code2, &code1, step, 1), None);
+    }
+}
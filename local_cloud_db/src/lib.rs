@@ -11,6 +11,13 @@ pub struct LocalDb(Pool<Sqlite>);
 
 impl LocalDb {
     pub async fn new(database_url: &str, migrator: &Migrator) -> Result<Self, sqlx::Error> {
+        let db = Self::connect(database_url).await?;
+        migrator.run(&db.0).await.expect("Failed to apply migrations to DB");
+        Ok(db)
+    }
+
+    /// Connects to the database without applying migrations, e.g. to the database owned by another service.
+    pub async fn connect(database_url: &str) -> Result<Self, sqlx::Error> {
         if !Sqlite::database_exists(database_url).await.unwrap_or(false) {
             info!("No Database file found... Creating Database {}", &database_url);
             match Sqlite::create_database(database_url).await {
@@ -23,7 +30,6 @@ impl LocalDb {
         let pool_options =
             SqliteConnectOptions::from_str(database_url).expect("failed to configure options for DB connection");
         let db_pool = SqlitePool::connect_with(pool_options).await?;
-        migrator.run(&db_pool).await.expect("Failed to apply migrations to DB");
 
         Ok(LocalDb(db_pool))
     }
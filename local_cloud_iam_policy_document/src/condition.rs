@@ -0,0 +1,508 @@
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+
+use crate::evaluator::RequestContext;
+use crate::matcher::{arn_match, ip_address_match, wildcard_match};
+use crate::types::Condition;
+
+const FOR_ANY_VALUE_PREFIX: &str = "ForAnyValue:";
+const FOR_ALL_VALUES_PREFIX: &str = "ForAllValues:";
+const IF_EXISTS_POSTFIX: &str = "IfExists";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConditionOperatorKind {
+    StringEquals,
+    StringNotEquals,
+    StringEqualsIgnoreCase,
+    StringNotEqualsIgnoreCase,
+    StringLike,
+    StringNotLike,
+    NumericEquals,
+    NumericNotEquals,
+    NumericLessThan,
+    NumericLessThanEquals,
+    NumericGreaterThan,
+    NumericGreaterThanEquals,
+    DateEquals,
+    DateNotEquals,
+    DateLessThan,
+    DateLessThanEquals,
+    DateGreaterThan,
+    DateGreaterThanEquals,
+    Bool,
+    BinaryEquals,
+    IpAddress,
+    NotIpAddress,
+    ArnEquals,
+    ArnLike,
+    ArnNotEquals,
+    ArnNotLike,
+    Null,
+}
+
+impl ConditionOperatorKind {
+    const ALL: [ConditionOperatorKind; 27] = [
+        ConditionOperatorKind::StringEquals,
+        ConditionOperatorKind::StringNotEquals,
+        ConditionOperatorKind::StringEqualsIgnoreCase,
+        ConditionOperatorKind::StringNotEqualsIgnoreCase,
+        ConditionOperatorKind::StringLike,
+        ConditionOperatorKind::StringNotLike,
+        ConditionOperatorKind::NumericEquals,
+        ConditionOperatorKind::NumericNotEquals,
+        ConditionOperatorKind::NumericLessThan,
+        ConditionOperatorKind::NumericLessThanEquals,
+        ConditionOperatorKind::NumericGreaterThan,
+        ConditionOperatorKind::NumericGreaterThanEquals,
+        ConditionOperatorKind::DateEquals,
+        ConditionOperatorKind::DateNotEquals,
+        ConditionOperatorKind::DateLessThan,
+        ConditionOperatorKind::DateLessThanEquals,
+        ConditionOperatorKind::DateGreaterThan,
+        ConditionOperatorKind::DateGreaterThanEquals,
+        ConditionOperatorKind::Bool,
+        ConditionOperatorKind::BinaryEquals,
+        ConditionOperatorKind::IpAddress,
+        ConditionOperatorKind::NotIpAddress,
+        ConditionOperatorKind::ArnEquals,
+        ConditionOperatorKind::ArnLike,
+        ConditionOperatorKind::ArnNotEquals,
+        ConditionOperatorKind::ArnNotLike,
+        ConditionOperatorKind::Null,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConditionOperatorKind::StringEquals => "StringEquals",
+            ConditionOperatorKind::StringNotEquals => "StringNotEquals",
+            ConditionOperatorKind::StringEqualsIgnoreCase => "StringEqualsIgnoreCase",
+            ConditionOperatorKind::StringNotEqualsIgnoreCase => "StringNotEqualsIgnoreCase",
+            ConditionOperatorKind::StringLike => "StringLike",
+            ConditionOperatorKind::StringNotLike => "StringNotLike",
+            ConditionOperatorKind::NumericEquals => "NumericEquals",
+            ConditionOperatorKind::NumericNotEquals => "NumericNotEquals",
+            ConditionOperatorKind::NumericLessThan => "NumericLessThan",
+            ConditionOperatorKind::NumericLessThanEquals => "NumericLessThanEquals",
+            ConditionOperatorKind::NumericGreaterThan => "NumericGreaterThan",
+            ConditionOperatorKind::NumericGreaterThanEquals => "NumericGreaterThanEquals",
+            ConditionOperatorKind::DateEquals => "DateEquals",
+            ConditionOperatorKind::DateNotEquals => "DateNotEquals",
+            ConditionOperatorKind::DateLessThan => "DateLessThan",
+            ConditionOperatorKind::DateLessThanEquals => "DateLessThanEquals",
+            ConditionOperatorKind::DateGreaterThan => "DateGreaterThan",
+            ConditionOperatorKind::DateGreaterThanEquals => "DateGreaterThanEquals",
+            ConditionOperatorKind::Bool => "Bool",
+            ConditionOperatorKind::BinaryEquals => "BinaryEquals",
+            ConditionOperatorKind::IpAddress => "IpAddress",
+            ConditionOperatorKind::NotIpAddress => "NotIpAddress",
+            ConditionOperatorKind::ArnEquals => "ArnEquals",
+            ConditionOperatorKind::ArnLike => "ArnLike",
+            ConditionOperatorKind::ArnNotEquals => "ArnNotEquals",
+            ConditionOperatorKind::ArnNotLike => "ArnNotLike",
+            ConditionOperatorKind::Null => "Null",
+        }
+    }
+
+    /// Negated operators match when none of the policy values match the context value.
+    fn is_negated(&self) -> bool {
+        matches!(
+            self,
+            ConditionOperatorKind::StringNotEquals
+                | ConditionOperatorKind::StringNotEqualsIgnoreCase
+                | ConditionOperatorKind::StringNotLike
+                | ConditionOperatorKind::NumericNotEquals
+                | ConditionOperatorKind::DateNotEquals
+                | ConditionOperatorKind::NotIpAddress
+                | ConditionOperatorKind::ArnNotEquals
+                | ConditionOperatorKind::ArnNotLike
+        )
+    }
+
+    /// Policy variables (`${aws:username}`) are supported by string and ARN operators only.
+    fn supports_variables(&self) -> bool {
+        matches!(
+            self,
+            ConditionOperatorKind::StringEquals
+                | ConditionOperatorKind::StringNotEquals
+                | ConditionOperatorKind::StringEqualsIgnoreCase
+                | ConditionOperatorKind::StringNotEqualsIgnoreCase
+                | ConditionOperatorKind::StringLike
+                | ConditionOperatorKind::StringNotLike
+                | ConditionOperatorKind::ArnEquals
+                | ConditionOperatorKind::ArnLike
+                | ConditionOperatorKind::ArnNotEquals
+                | ConditionOperatorKind::ArnNotLike
+        )
+    }
+
+    /// Policy values of string and ARN "like" operators may contain wildcards.
+    fn uses_wildcards(&self) -> bool {
+        matches!(
+            self,
+            ConditionOperatorKind::StringLike
+                | ConditionOperatorKind::StringNotLike
+                | ConditionOperatorKind::ArnEquals
+                | ConditionOperatorKind::ArnLike
+                | ConditionOperatorKind::ArnNotEquals
+                | ConditionOperatorKind::ArnNotLike
+        )
+    }
+
+    /// Compares a single context value with a single policy value ignoring negation of the operator.
+    fn compare(&self, policy_value: &str, context_value: &str) -> bool {
+        match self {
+            ConditionOperatorKind::StringEquals | ConditionOperatorKind::StringNotEquals => {
+                policy_value == context_value
+            }
+            ConditionOperatorKind::StringEqualsIgnoreCase | ConditionOperatorKind::StringNotEqualsIgnoreCase => {
+                policy_value.to_lowercase() == context_value.to_lowercase()
+            }
+            ConditionOperatorKind::StringLike | ConditionOperatorKind::StringNotLike => {
+                wildcard_match(policy_value, context_value, false)
+            }
+            ConditionOperatorKind::NumericEquals | ConditionOperatorKind::NumericNotEquals => {
+                compare_numbers(policy_value, context_value, |context, policy| context == policy)
+            }
+            ConditionOperatorKind::NumericLessThan => {
+                compare_numbers(policy_value, context_value, |context, policy| context < policy)
+            }
+            ConditionOperatorKind::NumericLessThanEquals => {
+                compare_numbers(policy_value, context_value, |context, policy| context <= policy)
+            }
+            ConditionOperatorKind::NumericGreaterThan => {
+                compare_numbers(policy_value, context_value, |context, policy| context > policy)
+            }
+            ConditionOperatorKind::NumericGreaterThanEquals => {
+                compare_numbers(policy_value, context_value, |context, policy| context >= policy)
+            }
+            ConditionOperatorKind::DateEquals | ConditionOperatorKind::DateNotEquals => {
+                compare_dates(policy_value, context_value, |context, policy| context == policy)
+            }
+            ConditionOperatorKind::DateLessThan => {
+                compare_dates(policy_value, context_value, |context, policy| context < policy)
+            }
+            ConditionOperatorKind::DateLessThanEquals => {
+                compare_dates(policy_value, context_value, |context, policy| context <= policy)
+            }
+            ConditionOperatorKind::DateGreaterThan => {
+                compare_dates(policy_value, context_value, |context, policy| context > policy)
+            }
+            ConditionOperatorKind::DateGreaterThanEquals => {
+                compare_dates(policy_value, context_value, |context, policy| context >= policy)
+            }
+            ConditionOperatorKind::Bool => policy_value.eq_ignore_ascii_case(context_value),
+            ConditionOperatorKind::BinaryEquals => policy_value == context_value,
+            ConditionOperatorKind::IpAddress | ConditionOperatorKind::NotIpAddress => {
+                ip_address_match(policy_value, context_value)
+            }
+            ConditionOperatorKind::ArnEquals
+            | ConditionOperatorKind::ArnLike
+            | ConditionOperatorKind::ArnNotEquals
+            | ConditionOperatorKind::ArnNotLike => arn_match(policy_value, context_value),
+            // 'Null' checks existence of the key and is handled separately
+            ConditionOperatorKind::Null => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetOperator {
+    ForAnyValue,
+    ForAllValues,
+}
+
+/// Condition operator with optional set operator prefix and `IfExists` postfix, e.g. `ForAnyValue:StringLikeIfExists`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConditionOperator {
+    pub kind: ConditionOperatorKind,
+    pub set_operator: Option<SetOperator>,
+    pub if_exists: bool,
+}
+
+impl FromStr for ConditionOperator {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (set_operator, name) = if let Some(name) = value.strip_prefix(FOR_ANY_VALUE_PREFIX) {
+            (Some(SetOperator::ForAnyValue), name)
+        } else if let Some(name) = value.strip_prefix(FOR_ALL_VALUES_PREFIX) {
+            (Some(SetOperator::ForAllValues), name)
+        } else {
+            (None, value)
+        };
+        let (if_exists, name) = match name.strip_suffix(IF_EXISTS_POSTFIX) {
+            Some(name) => (true, name),
+            None => (false, name),
+        };
+
+        let kind = ConditionOperatorKind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == name)
+            .ok_or_else(|| format!("Unsupported condition operator '{}'.", value))?;
+        if kind == ConditionOperatorKind::Null && (if_exists || set_operator.is_some()) {
+            return Err(format!("Condition operator 'Null' can't be qualified: '{}'.", value));
+        }
+        Ok(ConditionOperator {
+            kind,
+            set_operator,
+            if_exists,
+        })
+    }
+}
+
+impl Display for ConditionOperator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let prefix = match self.set_operator {
+            None => "",
+            Some(SetOperator::ForAnyValue) => FOR_ANY_VALUE_PREFIX,
+            Some(SetOperator::ForAllValues) => FOR_ALL_VALUES_PREFIX,
+        };
+        let postfix = if self.if_exists { IF_EXISTS_POSTFIX } else { "" };
+        write!(f, "{}{}{}", prefix, self.kind.as_str(), postfix)
+    }
+}
+
+impl ConditionOperator {
+    /// Evaluates a condition key with policy values against values of the key in the request context.
+    fn matches(&self, policy_values: &[String], context_values: Option<&Vec<String>>) -> bool {
+        let context_values = context_values.filter(|values| !values.is_empty());
+
+        if self.kind == ConditionOperatorKind::Null {
+            let is_absent = context_values.is_none();
+            return policy_values
+                .iter()
+                .any(|value| value.eq_ignore_ascii_case("true") == is_absent);
+        }
+
+        let context_values = match context_values {
+            Some(context_values) => context_values,
+            None => {
+                return match self.set_operator {
+                    Some(SetOperator::ForAllValues) => true,
+                    Some(SetOperator::ForAnyValue) => false,
+                    None => self.if_exists || self.kind.is_negated(),
+                }
+            }
+        };
+
+        let matches_any_policy_value = |context_value: &String| {
+            policy_values
+                .iter()
+                .any(|policy_value| self.kind.compare(policy_value, context_value))
+        };
+        let negated = self.kind.is_negated();
+        match self.set_operator {
+            Some(SetOperator::ForAllValues) => context_values
+                .iter()
+                .all(|context_value| matches_any_policy_value(context_value) != negated),
+            Some(SetOperator::ForAnyValue) => context_values
+                .iter()
+                .any(|context_value| matches_any_policy_value(context_value) != negated),
+            None if negated => !context_values.iter().any(matches_any_policy_value),
+            None => context_values.iter().any(matches_any_policy_value),
+        }
+    }
+}
+
+/// Evaluates all condition blocks of the statement. Blocks and keys are combined with logical AND, values of a key -
+/// with logical OR. Keys which are required by the condition but absent in the request are added to `missing_keys`.
+pub(crate) fn evaluate_condition(
+    condition: &Condition, request: &RequestContext, variables_enabled: bool, missing_keys: &mut BTreeSet<String>,
+) -> bool {
+    let mut result = true;
+    for (name, block) in condition.operators() {
+        let operator: ConditionOperator = match name.parse() {
+            Ok(operator) => operator,
+            Err(_) => return false,
+        };
+        for (key, values) in block {
+            let context_values = request.context_values(key);
+            let policy_values: Vec<String> = values
+                .values()
+                .into_iter()
+                .filter_map(|value| {
+                    if !variables_enabled || !operator.kind.supports_variables() {
+                        Some(value.to_owned())
+                    } else if operator.kind.uses_wildcards() {
+                        request.resolve_variables_as_pattern(value)
+                    } else {
+                        request.resolve_variables(value)
+                    }
+                })
+                .collect();
+            let matches = operator.matches(&policy_values, context_values);
+            // a missing key is reported only if the condition fails without it, e.g. negated operators match
+            if !matches && context_values.is_none() && operator.kind != ConditionOperatorKind::Null {
+                missing_keys.insert(key.to_owned());
+            }
+            result = result && matches;
+        }
+    }
+    result
+}
+
+fn compare_numbers(policy_value: &str, context_value: &str, cmp: impl Fn(f64, f64) -> bool) -> bool {
+    match (context_value.trim().parse::<f64>(), policy_value.trim().parse::<f64>()) {
+        (Ok(context), Ok(policy)) => cmp(context, policy),
+        _ => false,
+    }
+}
+
+fn compare_dates(policy_value: &str, context_value: &str, cmp: impl Fn(i64, i64) -> bool) -> bool {
+    match (parse_date(context_value), parse_date(policy_value)) {
+        (Some(context), Some(policy)) => cmp(context, policy),
+        _ => false,
+    }
+}
+
+/// Parses date in ISO 8601 format or epoch time (in seconds). Returns epoch time in milliseconds.
+fn parse_date(value: &str) -> Option<i64> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<i64>() {
+        return Some(seconds * 1000);
+    }
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(value) {
+        return Some(date_time.timestamp_millis());
+    }
+    if let Ok(date_time) = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S") {
+        return Some(date_time.and_utc().timestamp_millis());
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|date_time| date_time.and_utc().timestamp_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::condition::{evaluate_condition, ConditionOperator, ConditionOperatorKind, SetOperator};
+    use crate::evaluator::RequestContext;
+    use crate::types::Condition;
+
+    fn evaluate(condition_json: &str, request: &RequestContext) -> (bool, BTreeSet<String>) {
+        let condition: Condition = serde_json::from_str(condition_json).unwrap();
+        let mut missing_keys = BTreeSet::new();
+        let result = evaluate_condition(&condition, request, true, &mut missing_keys);
+        (result, missing_keys)
+    }
+
+    fn request() -> RequestContext {
+        RequestContext::new("s3:GetObject", "arn:aws:s3:::bucket/key")
+    }
+
+    #[test]
+    fn parse_condition_operator() {
+        let operator: ConditionOperator = "ForAnyValue:StringLikeIfExists".parse().unwrap();
+        assert_eq!(operator.kind, ConditionOperatorKind::StringLike);
+        assert_eq!(operator.set_operator, Some(SetOperator::ForAnyValue));
+        assert!(operator.if_exists);
+        assert_eq!(operator.to_string(), "ForAnyValue:StringLikeIfExists");
+
+        assert!("StringMatches".parse::<ConditionOperator>().is_err());
+        assert!("NullIfExists".parse::<ConditionOperator>().is_err());
+    }
+
+    #[test]
+    fn evaluate_string_conditions() {
+        let request = request()
+            .with_context_values("aws:username", vec!["Bob".to_owned()])
+            .with_context_values("s3:prefix", vec!["home/Bob/docs".to_owned()]);
+
+        assert!(evaluate(r#"{"StringEquals": {"aws:username": "Bob"}}"#, &request).0);
+        assert!(evaluate(r#"{"StringEquals": {"AWS:UserName": ["Alice", "Bob"]}}"#, &request).0);
+        assert!(!evaluate(r#"{"StringEquals": {"aws:username": "bob"}}"#, &request).0);
+        assert!(evaluate(r#"{"StringEqualsIgnoreCase": {"aws:username": "bob"}}"#, &request).0);
+        assert!(evaluate(r#"{"StringNotEquals": {"aws:username": "Alice"}}"#, &request).0);
+        assert!(!evaluate(r#"{"StringNotEqualsIgnoreCase": {"aws:username": "BOB"}}"#, &request).0);
+        assert!(evaluate(r#"{"StringLike": {"s3:prefix": "home/${aws:username}/*"}}"#, &request).0);
+        assert!(!evaluate(r#"{"StringNotLike": {"s3:prefix": "home/*"}}"#, &request).0);
+        // all keys must match
+        assert!(
+            !evaluate(r#"{"StringEquals": {"aws:username": "Bob"}, "StringLike": {"s3:prefix": "tmp/*"}}"#, &request).0
+        );
+    }
+
+    #[test]
+    fn evaluate_numeric_date_and_bool_conditions() {
+        let request = request()
+            .with_context_values("s3:max-keys", vec!["10".to_owned()])
+            .with_context_values("aws:CurrentTime", vec!["2024-02-01T12:00:00Z".to_owned()])
+            .with_context_values("aws:SecureTransport", vec!["true".to_owned()]);
+
+        assert!(evaluate(r#"{"NumericEquals": {"s3:max-keys": "10"}}"#, &request).0);
+        assert!(evaluate(r#"{"NumericNotEquals": {"s3:max-keys": "11"}}"#, &request).0);
+        assert!(evaluate(r#"{"NumericLessThan": {"s3:max-keys": "11"}}"#, &request).0);
+        assert!(evaluate(r#"{"NumericLessThanEquals": {"s3:max-keys": "10"}}"#, &request).0);
+        assert!(!evaluate(r#"{"NumericGreaterThan": {"s3:max-keys": "10"}}"#, &request).0);
+        assert!(evaluate(r#"{"NumericGreaterThanEquals": {"s3:max-keys": "10.0"}}"#, &request).0);
+        assert!(!evaluate(r#"{"NumericEquals": {"s3:max-keys": "ten"}}"#, &request).0);
+
+        assert!(evaluate(r#"{"DateEquals": {"aws:CurrentTime": "2024-02-01T12:00:00Z"}}"#, &request).0);
+        assert!(evaluate(r#"{"DateNotEquals": {"aws:CurrentTime": "2024-02-01"}}"#, &request).0);
+        assert!(evaluate(r#"{"DateLessThan": {"aws:CurrentTime": "2024-03-01"}}"#, &request).0);
+        assert!(evaluate(r#"{"DateLessThanEquals": {"aws:CurrentTime": "1706788800"}}"#, &request).0);
+        assert!(evaluate(r#"{"DateGreaterThan": {"aws:CurrentTime": "2024-01-01T00:00:00+01:00"}}"#, &request).0);
+        assert!(!evaluate(r#"{"DateGreaterThanEquals": {"aws:CurrentTime": "2025-01-01"}}"#, &request).0);
+
+        assert!(evaluate(r#"{"Bool": {"aws:SecureTransport": "True"}}"#, &request).0);
+        assert!(!evaluate(r#"{"Bool": {"aws:SecureTransport": "false"}}"#, &request).0);
+    }
+
+    #[test]
+    fn evaluate_binary_ip_and_arn_conditions() {
+        let request = request()
+            .with_context_values("aws:SourceIp", vec!["203.0.113.10".to_owned()])
+            .with_context_values("aws:SourceArn", vec!["arn:aws:sns:eu-local-1:000000000001:topic".to_owned()])
+            .with_context_values("custom:binary", vec!["QmluYXJ5VmFsdWU=".to_owned()]);
+
+        assert!(evaluate(r#"{"BinaryEquals": {"custom:binary": "QmluYXJ5VmFsdWU="}}"#, &request).0);
+        assert!(evaluate(r#"{"IpAddress": {"aws:SourceIp": ["10.0.0.0/8", "203.0.113.0/24"]}}"#, &request).0);
+        assert!(!evaluate(r#"{"NotIpAddress": {"aws:SourceIp": "203.0.113.0/24"}}"#, &request).0);
+        assert!(
+            evaluate(r#"{"ArnEquals": {"aws:SourceArn": "arn:aws:sns:eu-local-1:000000000001:topic"}}"#, &request).0
+        );
+        assert!(evaluate(r#"{"ArnLike": {"aws:SourceArn": "arn:aws:sns:*:*:*"}}"#, &request).0);
+        assert!(evaluate(r#"{"ArnNotEquals": {"aws:SourceArn": "arn:aws:sns:*:*:other"}}"#, &request).0);
+        assert!(!evaluate(r#"{"ArnNotLike": {"aws:SourceArn": "arn:aws:sns:*:000000000001:*"}}"#, &request).0);
+    }
+
+    #[test]
+    fn evaluate_missing_keys() {
+        let request = request();
+
+        let (result, missing_keys) = evaluate(r#"{"StringEquals": {"aws:username": "Bob"}}"#, &request);
+        assert!(!result);
+        assert!(missing_keys.contains("aws:username"));
+
+        let (result, missing_keys) = evaluate(r#"{"StringEqualsIfExists": {"aws:username": "Bob"}}"#, &request);
+        assert!(result);
+        assert!(missing_keys.is_empty());
+
+        let (result, missing_keys) = evaluate(r#"{"StringNotEquals": {"aws:username": "Bob"}}"#, &request);
+        assert!(result);
+        assert!(missing_keys.is_empty());
+        assert!(evaluate(r#"{"Null": {"aws:username": "true"}}"#, &request).0);
+        assert!(!evaluate(r#"{"Null": {"aws:username": "false"}}"#, &request).0);
+    }
+
+    #[test]
+    fn evaluate_set_operators() {
+        let request = request().with_context_values("aws:TagKeys", vec!["env".to_owned(), "team".to_owned()]);
+
+        assert!(evaluate(r#"{"ForAllValues:StringEquals": {"aws:TagKeys": ["env", "team", "cost"]}}"#, &request).0);
+        assert!(!evaluate(r#"{"ForAllValues:StringEquals": {"aws:TagKeys": ["env"]}}"#, &request).0);
+        assert!(evaluate(r#"{"ForAnyValue:StringEquals": {"aws:TagKeys": ["env"]}}"#, &request).0);
+        assert!(!evaluate(r#"{"ForAnyValue:StringEquals": {"aws:TagKeys": ["cost"]}}"#, &request).0);
+        assert!(evaluate(r#"{"ForAnyValue:StringNotEquals": {"aws:TagKeys": ["env"]}}"#, &request).0);
+        assert!(!evaluate(r#"{"ForAllValues:StringNotEquals": {"aws:TagKeys": ["env"]}}"#, &request).0);
+
+        // missing keys
+        assert!(evaluate(r#"{"ForAllValues:StringEquals": {"aws:PrincipalTag/dept": ["it"]}}"#, &request).0);
+        assert!(!evaluate(r#"{"ForAnyValue:StringEquals": {"aws:PrincipalTag/dept": ["it"]}}"#, &request).0);
+    }
+}
@@ -0,0 +1,638 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::condition::evaluate_condition;
+use crate::matcher::{arn_match, escape_wildcards, wildcard_match};
+use crate::types::{FlexiString, LocalPolicyDocument, PrincipalData, Statement};
+
+/// Policy variables are supported starting from this version of the policy language.
+const POLICY_VARIABLES_VERSION: &str = "2012-10-17";
+const EFFECT_ALLOW: &str = "Allow";
+const EFFECT_DENY: &str = "Deny";
+
+/// Request to evaluate policies against.
+#[derive(Debug, Clone, Default)]
+pub struct RequestContext {
+    action: String,
+    resource: String,
+    principal: Option<String>,
+    /// Values of the context keys. Context keys are case-insensitive, so keys are stored in lower case.
+    context_values: HashMap<String, Vec<String>>,
+}
+
+impl RequestContext {
+    pub fn new(action: impl Into<String>, resource: impl Into<String>) -> Self {
+        RequestContext {
+            action: action.into(),
+            resource: resource.into(),
+            principal: None,
+            context_values: HashMap::new(),
+        }
+    }
+
+    /// Sets ARN (or service name) of the principal making the request. Used to match `Principal` of resource policies.
+    pub fn with_principal(mut self, principal: impl Into<String>) -> Self {
+        self.principal = Some(principal.into());
+        self
+    }
+
+    pub fn with_context_values(mut self, key: &str, values: Vec<String>) -> Self {
+        self.context_values.insert(key.to_lowercase(), values);
+        self
+    }
+
+    pub fn action(&self) -> &str {
+        &self.action
+    }
+
+    pub fn resource(&self) -> &str {
+        &self.resource
+    }
+
+    pub fn principal(&self) -> Option<&str> {
+        self.principal.as_deref()
+    }
+
+    pub fn context_values(&self, key: &str) -> Option<&Vec<String>> {
+        self.context_values.get(&key.to_lowercase())
+    }
+
+    /// Replaces policy variables like `${aws:username}` or `${aws:username, 'default'}` with values from the request
+    /// context. Returns `None` if a variable has no value and no default.
+    pub(crate) fn resolve_variables(&self, value: &str) -> Option<String> {
+        self.resolve_variables_with(value, |value| value.to_owned(), |value| value.to_owned())
+    }
+
+    /// Same as [`RequestContext::resolve_variables`] for values with wildcards. Values of variables, including `${*}`,
+    /// `${?}` and `${$}`, are matched literally by [`wildcard_match`].
+    pub(crate) fn resolve_variables_as_pattern(&self, value: &str) -> Option<String> {
+        self.resolve_variables_with(value, |text| text.replace('\\', "\\\\"), escape_wildcards)
+    }
+
+    fn resolve_variables_with(
+        &self, value: &str, text: impl Fn(&str) -> String, variable_value: impl Fn(&str) -> String,
+    ) -> Option<String> {
+        let mut result = String::with_capacity(value.len());
+        let mut rest = value;
+        while let Some(start) = rest.find("${") {
+            result.push_str(&text(&rest[..start]));
+            let variable = &rest[start + 2..];
+            let end = match variable.find('}') {
+                Some(end) => end,
+                None => {
+                    result.push_str(&text(&rest[start..]));
+                    return Some(result);
+                }
+            };
+            let (key, default_value) = match variable[..end].split_once(',') {
+                Some((key, default_value)) => (key.trim(), Some(default_value.trim().trim_matches('\'').to_owned())),
+                None => (variable[..end].trim(), None),
+            };
+            let resolved = match key {
+                "*" | "?" | "$" => Some(key.to_owned()),
+                _ => self
+                    .context_values(key)
+                    .and_then(|values| values.first().cloned())
+                    .or(default_value),
+            };
+            result.push_str(&variable_value(&resolved?));
+            rest = &variable[end + 1..];
+        }
+        result.push_str(&text(rest));
+        Some(result)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    Allow,
+    ExplicitDeny,
+    ImplicitDeny,
+}
+
+impl Decision {
+    /// Decision in the format used by the IAM policy simulator.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Decision::Allow => "allowed",
+            Decision::ExplicitDeny => "explicitDeny",
+            Decision::ImplicitDeny => "implicitDeny",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyKind {
+    Identity,
+    Resource,
+    PermissionsBoundary,
+}
+
+#[derive(Debug)]
+pub struct SourcePolicy {
+    pub id: String,
+    pub kind: PolicyKind,
+    pub document: LocalPolicyDocument,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchedStatement {
+    pub source_policy_id: String,
+    pub source_policy_kind: PolicyKind,
+    /// Position of the statement in the `Statement` list of the policy.
+    pub statement_index: usize,
+    pub sid: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvaluationResult {
+    pub decision: Decision,
+    /// Statements which determined the decision: `Deny` statements for explicit deny, `Allow` statements otherwise.
+    pub matched_statements: Vec<MatchedStatement>,
+    /// Condition keys required by the matching statements but absent in the request context. Sorted.
+    pub missing_context_values: Vec<String>,
+    /// `None` if no permissions boundary was evaluated.
+    pub allowed_by_permissions_boundary: Option<bool>,
+}
+
+/// Identity policies, resource policies and permissions boundaries which apply to a request.
+#[derive(Debug, Default)]
+pub struct PolicySet {
+    policies: Vec<SourcePolicy>,
+}
+
+impl PolicySet {
+    pub fn new() -> Self {
+        PolicySet::default()
+    }
+
+    pub fn add_identity_policy(&mut self, id: impl Into<String>, document: LocalPolicyDocument) -> &mut Self {
+        self.add(id, PolicyKind::Identity, document)
+    }
+
+    pub fn add_resource_policy(&mut self, id: impl Into<String>, document: LocalPolicyDocument) -> &mut Self {
+        self.add(id, PolicyKind::Resource, document)
+    }
+
+    pub fn add_permissions_boundary(&mut self, id: impl Into<String>, document: LocalPolicyDocument) -> &mut Self {
+        self.add(id, PolicyKind::PermissionsBoundary, document)
+    }
+
+    fn add(&mut self, id: impl Into<String>, kind: PolicyKind, document: LocalPolicyDocument) -> &mut Self {
+        self.policies.push(SourcePolicy {
+            id: id.into(),
+            kind,
+            document,
+        });
+        self
+    }
+
+    pub fn policies(&self) -> &[SourcePolicy] {
+        &self.policies
+    }
+
+    /// Evaluates the request following AWS policy evaluation logic within a single account:
+    /// 1. an explicit `Deny` in any policy wins;
+    /// 2. an `Allow` in a resource policy allows the request;
+    /// 3. an `Allow` in an identity policy allows the request if every permissions boundary (when present)
+    ///    allows it too;
+    /// 4. otherwise the request is implicitly denied.
+    pub fn evaluate(&self, request: &RequestContext) -> EvaluationResult {
+        let mut missing_keys = BTreeSet::new();
+        let mut allows: Vec<MatchedStatement> = vec![];
+        let mut denies: Vec<MatchedStatement> = vec![];
+        for policy in &self.policies {
+            let variables_enabled = policy.document.version == POLICY_VARIABLES_VERSION;
+            for (statement_index, statement) in policy.document.statement.iter().enumerate() {
+                if !statement_applies(statement, policy.kind, request, variables_enabled, &mut missing_keys) {
+                    continue;
+                }
+                let matched_statement = MatchedStatement {
+                    source_policy_id: policy.id.clone(),
+                    source_policy_kind: policy.kind,
+                    statement_index,
+                    sid: statement.sid.clone(),
+                };
+                match statement.effect.as_str() {
+                    EFFECT_ALLOW => allows.push(matched_statement),
+                    EFFECT_DENY => denies.push(matched_statement),
+                    _ => {}
+                }
+            }
+        }
+
+        let has_boundaries = self
+            .policies
+            .iter()
+            .any(|policy| policy.kind == PolicyKind::PermissionsBoundary);
+        let boundary_allows = !has_boundaries
+            || allows
+                .iter()
+                .any(|statement| statement.source_policy_kind == PolicyKind::PermissionsBoundary);
+        let resource_allows = allows
+            .iter()
+            .any(|statement| statement.source_policy_kind == PolicyKind::Resource);
+        let identity_allows = allows
+            .iter()
+            .any(|statement| statement.source_policy_kind == PolicyKind::Identity);
+
+        let (decision, matched_statements) = if !denies.is_empty() {
+            (Decision::ExplicitDeny, denies)
+        } else if resource_allows || (identity_allows && boundary_allows) {
+            let matched_statements = allows
+                .into_iter()
+                .filter(|statement| match statement.source_policy_kind {
+                    PolicyKind::Resource => true,
+                    PolicyKind::Identity => boundary_allows,
+                    PolicyKind::PermissionsBoundary => false,
+                })
+                .collect();
+            (Decision::Allow, matched_statements)
+        } else {
+            (Decision::ImplicitDeny, vec![])
+        };
+
+        EvaluationResult {
+            decision,
+            matched_statements,
+            missing_context_values: missing_keys.into_iter().collect(),
+            allowed_by_permissions_boundary: if has_boundaries { Some(boundary_allows) } else { None },
+        }
+    }
+}
+
+fn statement_applies(
+    statement: &Statement, kind: PolicyKind, request: &RequestContext, variables_enabled: bool,
+    missing_keys: &mut BTreeSet<String>,
+) -> bool {
+    let action_matches = match (&statement.action, &statement.not_action) {
+        (Some(actions), _) => matches_any_action(actions, request.action()),
+        (None, Some(not_actions)) => !matches_any_action(not_actions, request.action()),
+        (None, None) => false,
+    };
+    if !action_matches {
+        return false;
+    }
+
+    let resource_matches = match (&statement.resource, &statement.not_resource) {
+        (Some(resources), _) => matches_any_resource(resources, request, variables_enabled),
+        (None, Some(not_resources)) => !matches_any_resource(not_resources, request, variables_enabled),
+        // trust policies don't have a resource element
+        (None, None) => true,
+    };
+    if !resource_matches {
+        return false;
+    }
+
+    if kind == PolicyKind::Resource {
+        let principal_matches = match (&statement.principal, &statement.not_principal) {
+            (Some(principal), _) => matches_principal(principal, request.principal()),
+            (None, Some(not_principal)) => !matches_principal(not_principal, request.principal()),
+            (None, None) => false,
+        };
+        if !principal_matches {
+            return false;
+        }
+    }
+
+    match &statement.condition {
+        None => true,
+        Some(condition) => evaluate_condition(condition, request, variables_enabled, missing_keys),
+    }
+}
+
+fn matches_any_action(actions: &FlexiString, action: &str) -> bool {
+    actions
+        .values()
+        .into_iter()
+        .any(|pattern| wildcard_match(pattern, action, true))
+}
+
+fn matches_any_resource(resources: &FlexiString, request: &RequestContext, variables_enabled: bool) -> bool {
+    resources.values().into_iter().any(|pattern| {
+        let pattern = if variables_enabled {
+            request.resolve_variables_as_pattern(pattern)
+        } else {
+            Some(pattern.to_owned())
+        };
+        pattern
+            .map(|pattern| arn_match(&pattern, request.resource()))
+            .unwrap_or(false)
+    })
+}
+
+fn matches_principal(principal: &PrincipalData, request_principal: Option<&str>) -> bool {
+    match principal {
+        PrincipalData::Str(value) => value == "*",
+        PrincipalData::Principal(principal) => [
+            &principal.aws,
+            &principal.canonical_user,
+            &principal.federated,
+            &principal.service,
+        ]
+        .into_iter()
+        .flatten()
+        .flat_map(|values| values.values())
+        .any(|value| matches_principal_value(value, request_principal)),
+    }
+}
+
+/// Principal could be specified as `*`, ARN, account ID or the account root ARN (`arn:aws:iam::123456789012:root`).
+/// Account forms match any principal of the account.
+fn matches_principal_value(value: &str, request_principal: Option<&str>) -> bool {
+    if value == "*" {
+        return true;
+    }
+    let request_principal = match request_principal {
+        Some(request_principal) => request_principal,
+        None => return false,
+    };
+    if value == request_principal {
+        return true;
+    }
+    let account_id = if value.len() == 12 && value.chars().all(|c| c.is_ascii_digit()) {
+        Some(value)
+    } else {
+        value
+            .strip_prefix("arn:aws:iam::")
+            .and_then(|value| value.strip_suffix(":root"))
+    };
+    match account_id {
+        Some(account_id) => request_principal.split(':').nth(4) == Some(account_id),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::evaluator::{Decision, PolicyKind, PolicySet, RequestContext};
+    use crate::types::LocalPolicyDocument;
+
+    const USER_ARN: &str = "arn:aws:iam::000000000001:user/Bob";
+
+    fn document(json: &str) -> LocalPolicyDocument {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn allow_s3_policy() -> LocalPolicyDocument {
+        document(
+            r#"{
+                "Version": "2012-10-17",
+                "Statement": [{"Sid": "AllowS3", "Effect": "Allow", "Action": "s3:*", "Resource": "*"}]
+            }"#,
+        )
+    }
+
+    #[test]
+    fn evaluate_implicit_deny_without_policies() {
+        let request = RequestContext::new("s3:GetObject", "arn:aws:s3:::bucket/key");
+
+        let result = PolicySet::new().evaluate(&request);
+
+        assert_eq!(result.decision, Decision::ImplicitDeny);
+        assert!(result.matched_statements.is_empty());
+        assert_eq!(result.allowed_by_permissions_boundary, None);
+    }
+
+    #[test]
+    fn evaluate_identity_policy() {
+        let mut policies = PolicySet::new();
+        policies.add_identity_policy("policy1", allow_s3_policy());
+
+        let result = policies.evaluate(&RequestContext::new("S3:GetObject", "arn:aws:s3:::bucket/key"));
+        assert_eq!(result.decision, Decision::Allow);
+        assert_eq!(result.matched_statements.len(), 1);
+        assert_eq!(result.matched_statements[0].source_policy_id, "policy1");
+        assert_eq!(result.matched_statements[0].sid.as_deref(), Some("AllowS3"));
+
+        let result = policies.evaluate(&RequestContext::new("ec2:RunInstances", "*"));
+        assert_eq!(result.decision, Decision::ImplicitDeny);
+    }
+
+    #[test]
+    fn evaluate_explicit_deny() {
+        let mut policies = PolicySet::new();
+        policies
+            .add_identity_policy("policy1", allow_s3_policy())
+            .add_identity_policy(
+                "policy2",
+                document(
+                    r#"{
+                    "Version": "2012-10-17",
+                    "Statement": [{"Effect": "Deny", "Action": "s3:Delete*", "Resource": "arn:aws:s3:::bucket/*"}]
+                }"#,
+                ),
+            );
+
+        let result = policies.evaluate(&RequestContext::new("s3:DeleteObject", "arn:aws:s3:::bucket/key"));
+        assert_eq!(result.decision, Decision::ExplicitDeny);
+        assert_eq!(result.matched_statements.len(), 1);
+        assert_eq!(result.matched_statements[0].source_policy_id, "policy2");
+
+        let result = policies.evaluate(&RequestContext::new("s3:DeleteObject", "arn:aws:s3:::other/key"));
+        assert_eq!(result.decision, Decision::Allow);
+    }
+
+    #[test]
+    fn evaluate_not_action_and_not_resource() {
+        let mut policies = PolicySet::new();
+        policies.add_identity_policy(
+            "policy1",
+            document(
+                r#"{
+                    "Version": "2012-10-17",
+                    "Statement": [{"Effect": "Allow", "NotAction": "iam:*", "NotResource": "arn:aws:s3:::secret/*"}]
+                }"#,
+            ),
+        );
+
+        let allowed = policies.evaluate(&RequestContext::new("s3:GetObject", "arn:aws:s3:::bucket/key"));
+        let not_action = policies.evaluate(&RequestContext::new("iam:CreateUser", "*"));
+        let not_resource = policies.evaluate(&RequestContext::new("s3:GetObject", "arn:aws:s3:::secret/key"));
+
+        assert_eq!(allowed.decision, Decision::Allow);
+        assert_eq!(not_action.decision, Decision::ImplicitDeny);
+        assert_eq!(not_resource.decision, Decision::ImplicitDeny);
+    }
+
+    #[test]
+    fn evaluate_permissions_boundary() {
+        let mut policies = PolicySet::new();
+        policies
+            .add_identity_policy("policy1", allow_s3_policy())
+            .add_permissions_boundary(
+                "boundary",
+                document(
+                    r#"{
+                    "Version": "2012-10-17",
+                    "Statement": [{"Effect": "Allow", "Action": "s3:Get*", "Resource": "*"}]
+                }"#,
+                ),
+            );
+
+        let allowed = policies.evaluate(&RequestContext::new("s3:GetObject", "arn:aws:s3:::bucket/key"));
+        assert_eq!(allowed.decision, Decision::Allow);
+        assert_eq!(allowed.allowed_by_permissions_boundary, Some(true));
+        assert!(allowed
+            .matched_statements
+            .iter()
+            .all(|statement| statement.source_policy_kind == PolicyKind::Identity));
+
+        let denied = policies.evaluate(&RequestContext::new("s3:PutObject", "arn:aws:s3:::bucket/key"));
+        assert_eq!(denied.decision, Decision::ImplicitDeny);
+        assert_eq!(denied.allowed_by_permissions_boundary, Some(false));
+    }
+
+    #[test]
+    fn evaluate_resource_policy() {
+        let mut policies = PolicySet::new();
+        policies.add_resource_policy(
+            "bucket-policy",
+            document(
+                r#"{
+                    "Version": "2012-10-17",
+                    "Statement": [{
+                        "Effect": "Allow",
+                        "Principal": {"AWS": "arn:aws:iam::000000000001:root"},
+                        "Action": "s3:GetObject",
+                        "Resource": "arn:aws:s3:::bucket/*"
+                    }]
+                }"#,
+            ),
+        );
+
+        let request = RequestContext::new("s3:GetObject", "arn:aws:s3:::bucket/key");
+        let allowed = policies.evaluate(&request.clone().with_principal(USER_ARN));
+        let other_account = policies.evaluate(&request.clone().with_principal("arn:aws:iam::000000000002:user/Bob"));
+        let anonymous = policies.evaluate(&request);
+
+        assert_eq!(allowed.decision, Decision::Allow);
+        assert_eq!(other_account.decision, Decision::ImplicitDeny);
+        assert_eq!(anonymous.decision, Decision::ImplicitDeny);
+    }
+
+    #[test]
+    fn evaluate_policy_variables_and_conditions() {
+        let mut policies = PolicySet::new();
+        policies.add_identity_policy(
+            "policy1",
+            document(
+                r#"{
+                    "Version": "2012-10-17",
+                    "Statement": [{
+                        "Effect": "Allow",
+                        "Action": "s3:PutObject",
+                        "Resource": "arn:aws:s3:::bucket/home/${aws:username}/*",
+                        "Condition": {
+                            "Bool": {"aws:MultiFactorAuthPresent": "true"},
+                            "ForAnyValue:StringEquals": {"aws:TagKeys": "project"}
+                        }
+                    }]
+                }"#,
+            ),
+        );
+
+        let request = RequestContext::new("s3:PutObject", "arn:aws:s3:::bucket/home/Bob/file.txt")
+            .with_context_values("aws:username", vec!["Bob".to_owned()]);
+
+        let missing = policies.evaluate(&request);
+        assert_eq!(missing.decision, Decision::ImplicitDeny);
+        assert_eq!(missing.missing_context_values, vec!["aws:MultiFactorAuthPresent", "aws:TagKeys"]);
+
+        let allowed = policies.evaluate(
+            &request
+                .clone()
+                .with_context_values("aws:MultiFactorAuthPresent", vec!["true".to_owned()])
+                .with_context_values("aws:TagKeys", vec!["project".to_owned(), "env".to_owned()]),
+        );
+        assert_eq!(allowed.decision, Decision::Allow);
+        assert!(allowed.missing_context_values.is_empty());
+
+        let other_user = policies.evaluate(
+            &RequestContext::new("s3:PutObject", "arn:aws:s3:::bucket/home/Bob/file.txt")
+                .with_context_values("aws:username", vec!["Alice".to_owned()])
+                .with_context_values("aws:MultiFactorAuthPresent", vec!["true".to_owned()]),
+        );
+        assert_eq!(other_user.decision, Decision::ImplicitDeny);
+    }
+
+    #[test]
+    fn resolve_policy_variables() {
+        let request = RequestContext::new("s3:GetObject", "*").with_context_values("aws:username", vec!["Bob".into()]);
+
+        assert_eq!(request.resolve_variables("home/${aws:username}/*").unwrap(), "home/Bob/*");
+        assert_eq!(request.resolve_variables("${aws:userid, 'none'}").unwrap(), "none");
+        assert_eq!(request.resolve_variables("${*}").unwrap(), "*");
+        assert!(request.resolve_variables("${aws:userid}").is_none());
+    }
+
+    #[test]
+    fn resolve_policy_variables_as_pattern() {
+        let request = RequestContext::new("s3:GetObject", "*").with_context_values("aws:username", vec!["B*b".into()]);
+
+        assert_eq!(request.resolve_variables_as_pattern("home/${aws:username}/*").unwrap(), r"home/B\*b/*");
+        assert_eq!(request.resolve_variables_as_pattern("${*}").unwrap(), r"\*");
+    }
+
+    #[test]
+    fn evaluate_negated_conditions_on_missing_keys() {
+        let mut policies = PolicySet::new();
+        policies.add_identity_policy(
+            "policy1",
+            document(
+                r#"{
+                    "Version": "2012-10-17",
+                    "Statement": [{
+                        "Effect": "Allow",
+                        "Action": "s3:GetObject",
+                        "Resource": "*",
+                        "Condition": {
+                            "StringNotEquals": {"aws:PrincipalTag/team": "guests"},
+                            "ArnNotLike": {"aws:SourceArn": "arn:aws:sns:*:*:blocked"}
+                        }
+                    }]
+                }"#,
+            ),
+        );
+
+        let result = policies.evaluate(&RequestContext::new("s3:GetObject", "arn:aws:s3:::bucket/key"));
+
+        assert_eq!(result.decision, Decision::Allow);
+        assert!(result.missing_context_values.is_empty());
+    }
+
+    #[test]
+    fn evaluate_literal_asterisk_variable() {
+        let mut policies = PolicySet::new();
+        policies.add_identity_policy(
+            "policy1",
+            document(
+                r#"{
+                    "Version": "2012-10-17",
+                    "Statement": [{
+                        "Effect": "Allow",
+                        "Action": "s3:GetObject",
+                        "Resource": "arn:aws:s3:::bucket/${*}",
+                        "Condition": {"StringLike": {"s3:prefix": "home/${*}"}}
+                    }]
+                }"#,
+            ),
+        );
+
+        let literal = policies.evaluate(
+            &RequestContext::new("s3:GetObject", "arn:aws:s3:::bucket/*")
+                .with_context_values("s3:prefix", vec!["home/*".to_owned()]),
+        );
+        let other_resource = policies.evaluate(
+            &RequestContext::new("s3:GetObject", "arn:aws:s3:::bucket/key")
+                .with_context_values("s3:prefix", vec!["home/*".to_owned()]),
+        );
+        let other_prefix = policies.evaluate(
+            &RequestContext::new("s3:GetObject", "arn:aws:s3:::bucket/*")
+                .with_context_values("s3:prefix", vec!["home/Bob".to_owned()]),
+        );
+
+        assert_eq!(literal.decision, Decision::Allow);
+        assert_eq!(other_resource.decision, Decision::ImplicitDeny);
+        assert_eq!(other_prefix.decision, Decision::ImplicitDeny);
+    }
+}
@@ -1 +1,4 @@
+pub mod condition;
+pub mod evaluator;
+mod matcher;
 pub mod types;
@@ -0,0 +1,176 @@
+use std::net::IpAddr;
+
+const ARN_SEGMENTS_COUNT: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PatternToken {
+    AnySequence,
+    AnyChar,
+    Char(char),
+}
+
+/// Escapes `*`, `?` and `\` so that they are matched literally by [`wildcard_match`].
+pub(crate) fn escape_wildcards(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '*' | '?' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Matches value against a pattern which may contain `*` (any sequence of characters) and `?` (any single character).
+/// A character escaped with `\` is matched literally, see [`escape_wildcards`].
+pub(crate) fn wildcard_match(pattern: &str, value: &str, ignore_case: bool) -> bool {
+    let mut pattern_chars = pattern.chars();
+    let mut pattern = Vec::with_capacity(pattern.len());
+    while let Some(c) = pattern_chars.next() {
+        pattern.push(match c {
+            '*' => PatternToken::AnySequence,
+            '?' => PatternToken::AnyChar,
+            '\\' => PatternToken::Char(pattern_chars.next().unwrap_or('\\')),
+            c => PatternToken::Char(c),
+        });
+    }
+    let value: Vec<char> = value.chars().collect();
+    let eq = |left: char, right: char| {
+        if ignore_case {
+            left.to_lowercase().eq(right.to_lowercase())
+        } else {
+            left == right
+        }
+    };
+
+    let (mut p, mut v) = (0usize, 0usize);
+    // position of the last '*' in the pattern and the position in the value it was matched with
+    let mut backtrack: Option<(usize, usize)> = None;
+    while v < value.len() {
+        let token = pattern.get(p).copied();
+        match token {
+            Some(PatternToken::AnyChar) => {
+                p += 1;
+                v += 1;
+            }
+            Some(PatternToken::Char(c)) if eq(c, value[v]) => {
+                p += 1;
+                v += 1;
+            }
+            Some(PatternToken::AnySequence) => {
+                backtrack = Some((p, v));
+                p += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_v)) => {
+                    p = star_p + 1;
+                    v = star_v + 1;
+                    backtrack = Some((star_p, star_v + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|token| *token == PatternToken::AnySequence)
+}
+
+/// Matches ARN against a pattern segment by segment. Segments are separated by `:`, the resource segment may
+/// contain `:` itself. Falls back to a plain wildcard match if any of the values is not an ARN.
+pub(crate) fn arn_match(pattern: &str, arn: &str) -> bool {
+    if pattern == "*" {
+        return true;
+    }
+    let pattern_segments: Vec<&str> = pattern.splitn(ARN_SEGMENTS_COUNT, ':').collect();
+    let arn_segments: Vec<&str> = arn.splitn(ARN_SEGMENTS_COUNT, ':').collect();
+    if pattern_segments.len() != ARN_SEGMENTS_COUNT || arn_segments.len() != ARN_SEGMENTS_COUNT {
+        return wildcard_match(pattern, arn, false);
+    }
+    pattern_segments
+        .iter()
+        .zip(arn_segments.iter())
+        .all(|(pattern, segment)| wildcard_match(pattern, segment, false))
+}
+
+/// Checks if IP address belongs to the network defined in CIDR notation. Single IP address is treated as
+/// a network with the maximum prefix length.
+pub(crate) fn ip_address_match(cidr: &str, ip_address: &str) -> bool {
+    let ip_address: IpAddr = match ip_address.trim().parse() {
+        Ok(ip_address) => ip_address,
+        Err(_) => return false,
+    };
+    let (network, prefix_length) = match cidr.trim().split_once('/') {
+        Some((network, prefix_length)) => match prefix_length.parse::<u32>() {
+            Ok(prefix_length) => (network, Some(prefix_length)),
+            Err(_) => return false,
+        },
+        None => (cidr.trim(), None),
+    };
+    let network: IpAddr = match network.parse() {
+        Ok(network) => network,
+        Err(_) => return false,
+    };
+
+    let (network, ip_address, bits) = match (network, ip_address) {
+        (IpAddr::V4(network), IpAddr::V4(ip_address)) => {
+            (u32::from(network) as u128, u32::from(ip_address) as u128, 32)
+        }
+        (IpAddr::V6(network), IpAddr::V6(ip_address)) => (u128::from(network), u128::from(ip_address), 128),
+        _ => return false,
+    };
+    let prefix_length = prefix_length.unwrap_or(bits);
+    if prefix_length > bits {
+        return false;
+    }
+    if prefix_length == 0 {
+        return true;
+    }
+    let shift = bits - prefix_length;
+    (network >> shift) == (ip_address >> shift)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{arn_match, escape_wildcards, ip_address_match, wildcard_match};
+
+    #[test]
+    fn match_wildcards() {
+        assert!(wildcard_match("iam:*", "iam:CreateUser", false));
+        assert!(wildcard_match("iam:*User", "iam:CreateUser", false));
+        assert!(wildcard_match("iam:?reateUser", "iam:CreateUser", false));
+        assert!(wildcard_match("IAM:createuser", "iam:CreateUser", true));
+        assert!(wildcard_match("*", "", false));
+        assert!(!wildcard_match("iam:Create?", "iam:CreateUser", false));
+        assert!(!wildcard_match("IAM:createuser", "iam:CreateUser", false));
+        assert!(wildcard_match("a*b*c", "aXXbYYbc", false));
+    }
+
+    #[test]
+    fn match_escaped_wildcards() {
+        assert_eq!(escape_wildcards("a*b?c\\d"), "a\\*b\\?c\\\\d");
+        assert!(wildcard_match(&escape_wildcards("home/*"), "home/*", false));
+        assert!(!wildcard_match(&escape_wildcards("home/*"), "home/Bob", false));
+        assert!(!wildcard_match(&escape_wildcards("?"), "a", false));
+        assert!(wildcard_match(&escape_wildcards("a\\b"), "a\\b", false));
+    }
+
+    #[test]
+    fn match_arns() {
+        assert!(arn_match("*", "arn:aws:s3:::bucket"));
+        assert!(arn_match("arn:aws:s3:::*", "arn:aws:s3:::bucket/key"));
+        assert!(arn_match("arn:aws:iam::*:user/*", "arn:aws:iam::000000000001:user/division/Bob"));
+        assert!(arn_match("arn:aws:ec2:*:*:instance/*", "arn:aws:ec2:eu-local-1:000000000001:instance/i-1"));
+        assert!(!arn_match("arn:aws:iam::000000000002:user/*", "arn:aws:iam::000000000001:user/Bob"));
+        assert!(!arn_match("arn:aws:s3:::bucket", "arn:aws:s3:::bucket/key"));
+    }
+
+    #[test]
+    fn match_ip_addresses() {
+        assert!(ip_address_match("203.0.113.0/24", "203.0.113.10"));
+        assert!(ip_address_match("203.0.113.10", "203.0.113.10"));
+        assert!(ip_address_match("0.0.0.0/0", "10.1.1.1"));
+        assert!(!ip_address_match("203.0.113.0/24", "203.0.114.10"));
+        assert!(ip_address_match("2001:db8::/32", "2001:db8:1234::1"));
+        assert!(!ip_address_match("2001:db8::/32", "203.0.113.10"));
+        assert!(!ip_address_match("not-an-ip", "203.0.113.10"));
+    }
+}
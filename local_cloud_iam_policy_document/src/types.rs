@@ -1,11 +1,11 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use serde::{Deserialize, Serialize};
 // A trait that the Validate derive will impl
-use validator::{Validate, ValidationError};
+use validator::{Validate, ValidationError, ValidationErrors, ValidationErrorsKind};
 
-const VALID_CONDITION_PREFIXES: [&'static str; 2] = ["ForAnyValue:", "ForAllValues:"];
-const VALID_CONDITION_POSTFIXES: [&'static str; 1] = ["IfExists"];
+use crate::condition::ConditionOperator;
+use crate::matcher;
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(untagged)]
@@ -14,6 +14,15 @@ pub enum FlexiString {
     Multiple(Vec<String>),
 }
 
+impl FlexiString {
+    pub fn values(&self) -> Vec<&str> {
+        match self {
+            FlexiString::Single(value) => vec![value.as_str()],
+            FlexiString::Multiple(values) => values.iter().map(|value| value.as_str()).collect(),
+        }
+    }
+}
+
 #[derive(Debug, Validate, Deserialize, Serialize)]
 pub struct Principal {
     #[serde(rename = "AWS", skip_serializing_if = "Option::is_none")]
@@ -89,6 +98,55 @@ pub struct Condition {
     pub arn_not_like: Option<HashMap<String, FlexiString>>,
     #[serde(rename = "Null", skip_serializing_if = "Option::is_none")]
     pub null: Option<HashMap<String, FlexiString>>,
+    /// Operators qualified with `ForAnyValue:`/`ForAllValues:` prefixes or `IfExists` postfix.
+    #[validate(custom = "validate_condition_operators")]
+    #[serde(flatten)]
+    pub qualified_operators: HashMap<String, HashMap<String, FlexiString>>,
+}
+
+impl Condition {
+    /// Returns all condition blocks of the condition with the names of their operators.
+    pub fn operators(&self) -> Vec<(&str, &HashMap<String, FlexiString>)> {
+        let operators = [
+            ("StringEquals", &self.string_equals),
+            ("StringNotEquals", &self.string_not_equals),
+            ("StringEqualsIgnoreCase", &self.string_equals_ignore_case),
+            ("StringNotEqualsIgnoreCase", &self.string_not_equals_ignore_case),
+            ("StringLike", &self.string_like),
+            ("StringNotLike", &self.string_not_like),
+            ("NumericEquals", &self.numeric_equals),
+            ("NumericNotEquals", &self.numeric_not_equals),
+            ("NumericLessThan", &self.numeric_less_than),
+            ("NumericLessThanEquals", &self.numeric_less_than_equals),
+            ("NumericGreaterThan", &self.numeric_greater_than),
+            ("NumericGreaterThanEquals", &self.numeric_greater_than_equals),
+            ("DateEquals", &self.date_equals),
+            ("DateNotEquals", &self.date_not_equals),
+            ("DateLessThan", &self.date_less_than),
+            ("DateLessThanEquals", &self.date_less_than_equals),
+            ("DateGreaterThan", &self.date_greater_than),
+            ("DateGreaterThanEquals", &self.date_greater_than_equals),
+            ("Bool", &self.bool),
+            ("BinaryEquals", &self.binary_equals),
+            ("IpAddress", &self.ip_address),
+            ("NotIpAddress", &self.not_ip_address),
+            ("ArnEquals", &self.arn_equals),
+            ("ArnLike", &self.arn_like),
+            ("ArnNotEquals", &self.arn_not_equals),
+            ("ArnNotLike", &self.arn_not_like),
+            ("Null", &self.null),
+        ];
+        let mut result: Vec<(&str, &HashMap<String, FlexiString>)> = operators
+            .into_iter()
+            .filter_map(|(name, block)| block.as_ref().map(|block| (name, block)))
+            .collect();
+        result.extend(
+            self.qualified_operators
+                .iter()
+                .map(|(name, block)| (name.as_str(), block)),
+        );
+        result
+    }
 }
 
 #[derive(Debug, Validate, Serialize, Deserialize)]
@@ -98,8 +156,10 @@ pub struct Statement {
     #[validate(custom = "validate_principal")]
     #[serde(rename = "Principal", skip_serializing_if = "Option::is_none")]
     pub principal: Option<PrincipalData>,
+    #[validate(custom = "validate_principal")]
     #[serde(rename = "NotPrincipal", skip_serializing_if = "Option::is_none")]
     pub not_principal: Option<PrincipalData>,
+    #[validate]
     #[serde(rename = "Condition", skip_serializing_if = "Option::is_none")]
     pub condition: Option<Condition>,
     #[validate(length(min = 1), custom = "validate_effect")]
@@ -112,6 +172,7 @@ pub struct Statement {
     #[validate(custom = "validate_resource")]
     #[serde(rename = "Resource", skip_serializing_if = "Option::is_none")]
     pub resource: Option<FlexiString>,
+    #[validate(custom = "validate_resource")]
     #[serde(rename = "NotResource", skip_serializing_if = "Option::is_none")]
     pub not_resource: Option<FlexiString>,
 }
@@ -131,6 +192,150 @@ pub struct LocalPolicyDocument {
     pub conditions: Option<Vec<Condition>>,
 }
 
+impl LocalPolicyDocument {
+    /// Returns sorted and de-duplicated condition keys used by all statements of the policy document. Keys are
+    /// case-insensitive, so only the first spelling of a key is returned.
+    pub fn condition_keys(&self) -> Vec<&str> {
+        let mut keys = BTreeMap::new();
+        for key in self
+            .statement
+            .iter()
+            .filter_map(|statement| statement.condition.as_ref())
+            .chain(self.conditions.iter().flatten())
+            .flat_map(|condition| condition.operators())
+            .flat_map(|(_operator, block)| {
+                // keys of a block are unordered, sort them to pick the same spelling on every call
+                let mut keys: Vec<&String> = block.keys().collect();
+                keys.sort();
+                keys
+            })
+        {
+            keys.entry(key.to_lowercase()).or_insert(key.as_str());
+        }
+        keys.into_values().collect()
+    }
+
+    /// Validates elements of all statements. The error names the first statement and the field which failed the
+    /// validation, e.g. `Statement[0].Principal: Invalid principal in policy: "AWS":"user1"`.
+    pub fn validate_elements(&self) -> Result<(), String> {
+        match self.validate() {
+            Ok(_) => Ok(()),
+            Err(errors) => {
+                let mut messages = vec![];
+                describe_errors("", &errors, &mut messages);
+                Err(messages
+                    .into_iter()
+                    .next()
+                    .unwrap_or_else(|| "Malformed policy document.".to_owned()))
+            }
+        }
+    }
+
+    /// Validates the document as a policy of the given kind: elements of all statements and the statement elements
+    /// which are required or prohibited for the kind.
+    pub fn validate_as(&self, kind: PolicyDocumentKind) -> Result<(), String> {
+        self.validate_elements()?;
+        for (index, statement) in self.statement.iter().enumerate() {
+            statement
+                .validate_as(kind)
+                .map_err(|(field, message)| format!("Statement[{index}].{field}: {message}"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Kind of the policy document. Kinds differ in the statement elements which are required or prohibited.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PolicyDocumentKind {
+    /// Managed and inline policies of IAM identities and permissions boundaries.
+    Identity,
+    /// Trust policy of IAM role which defines the principals allowed to assume the role.
+    Trust,
+}
+
+/// Actions which trust policies may grant except `sts:AssumeRole*` ones.
+const TRUST_POLICY_ACTIONS: [&str; 3] = ["sts:TagSession", "sts:SetSourceIdentity", "sts:SetContext"];
+
+impl Statement {
+    fn has_principal(&self) -> bool {
+        self.principal.is_some() || self.not_principal.is_some()
+    }
+
+    fn validate_as(&self, kind: PolicyDocumentKind) -> Result<(), (&'static str, String)> {
+        match kind {
+            PolicyDocumentKind::Identity => {
+                if self.has_principal() {
+                    return Err(("Principal", "Policy document should not specify a principal.".to_owned()));
+                }
+            }
+            PolicyDocumentKind::Trust => {
+                if !self.has_principal() {
+                    return Err(("Principal", "Missing required field Principal.".to_owned()));
+                }
+                if self.resource.is_some() {
+                    return Err(("Resource", "Has prohibited field Resource.".to_owned()));
+                }
+                if self.not_resource.is_some() {
+                    return Err(("NotResource", "Has prohibited field NotResource.".to_owned()));
+                }
+                let actions = match &self.action {
+                    None => return Err(("Action", "Missing required field Action.".to_owned())),
+                    Some(actions) => actions.values(),
+                };
+                if let Some(action) = actions.iter().find(|action| !is_trust_policy_action(action)) {
+                    return Err((
+                        "Action",
+                        format!("Action '{action}' is not supported in trust policies, only sts:AssumeRole* actions are allowed."),
+                    ));
+                }
+                if !actions.iter().any(|action| is_assume_role_action(action)) {
+                    return Err(("Action", "Trust policy statement must allow an sts:AssumeRole* action.".to_owned()));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_assume_role_action(action: &str) -> bool {
+    action.to_lowercase().starts_with("sts:assumerole") || matcher::wildcard_match(action, "sts:AssumeRole", true)
+}
+
+fn is_trust_policy_action(action: &str) -> bool {
+    is_assume_role_action(action)
+        || TRUST_POLICY_ACTIONS
+            .iter()
+            .any(|allowed| matcher::wildcard_match(action, allowed, true))
+}
+
+/// Collects messages of the validation errors prefixed with the path of the failed field. Items of lists are visited
+/// in their order in the document, fields of the same item are visited in the order of their names.
+fn describe_errors(path: &str, errors: &ValidationErrors, messages: &mut Vec<String>) {
+    let mut fields: Vec<_> = errors.errors().iter().collect();
+    fields.sort_by_key(|(field, _kind)| **field);
+    for (field, kind) in fields {
+        let field_path = if path.is_empty() {
+            field.to_string()
+        } else {
+            format!("{path}.{field}")
+        };
+        match kind {
+            ValidationErrorsKind::Field(field_errors) => {
+                for error in field_errors {
+                    let message = error.message.as_ref().unwrap_or(&error.code);
+                    messages.push(format!("{field_path}: {message}"));
+                }
+            }
+            ValidationErrorsKind::Struct(struct_errors) => describe_errors(&field_path, struct_errors, messages),
+            ValidationErrorsKind::List(list_errors) => {
+                for (index, item_errors) in list_errors {
+                    describe_errors(&format!("{field_path}[{index}]"), item_errors, messages);
+                }
+            }
+        }
+    }
+}
+
 fn validate_effect(effect: &str) -> Result<(), ValidationError> {
     if effect == "Allow" || effect == "Deny" {
         Ok(())
@@ -147,19 +352,147 @@ fn validate_version(version: &str) -> Result<(), ValidationError> {
     }
 }
 
+fn validate_condition_operators(
+    operators: &HashMap<String, HashMap<String, FlexiString>>,
+) -> Result<(), ValidationError> {
+    let mut names: Vec<&String> = operators.keys().collect();
+    names.sort();
+    match names
+        .into_iter()
+        .find_map(|name| name.parse::<ConditionOperator>().err())
+    {
+        None => Ok(()),
+        Some(message) => Err(validation_error("condition_operator", message)),
+    }
+}
+
 fn validate_resource(resource: &FlexiString) -> Result<(), ValidationError> {
-    Result::Ok(())
+    match resource
+        .values()
+        .into_iter()
+        .find(|value| *value != "*" && !is_arn(value))
+    {
+        None => Ok(()),
+        Some(value) => Err(validation_error("resource", format!("Resource {value} must be in ARN format or \"*\"."))),
+    }
 }
 
+/// Principal block name paired with its values and the check of a single value.
+type PrincipalBlockValidator<'a> = (&'a str, &'a Option<FlexiString>, fn(&str) -> bool);
+
 fn validate_principal(principal: &PrincipalData) -> Result<(), ValidationError> {
-    Result::Ok(())
+    let principal = match principal {
+        PrincipalData::Str(value) if value == "*" => return Ok(()),
+        PrincipalData::Str(value) => {
+            return Err(validation_error("principal", format!("Invalid principal in policy: \"{value}\"")))
+        }
+        PrincipalData::Principal(principal) => principal,
+    };
+    let blocks: [PrincipalBlockValidator; 4] = [
+        ("AWS", &principal.aws, is_aws_principal),
+        ("CanonicalUser", &principal.canonical_user, is_canonical_user_principal),
+        ("Federated", &principal.federated, is_federated_principal),
+        ("Service", &principal.service, is_service_principal),
+    ];
+    let mut is_empty = true;
+    for (name, values, is_valid) in blocks {
+        for value in values.iter().flat_map(|values| values.values()) {
+            is_empty = false;
+            if !is_valid(value) {
+                return Err(validation_error(
+                    "principal",
+                    format!("Invalid principal in policy: \"{name}\":\"{value}\""),
+                ));
+            }
+        }
+    }
+    if is_empty {
+        return Err(validation_error("principal", "Principal must not be empty."));
+    }
+    Ok(())
+}
+
+fn validation_error(code: &'static str, message: impl Into<String>) -> ValidationError {
+    let mut error = ValidationError::new(code);
+    error.message = Some(message.into().into());
+    error
+}
+
+/// Checks that the value has all ARN segments: `arn:partition:service:region:account-id:resource`.
+fn is_arn(value: &str) -> bool {
+    let segments: Vec<&str> = value.splitn(6, ':').collect();
+    segments.len() == 6
+        && segments[0] == "arn"
+        && !segments[1].is_empty()
+        && !segments[2].is_empty()
+        && !segments[5].is_empty()
+}
+
+fn is_account_id(value: &str) -> bool {
+    value.len() == 12 && value.chars().all(|c| c.is_ascii_digit())
+}
+
+/// AWS principals are `*`, account IDs and ARNs of the account root, IAM users, IAM roles and STS sessions.
+fn is_aws_principal(value: &str) -> bool {
+    if value == "*" || is_account_id(value) {
+        return true;
+    }
+    let segments: Vec<&str> = value.splitn(6, ':').collect();
+    if !is_arn(value) || !segments[3].is_empty() || !is_account_id(segments[4]) {
+        return false;
+    }
+    let resource = segments[5];
+    match segments[2] {
+        "iam" => resource == "root" || resource.starts_with("user/") || resource.starts_with("role/"),
+        "sts" => resource.starts_with("assumed-role/") || resource.starts_with("federated-user/"),
+        _ => false,
+    }
+}
+
+fn is_canonical_user_principal(value: &str) -> bool {
+    value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Federated principals are the supported web identity providers and ARNs of SAML and OIDC providers.
+fn is_federated_principal(value: &str) -> bool {
+    const WEB_IDENTITY_PROVIDERS: [&str; 5] = [
+        "cognito-identity.amazonaws.com",
+        "www.amazon.com",
+        "graph.facebook.com",
+        "accounts.google.com",
+        "appleid.apple.com",
+    ];
+    if WEB_IDENTITY_PROVIDERS.contains(&value) {
+        return true;
+    }
+    let segments: Vec<&str> = value.splitn(6, ':').collect();
+    is_arn(value)
+        && segments[2] == "iam"
+        && segments[3].is_empty()
+        && is_account_id(segments[4])
+        && (segments[5].starts_with("saml-provider/") || segments[5].starts_with("oidc-provider/"))
+}
+
+/// Service principals are DNS names of AWS services, e.g. `ec2.amazonaws.com`.
+fn is_service_principal(value: &str) -> bool {
+    let name = match value
+        .strip_suffix(".amazonaws.com")
+        .or_else(|| value.strip_suffix(".amazonaws.com.cn"))
+    {
+        Some(name) => name,
+        None => return false,
+    };
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '.' || c == '-')
 }
 
 #[cfg(test)]
 mod tests {
     use validator::Validate;
 
-    use crate::types::LocalPolicyDocument;
+    use crate::types::{LocalPolicyDocument, PolicyDocumentKind};
 
     #[test]
     fn test_policy_document_version_2008_10_17() {
@@ -316,4 +649,244 @@ mod tests {
         let validation_result = policy_document.validate();
         assert!(validation_result.is_ok());
     }
+
+    #[test]
+    fn test_policy_document_qualified_condition_operators() {
+        let policy_document_json = r#"{
+            "Version": "2012-10-17",
+            "Statement": [
+                {
+                    "Effect": "Allow",
+                    "Action": "ec2:RunInstances",
+                    "Resource": "*",
+                    "Condition": {
+                        "ForAnyValue:StringLike": {"aws:TagKeys": ["env*"]},
+                        "StringEqualsIfExists": {"ec2:InstanceType": "t3.micro"}
+                    }
+                }
+            ]
+        }"#;
+        let policy_document: LocalPolicyDocument = serde_json::from_str(policy_document_json).unwrap();
+        let validation_result = policy_document.validate();
+        assert!(validation_result.is_ok());
+    }
+
+    #[test]
+    fn test_policy_document_invalid_condition_operator() {
+        let policy_document_json = r#"{
+            "Version": "2012-10-17",
+            "Statement": [
+                {
+                    "Effect": "Allow",
+                    "Action": "s3:*",
+                    "Resource": "*",
+                    "Condition": {"StringMatches": {"s3:prefix": "home/"}}
+                }
+            ]
+        }"#;
+        let policy_document: LocalPolicyDocument = serde_json::from_str(policy_document_json).unwrap();
+        let validation_result = policy_document.validate();
+        assert!(validation_result.is_err());
+    }
+
+    #[test]
+    fn test_policy_document_condition_keys() {
+        let policy_document_json = r#"{
+            "Version": "2012-10-17",
+            "Statement": [
+                {
+                    "Effect": "Allow",
+                    "Action": "ec2:RunInstances",
+                    "Resource": "*",
+                    "Condition": {
+                        "ForAnyValue:StringLike": {"aws:TagKeys": ["env*"]},
+                        "StringEqualsIfExists": {"ec2:InstanceType": "t3.micro"}
+                    }
+                },
+                {
+                    "Effect": "Deny",
+                    "Action": "ec2:*",
+                    "Resource": "*",
+                    "Condition": {
+                        "ForAllValues:StringEquals": {"aws:TagKeys": ["env", "team"]},
+                        "Bool": {"aws:SecureTransport": "false"}
+                    }
+                },
+                {
+                    "Effect": "Allow",
+                    "Action": "s3:*",
+                    "Resource": "*"
+                }
+            ]
+        }"#;
+        let policy_document: LocalPolicyDocument = serde_json::from_str(policy_document_json).unwrap();
+        assert_eq!(policy_document.condition_keys(), vec!["aws:SecureTransport", "aws:TagKeys", "ec2:InstanceType"]);
+    }
+
+    #[test]
+    fn test_policy_document_condition_keys_ignore_case() {
+        let policy_document_json = r#"{
+            "Version": "2012-10-17",
+            "Statement": [
+                {
+                    "Effect": "Allow",
+                    "Action": "s3:*",
+                    "Resource": "*",
+                    "Condition": {"StringEquals": {"aws:PrincipalTag/Team": "dev"}}
+                },
+                {
+                    "Effect": "Deny",
+                    "Action": "s3:DeleteObject",
+                    "Resource": "*",
+                    "Condition": {"StringNotEquals": {"AWS:principaltag/team": "ops", "aws:SourceIp": "10.0.0.1"}}
+                }
+            ]
+        }"#;
+        let policy_document: LocalPolicyDocument = serde_json::from_str(policy_document_json).unwrap();
+        assert_eq!(policy_document.condition_keys(), vec!["aws:PrincipalTag/Team", "aws:SourceIp"]);
+    }
+
+    fn trust_policy(statement: &str) -> LocalPolicyDocument {
+        serde_json::from_str(&format!(r#"{{"Version": "2012-10-17", "Statement": [{statement}]}}"#)).unwrap()
+    }
+
+    #[test]
+    fn test_trust_policy() {
+        let policy_document = trust_policy(
+            r#"{
+                "Effect": "Allow",
+                "Principal": {
+                    "AWS": ["arn:aws:iam::000000000001:root", "000000000002", "arn:aws:iam::000000000001:role/admin"],
+                    "Service": "ec2.amazonaws.com",
+                    "Federated": "arn:aws:iam::000000000001:saml-provider/ADFS"
+                },
+                "Action": ["sts:AssumeRole", "sts:AssumeRoleWithSAML", "sts:TagSession"]
+            }"#,
+        );
+        assert_eq!(Ok(()), policy_document.validate_as(PolicyDocumentKind::Trust));
+    }
+
+    #[test]
+    fn test_trust_policy_without_principal() {
+        let policy_document = trust_policy(r#"{"Effect": "Allow", "Action": "sts:AssumeRole"}"#);
+        assert_eq!(
+            Err("Statement[0].Principal: Missing required field Principal.".to_owned()),
+            policy_document.validate_as(PolicyDocumentKind::Trust)
+        );
+    }
+
+    #[test]
+    fn test_trust_policy_with_resource() {
+        let policy_document =
+            trust_policy(r#"{"Effect": "Allow", "Principal": "*", "Action": "sts:AssumeRole", "Resource": "*"}"#);
+        assert_eq!(
+            Err("Statement[0].Resource: Has prohibited field Resource.".to_owned()),
+            policy_document.validate_as(PolicyDocumentKind::Trust)
+        );
+    }
+
+    #[test]
+    fn test_trust_policy_unsupported_action() {
+        let policy_document = trust_policy(
+            r#"{"Effect": "Allow", "Principal": {"Service": "ec2.amazonaws.com"}, "Action": ["sts:AssumeRole", "s3:*"]}"#,
+        );
+        let error = policy_document.validate_as(PolicyDocumentKind::Trust).unwrap_err();
+        assert!(error.starts_with("Statement[0].Action: Action 's3:*' is not supported"), "{error}");
+
+        let policy_document =
+            trust_policy(r#"{"Effect": "Allow", "Principal": {"Service": "ec2.amazonaws.com"}, "Action": "sts:*"}"#);
+        assert_eq!(Ok(()), policy_document.validate_as(PolicyDocumentKind::Trust));
+    }
+
+    #[test]
+    fn test_invalid_principals() {
+        for (principal, invalid_value) in [
+            (r#"{"AWS": "arn:aws:iam::00000000001:root"}"#, r#""AWS":"arn:aws:iam::00000000001:root""#),
+            (r#"{"AWS": "arn:aws:iam::000000000001:group/devs"}"#, r#""AWS":"arn:aws:iam::000000000001:group/devs""#),
+            (r#"{"AWS": "user1"}"#, r#""AWS":"user1""#),
+            (r#"{"Service": "ec2"}"#, r#""Service":"ec2""#),
+            (r#"{"Federated": "example.com"}"#, r#""Federated":"example.com""#),
+            (r#""account""#, r#""account""#),
+        ] {
+            let policy_document = trust_policy(&format!(
+                r#"{{"Sid": "Trust", "Effect": "Allow", "Principal": {principal}, "Action": "sts:AssumeRole"}}"#
+            ));
+            assert_eq!(
+                Err(format!("Statement[0].Principal: Invalid principal in policy: {invalid_value}")),
+                policy_document.validate_as(PolicyDocumentKind::Trust),
+                "{principal}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_identity_policy_with_principal() {
+        let policy_document: LocalPolicyDocument = serde_json::from_str(
+            r#"{
+                "Version": "2012-10-17",
+                "Statement": [
+                    {"Effect": "Allow", "Action": "s3:*", "Resource": "*"},
+                    {"Effect": "Allow", "Principal": "*", "Action": "s3:*", "Resource": "*"}
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            Err("Statement[1].Principal: Policy document should not specify a principal.".to_owned()),
+            policy_document.validate_as(PolicyDocumentKind::Identity)
+        );
+        assert_eq!(Ok(()), policy_document.validate_elements());
+    }
+
+    #[test]
+    fn test_malformed_resource() {
+        let policy_document: LocalPolicyDocument = serde_json::from_str(
+            r#"{
+                "Version": "2012-10-17",
+                "Statement": [{"Effect": "Allow", "Action": "s3:*", "Resource": ["arn:aws:s3:::bucket", "bucket"]}]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            Err(r#"Statement[0].Resource: Resource bucket must be in ARN format or "*"."#.to_owned()),
+            policy_document.validate_elements()
+        );
+    }
+
+    #[test]
+    fn test_first_malformed_statement() {
+        let statements: Vec<String> = (0..11)
+            .map(|index| match index {
+                2 => r#"{"Effect": "Allow", "Action": "s3:*", "Resource": "bucket2"}"#.to_owned(),
+                10 => r#"{"Effect": "Allow", "Action": "s3:*", "Resource": "bucket10"}"#.to_owned(),
+                _ => r#"{"Effect": "Allow", "Action": "s3:*", "Resource": "*"}"#.to_owned(),
+            })
+            .collect();
+        let policy_document: LocalPolicyDocument =
+            serde_json::from_str(&format!(r#"{{"Version": "2012-10-17", "Statement": [{}]}}"#, statements.join(",")))
+                .unwrap();
+        assert_eq!(
+            Err(r#"Statement[2].Resource: Resource bucket2 must be in ARN format or "*"."#.to_owned()),
+            policy_document.validate_elements()
+        );
+    }
+
+    #[test]
+    fn test_unknown_condition_operator() {
+        let policy_document: LocalPolicyDocument = serde_json::from_str(
+            r#"{
+                "Version": "2012-10-17",
+                "Statement": [{
+                    "Effect": "Allow",
+                    "Action": "s3:*",
+                    "Resource": "*",
+                    "Condition": {"StringEqualz": {"aws:username": "user1"}}
+                }]
+            }"#,
+        )
+        .unwrap();
+        let error = policy_document.validate_elements().unwrap_err();
+        assert!(error.starts_with("Statement[0].Condition."), "{error}");
+        assert!(error.ends_with("Unsupported condition operator 'StringEqualz'."), "{error}");
+    }
 }
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS fk_access_keys__user_id;
+DROP INDEX IF EXISTS fk_access_keys__account_id;
+DROP TABLE IF EXISTS access_keys;
@@ -0,0 +1,14 @@
+-- Access keys
+CREATE TABLE IF NOT EXISTS access_keys
+(
+    id                INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    account_id        INTEGER REFERENCES accounts (id)  NOT NULL,
+    user_id           INTEGER REFERENCES users (id)     NOT NULL,
+    access_key_id     VARCHAR2(20)                      NOT NULL,
+    secret_access_key VARCHAR2(40)                      NOT NULL,
+    status            INTEGER                           NOT NULL,
+    create_date       INTEGER                           NOT NULL,
+    UNIQUE (access_key_id)
+);
+CREATE INDEX IF NOT EXISTS fk_access_keys__account_id ON access_keys (account_id ASC);
+CREATE INDEX IF NOT EXISTS fk_access_keys__user_id ON access_keys (user_id ASC);
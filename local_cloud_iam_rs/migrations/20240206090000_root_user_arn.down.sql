@@ -0,0 +1,3 @@
+UPDATE users
+SET arn = '"arn:aws:iam::000000000001:user/Root"'
+WHERE user_id = 'AIDAHOMECLOUDROOT101A';
@@ -0,0 +1,2 @@
+DELETE FROM access_keys WHERE access_key_id = 'AKIAIOSFODNN201ADMIN';
+DELETE FROM unique_identifiers WHERE unique_id = 'AKIAIOSFODNN201ADMIN';
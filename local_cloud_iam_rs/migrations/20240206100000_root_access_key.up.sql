@@ -0,0 +1,13 @@
+-- Bootstrap access key of the Root user. Used to sign the very first requests when signature verification is enabled.
+-- The secret is replaced at startup with the one configured with IAM_ROOT_SECRET_ACCESS_KEY.
+INSERT INTO unique_identifiers(unique_id, resource_type)
+VALUES ('AKIAIOSFODNN201ADMIN', 10);
+INSERT INTO access_keys(account_id,
+                        user_id,
+                        access_key_id,
+                        secret_access_key,
+                        status,
+                        create_date)
+SELECT account_id, id, 'AKIAIOSFODNN201ADMIN', 'secret_access_key', 1, 1706219306
+FROM users
+WHERE user_id = 'AIDAHOMECLOUDROOT101A';
@@ -0,0 +1,8 @@
+DELETE FROM policy_users WHERE policy_id IN (SELECT id FROM policies WHERE account_id = 0);
+DELETE FROM policy_groups WHERE policy_id IN (SELECT id FROM policies WHERE account_id = 0);
+DELETE FROM policy_roles WHERE policy_id IN (SELECT id FROM policies WHERE account_id = 0);
+UPDATE users SET policy_id = NULL WHERE policy_id IN (SELECT id FROM policies WHERE account_id = 0);
+UPDATE roles SET policy_id = NULL WHERE policy_id IN (SELECT id FROM policies WHERE account_id = 0);
+DELETE FROM policy_versions WHERE account_id = 0;
+DELETE FROM policies WHERE account_id = 0;
+DELETE FROM accounts WHERE id = 0;
@@ -0,0 +1,4 @@
+-- Namespace of AWS managed policies. The policies are loaded from the bundled catalog at startup. The account has no
+-- alias, so that 'aws' could be used by local accounts.
+INSERT INTO accounts(id, alias)
+VALUES (0, '');
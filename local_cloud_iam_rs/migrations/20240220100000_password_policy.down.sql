@@ -0,0 +1,4 @@
+ALTER TABLE login_profiles DROP COLUMN password_change_date;
+DROP INDEX IF EXISTS fk_password_history__user_id;
+DROP TABLE IF EXISTS password_history;
+DROP TABLE IF EXISTS account_password_policies;
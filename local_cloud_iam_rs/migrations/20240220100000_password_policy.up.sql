@@ -0,0 +1,30 @@
+-- Account password policies
+CREATE TABLE IF NOT EXISTS account_password_policies
+(
+    id                             INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    account_id                     INTEGER REFERENCES accounts (id)  NOT NULL,
+    minimum_password_length        INTEGER                           NOT NULL,
+    require_symbols                BOOLEAN                           NOT NULL,
+    require_numbers                BOOLEAN                           NOT NULL,
+    require_uppercase_characters   BOOLEAN                           NOT NULL,
+    require_lowercase_characters   BOOLEAN                           NOT NULL,
+    allow_users_to_change_password BOOLEAN                           NOT NULL,
+    max_password_age               INTEGER                           NOT NULL,
+    password_reuse_prevention      INTEGER                           NOT NULL,
+    hard_expiry                    BOOLEAN                           NOT NULL,
+    UNIQUE (account_id)
+);
+-- Password history of IAM users. Used to prevent password reuse.
+CREATE TABLE IF NOT EXISTS password_history
+(
+    id            INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    user_id       INTEGER REFERENCES users (id)     NOT NULL,
+    password_hash VARCHAR2(200)                     NOT NULL,
+    create_date   INTEGER                           NOT NULL
+);
+CREATE INDEX IF NOT EXISTS fk_password_history__user_id ON password_history (user_id ASC);
+-- Date of the last password change. Used to detect expired passwords.
+ALTER TABLE login_profiles ADD COLUMN password_change_date INTEGER NOT NULL DEFAULT 0;
+UPDATE login_profiles SET password_change_date = create_date;
+INSERT INTO password_history(user_id, password_hash, create_date)
+SELECT user_id, password_hash, create_date FROM login_profiles;
@@ -0,0 +1,12 @@
+-- Credential reports. Only the latest report of the account is kept.
+CREATE TABLE IF NOT EXISTS credential_reports
+(
+    id             INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    account_id     INTEGER REFERENCES accounts (id)  NOT NULL,
+    state          INTEGER                           NOT NULL,
+    content        BLOB,
+    create_date    INTEGER                           NOT NULL,
+    start_date     INTEGER,
+    generated_time INTEGER,
+    UNIQUE (account_id)
+);
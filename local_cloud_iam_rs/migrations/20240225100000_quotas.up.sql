@@ -0,0 +1,8 @@
+-- IAM quotas. Values are loaded from the service configuration at startup.
+CREATE TABLE IF NOT EXISTS quotas
+(
+    id    INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    name  VARCHAR2(64)                      NOT NULL,
+    value INTEGER                           NOT NULL,
+    UNIQUE (name)
+);
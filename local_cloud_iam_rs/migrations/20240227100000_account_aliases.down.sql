@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS ux_accounts__alias;
+UPDATE accounts
+SET alias = 'Home Account'
+WHERE id = 1
+  AND alias = '';
@@ -0,0 +1,7 @@
+-- An empty alias means that the account has no alias. The bootstrap account was created with a display name which is
+-- not a valid account alias.
+UPDATE accounts
+SET alias = ''
+WHERE id = 1
+  AND alias = 'Home Account';
+CREATE UNIQUE INDEX IF NOT EXISTS ux_accounts__alias ON accounts (alias) WHERE alias != '';
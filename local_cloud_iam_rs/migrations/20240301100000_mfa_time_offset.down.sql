@@ -0,0 +1 @@
+ALTER TABLE mfa_devices DROP COLUMN time_offset;
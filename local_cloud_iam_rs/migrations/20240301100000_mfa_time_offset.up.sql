@@ -0,0 +1,2 @@
+-- Clock drift of an MFA device in TOTP time steps. It is detected when the device is enabled or resynchronized.
+ALTER TABLE mfa_devices ADD COLUMN time_offset INTEGER NOT NULL DEFAULT 0;
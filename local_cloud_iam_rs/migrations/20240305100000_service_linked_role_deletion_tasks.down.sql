@@ -0,0 +1 @@
+DROP TABLE IF EXISTS service_linked_role_deletion_tasks;
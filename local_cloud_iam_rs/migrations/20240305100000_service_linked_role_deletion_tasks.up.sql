@@ -0,0 +1,15 @@
+-- Asynchronous deletion tasks of service-linked roles. Tasks are kept after the role is deleted, so that the deletion
+-- status could be requested with the task ID.
+CREATE TABLE IF NOT EXISTS service_linked_role_deletion_tasks
+(
+    id               INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    account_id       INTEGER REFERENCES accounts (id)  NOT NULL,
+    deletion_task_id VARCHAR2(1000)                    NOT NULL,
+    role_id          INTEGER                           NOT NULL,
+    state            INTEGER                           NOT NULL,
+    reason           VARCHAR2(1000),
+    create_date      INTEGER                           NOT NULL,
+    start_date       INTEGER,
+    UNIQUE (deletion_task_id)
+);
+CREATE INDEX IF NOT EXISTS idx_service_linked_role_deletion_tasks__role_id ON service_linked_role_deletion_tasks (role_id ASC);
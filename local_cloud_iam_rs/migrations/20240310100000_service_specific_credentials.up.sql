@@ -0,0 +1,17 @@
+-- Service-specific credentials of IAM users (CodeCommit, Keyspaces, Bedrock). Passwords are stored as Argon2 hashes in
+-- the PHC string format.
+CREATE TABLE IF NOT EXISTS service_specific_credentials
+(
+    id                INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    account_id        INTEGER REFERENCES accounts (id)  NOT NULL,
+    user_id           INTEGER REFERENCES users (id)     NOT NULL,
+    credential_id     VARCHAR2(128)                     NOT NULL,
+    service_name      VARCHAR2(255)                     NOT NULL,
+    service_user_name VARCHAR2(200)                     NOT NULL,
+    password_hash     VARCHAR2(200)                     NOT NULL,
+    status            INTEGER                           NOT NULL,
+    create_date       INTEGER                           NOT NULL,
+    UNIQUE (credential_id)
+);
+CREATE INDEX IF NOT EXISTS fk_service_specific_credentials__user_id ON service_specific_credentials (user_id ASC);
+CREATE INDEX IF NOT EXISTS idx_service_specific_credentials__service_user_name ON service_specific_credentials (service_user_name ASC);
@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS service_last_accessed_jobs;
+DROP TABLE IF EXISTS service_last_accesses;
+ALTER TABLE access_keys DROP COLUMN last_used_service;
+ALTER TABLE access_keys DROP COLUMN last_used_region_id;
+ALTER TABLE access_keys DROP COLUMN last_used_date;
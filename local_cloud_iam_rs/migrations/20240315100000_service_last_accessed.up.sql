@@ -0,0 +1,35 @@
+-- Last usage of access keys. The service is the signing name of the request, e.g. `iam`.
+ALTER TABLE access_keys ADD COLUMN last_used_date INTEGER;
+ALTER TABLE access_keys ADD COLUMN last_used_region_id INTEGER REFERENCES regions (id);
+ALTER TABLE access_keys ADD COLUMN last_used_service VARCHAR2(64);
+-- The latest access of each principal to each action of a service. Principals are referenced by ARN, so that the
+-- access history of deleted and recreated entities doesn't mix.
+CREATE TABLE IF NOT EXISTS service_last_accesses
+(
+    id                 INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    account_id         INTEGER REFERENCES accounts (id)  NOT NULL,
+    principal_arn      VARCHAR2(2048)                    NOT NULL,
+    service_namespace  VARCHAR2(64)                      NOT NULL,
+    action_name        VARCHAR2(128)                     NOT NULL,
+    region_id          INTEGER REFERENCES regions (id),
+    last_accessed_date INTEGER                           NOT NULL,
+    UNIQUE (principal_arn, service_namespace, action_name)
+);
+CREATE INDEX IF NOT EXISTS fk_service_last_accesses__account_id ON service_last_accesses (account_id ASC);
+-- Asynchronous jobs of service last accessed details reports.
+CREATE TABLE IF NOT EXISTS service_last_accessed_jobs
+(
+    id              INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    account_id      INTEGER REFERENCES accounts (id)  NOT NULL,
+    job_id          VARCHAR2(36)                      NOT NULL,
+    arn             VARCHAR2(2048)                    NOT NULL,
+    granularity     INTEGER                           NOT NULL,
+    state           INTEGER                           NOT NULL,
+    content         BLOB,
+    error_code      VARCHAR2(64),
+    error_message   VARCHAR2(1000),
+    create_date     INTEGER                           NOT NULL,
+    start_date      INTEGER,
+    completion_date INTEGER,
+    UNIQUE (job_id)
+);
@@ -1,9 +1,16 @@
 use local_cloud_common::config;
 
+use crate::http::aws::iam::constants;
+
 const ENV_DATABASE_URL: &str = "IAM_DATABASE_URL";
 const ENV_ETCD_ENDPOINTS: &str = "ETCD_ENDPOINTS";
 const ENV_ETCD_ENABLED: &str = "ETCD_ENABLED";
 const ENV_SERVICE_PORT: &str = "IAM_SERVICE_PORT";
+const ENV_TRUST_ANY_ACCESS_KEY: &str = "IAM_TRUST_ANY_ACCESS_KEY";
+const ENV_ENFORCE_POLICIES: &str = "IAM_ENFORCE_POLICIES";
+const ENV_QUOTAS: &str = "IAM_QUOTAS";
+const ENV_MFA_CLOCK_SKEW_STEPS: &str = "IAM_MFA_CLOCK_SKEW_STEPS";
+const ENV_ROOT_SECRET_ACCESS_KEY: &str = "IAM_ROOT_SECRET_ACCESS_KEY";
 
 const DEFAULT_DATABASE_URL: &str = "sqlite://iam.db";
 const DEFAULT_SERVICE_PORT: u16 = 4502;
@@ -14,6 +21,16 @@ pub(crate) struct AppConfig {
     pub etcd_enabled: bool,
     pub etcd_endpoints: Option<String>,
     pub service_port: u16,
+    /// Skip SigV4 signature verification and accept requests signed with unknown access keys.
+    pub trust_any_access_key: bool,
+    /// Authorize every action against the effective IAM policies of the caller.
+    pub enforce_policies: bool,
+    /// Overridden IAM quotas, e.g. `UsersQuota=10,RolesQuota=5`. Other quotas keep their AWS default values.
+    pub quotas: Vec<(String, i32)>,
+    /// Number of 30 seconds time steps before and after the current one in which MFA codes are accepted.
+    pub mfa_clock_skew_steps: u16,
+    /// Secret of the bootstrap access key of the `Root` user.
+    pub root_secret_access_key: String,
 }
 
 impl AppConfig {
@@ -24,11 +41,26 @@ impl AppConfig {
         let etcd_enabled = config::get_bool_env_with_default(ENV_ETCD_ENABLED, false).into();
         let etcd_endpoints = Some(config::get_string_env_with_default(ENV_ETCD_ENDPOINTS, "").into());
         let service_port = config::get_u16_env_with_default(ENV_SERVICE_PORT, DEFAULT_SERVICE_PORT);
+        let trust_any_access_key = config::get_bool_env_with_default(ENV_TRUST_ANY_ACCESS_KEY, false).into();
+        let enforce_policies = config::get_bool_env_with_default(ENV_ENFORCE_POLICIES, false).into();
+        let quotas = parse_quotas(&config::get_string_env_with_default(ENV_QUOTAS, "").into());
+        let mfa_clock_skew_steps =
+            config::get_u16_env_with_default(ENV_MFA_CLOCK_SKEW_STEPS, constants::mfa::DEFAULT_CLOCK_SKEW_STEPS);
+        let root_secret_access_key = config::get_string_env_with_default(
+            ENV_ROOT_SECRET_ACCESS_KEY,
+            constants::access_key::DEFAULT_ROOT_SECRET_ACCESS_KEY,
+        )
+        .into();
         AppConfig {
             database_url,
             etcd_enabled,
             etcd_endpoints,
             service_port,
+            trust_any_access_key,
+            enforce_policies,
+            quotas,
+            mfa_clock_skew_steps,
+            root_secret_access_key,
         }
     }
 
@@ -39,6 +71,31 @@ impl AppConfig {
             etcd_enabled: false,
             etcd_endpoints: Some(String::from("")),
             service_port: port,
+            trust_any_access_key: false,
+            enforce_policies: false,
+            quotas: vec![],
+            mfa_clock_skew_steps: constants::mfa::DEFAULT_CLOCK_SKEW_STEPS,
+            root_secret_access_key: constants::access_key::DEFAULT_ROOT_SECRET_ACCESS_KEY.to_owned(),
         }
     }
 }
+
+/// Parses comma separated `<QuotaName>=<value>` pairs. Malformed pairs are skipped.
+fn parse_quotas(raw: &str) -> Vec<(String, i32)> {
+    raw.split(',')
+        .map(|pair| pair.trim())
+        .filter(|pair| !pair.is_empty())
+        .filter_map(|pair| {
+            match pair
+                .split_once('=')
+                .map(|(key, value)| (key.trim(), value.trim().parse::<i32>()))
+            {
+                Some((key, Ok(value))) => Some((key.to_owned(), value)),
+                _ => {
+                    log::warn!("Failed to parse IAM quota '{}'. The pair is skipped.", pair);
+                    None
+                }
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,63 @@
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use axum::Extension;
+use chrono::Utc;
+
+use local_cloud_axum::local::web::CallerIdentity;
+use local_cloud_common::arn::assumed_role_name;
+use local_cloud_db::LocalDb;
+
+use crate::http::aws::iam::db;
+use crate::http::aws::iam::db::types::service_last_accessed::InsertServiceLastAccess;
+use crate::http::aws::iam::operations::error::ActionError;
+use crate::http::aws::iam::request_params::RequestParams;
+
+/// Middleware which records the service, action, region and time of every authenticated request, per principal and
+/// per access key. The data is reported by `GetAccessKeyLastUsed`, `GetRole` and service last accessed details.
+/// Requests are recorded before authorization, since access attempts which are denied count as well.
+pub(crate) async fn track(
+    State(db): State<LocalDb>, Extension(caller): Extension<CallerIdentity>,
+    Extension(params): Extension<RequestParams>, request: Request, next: Next,
+) -> Response {
+    if let Some(action) = params.action() {
+        if let Err(err) = record_access(&db, &caller, action).await {
+            log::error!("Failed to record access of '{}' to '{}': {}", caller.access_key_id, action, err);
+        }
+    }
+
+    next.run(request).await
+}
+
+async fn record_access(db: &LocalDb, caller: &CallerIdentity, action: &str) -> Result<(), ActionError> {
+    let (principal, scope) = match (&caller.principal, &caller.scope) {
+        (Some(principal), Some(scope)) => (principal, scope),
+        // requests which are not bound to a principal are not tracked
+        _ => return Ok(()),
+    };
+
+    let current_time = Utc::now().timestamp();
+    let access = InsertServiceLastAccess {
+        account_id: caller.account_id,
+        principal_arn: principal.arn.to_owned(),
+        access_key_id: caller.access_key_id.to_owned(),
+        service_namespace: scope.service.to_owned(),
+        action_name: action.to_owned(),
+        region: scope.region.to_owned(),
+        last_accessed_date: current_time,
+    };
+    let mut tx = db.new_tx().await?;
+    db::service_last_accessed::record(&mut tx, &access).await?;
+    if let Some(role_name) = assumed_role_name(&principal.arn) {
+        db::service_last_accessed::record_role_usage(
+            &mut tx,
+            caller.account_id,
+            role_name,
+            &scope.region,
+            current_time,
+        )
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
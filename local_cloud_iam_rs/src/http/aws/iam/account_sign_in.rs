@@ -0,0 +1,73 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Serialize;
+
+use local_cloud_db::LocalDb;
+
+use crate::http::aws::iam::actions::error::ApiErrorKind;
+use crate::http::aws::iam::db;
+use crate::http::aws::iam::operations;
+use crate::http::aws::iam::operations::error::ActionError;
+use crate::http::aws::iam::service_credentials::ErrorResponse;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct AccountSignInUrlResponse {
+    account_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account_alias: Option<String>,
+    sign_in_url: String,
+}
+
+/// Returns the console sign-in URL of the account, which is based on the account alias or on the 12-digit account ID
+/// if the account has no alias.
+pub(crate) async fn get_sign_in_url(State(db): State<LocalDb>, Path(account_id): Path<String>) -> Response {
+    let id = match account_id.parse::<i64>() {
+        Ok(id) if account_id.len() == 12 => id,
+        _ => {
+            let response = ErrorResponse {
+                code: "ValidationError".to_owned(),
+                message: format!("Account ID '{account_id}' must consist of 12 digits."),
+            };
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+    match find_account_alias(&db, id).await {
+        Ok(account_alias) => {
+            let account_name = account_alias.clone().unwrap_or(account_id.clone());
+            let response = AccountSignInUrlResponse {
+                account_id,
+                account_alias,
+                sign_in_url: operations::account_alias::sign_in_url(&account_name),
+            };
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(ActionError::Service { kind, msg }) => {
+            let response = ErrorResponse {
+                code: kind.as_str().to_owned(),
+                message: msg,
+            };
+            (kind.status_code(), Json(response)).into_response()
+        }
+        Err(ActionError::Validation(error)) => {
+            let response = ErrorResponse {
+                code: "ValidationError".to_owned(),
+                message: error.to_string(),
+            };
+            (StatusCode::BAD_REQUEST, Json(response)).into_response()
+        }
+    }
+}
+
+async fn find_account_alias(db: &LocalDb, account_id: i64) -> Result<Option<String>, ActionError> {
+    let mut connection = db.new_connection().await?;
+    if !db::account::exists(connection.as_mut(), account_id).await? {
+        return Err(ActionError::new(
+            ApiErrorKind::NoSuchEntity,
+            format!("The account with ID '{account_id:0>12}' cannot be found.").as_str(),
+        ));
+    }
+    Ok(db::account::find_alias(connection.as_mut(), account_id).await?)
+}
@@ -30,6 +30,7 @@ impl ApiError {
     pub(crate) fn from_validation_error(error: &ValidationError, aws_request_id: &str) -> Self {
         let kind = match error.kind {
             ValidationErrorKind::Password => ApiErrorKind::PasswordPolicyViolation,
+            ValidationErrorKind::PolicyDocument => ApiErrorKind::MalformedPolicyDocument,
             _ => ApiErrorKind::InvalidInput,
         };
 
@@ -55,6 +56,7 @@ impl ApiError {
 
 #[derive(Debug, Display, PartialEq)]
 pub enum ApiErrorKind {
+    AccessDenied,
     ConcurrentModification,
     CredentialReportExpired,
     CredentialReportNotPresent,
@@ -87,17 +89,25 @@ pub enum ApiErrorKind {
 impl ApiErrorKind {
     pub(crate) fn status_code(&self) -> StatusCode {
         match self {
+            ApiErrorKind::AccessDenied => StatusCode::FORBIDDEN,
+            ApiErrorKind::CredentialReportExpired => StatusCode::GONE,
+            ApiErrorKind::CredentialReportNotPresent => StatusCode::GONE,
+            ApiErrorKind::CredentialReportNotReady => StatusCode::NOT_FOUND,
             ApiErrorKind::NoSuchEntity => StatusCode::NOT_FOUND,
             ApiErrorKind::LimitExceeded => StatusCode::CONFLICT,
             ApiErrorKind::EntityAlreadyExists => StatusCode::CONFLICT,
+            ApiErrorKind::DeleteConflict => StatusCode::CONFLICT,
+            ApiErrorKind::EntityTemporarilyUnmodifiable => StatusCode::CONFLICT,
+            ApiErrorKind::InvalidAuthenticationCode => StatusCode::FORBIDDEN,
             ApiErrorKind::ServiceFailure => StatusCode::INTERNAL_SERVER_ERROR,
-            ApiErrorKind::ServiceNotSupported => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiErrorKind::ServiceNotSupported => StatusCode::NOT_FOUND,
             _ => StatusCode::BAD_REQUEST,
         }
     }
 
     pub(crate) fn as_str(&self) -> &str {
         match self {
+            ApiErrorKind::AccessDenied => "AccessDenied",
             ApiErrorKind::ConcurrentModification => "ConcurrentModification",
             ApiErrorKind::CredentialReportExpired => "CredentialReportExpired",
             ApiErrorKind::CredentialReportNotPresent => "CredentialReportNotPresent",
@@ -122,7 +132,8 @@ impl ApiErrorKind {
             ApiErrorKind::PolicyNotAttachable => "PolicyNotAttachable",
             ApiErrorKind::ReportGenerationLimitExceeded => "ReportGenerationLimitExceeded",
             ApiErrorKind::ServiceFailure => "ServiceFailure",
-            ApiErrorKind::ServiceNotSupported => "ServiceNotSupported",
+            // AWS uses a different error code than the name of the exception
+            ApiErrorKind::ServiceNotSupported => "NotSupportedService",
             ApiErrorKind::UnmodifiableEntity => "UnmodifiableEntity",
             ApiErrorKind::UnrecognizedPublicKeyEncoding => "UnrecognizedPublicKeyEncoding",
         }
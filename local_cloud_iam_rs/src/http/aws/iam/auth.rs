@@ -0,0 +1,62 @@
+use axum::async_trait;
+
+use local_cloud_axum::local::web::{CallerIdentity, CredentialsResolver, Principal, ResolvedCredentials};
+use local_cloud_db::LocalDb;
+
+use crate::http::aws::iam::constants;
+use crate::http::aws::iam::db;
+use crate::http::aws::iam::db::types::access_key_status_type::AccessKeyStatusType;
+
+/// Sets the secret of the bootstrap access key of the `Root` user. Warns if the key keeps its publicly known default
+/// secret, since anyone could sign requests as `Root` then.
+pub(crate) async fn configure_root_access_key(db: &LocalDb, secret_access_key: &str) -> Result<(), sqlx::Error> {
+    if secret_access_key == constants::access_key::DEFAULT_ROOT_SECRET_ACCESS_KEY {
+        log::warn!(
+            "The bootstrap access key {} of the Root user has the publicly known secret access key. \
+            Set IAM_ROOT_SECRET_ACCESS_KEY to change it.",
+            constants::access_key::ROOT_ACCESS_KEY_ID
+        );
+    }
+    let mut connection = db.new_connection().await?;
+    db::access_key::update_secret(connection.as_mut(), constants::access_key::ROOT_ACCESS_KEY_ID, secret_access_key)
+        .await?;
+    Ok(())
+}
+
+/// Resolves access keys of IAM users stored in the `access_keys` table.
+#[derive(Debug, Clone)]
+pub(crate) struct IamCredentialsResolver {
+    db: LocalDb,
+}
+
+impl IamCredentialsResolver {
+    pub(crate) fn new(db: LocalDb) -> Self {
+        IamCredentialsResolver { db }
+    }
+}
+
+#[async_trait]
+impl CredentialsResolver for IamCredentialsResolver {
+    type Error = sqlx::Error;
+
+    async fn resolve(&self, access_key_id: &str) -> Result<Option<ResolvedCredentials>, Self::Error> {
+        let mut connection = self.db.new_connection().await?;
+        let access_key = db::access_key::find_by_access_key_id(connection.as_mut(), access_key_id).await?;
+
+        let credentials = access_key
+            .filter(|access_key| access_key.status == AccessKeyStatusType::Active)
+            .map(|access_key| ResolvedCredentials {
+                secret_access_key: access_key.secret_access_key,
+                session_token: None,
+                identity: CallerIdentity::new(
+                    access_key.access_key_id,
+                    access_key.account_id,
+                    Some(Principal {
+                        id: access_key.user_unique_id,
+                        arn: access_key.user_arn,
+                    }),
+                ),
+            });
+        Ok(credentials)
+    }
+}
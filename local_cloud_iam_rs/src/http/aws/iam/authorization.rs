@@ -0,0 +1,311 @@
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use axum::Extension;
+use chrono::Utc;
+use sqlx::{Sqlite, Transaction};
+use uuid::Uuid;
+
+use local_cloud_axum::local::web::{CallerIdentity, Principal};
+use local_cloud_common::arn::assumed_role_name;
+use local_cloud_db::LocalDb;
+use local_cloud_iam_policy_document::evaluator::{Decision, PolicyKind, RequestContext};
+
+use crate::http::aws::iam::actions::error::{ApiError, ApiErrorKind};
+use crate::http::aws::iam::db::types::role::SelectRoleWithDetails;
+use crate::http::aws::iam::db::types::user::SelectUser;
+use crate::http::aws::iam::operations::ctx::OperationCtx;
+use crate::http::aws::iam::operations::error::ActionError;
+use crate::http::aws::iam::request_params::{error_response, RequestParams};
+use crate::http::aws::iam::{constants, db, operations};
+
+/// Request parameters which identify the resource of an action paired with the type of the resource.
+/// The first parameter present in the request wins, e.g. `AddUserToGroup` is authorized against the group.
+const RESOURCE_NAME_PARAMS: [(&str, &str); 6] = [
+    ("InstanceProfileName", "instance-profile"),
+    ("GroupName", "group"),
+    ("RoleName", "role"),
+    ("UserName", "user"),
+    ("ServerCertificateName", "server-certificate"),
+    ("PolicyName", "policy"),
+];
+const RESOURCE_ARN_PARAMS: [&str; 4] = [
+    "PolicyArn",
+    "OpenIDConnectProviderArn",
+    "SAMLProviderArn",
+    "SerialNumber",
+];
+
+/// Actions which target the caller itself when `UserName` is not specified.
+const SELF_SERVICE_ACTIONS: [&str; 17] = [
+    "ChangePassword",
+    "CreateAccessKey",
+    "DeleteAccessKey",
+    "DeleteSigningCertificate",
+    "DeleteSSHPublicKey",
+    "GetLoginProfile",
+    "GetSSHPublicKey",
+    "GetUser",
+    "ListAccessKeys",
+    "ListMFADevices",
+    "ListServiceSpecificCredentials",
+    "ListSigningCertificates",
+    "ListSSHPublicKeys",
+    "UpdateAccessKey",
+    "UpdateSigningCertificate",
+    "UpdateSSHPublicKey",
+    "UploadSigningCertificate",
+];
+
+/// IAM entity whose policies apply to the caller. Role sessions are authorized against the policies of the role.
+enum CallerEntity {
+    User(SelectUser),
+    Role(SelectRoleWithDetails),
+}
+
+impl CallerEntity {
+    /// ARN of the user or role which owns the policies.
+    fn arn(&self) -> &str {
+        match self {
+            CallerEntity::User(user) => &user.arn,
+            CallerEntity::Role(role) => &role.arn,
+        }
+    }
+
+    /// Adds global condition keys which describe the caller, e.g. `aws:PrincipalType` is `AssumedRole` for sessions.
+    fn add_context_values(&self, request: RequestContext, principal: &Principal) -> RequestContext {
+        let request = request
+            .with_context_values("aws:PrincipalArn", vec![self.arn().to_owned()])
+            .with_context_values("aws:PrincipalAccount", vec![account_of(self.arn()).to_owned()])
+            .with_context_values("aws:userid", vec![principal.id.clone()]);
+        match self {
+            CallerEntity::User(user) => request
+                .with_context_values("aws:PrincipalType", vec!["User".to_owned()])
+                .with_context_values("aws:username", vec![user.username.clone()]),
+            CallerEntity::Role(_role) => {
+                request.with_context_values("aws:PrincipalType", vec!["AssumedRole".to_owned()])
+            }
+        }
+    }
+}
+
+/// Middleware which authorizes the action against the effective policies of the caller before it is executed.
+/// Callers which are not bound to an IAM user or role (e.g. unknown keys accepted in `trust any key` mode) are denied,
+/// the `Root` user is not restricted.
+pub(crate) async fn authorize(
+    State(db): State<LocalDb>, Extension(caller): Extension<CallerIdentity>,
+    Extension(params): Extension<RequestParams>, request: Request, next: Next,
+) -> Response {
+    let aws_request_id = Uuid::new_v4().to_string();
+    let result = match params.action() {
+        Some(action) => authorize_action(&db, &caller, action, &params, &aws_request_id).await,
+        // let the handler reject the request
+        None => Ok(()),
+    };
+    if let Err(error) = result {
+        let error = match error {
+            ActionError::Service { kind, msg } => ApiError::new(kind, msg, &aws_request_id),
+            ActionError::Validation(error) => ApiError::from_validation_error(&error, &aws_request_id),
+        };
+        return error_response(error);
+    }
+
+    next.run(request).await
+}
+
+async fn authorize_action(
+    db: &LocalDb, caller: &CallerIdentity, action: &str, params: &RequestParams, aws_request_id: &str,
+) -> Result<(), ActionError> {
+    let principal = match &caller.principal {
+        None => {
+            return Err(ActionError::new(
+                ApiErrorKind::AccessDenied,
+                format!(
+                    "Access key {} is not bound to an IAM principal, so IAM policies can't be evaluated.",
+                    &caller.access_key_id
+                )
+                .as_str(),
+            ))
+        }
+        Some(principal) => principal,
+    };
+
+    if principal.id == constants::user::ROOT_USER_ID {
+        return Ok(());
+    }
+
+    let iam_action = format!("iam:{action}");
+    let mut tx = db.new_tx().await?;
+    let entity = match find_caller_entity(&mut tx, caller.account_id, principal).await? {
+        Some(entity) => entity,
+        None => return Err(access_denied(&principal.arn, &iam_action, "*", Decision::ImplicitDeny, false)),
+    };
+
+    let ctx = OperationCtx::new(caller, aws_request_id);
+    let policies = operations::simulate::find_principal_policies(&mut tx, &ctx, entity.arn()).await?;
+    let policy_set = operations::simulate::create_policy_set(&policies)?;
+    let resource = resolve_resource(&mut tx, &ctx, &principal.arn, action, params).await?;
+    tx.commit().await?;
+
+    let current_time = Utc::now();
+    let request = RequestContext::new(&iam_action, &resource).with_principal(&principal.arn);
+    let request = entity
+        .add_context_values(request, principal)
+        .with_context_values("aws:CurrentTime", vec![current_time.to_rfc3339()])
+        .with_context_values("aws:EpochTime", vec![current_time.timestamp().to_string()])
+        .with_context_values("aws:SecureTransport", vec!["false".to_owned()]);
+    let evaluation = policy_set.evaluate(&request);
+    match evaluation.decision {
+        Decision::Allow => Ok(()),
+        decision => {
+            let denied_by_boundary = evaluation.allowed_by_permissions_boundary == Some(false)
+                || evaluation
+                    .matched_statements
+                    .iter()
+                    .any(|statement| statement.source_policy_kind == PolicyKind::PermissionsBoundary);
+            Err(access_denied(&principal.arn, &iam_action, &resource, decision, denied_by_boundary))
+        }
+    }
+}
+
+/// Finds the IAM user of the caller, or the role of a role session like
+/// `arn:aws:sts::123456789012:assumed-role/name/session`. Returns `None` if the entity doesn't exist anymore.
+async fn find_caller_entity<'a>(
+    tx: &mut Transaction<'a, Sqlite>, account_id: i64, principal: &Principal,
+) -> Result<Option<CallerEntity>, ActionError> {
+    if let Some(role_name) = assumed_role_name(&principal.arn) {
+        let role = db::role::find_by_name(tx.as_mut(), account_id, role_name).await?;
+        return Ok(role.map(CallerEntity::Role));
+    }
+    if let Some(role_name) = principal
+        .arn
+        .split_once(":role/")
+        .map(|(_, path)| path.rsplit('/').next().unwrap())
+    {
+        let role = db::role::find_by_name(tx.as_mut(), account_id, role_name)
+            .await?
+            .filter(|role| role.arn == principal.arn);
+        return Ok(role.map(CallerEntity::Role));
+    }
+    let user = db::user::find_by_unique_id(tx.as_mut(), &principal.id).await?;
+    Ok(user.map(CallerEntity::User))
+}
+
+/// Identifies ARN of the resource targeted by the action. Uses the ARN of the existing entity when it is found, so
+/// that its path is taken into account.
+async fn resolve_resource<'a>(
+    tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, caller_arn: &str, action: &str, params: &RequestParams,
+) -> Result<String, ActionError> {
+    let account = account_of(caller_arn);
+    for (param, resource_type) in RESOURCE_NAME_PARAMS {
+        if let Some(name) = params.get(param).map(|name| name.trim()) {
+            let existing_arn = match resource_type {
+                "user" => db::user::find_by_name(tx.as_mut(), ctx.account_id, name)
+                    .await?
+                    .map(|user| user.arn),
+                "group" => db::group::find_by_name(tx.as_mut(), ctx.account_id, name)
+                    .await?
+                    .map(|group| group.arn),
+                "role" => db::role::find_by_name(tx.as_mut(), ctx.account_id, name)
+                    .await?
+                    .map(|role| role.arn),
+                _ => None,
+            };
+            let arn = existing_arn.unwrap_or_else(|| {
+                let path = params.get("Path").unwrap_or("/");
+                format!("arn:aws:iam::{account}:{resource_type}{path}{name}")
+            });
+            return Ok(arn);
+        }
+    }
+    for param in RESOURCE_ARN_PARAMS {
+        if let Some(arn) = params.get(param).filter(|arn| arn.starts_with("arn:")) {
+            return Ok(arn.to_owned());
+        }
+    }
+    if SELF_SERVICE_ACTIONS.contains(&action) {
+        return Ok(caller_arn.to_owned());
+    }
+    Ok("*".to_owned())
+}
+
+fn account_of(arn: &str) -> &str {
+    arn.split(':').nth(4).unwrap_or_default()
+}
+
+fn access_denied(
+    principal_arn: &str, action: &str, resource: &str, decision: Decision, denied_by_boundary: bool,
+) -> ActionError {
+    let reason = match (decision, denied_by_boundary) {
+        (Decision::ExplicitDeny, true) => "with an explicit deny in a permissions boundary".to_owned(),
+        (Decision::ExplicitDeny, false) => "with an explicit deny in an identity-based policy".to_owned(),
+        (_, true) => format!("because no permissions boundary allows the {action} action"),
+        (_, false) => format!("because no identity-based policy allows the {action} action"),
+    };
+    ActionError::new(
+        ApiErrorKind::AccessDenied,
+        format!("User: {principal_arn} is not authorized to perform: {action} on resource: {resource} {reason}")
+            .as_str(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use local_cloud_axum::local::web::{CallerIdentity, Principal};
+    use local_cloud_db::LocalDb;
+
+    use crate::http::aws::iam::actions::error::ApiErrorKind;
+    use crate::http::aws::iam::operations;
+    use crate::http::aws::iam::operations::ctx::OperationCtx;
+    use crate::http::aws::iam::operations::error::ActionError;
+    use crate::http::aws::iam::request_params::RequestParams;
+
+    const TRUST_POLICY: &str = r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Principal":{"AWS":"*"},"Action":"sts:AssumeRole"}]}"#;
+    const ALLOW_LIST_ROLES_POLICY: &str =
+        r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Action":"iam:ListRoles","Resource":"*"}]}"#;
+
+    fn params(action: &str) -> RequestParams {
+        [("Action".to_owned(), action.to_owned())].into_iter().collect()
+    }
+
+    #[tokio::test]
+    async fn test_authorize_assumed_role_session() {
+        let database_url = format!("file:{}?mode=memory&cache=shared", Uuid::new_v4());
+        let db = LocalDb::new(&database_url, &sqlx::migrate!()).await.unwrap();
+
+        let ctx = OperationCtx::new(&CallerIdentity::new("", 1, None), "");
+        let mut tx = db.new_tx().await.unwrap();
+        let query = format!("RoleName=app&AssumeRolePolicyDocument={}", urlencoding::encode(TRUST_POLICY));
+        operations::role::create_role(&mut tx, &ctx, &serde_urlencoded::from_str(&query).unwrap())
+            .await
+            .unwrap();
+        let query = format!(
+            "RoleName=app&PolicyName=list-roles&PolicyDocument={}",
+            urlencoding::encode(ALLOW_LIST_ROLES_POLICY)
+        );
+        operations::role::put_role_policy(&mut tx, &ctx, &serde_urlencoded::from_str(&query).unwrap())
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let principal = Principal {
+            id: "AROAEXAMPLE:session".to_owned(),
+            arn: "arn:aws:sts::000000000001:assumed-role/app/session".to_owned(),
+        };
+        let caller = CallerIdentity::new("ASIAEXAMPLE", 1, Some(principal));
+
+        let result = super::authorize_action(&db, &caller, "ListRoles", &params("ListRoles"), "").await;
+        assert!(result.is_ok());
+
+        let result = super::authorize_action(&db, &caller, "ListUsers", &params("ListUsers"), "").await;
+        match result.unwrap_err() {
+            ActionError::Service { kind, msg } => {
+                assert_eq!(kind, ApiErrorKind::AccessDenied);
+                assert!(msg.starts_with("User: arn:aws:sts::000000000001:assumed-role/app/session is not authorized"));
+            }
+            error => panic!("Unexpected error: {error}"),
+        }
+    }
+}
@@ -6,19 +6,26 @@ pub(crate) mod tag {
     pub(crate) const MAX_COUNT: usize = 50;
 }
 
+pub(crate) mod account {
+    /// An account has at most one alias. Unlike other quotas this one could not be configured.
+    pub(crate) const MAX_ALIASES: i32 = 1;
+}
+
 pub(crate) mod group {
     pub(crate) const PREFIX: &str = "AIDG";
 }
 
 pub(crate) mod policy_version {
     pub(crate) const PREFIX: &str = "ANVA";
-    pub(crate) const POLICY_VERSION_MAX_COUNT: usize = 5;
 }
 
 pub(crate) mod policy {
     use std::time::Duration;
 
     pub(crate) const PREFIX: &str = "ANPA";
+    /// ID of the account which owns AWS managed policies. The policies are visible to every account.
+    pub(crate) const AWS_MANAGED_ACCOUNT_ID: i64 = 0;
+    pub(crate) const AWS_MANAGED_ARN_PREFIX: &str = "arn:aws:iam::aws:policy";
 
     /// 15 minutes
     pub(crate) const ROLE_SESSION_DURATION_MIN_SEC: Duration = Duration::new(900, 0);
@@ -28,17 +35,57 @@ pub(crate) mod policy {
     pub(crate) const ROLE_SESSION_DEFAULT_DURATION_SEC: Duration = Duration::new(3600, 0);
 }
 
+pub(crate) mod report {
+    use std::time::Duration;
+
+    /// Credential reports older than 4 hours are expired.
+    pub(crate) const CREDENTIAL_REPORT_EXPIRATION: Duration = Duration::new(4 * 3600, 0);
+}
+
+pub(crate) mod service_last_accessed {
+    /// Names of services which are reported by service last accessed details. Namespaces which are not listed here
+    /// are reported with the namespace as the service name.
+    pub(crate) const SERVICE_NAMES: [(&str, &str); 12] = [
+        ("cloudwatch", "Amazon CloudWatch"),
+        ("dynamodb", "Amazon DynamoDB"),
+        ("ec2", "Amazon EC2"),
+        ("ecr", "Amazon Elastic Container Registry"),
+        ("iam", "AWS Identity and Access Management"),
+        ("kms", "AWS Key Management Service"),
+        ("lambda", "AWS Lambda"),
+        ("logs", "Amazon CloudWatch Logs"),
+        ("s3", "Amazon S3"),
+        ("sns", "Amazon SNS"),
+        ("sqs", "Amazon SQS"),
+        ("sts", "AWS Security Token Service"),
+    ];
+}
+
+pub(crate) mod job {
+    use std::time::Duration;
+
+    /// How often the background job runner checks for pending jobs.
+    pub(crate) const POLL_INTERVAL: Duration = Duration::from_millis(100);
+    /// Jobs which are in progress for longer are considered abandoned (e.g. the server has been stopped in the middle
+    /// of the job) and are restarted.
+    pub(crate) const IN_PROGRESS_TIMEOUT: Duration = Duration::from_secs(60);
+}
+
 pub(crate) mod server_certificate {
     pub(crate) const PREFIX: &str = "ASCA";
 }
 
 pub(crate) mod user {
     pub(crate) const PREFIX: &str = "AIDA";
+    /// Unique ID of the bootstrap `Root` user. Policies are not enforced for this user.
+    pub(crate) const ROOT_USER_ID: &str = "AIDAHOMECLOUDROOT101A";
 }
 
 pub(crate) mod role {
     pub(crate) const PREFIX: &str = "AROA";
     pub(crate) const DEFAULT_MAX_SESSION_DURATION: i32 = 3600;
+    /// Service-linked roles are created under `/aws-service-role/<service name>/` path.
+    pub(crate) const SERVICE_LINKED_PATH_PREFIX: &str = "/aws-service-role/";
 }
 
 pub(crate) mod instance_profile {
@@ -52,8 +99,36 @@ pub(crate) mod open_id_connect_provider {
 pub(crate) mod mfa {
     pub(crate) const SEED_LENGTH: usize = 20;
     pub(crate) const DEVICE_MAX_COUNT_PER_USER: usize = 8;
+    /// Number of time steps (30 seconds each) before and after the current one which are accepted by default.
+    pub(crate) const DEFAULT_CLOCK_SKEW_STEPS: u16 = 1;
+    /// Resynchronization looks for codes of a device which clock has drifted up to 10 minutes.
+    pub(crate) const RESYNC_WINDOW_STEPS: i64 = 20;
+    /// Issuer which is shown by authenticator apps for the devices registered with the `otpauth://` URI.
+    pub(crate) const ISSUER: &str = "Amazon Web Services";
 }
 
 pub(crate) mod ssh_public_key {
     pub(crate) const PREFIX: &str = "APKA";
 }
+
+pub(crate) mod service_specific_credential {
+    pub(crate) const PREFIX: &str = "ACCA";
+    pub(crate) const PASSWORD_LENGTH: usize = 44;
+    pub(crate) const MAX_COUNT_PER_SERVICE: usize = 2;
+    /// Services which support service-specific credentials: CodeCommit, Keyspaces and Bedrock.
+    pub(crate) const SUPPORTED_SERVICES: [&str; 3] = [
+        "codecommit.amazonaws.com",
+        "cassandra.amazonaws.com",
+        "bedrock.amazonaws.com",
+    ];
+}
+
+pub(crate) mod access_key {
+    pub(crate) const PREFIX: &str = "AKIA";
+    pub(crate) const ID_LENGTH: usize = 20;
+    pub(crate) const SECRET_LENGTH: usize = 40;
+    /// Bootstrap access key of the `Root` user which is created by the migrations.
+    pub(crate) const ROOT_ACCESS_KEY_ID: &str = "AKIAIOSFODNN201ADMIN";
+    /// Publicly known secret of the bootstrap access key which is used unless another one is configured.
+    pub(crate) const DEFAULT_ROOT_SECRET_ACCESS_KEY: &str = "secret_access_key";
+}
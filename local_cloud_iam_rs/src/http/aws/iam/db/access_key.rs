@@ -0,0 +1,182 @@
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Error, Executor, FromRow, QueryBuilder, Row, Sqlite, Transaction};
+
+use crate::http::aws::iam::db::types::access_key::{
+    InsertAccessKey, ListAccessKeysQuery, SelectAccessKey, SelectAccessKeyLastUsed, UpdateAccessKeyQuery,
+};
+
+pub(crate) async fn create<'a>(
+    tx: &mut Transaction<'a, Sqlite>, access_key: &mut InsertAccessKey,
+) -> Result<(), Error> {
+    let result = sqlx::query(
+        r#"INSERT INTO access_keys (
+                    account_id,
+                    user_id,
+                    access_key_id,
+                    secret_access_key,
+                    status,
+                    create_date
+                )
+                VALUES ($1, $2, $3, $4, $5, $6)
+                RETURNING id"#,
+    )
+    .bind(access_key.account_id)
+    .bind(access_key.user_id)
+    .bind(&access_key.access_key_id)
+    .bind(&access_key.secret_access_key)
+    .bind(access_key.status.as_i32())
+    .bind(access_key.create_date)
+    .map(|row: SqliteRow| row.get::<i64, &str>("id"))
+    .fetch_one(tx.as_mut())
+    .await?;
+
+    access_key.id = Some(result);
+    Ok(())
+}
+
+pub(crate) async fn count_by_user_id<'a, E>(executor: E, user_id: i64) -> Result<usize, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("SELECT COUNT(id) AS count FROM access_keys WHERE user_id = $1")
+        .bind(user_id)
+        .map(|row: SqliteRow| row.get::<i32, &str>("count"))
+        .fetch_one(executor)
+        .await?;
+    Ok(result as usize)
+}
+
+pub(crate) async fn find_by_access_key_id<'a, E>(
+    executor: E, access_key_id: &str,
+) -> Result<Option<SelectAccessKey>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let access_key = sqlx::query(
+        r#"
+        SELECT
+            ak.id AS id,
+            ak.account_id AS account_id,
+            ak.user_id AS user_id,
+            u.username AS user_name,
+            u.arn AS user_arn,
+            u.user_id AS user_unique_id,
+            ak.access_key_id AS access_key_id,
+            ak.secret_access_key AS secret_access_key,
+            ak.status AS status,
+            ak.create_date AS create_date
+        FROM access_keys ak LEFT JOIN users u ON ak.user_id = u.id
+        WHERE ak.access_key_id = $1"#,
+    )
+    .bind(access_key_id)
+    .map(|row: SqliteRow| SelectAccessKey::from_row(&row).unwrap())
+    .fetch_optional(executor)
+    .await?;
+    Ok(access_key)
+}
+
+pub(crate) async fn find_last_used<'a, E>(
+    executor: E, account_id: i64, access_key_id: &str,
+) -> Result<Option<SelectAccessKeyLastUsed>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"
+        SELECT
+            u.username AS user_name,
+            ak.last_used_date AS last_used_date,
+            r.region AS last_used_region,
+            ak.last_used_service AS last_used_service
+        FROM access_keys ak
+            LEFT JOIN users u ON ak.user_id = u.id
+            LEFT JOIN regions r ON ak.last_used_region_id = r.id
+        WHERE ak.account_id = $1 AND ak.access_key_id = $2"#,
+    )
+    .bind(account_id)
+    .bind(access_key_id)
+    .map(|row: SqliteRow| SelectAccessKeyLastUsed::from_row(&row).unwrap())
+    .fetch_optional(executor)
+    .await?;
+    Ok(result)
+}
+
+pub(crate) async fn list<'a, E>(executor: E, query: &ListAccessKeysQuery) -> Result<Vec<SelectAccessKey>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new(
+        r#"
+        SELECT
+            ak.id AS id,
+            ak.account_id AS account_id,
+            ak.user_id AS user_id,
+            u.username AS user_name,
+            u.arn AS user_arn,
+            u.user_id AS user_unique_id,
+            ak.access_key_id AS access_key_id,
+            ak.secret_access_key AS secret_access_key,
+            ak.status AS status,
+            ak.create_date AS create_date
+        FROM access_keys ak LEFT JOIN users u ON ak.user_id = u.id
+        WHERE ak.user_id = "#,
+    );
+    let access_keys = query_builder
+        .push_bind(query.user_id)
+        .push(" ORDER BY ak.id ASC")
+        .push(" LIMIT ")
+        .push_bind(query.limit + 1) // request more elements than we need to return. used to identify if NextPage token needs to be generated
+        .push(" OFFSET ")
+        .push_bind(query.skip)
+        .build()
+        .map(|row: SqliteRow| SelectAccessKey::from_row(&row).unwrap())
+        .fetch_all(executor)
+        .await?;
+    Ok(access_keys)
+}
+
+pub(crate) async fn update<'a, E>(executor: E, query: &UpdateAccessKeyQuery) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        "UPDATE access_keys \
+            SET status=$1 \
+            WHERE access_key_id=$2 AND user_id=$3",
+    )
+    .bind(query.status.as_i32())
+    .bind(&query.access_key_id)
+    .bind(query.user_id)
+    .execute(executor)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+pub(crate) async fn update_secret<'a, E>(
+    executor: E, access_key_id: &str, secret_access_key: &str,
+) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("UPDATE access_keys SET secret_access_key=$1 WHERE access_key_id=$2")
+        .bind(secret_access_key)
+        .bind(access_key_id)
+        .execute(executor)
+        .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+pub(crate) async fn delete<'a, E>(executor: E, user_id: i64, access_key_id: &str) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("DELETE FROM access_keys WHERE access_key_id=$1 AND user_id=$2")
+        .bind(access_key_id)
+        .bind(user_id)
+        .execute(executor)
+        .await?;
+
+    Ok(result.rows_affected() == 1)
+}
@@ -0,0 +1,173 @@
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Error, Executor, FromRow, Row, Sqlite};
+
+use crate::http::aws::iam::constants;
+use crate::http::aws::iam::db::types::account::{SelectAccountSummary, SelectAuthorizationEntityCounts};
+use crate::http::aws::iam::db::types::policy_type::PolicyType;
+use crate::http::aws::iam::quotas::Quota;
+
+// counts of entities limited by quotas; `$1` is the account ID and `$2` is the ID of the root user
+const USERS_COUNT: &str = "SELECT COUNT(id) FROM users WHERE account_id = $1 AND user_id != $2";
+const GROUPS_COUNT: &str = "SELECT COUNT(id) FROM groups WHERE account_id = $1";
+const ROLES_COUNT: &str = "SELECT COUNT(id) FROM roles WHERE account_id = $1";
+const POLICIES_COUNT: &str = "SELECT COUNT(id) FROM policies WHERE account_id = $1";
+const INSTANCE_PROFILES_COUNT: &str = "SELECT COUNT(id) FROM instance_profiles WHERE account_id = $1";
+const SERVER_CERTIFICATES_COUNT: &str = "SELECT COUNT(id) FROM server_certificates WHERE account_id = $1";
+const PROVIDERS_COUNT: &str = "SELECT (SELECT COUNT(id) FROM saml_providers WHERE account_id = $1) \
+    + (SELECT COUNT(id) FROM open_id_connect_providers WHERE account_id = $1)";
+const POLICY_VERSIONS_IN_USE_COUNT: &str = "SELECT \
+    (SELECT COUNT(*) FROM policy_users pu JOIN users u ON pu.user_id = u.id WHERE u.account_id = $1) \
+    + (SELECT COUNT(*) FROM policy_groups pg JOIN groups g ON pg.group_id = g.id WHERE g.account_id = $1) \
+    + (SELECT COUNT(*) FROM policy_roles pr JOIN roles r ON pr.role_id = r.id WHERE r.account_id = $1)";
+
+/// Counts IAM entities of the account. AWS managed policies are not owned by the account and are not counted.
+pub(crate) async fn find_summary<'a, E>(executor: E, account_id: i64) -> Result<SelectAccountSummary, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let summary = sqlx::query(&format!(
+        r#"SELECT
+                ({USERS_COUNT}) AS users,
+                ({GROUPS_COUNT}) AS groups,
+                ({ROLES_COUNT}) AS roles,
+                ({POLICIES_COUNT}) AS policies,
+                ({INSTANCE_PROFILES_COUNT}) AS instance_profiles,
+                ({SERVER_CERTIFICATES_COUNT}) AS server_certificates,
+                ({PROVIDERS_COUNT}) AS providers,
+                ({POLICY_VERSIONS_IN_USE_COUNT}) AS policy_versions_in_use,
+                (SELECT COUNT(id) FROM mfa_devices WHERE account_id = $1) AS mfa_devices,
+                (SELECT COUNT(id) FROM mfa_devices WHERE account_id = $1 AND user_id IS NOT NULL AND enable_date IS NOT NULL)
+                    AS mfa_devices_in_use,
+                EXISTS(
+                    SELECT 1 FROM mfa_devices md JOIN users u ON md.user_id = u.id
+                    WHERE md.account_id = $1 AND u.user_id = $2 AND md.enable_date IS NOT NULL
+                ) AS account_mfa_enabled,
+                EXISTS(
+                    SELECT 1 FROM access_keys ak JOIN users u ON ak.user_id = u.id
+                    WHERE ak.account_id = $1 AND u.user_id = $2
+                ) AS account_access_keys_present,
+                EXISTS(
+                    SELECT 1 FROM signing_certificates sc JOIN users u ON sc.user_id = u.id
+                    WHERE sc.account_id = $1 AND u.user_id = $2
+                ) AS account_signing_certificates_present"#
+    ))
+    .bind(account_id)
+    .bind(constants::user::ROOT_USER_ID)
+    .map(|row: SqliteRow| SelectAccountSummary::from_row(&row).unwrap())
+    .fetch_one(executor)
+    .await?;
+    Ok(summary)
+}
+
+/// Counts entities of the account which are limited by the quota. Returns `None` for quotas which limit entities per
+/// user, group, role or policy.
+pub(crate) async fn count_for_quota<'a, E>(executor: E, account_id: i64, quota: Quota) -> Result<Option<i32>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let sql = match quota {
+        Quota::Users => USERS_COUNT,
+        Quota::Groups => GROUPS_COUNT,
+        Quota::Roles => ROLES_COUNT,
+        Quota::Policies => POLICIES_COUNT,
+        Quota::InstanceProfiles => INSTANCE_PROFILES_COUNT,
+        Quota::ServerCertificates => SERVER_CERTIFICATES_COUNT,
+        Quota::Providers => PROVIDERS_COUNT,
+        Quota::PolicyVersionsInUse => POLICY_VERSIONS_IN_USE_COUNT,
+        _ => return Ok(None),
+    };
+    let mut query = sqlx::query(sql).bind(account_id);
+    if quota == Quota::Users {
+        query = query.bind(constants::user::ROOT_USER_ID);
+    }
+    let count = query
+        .map(|row: SqliteRow| row.get::<i32, usize>(0))
+        .fetch_one(executor)
+        .await?;
+    Ok(Some(count))
+}
+
+/// Counts entities reported by `GetAccountAuthorizationDetails`, so that only entities of the requested page are
+/// loaded. Only AWS managed policies which are attached to entities of the account are counted.
+pub(crate) async fn count_authorization_entities<'a, E>(
+    executor: E, account_id: i64,
+) -> Result<SelectAuthorizationEntityCounts, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let counts = sqlx::query(
+        r#"SELECT
+                (SELECT COUNT(id) FROM users WHERE account_id = $1 AND user_id != $2) AS users,
+                (SELECT COUNT(id) FROM groups WHERE account_id = $1) AS groups,
+                (SELECT COUNT(id) FROM roles WHERE account_id = $1) AS roles,
+                (SELECT COUNT(id) FROM policies WHERE account_id IN ($1, $3) AND policy_type = $4)
+                    AS local_managed_policies,
+                (SELECT COUNT(p.id) FROM policies p
+                    WHERE p.account_id IN ($1, $3) AND p.policy_type = $5 AND (
+                        EXISTS(SELECT 1 FROM policy_users pu JOIN users u ON pu.user_id = u.id
+                            WHERE pu.policy_id = p.id AND u.account_id = $1)
+                        OR EXISTS(SELECT 1 FROM policy_groups pg JOIN groups g ON pg.group_id = g.id
+                            WHERE pg.policy_id = p.id AND g.account_id = $1)
+                        OR EXISTS(SELECT 1 FROM policy_roles pr JOIN roles r ON pr.role_id = r.id
+                            WHERE pr.policy_id = p.id AND r.account_id = $1)
+                    )) AS aws_managed_policies"#,
+    )
+    .bind(account_id)
+    .bind(constants::user::ROOT_USER_ID)
+    .bind(constants::policy::AWS_MANAGED_ACCOUNT_ID)
+    .bind(PolicyType::CustomerManaged.as_i32())
+    .bind(PolicyType::LocalCloudManaged.as_i32())
+    .map(|row: SqliteRow| SelectAuthorizationEntityCounts::from_row(&row).unwrap())
+    .fetch_one(executor)
+    .await?;
+    Ok(counts)
+}
+
+pub(crate) async fn exists<'a, E>(executor: E, account_id: i64) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let id = sqlx::query("SELECT id FROM accounts WHERE id = $1")
+        .bind(account_id)
+        .fetch_optional(executor)
+        .await?;
+    Ok(id.is_some())
+}
+
+/// Returns the alias of the account, or `None` if the account has no alias.
+pub(crate) async fn find_alias<'a, E>(executor: E, account_id: i64) -> Result<Option<String>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let alias = sqlx::query("SELECT alias FROM accounts WHERE id = $1 AND alias != ''")
+        .bind(account_id)
+        .map(|row: SqliteRow| row.get::<String, &str>("alias"))
+        .fetch_optional(executor)
+        .await?;
+    Ok(alias)
+}
+
+pub(crate) async fn find_id_by_alias<'a, E>(executor: E, alias: &str) -> Result<Option<i64>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let id = sqlx::query("SELECT id FROM accounts WHERE alias = $1")
+        .bind(alias)
+        .map(|row: SqliteRow| row.get::<i64, &str>("id"))
+        .fetch_optional(executor)
+        .await?;
+    Ok(id)
+}
+
+/// Sets the alias of the account. `None` removes the alias.
+pub(crate) async fn update_alias<'a, E>(executor: E, account_id: i64, alias: Option<&str>) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("UPDATE accounts SET alias = $1 WHERE id = $2")
+        .bind(alias.unwrap_or_default())
+        .bind(account_id)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected() == 1)
+}
@@ -0,0 +1,232 @@
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Error, Executor, FromRow, Sqlite};
+
+use crate::http::aws::iam::db::types::access_key_status_type::AccessKeyStatusType;
+use crate::http::aws::iam::db::types::credential_report::{
+    ReportStateType, SelectCredentialReport, SelectReportAccessKey, SelectReportCredential, SelectReportUser,
+};
+use crate::http::aws::iam::db::types::signing_certificate_status_type::SigningCertificateStatusType;
+
+/// Starts generation of a new credential report of the account. The previous report (if any) is discarded.
+pub(crate) async fn start<'a, E>(executor: E, account_id: i64, create_date: i64) -> Result<(), Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    sqlx::query(
+        r#"INSERT INTO credential_reports (account_id, state, content, create_date, start_date, generated_time)
+            VALUES ($1, $2, NULL, $3, NULL, NULL)
+            ON CONFLICT (account_id) DO UPDATE SET
+                state = excluded.state,
+                content = NULL,
+                create_date = excluded.create_date,
+                start_date = NULL,
+                generated_time = NULL"#,
+    )
+    .bind(account_id)
+    .bind(ReportStateType::Started.as_i32())
+    .bind(create_date)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+pub(crate) async fn find_by_account_id<'a, E>(
+    executor: E, account_id: i64,
+) -> Result<Option<SelectCredentialReport>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"SELECT id, account_id, state, content, create_date, generated_time
+            FROM credential_reports
+            WHERE account_id = $1"#,
+    )
+    .bind(account_id)
+    .map(|row: SqliteRow| SelectCredentialReport::from_row(&row).unwrap())
+    .fetch_optional(executor)
+    .await?;
+    Ok(result)
+}
+
+/// Returns reports which are waiting to be generated.
+pub(crate) async fn find_started<'a, E>(executor: E) -> Result<Vec<SelectCredentialReport>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"SELECT id, account_id, state, NULL AS content, create_date, generated_time
+            FROM credential_reports
+            WHERE state = $1"#,
+    )
+    .bind(ReportStateType::Started.as_i32())
+    .map(|row: SqliteRow| SelectCredentialReport::from_row(&row).unwrap())
+    .fetch_all(executor)
+    .await?;
+    Ok(result)
+}
+
+/// Moves the report from `STARTED` to `INPROGRESS` state. Returns `false` if the report has been already taken.
+pub(crate) async fn mark_in_progress<'a, E>(executor: E, id: i64, start_date: i64) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("UPDATE credential_reports SET state = $3, start_date = $4 WHERE id = $1 AND state = $2")
+        .bind(id)
+        .bind(ReportStateType::Started.as_i32())
+        .bind(ReportStateType::InProgress.as_i32())
+        .bind(start_date)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+/// Moves reports which have been `INPROGRESS` since before `start_date` back to `STARTED` state.
+pub(crate) async fn restart_stale<'a, E>(executor: E, start_date: i64) -> Result<u64, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"UPDATE credential_reports SET state = $2, start_date = NULL
+            WHERE state = $1 AND (start_date IS NULL OR start_date < $3)"#,
+    )
+    .bind(ReportStateType::InProgress.as_i32())
+    .bind(ReportStateType::Started.as_i32())
+    .bind(start_date)
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Deletes the `INPROGRESS` report which has failed to be generated.
+pub(crate) async fn discard<'a, E>(executor: E, id: i64) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("DELETE FROM credential_reports WHERE id = $1 AND state = $2")
+        .bind(id)
+        .bind(ReportStateType::InProgress.as_i32())
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+pub(crate) async fn complete<'a, E>(executor: E, id: i64, content: &[u8], generated_time: i64) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        "UPDATE credential_reports SET state = $2, content = $3, generated_time = $4 WHERE id = $1 AND state = $5",
+    )
+    .bind(id)
+    .bind(ReportStateType::Complete.as_i32())
+    .bind(content)
+    .bind(generated_time)
+    .bind(ReportStateType::InProgress.as_i32())
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+pub(crate) async fn list_users<'a, E>(executor: E, account_id: i64) -> Result<Vec<SelectReportUser>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"SELECT
+                u.id AS id,
+                u.username AS username,
+                u.arn AS arn,
+                u.user_id AS user_id,
+                u.create_date AS create_date,
+                lp.password_change_date AS password_change_date,
+                EXISTS(
+                    SELECT 1 FROM mfa_devices md WHERE md.user_id = u.id AND md.enable_date IS NOT NULL
+                ) AS mfa_active
+            FROM users u LEFT JOIN login_profiles lp ON u.id = lp.user_id
+            WHERE u.account_id = $1
+            ORDER BY u.create_date ASC, u.id ASC"#,
+    )
+    .bind(account_id)
+    .map(|row: SqliteRow| SelectReportUser::from_row(&row).unwrap())
+    .fetch_all(executor)
+    .await?;
+    Ok(result)
+}
+
+pub(crate) async fn list_access_keys<'a, E>(executor: E, account_id: i64) -> Result<Vec<SelectReportAccessKey>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"SELECT
+                ak.user_id AS user_id,
+                ak.status = $2 AS is_active,
+                ak.create_date AS create_date,
+                ak.last_used_date AS last_used_date,
+                r.region AS last_used_region,
+                ak.last_used_service AS last_used_service
+            FROM access_keys ak LEFT JOIN regions r ON ak.last_used_region_id = r.id
+            WHERE ak.account_id = $1
+            ORDER BY ak.create_date ASC, ak.id ASC"#,
+    )
+    .bind(account_id)
+    .bind(AccessKeyStatusType::Active.as_i32())
+    .map(|row: SqliteRow| SelectReportAccessKey::from_row(&row).unwrap())
+    .fetch_all(executor)
+    .await?;
+    Ok(result)
+}
+
+pub(crate) async fn list_signing_certificates<'a, E>(
+    executor: E, account_id: i64,
+) -> Result<Vec<SelectReportCredential>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"SELECT user_id, status = $2 AS is_active, upload_date AS create_date
+            FROM signing_certificates
+            WHERE account_id = $1 AND user_id IS NOT NULL
+            ORDER BY upload_date ASC, id ASC"#,
+    )
+    .bind(account_id)
+    .bind(SigningCertificateStatusType::Active.as_i32())
+    .map(|row: SqliteRow| SelectReportCredential::from_row(&row).unwrap())
+    .fetch_all(executor)
+    .await?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use local_cloud_db::LocalDb;
+
+    use crate::http::aws::iam::db::credential_report::{
+        discard, find_by_account_id, find_started, mark_in_progress, restart_stale, start,
+    };
+
+    #[tokio::test]
+    async fn test_restart_stale_and_discard() {
+        let database_url = format!("file:{}?mode=memory&cache=shared", Uuid::new_v4());
+        let db = LocalDb::new(&database_url, &sqlx::migrate!()).await.unwrap();
+        let mut connection = db.new_connection().await.unwrap();
+
+        start(connection.as_mut(), 1, 100).await.unwrap();
+        let id = find_started(connection.as_mut()).await.unwrap()[0].id;
+        assert!(mark_in_progress(connection.as_mut(), id, 200).await.unwrap());
+        assert!(!mark_in_progress(connection.as_mut(), id, 200).await.unwrap());
+
+        // the report has been taken after the deadline
+        assert_eq!(0, restart_stale(connection.as_mut(), 200).await.unwrap());
+        assert_eq!(1, restart_stale(connection.as_mut(), 201).await.unwrap());
+        assert_eq!(id, find_started(connection.as_mut()).await.unwrap()[0].id);
+
+        // only reports in progress are discarded
+        assert!(!discard(connection.as_mut(), id).await.unwrap());
+        assert!(mark_in_progress(connection.as_mut(), id, 300).await.unwrap());
+        assert!(discard(connection.as_mut(), id).await.unwrap());
+        assert!(find_by_account_id(connection.as_mut(), 1).await.unwrap().is_none());
+    }
+}
@@ -133,6 +133,21 @@ pub(crate) async fn assign_user_to_group<'a>(
     Ok(())
 }
 
+/// Checks whether the user is a member of the group.
+pub(crate) async fn is_member<'a, E>(executor: E, group_id: i64, user_id: i64) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result =
+        sqlx::query("SELECT EXISTS(SELECT 1 FROM group_users WHERE group_id = $1 AND user_id = $2) AS is_member")
+            .bind(group_id)
+            .bind(user_id)
+            .map(|row: SqliteRow| row.get::<bool, &str>("is_member"))
+            .fetch_one(executor)
+            .await?;
+    Ok(result)
+}
+
 pub(crate) async fn assign_policy_to_group<'a>(
     tx: &mut Transaction<'a, Sqlite>, group_id: i64, policy_id: i64,
 ) -> Result<(), Error> {
@@ -178,6 +193,33 @@ where
     Ok(groups)
 }
 
+pub(crate) async fn find_all_by_user_id<'a, E>(executor: E, user_id: i64) -> Result<Vec<SelectGroup>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let groups = sqlx::query(
+        r#"
+            SELECT
+                g.id AS id,
+                g.account_id AS account_id,
+                g.group_name AS group_name,
+                g.arn AS arn,
+                g.path AS path,
+                g.group_id AS group_id,
+                g.create_date AS create_date
+            FROM group_users gu
+            LEFT JOIN groups g ON gu.group_id = g.id
+            WHERE gu.user_id = $1 ORDER BY g.unique_group_name
+    "#,
+    )
+    .bind(user_id)
+    .map(|row: SqliteRow| SelectGroup::from_row(&row).unwrap())
+    .fetch_all(executor)
+    .await?;
+
+    Ok(groups)
+}
+
 pub(crate) async fn update<'a, E>(executor: E, account_id: i64, query: &UpdateGroupQuery) -> Result<bool, Error>
 where
     E: 'a + Executor<'a, Database = Sqlite>,
@@ -210,3 +252,72 @@ where
 
     Ok(result.rows_affected() == 1)
 }
+/// Removes the user from the group. Returns `false` if the user is not a member of the group.
+pub(crate) async fn remove_user_from_group<'a, E>(executor: E, group_id: i64, user_id: i64) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("DELETE FROM group_users WHERE group_id=$1 AND user_id=$2")
+        .bind(group_id)
+        .bind(user_id)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+/// Counts members of the group.
+pub(crate) async fn count_users<'a, E>(executor: E, group_id: i64) -> Result<usize, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("SELECT COUNT(*) AS count FROM group_users WHERE group_id = $1")
+        .bind(group_id)
+        .map(|row: SqliteRow| row.get::<i64, &str>("count"))
+        .fetch_one(executor)
+        .await?;
+    Ok(result as usize)
+}
+
+/// Detaches the managed policy from the group. Returns `false` if the policy was not attached.
+pub(crate) async fn detach_policy_from_group<'a, E>(executor: E, group_id: i64, policy_id: i64) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("DELETE FROM policy_groups WHERE group_id=$1 AND policy_id=$2")
+        .bind(group_id)
+        .bind(policy_id)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+/// Counts managed policies attached to the group.
+pub(crate) async fn count_attached_policies<'a, E>(executor: E, group_id: i64) -> Result<usize, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("SELECT COUNT(*) AS count FROM policy_groups WHERE group_id = $1")
+        .bind(group_id)
+        .map(|row: SqliteRow| row.get::<i64, &str>("count"))
+        .fetch_one(executor)
+        .await?;
+    Ok(result as usize)
+}
+
+/// Deletes the group together with its memberships and policy attachments.
+pub(crate) async fn delete<'a>(tx: &mut Transaction<'a, Sqlite>, id: i64) -> Result<bool, Error> {
+    sqlx::query("DELETE FROM group_users WHERE group_id=$1")
+        .bind(id)
+        .execute(tx.as_mut())
+        .await?;
+    sqlx::query("DELETE FROM policy_groups WHERE group_id=$1")
+        .bind(id)
+        .execute(tx.as_mut())
+        .await?;
+    let result = sqlx::query("DELETE FROM groups WHERE id=$1")
+        .bind(id)
+        .execute(tx.as_mut())
+        .await?;
+
+    Ok(result.rows_affected() == 1)
+}
@@ -30,3 +30,26 @@ where
 {
     db::inline_policy::find_by_parent_id(executor, "groups", "group_inline_policies", query).await
 }
+
+pub(crate) async fn find_all_by_group_id<'a, E>(executor: E, group_id: i64) -> Result<Vec<DbInlinePolicy>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    db::inline_policy::find_all_by_parent_id(executor, "group_inline_policies", group_id).await
+}
+
+pub(crate) async fn count_by_group_id<'a, E>(executor: E, group_id: i64) -> Result<usize, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    db::inline_policy::count_by_parent_id(executor, "group_inline_policies", group_id).await
+}
+
+pub(crate) async fn delete_by_group_id_and_name<'a, E>(
+    executor: E, group_id: i64, policy_name: &str,
+) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    db::inline_policy::delete_by_parent_id_and_name(executor, "group_inline_policies", group_id, policy_name).await
+}
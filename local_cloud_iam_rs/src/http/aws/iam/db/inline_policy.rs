@@ -92,3 +92,66 @@ where
 
     Ok(policies)
 }
+
+pub(crate) async fn find_all_by_parent_id<'a, E>(
+    executor: E, table_name: &str, parent_id: i64,
+) -> Result<Vec<DbInlinePolicy>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let policies = sqlx::query(
+        format!(
+            "SELECT id, parent_id, policy_name, policy_document \
+             FROM {table_name} \
+             WHERE parent_id = $1 \
+             ORDER BY unique_policy_name"
+        )
+        .as_str(),
+    )
+    .bind(parent_id)
+    .map(|row: SqliteRow| DbInlinePolicy::from_row(&row).unwrap())
+    .fetch_all(executor)
+    .await?;
+
+    Ok(policies)
+}
+
+pub(crate) async fn count_by_parent_id<'a, E>(executor: E, table_name: &str, parent_id: i64) -> Result<usize, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(format!("SELECT COUNT(id) AS count FROM {table_name} WHERE parent_id = $1").as_str())
+        .bind(parent_id)
+        .map(|row: SqliteRow| row.get::<i64, &str>("count"))
+        .fetch_one(executor)
+        .await?;
+    Ok(result as usize)
+}
+
+/// Deletes the inline policy of the parent entity. Returns `false` if the policy doesn't exist.
+pub(crate) async fn delete_by_parent_id_and_name<'a, E>(
+    executor: E, table_name: &str, parent_id: i64, policy_name: &str,
+) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result =
+        sqlx::query(format!("DELETE FROM {table_name} WHERE parent_id = $1 AND unique_policy_name = $2").as_str())
+            .bind(parent_id)
+            .bind(policy_name.to_uppercase())
+            .execute(executor)
+            .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+/// Deletes all inline policies of the parent entity. Returns the number of deleted policies.
+pub(crate) async fn delete_by_parent_id<'a, E>(executor: E, table_name: &str, parent_id: i64) -> Result<u64, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(format!("DELETE FROM {table_name} WHERE parent_id = $1").as_str())
+        .bind(parent_id)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected())
+}
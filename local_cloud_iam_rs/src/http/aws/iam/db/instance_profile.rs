@@ -128,3 +128,70 @@ where
         .await?;
     Ok(policies)
 }
+
+/// Finds instance profiles which the role is added to.
+pub(crate) async fn find_by_role_id<'a, E>(executor: E, role_id: i64) -> Result<Vec<SelectInstanceProfile>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let profiles = sqlx::query(
+        "SELECT ip.id AS id, \
+        ip.account_id AS account_id, \
+        ip.instance_profile_name AS instance_profile_name, \
+        ip.unique_instance_profile_name AS unique_instance_profile_name, \
+        ip.instance_profile_id AS instance_profile_id, \
+        ip.arn AS arn, \
+        ip.path AS path, \
+        ip.create_date AS create_date \
+        FROM instance_profile_roles ipr LEFT JOIN instance_profiles ip ON ipr.instance_profile_id = ip.id \
+        WHERE ipr.role_id = $1 \
+        ORDER BY ip.unique_instance_profile_name",
+    )
+    .bind(role_id)
+    .map(|row: SqliteRow| SelectInstanceProfile::from_row(&row).unwrap())
+    .fetch_all(executor)
+    .await?;
+    Ok(profiles)
+}
+
+/// Removes the role from the instance profile. Returns `false` if the role was not added to the instance profile.
+pub(crate) async fn remove_role_from_instance_profile<'a, E>(
+    executor: E, instance_profile_id: i64, role_id: i64,
+) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("DELETE FROM instance_profile_roles WHERE instance_profile_id=$1 AND role_id=$2")
+        .bind(instance_profile_id)
+        .bind(role_id)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+/// Counts roles added to the instance profile.
+pub(crate) async fn count_roles<'a, E>(executor: E, instance_profile_id: i64) -> Result<usize, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("SELECT COUNT(*) AS count FROM instance_profile_roles WHERE instance_profile_id = $1")
+        .bind(instance_profile_id)
+        .map(|row: SqliteRow| row.get::<i64, &str>("count"))
+        .fetch_one(executor)
+        .await?;
+    Ok(result as usize)
+}
+
+/// Deletes the instance profile together with its role assignments.
+pub(crate) async fn delete<'a>(tx: &mut Transaction<'a, Sqlite>, id: i64) -> Result<bool, Error> {
+    sqlx::query("DELETE FROM instance_profile_roles WHERE instance_profile_id=$1")
+        .bind(id)
+        .execute(tx.as_mut())
+        .await?;
+    let result = sqlx::query("DELETE FROM instance_profiles WHERE id=$1")
+        .bind(id)
+        .execute(tx.as_mut())
+        .await?;
+
+    Ok(result.rows_affected() == 1)
+}
@@ -1,7 +1,7 @@
 use sqlx::sqlite::SqliteRow;
-use sqlx::{Error, Row, Sqlite, Transaction};
+use sqlx::{Error, Executor, FromRow, Row, Sqlite, Transaction};
 
-use crate::http::aws::iam::db::types::login_profile::InsertLoginProfile;
+use crate::http::aws::iam::db::types::login_profile::{InsertLoginProfile, SelectLoginProfile, UpdateLoginProfile};
 
 pub(crate) async fn create<'a>(
     tx: &mut Transaction<'a, Sqlite>, login_profile: &mut InsertLoginProfile,
@@ -11,9 +11,10 @@ pub(crate) async fn create<'a>(
                  user_id,
                  create_date,
                  password_hash,
-                 password_reset_required
+                 password_reset_required,
+                 password_change_date
               )
-              VALUES ($1, $2, $3, $4)
+              VALUES ($1, $2, $3, $4, $2)
               RETURNING id"#,
     )
     .bind(login_profile.user_id)
@@ -26,3 +27,62 @@ pub(crate) async fn create<'a>(
     login_profile.id = Some(result);
     Ok(())
 }
+
+pub(crate) async fn count_by_user_id<'a, E>(executor: E, user_id: i64) -> Result<usize, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("SELECT COUNT(*) AS count FROM login_profiles WHERE user_id = $1")
+        .bind(user_id)
+        .map(|row: SqliteRow| row.get::<i64, &str>("count"))
+        .fetch_one(executor)
+        .await?;
+    Ok(result as usize)
+}
+
+pub(crate) async fn find_by_user_id<'a, E>(executor: E, user_id: i64) -> Result<Option<SelectLoginProfile>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"SELECT id, user_id, password_hash, password_reset_required, create_date, password_change_date
+              FROM login_profiles
+              WHERE user_id = $1"#,
+    )
+    .bind(user_id)
+    .map(|row: SqliteRow| SelectLoginProfile::from_row(&row).unwrap())
+    .fetch_optional(executor)
+    .await?;
+    Ok(result)
+}
+
+pub(crate) async fn update<'a, E>(executor: E, login_profile: &UpdateLoginProfile) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"UPDATE login_profiles
+              SET password_hash = COALESCE($2, password_hash),
+                  password_change_date = COALESCE($3, password_change_date),
+                  password_reset_required = COALESCE($4, password_reset_required)
+              WHERE user_id = $1"#,
+    )
+    .bind(login_profile.user_id)
+    .bind(&login_profile.password_hash)
+    .bind(login_profile.password_change_date)
+    .bind(login_profile.password_reset_required)
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+pub(crate) async fn delete_by_user_id<'a, E>(executor: E, user_id: i64) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("DELETE FROM login_profiles WHERE user_id = $1")
+        .bind(user_id)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected() == 1)
+}
@@ -2,7 +2,7 @@ use sqlx::sqlite::SqliteRow;
 use sqlx::{Error, Executor, FromRow, QueryBuilder, Row, Sqlite, Transaction};
 
 use crate::http::aws::iam::db::types::mfa_device::{
-    EnableMfaDeviceQuery, InsertMfaDevice, ListVirtualMfaDevicesQuery, SelectMfaDevice,
+    EnableMfaDeviceQuery, InsertMfaDevice, ListMfaDevicesQuery, ListVirtualMfaDevicesQuery, SelectMfaDevice,
 };
 use crate::http::aws::iam::types::assignment_status_type::AssignmentStatusType;
 
@@ -69,6 +69,7 @@ where
                     md.create_date AS create_date, \
                     md.enable_date AS enable_date, \
                     md.user_id AS user_id, \
+                    md.time_offset AS time_offset, \
                     u.user_id AS user_user_id, \
                     u.username AS user_name, \
                     u.arn AS user_arn, \
@@ -115,13 +116,15 @@ pub(crate) async fn enable<'a>(tx: &mut Transaction<'a, Sqlite>, query: &EnableM
             user_id = $1, \
             enable_date = $2, \
             code1 = $3, \
-            code2 = $4 \
-        WHERE id = $5 ",
+            code2 = $4, \
+            time_offset = $5 \
+        WHERE id = $6 ",
     )
     .bind(query.user_id)
     .bind(query.enable_date)
     .bind(&query.code1)
     .bind(&query.code2)
+    .bind(query.time_offset)
     .bind(query.id)
     .execute(tx.as_mut())
     .await?;
@@ -129,12 +132,25 @@ pub(crate) async fn enable<'a>(tx: &mut Transaction<'a, Sqlite>, query: &EnableM
     Ok(())
 }
 
+pub(crate) async fn resync<'a>(
+    tx: &mut Transaction<'a, Sqlite>, mfa_device_id: i64, code1: &str, code2: &str, time_offset: i64,
+) -> Result<bool, Error> {
+    let result = sqlx::query("UPDATE mfa_devices SET code1 = $1, code2 = $2, time_offset = $3 WHERE id = $4")
+        .bind(code1)
+        .bind(code2)
+        .bind(time_offset)
+        .bind(mfa_device_id)
+        .execute(tx.as_mut())
+        .await?;
+    Ok(result.rows_affected() == 1)
+}
+
 pub(crate) async fn disable<'a>(
     tx: &mut Transaction<'a, Sqlite>, mfa_device_id: i64, user_id: i64,
 ) -> Result<bool, Error> {
     let result = sqlx::query(
         "UPDATE mfa_devices \
-        SET user_id = NULL, enable_date = NULL, code1 = NULL, code2 = NULL \
+        SET user_id = NULL, enable_date = NULL, code1 = NULL, code2 = NULL, time_offset = 0 \
         WHERE id = $1 AND user_id = $2",
     )
     .bind(mfa_device_id)
@@ -162,6 +178,7 @@ where
                     md.create_date AS create_date, \
                     md.enable_date AS enable_date, \
                     md.user_id AS user_id, \
+                    md.time_offset AS time_offset, \
                     u.user_id AS user_user_id, \
                     u.username AS user_name, \
                     u.arn AS user_arn, \
@@ -192,3 +209,67 @@ where
 
     Ok(result)
 }
+
+pub(crate) async fn list_by_user_id<'a, E>(
+    executor: E, query: &ListMfaDevicesQuery,
+) -> Result<Vec<SelectMfaDevice>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new(
+        "
+            SELECT md.id AS id, \
+                    md.account_id AS account_id, \
+                    md.serial_number AS serial_number, \
+                    md.path AS path, \
+                    md.name AS name, \
+                    md.unique_name AS unique_name, \
+                    md.seed AS seed, \
+                    md.create_date AS create_date, \
+                    md.enable_date AS enable_date, \
+                    md.user_id AS user_id, \
+                    md.time_offset AS time_offset, \
+                    u.user_id AS user_user_id, \
+                    u.username AS user_name, \
+                    u.arn AS user_arn, \
+                    u.path AS user_path, \
+                    u.create_date AS user_create_date, \
+                    u.last_used_date AS user_password_last_used \
+            FROM mfa_devices md JOIN users u ON md.user_id = u.id \
+            WHERE md.user_id = ",
+    );
+
+    let result = query_builder
+        .push_bind(query.user_id)
+        .push(" ORDER BY md.enable_date ASC, md.id ASC")
+        .push(" LIMIT ")
+        .push_bind(query.limit + 1) // request more elements than we need to return. used to identify if NextPage token needs to be generated
+        .push(" OFFSET ")
+        .push_bind(query.skip)
+        .build()
+        .map(|row: SqliteRow| SelectMfaDevice::from_row(&row).unwrap())
+        .fetch_all(executor)
+        .await?;
+
+    Ok(result)
+}
+
+pub(crate) async fn delete<'a>(tx: &mut Transaction<'a, Sqlite>, mfa_device_id: i64) -> Result<bool, Error> {
+    let result = sqlx::query("DELETE FROM mfa_devices WHERE id = $1")
+        .bind(mfa_device_id)
+        .execute(tx.as_mut())
+        .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+pub(crate) async fn count_by_user_id<'a, E>(executor: E, user_id: i64) -> Result<usize, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("SELECT COUNT(*) AS count FROM mfa_devices WHERE user_id = $1")
+        .bind(user_id)
+        .map(|row: SqliteRow| row.get::<i64, &str>("count"))
+        .fetch_one(executor)
+        .await?;
+    Ok(result as usize)
+}
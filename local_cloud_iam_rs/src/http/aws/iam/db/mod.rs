@@ -1,6 +1,9 @@
 pub(crate) use tag::Tags;
 
+pub(crate) mod access_key;
+pub(crate) mod account;
 pub(crate) mod constants;
+pub(crate) mod credential_report;
 pub(crate) mod group;
 pub(crate) mod group_inline_policy;
 pub(crate) mod inline_policy;
@@ -10,13 +13,19 @@ pub(crate) mod mfa_device;
 pub(crate) mod open_id_connect_provider;
 pub(crate) mod open_id_connect_provider_client_id;
 pub(crate) mod open_id_connect_provider_client_thumbprint;
+pub(crate) mod password_history;
+pub(crate) mod password_policy;
 pub(crate) mod policy;
 pub(crate) mod policy_version;
+pub(crate) mod quota;
 pub(crate) mod resource_identifier;
 pub(crate) mod role;
 pub(crate) mod role_inline_policy;
 pub(crate) mod saml_provider;
 pub(crate) mod server_certificate;
+pub(crate) mod service_last_accessed;
+pub(crate) mod service_linked_role;
+pub(crate) mod service_specific_credential;
 pub(crate) mod sighing_certificate;
 pub(crate) mod ssh_public_key;
 pub(crate) mod tag;
@@ -32,3 +32,21 @@ where
         .await?;
     Ok(result)
 }
+
+/// Deletes the provider together with its client IDs and thumbprints.
+pub(crate) async fn delete<'a>(tx: &mut Transaction<'a, Sqlite>, id: i64) -> Result<bool, Error> {
+    sqlx::query("DELETE FROM open_id_connect_provider_client_ids WHERE provider_id=$1")
+        .bind(id)
+        .execute(tx.as_mut())
+        .await?;
+    sqlx::query("DELETE FROM open_id_connect_provider_thumbprints WHERE provider_id=$1")
+        .bind(id)
+        .execute(tx.as_mut())
+        .await?;
+    let result = sqlx::query("DELETE FROM open_id_connect_providers WHERE id=$1")
+        .bind(id)
+        .execute(tx.as_mut())
+        .await?;
+
+    Ok(result.rows_affected() == 1)
+}
@@ -0,0 +1,31 @@
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Error, Executor, Row, Sqlite};
+
+pub(crate) async fn create<'a, E>(executor: E, user_id: i64, password_hash: &str, create_date: i64) -> Result<(), Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("INSERT INTO password_history (user_id, password_hash, create_date) VALUES ($1, $2, $3)")
+        .bind(user_id)
+        .bind(password_hash)
+        .bind(create_date)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// Returns hashes of the last `limit` passwords of the user, the most recent first.
+pub(crate) async fn find_recent_hashes<'a, E>(executor: E, user_id: i64, limit: i32) -> Result<Vec<String>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        "SELECT password_hash FROM password_history WHERE user_id = $1 ORDER BY create_date DESC, id DESC LIMIT $2",
+    )
+    .bind(user_id)
+    .bind(limit)
+    .map(|row: SqliteRow| row.get::<String, &str>("password_hash"))
+    .fetch_all(executor)
+    .await?;
+    Ok(result)
+}
@@ -0,0 +1,89 @@
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Error, Executor, FromRow, Sqlite};
+
+use crate::http::aws::iam::db::types::password_policy::{InsertPasswordPolicy, SelectPasswordPolicy};
+
+/// Creates the password policy of the account or replaces the existing one.
+pub(crate) async fn save<'a, E>(executor: E, policy: &InsertPasswordPolicy) -> Result<(), Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    sqlx::query(
+        r#"INSERT INTO account_password_policies (
+                account_id,
+                minimum_password_length,
+                require_symbols,
+                require_numbers,
+                require_uppercase_characters,
+                require_lowercase_characters,
+                allow_users_to_change_password,
+                max_password_age,
+                password_reuse_prevention,
+                hard_expiry
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT (account_id) DO UPDATE SET
+                minimum_password_length = excluded.minimum_password_length,
+                require_symbols = excluded.require_symbols,
+                require_numbers = excluded.require_numbers,
+                require_uppercase_characters = excluded.require_uppercase_characters,
+                require_lowercase_characters = excluded.require_lowercase_characters,
+                allow_users_to_change_password = excluded.allow_users_to_change_password,
+                max_password_age = excluded.max_password_age,
+                password_reuse_prevention = excluded.password_reuse_prevention,
+                hard_expiry = excluded.hard_expiry"#,
+    )
+    .bind(policy.account_id)
+    .bind(policy.minimum_password_length)
+    .bind(policy.require_symbols)
+    .bind(policy.require_numbers)
+    .bind(policy.require_uppercase_characters)
+    .bind(policy.require_lowercase_characters)
+    .bind(policy.allow_users_to_change_password)
+    .bind(policy.max_password_age)
+    .bind(policy.password_reuse_prevention)
+    .bind(policy.hard_expiry)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+pub(crate) async fn find_by_account_id<'a, E>(
+    executor: E, account_id: i64,
+) -> Result<Option<SelectPasswordPolicy>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"SELECT
+                id,
+                account_id,
+                minimum_password_length,
+                require_symbols,
+                require_numbers,
+                require_uppercase_characters,
+                require_lowercase_characters,
+                allow_users_to_change_password,
+                max_password_age,
+                password_reuse_prevention,
+                hard_expiry
+            FROM account_password_policies
+            WHERE account_id = $1"#,
+    )
+    .bind(account_id)
+    .map(|row: SqliteRow| SelectPasswordPolicy::from_row(&row).unwrap())
+    .fetch_optional(executor)
+    .await?;
+    Ok(result)
+}
+
+pub(crate) async fn delete<'a, E>(executor: E, account_id: i64) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("DELETE FROM account_password_policies WHERE account_id = $1")
+        .bind(account_id)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected() == 1)
+}
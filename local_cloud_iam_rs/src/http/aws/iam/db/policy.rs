@@ -1,7 +1,11 @@
 use sqlx::sqlite::SqliteRow;
 use sqlx::{Error, Executor, FromRow, QueryBuilder, Row, Sqlite, Transaction};
 
-use crate::http::aws::iam::db::types::policy::{InsertPolicy, ListPoliciesQuery, SelectPolicy};
+use crate::http::aws::iam::constants;
+use crate::http::aws::iam::db::types::policy::{
+    InsertPolicy, ListAttachedPoliciesQuery, ListEntitiesForPolicyQuery, ListPoliciesQuery, PolicyEntityType,
+    SelectAttachedPolicy, SelectPolicy, SelectPolicyEntity,
+};
 
 pub(crate) async fn create<'a>(tx: &mut Transaction<'a, Sqlite>, policy: &mut InsertPolicy) -> Result<(), Error> {
     let result = sqlx::query(
@@ -27,7 +31,9 @@ pub(crate) async fn create<'a>(tx: &mut Transaction<'a, Sqlite>, policy: &mut In
     .bind(&policy.policy_id)
     .bind(&policy.arn)
     .bind(&policy.path)
-    .bind(&policy.policy_type)
+    // `PolicyType` derives `sqlx::Type`, so binding it directly would store the enum discriminant (0/1) while
+    // the rows are read back with `PolicyType::from(i32)` which expects 1/2
+    .bind(policy.policy_type.as_i32())
     .bind(policy.attachable)
     .bind(&policy.description)
     .bind(policy.create_date)
@@ -44,9 +50,26 @@ pub(crate) async fn find_id_by_arn<'a, E>(executor: E, account_id: i64, policy_a
 where
     E: 'a + Executor<'a, Database = Sqlite>,
 {
-    let result = sqlx::query("SELECT id FROM policies WHERE account_id = $1 AND arn = $2")
+    let result = sqlx::query("SELECT id FROM policies WHERE account_id IN ($1, $3) AND arn = $2")
         .bind(account_id)
         .bind(policy_arn)
+        .bind(constants::policy::AWS_MANAGED_ACCOUNT_ID)
+        .map(|row: SqliteRow| row.get::<i64, &str>("id"))
+        .fetch_optional(executor)
+        .await?;
+    Ok(result)
+}
+
+/// Looks up a customer managed policy of the account by name. Policy names are unique regardless of paths.
+pub(crate) async fn find_id_by_name<'a, E>(
+    executor: E, account_id: i64, policy_name: &str,
+) -> Result<Option<i64>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("SELECT id FROM policies WHERE account_id = $1 AND unique_policy_name = $2")
+        .bind(account_id)
+        .bind(policy_name.to_uppercase())
         .map(|row: SqliteRow| row.get::<i64, &str>("id"))
         .fetch_optional(executor)
         .await?;
@@ -73,17 +96,22 @@ where
                 p.policy_type AS policy_type,
                 p.description AS description,
                 p.is_attachable AS is_attachable,
-                ((SELECT COUNT(pg.policy_id) FROM policy_groups pg WHERE pg.policy_id = p.id)
-                + (SELECT COUNT(pr.policy_id) FROM policy_roles pr WHERE pr.policy_id = p.id)
-                + (SELECT COUNT(pu.policy_id) FROM policy_users pu WHERE pu.policy_id = p.id)) AS attachment_count,
-                ((SELECT COUNT(r.policy_id) FROM roles r WHERE r.policy_id = p.id) 
-                + (SELECT COUNT(u.policy_id) FROM users u WHERE u.policy_id = p.id)) AS permissions_boundary_usage_count,
+                ((SELECT COUNT(*) FROM policy_groups pg JOIN groups g ON pg.group_id = g.id
+                    WHERE pg.policy_id = p.id AND g.account_id = $2)
+                + (SELECT COUNT(*) FROM policy_roles pr JOIN roles r ON pr.role_id = r.id
+                    WHERE pr.policy_id = p.id AND r.account_id = $2)
+                + (SELECT COUNT(*) FROM policy_users pu JOIN users u ON pu.user_id = u.id
+                    WHERE pu.policy_id = p.id AND u.account_id = $2)) AS attachment_count,
+                ((SELECT COUNT(r.policy_id) FROM roles r WHERE r.policy_id = p.id AND r.account_id = $2)
+                + (SELECT COUNT(u.policy_id) FROM users u WHERE u.policy_id = p.id AND u.account_id = $2))
+                    AS permissions_boundary_usage_count,
                 p.version AS version 
             FROM policies p LEFT JOIN policy_versions pv ON p.id = pv.policy_id AND pv.is_default = true
-            WHERE p.id = $1 AND p.account_id = $2"#,
+            WHERE p.id = $1 AND p.account_id IN ($2, $3)"#,
     )
     .bind(policy_id)
     .bind(account_id)
+    .bind(constants::policy::AWS_MANAGED_ACCOUNT_ID)
     .map(|row: SqliteRow| SelectPolicy::from_row(&row).unwrap())
     .fetch_optional(executor)
     .await?;
@@ -108,20 +136,25 @@ where
                 p.policy_type AS policy_type,
                 p.description AS description,
                 p.is_attachable AS is_attachable,
-                ((SELECT COUNT(pg.policy_id) FROM policy_groups pg WHERE pg.policy_id = p.id)
-                + (SELECT COUNT(pr.policy_id) FROM policy_roles pr WHERE pr.policy_id = p.id)
-                + (SELECT COUNT(pu.policy_id) FROM policy_users pu WHERE pu.policy_id = p.id)) AS attachment_count,
-                ((SELECT COUNT(r.policy_id) FROM roles r WHERE r.policy_id = p.id)
-                + (SELECT COUNT(u.policy_id) FROM users u WHERE u.policy_id = p.id)) AS permissions_boundary_usage_count,
+                ((SELECT COUNT(*) FROM policy_groups pg JOIN groups g ON pg.group_id = g.id
+                    WHERE pg.policy_id = p.id AND g.account_id = $2)
+                + (SELECT COUNT(*) FROM policy_roles pr JOIN roles r ON pr.role_id = r.id
+                    WHERE pr.policy_id = p.id AND r.account_id = $2)
+                + (SELECT COUNT(*) FROM policy_users pu JOIN users u ON pu.user_id = u.id
+                    WHERE pu.policy_id = p.id AND u.account_id = $2)) AS attachment_count,
+                ((SELECT COUNT(r.policy_id) FROM roles r WHERE r.policy_id = p.id AND r.account_id = $2)
+                + (SELECT COUNT(u.policy_id) FROM users u WHERE u.policy_id = p.id AND u.account_id = $2))
+                    AS permissions_boundary_usage_count,
                 pv.version AS version
             FROM policies p LEFT JOIN policy_versions pv ON p.id = pv.policy_id AND pv.is_default = true
-            WHERE p.arn = $1 AND p.account_id = $2"#,
+            WHERE p.arn = $1 AND p.account_id IN ($2, $3)"#,
     )
-        .bind(arn)
-        .bind(account_id)
-        .map(|row: SqliteRow| SelectPolicy::from_row(&row).unwrap())
-        .fetch_optional(executor)
-        .await?;
+    .bind(arn)
+    .bind(account_id)
+    .bind(constants::policy::AWS_MANAGED_ACCOUNT_ID)
+    .map(|row: SqliteRow| SelectPolicy::from_row(&row).unwrap())
+    .fetch_optional(executor)
+    .await?;
     Ok(result)
 }
 
@@ -148,17 +181,36 @@ where
                 p.policy_type AS policy_type,
                 p.description AS description,
                 p.is_attachable AS is_attachable,
-                ((SELECT COUNT(pg.policy_id) FROM policy_groups pg WHERE pg.policy_id = p.id)
-                + (SELECT COUNT(pr.policy_id) FROM policy_roles pr WHERE pr.policy_id = p.id)
-                + (SELECT COUNT(pu.policy_id) FROM policy_users pu WHERE pu.policy_id = p.id)) AS attachment_count,
-                ((SELECT COUNT(r.policy_id) FROM roles r WHERE r.policy_id = p.id) 
-                + (SELECT COUNT(u.policy_id) FROM users u WHERE u.policy_id = p.id)) AS permissions_boundary_usage_count,
-                pv.version AS version
-            FROM policies p LEFT JOIN policy_versions pv ON p.id = pv.policy_id AND pv.is_default = true
-            WHERE p.account_id = "#,
+                ((SELECT COUNT(*) FROM policy_groups pg JOIN groups g ON pg.group_id = g.id
+                    WHERE pg.policy_id = p.id AND g.account_id = "#,
     );
+    // attachments and permissions boundaries of other accounts are not visible to the caller
     query_builder
         .push_bind(account_id)
+        .push(
+            ") + (SELECT COUNT(*) FROM policy_roles pr JOIN roles r ON pr.role_id = r.id \
+                WHERE pr.policy_id = p.id AND r.account_id = ",
+        )
+        .push_bind(account_id)
+        .push(
+            ") + (SELECT COUNT(*) FROM policy_users pu JOIN users u ON pu.user_id = u.id \
+                WHERE pu.policy_id = p.id AND u.account_id = ",
+        )
+        .push_bind(account_id)
+        .push(")) AS attachment_count, ((SELECT COUNT(r.policy_id) FROM roles r WHERE r.policy_id = p.id AND r.account_id = ")
+        .push_bind(account_id)
+        .push(") + (SELECT COUNT(u.policy_id) FROM users u WHERE u.policy_id = p.id AND u.account_id = ")
+        .push_bind(account_id)
+        .push(
+            ")) AS permissions_boundary_usage_count, \
+                pv.version AS version \
+            FROM policies p LEFT JOIN policy_versions pv ON p.id = pv.policy_id AND pv.is_default = true \
+            WHERE p.account_id IN (",
+        )
+        .push_bind(account_id)
+        .push(", ")
+        .push_bind(constants::policy::AWS_MANAGED_ACCOUNT_ID)
+        .push(")")
         .push(" AND p.path LIKE ")
         .push_bind(format!("{}%", &query.path_prefix))
         .push(" AND p.policy_type in (");
@@ -167,6 +219,25 @@ where
         separated.push_bind(scope);
     }
     separated.push_unseparated(")");
+    if query.is_attached {
+        query_builder
+            .push(
+                " AND (EXISTS(SELECT 1 FROM policy_users pu JOIN users u ON pu.user_id = u.id \
+                    WHERE pu.policy_id = p.id AND u.account_id = ",
+            )
+            .push_bind(account_id)
+            .push(
+                ") OR EXISTS(SELECT 1 FROM policy_groups pg JOIN groups g ON pg.group_id = g.id \
+                    WHERE pg.policy_id = p.id AND g.account_id = ",
+            )
+            .push_bind(account_id)
+            .push(
+                ") OR EXISTS(SELECT 1 FROM policy_roles pr JOIN roles r ON pr.role_id = r.id \
+                    WHERE pr.policy_id = p.id AND r.account_id = ",
+            )
+            .push_bind(account_id)
+            .push("))");
+    }
     let policies = query_builder
         .push(" ORDER BY p.unique_policy_name")
         .push(" LIMIT ")
@@ -180,6 +251,243 @@ where
     Ok(policies)
 }
 
+pub(crate) async fn list_attached_to_user<'a, E>(
+    executor: E, query: &ListAttachedPoliciesQuery,
+) -> Result<Vec<SelectAttachedPolicy>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    list_attached(executor, "policy_users", "user_id", query).await
+}
+
+pub(crate) async fn list_attached_to_group<'a, E>(
+    executor: E, query: &ListAttachedPoliciesQuery,
+) -> Result<Vec<SelectAttachedPolicy>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    list_attached(executor, "policy_groups", "group_id", query).await
+}
+
+pub(crate) async fn list_attached_to_role<'a, E>(
+    executor: E, query: &ListAttachedPoliciesQuery,
+) -> Result<Vec<SelectAttachedPolicy>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    list_attached(executor, "policy_roles", "role_id", query).await
+}
+
+async fn list_attached<'a, E>(
+    executor: E, link_table_name: &str, link_column_name: &str, query: &ListAttachedPoliciesQuery,
+) -> Result<Vec<SelectAttachedPolicy>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new(format!(
+        "SELECT \
+            p.policy_name AS policy_name, \
+            p.arn AS arn \
+         FROM {link_table_name} l \
+         LEFT JOIN policies p ON l.policy_id = p.id \
+         WHERE l.{link_column_name} = "
+    ));
+    let policies = query_builder
+        .push_bind(query.parent_id)
+        .push(" AND p.path LIKE ")
+        .push_bind(format!("{}%", &query.path_prefix))
+        .push(" ORDER BY p.unique_policy_name")
+        .push(" LIMIT ")
+        .push_bind(query.limit + 1) // request more elements than we need to return. used to identify if NextPage token needs to be generated
+        .push(" OFFSET ")
+        .push_bind(query.skip)
+        .build()
+        .map(|row: SqliteRow| SelectAttachedPolicy::from_row(&row).unwrap())
+        .fetch_all(executor)
+        .await?;
+    Ok(policies)
+}
+
+/// Lists users, groups and roles of the account which use the policy as a permissions policy (attachment) and/or
+/// as a permissions boundary.
+pub(crate) async fn list_entities<'a, E>(
+    executor: E, query: &ListEntitiesForPolicyQuery,
+) -> Result<Vec<SelectPolicyEntity>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    // (entity type, entity table, name column, id column, attachment table)
+    let sources = [
+        (PolicyEntityType::User, "users", "username", "user_id", "policy_users"),
+        (PolicyEntityType::Group, "groups", "group_name", "group_id", "policy_groups"),
+        (PolicyEntityType::Role, "roles", "role_name", "role_id", "policy_roles"),
+    ];
+
+    let mut query_builder: QueryBuilder<Sqlite> =
+        QueryBuilder::new("SELECT entity_type, entity_name, entity_id FROM (");
+    let mut is_first = true;
+    for (entity_type, table, name_column, id_column, attachment_table) in sources {
+        // groups can't have a permissions boundary
+        let include_boundary = query.include_permissions_boundary && entity_type != PolicyEntityType::Group;
+        if !query.entity_types.contains(&entity_type) || !(query.include_permissions_policy || include_boundary) {
+            continue;
+        }
+
+        if !is_first {
+            query_builder.push(" UNION ALL ");
+        }
+        is_first = false;
+        query_builder
+            .push("SELECT ")
+            .push_bind(entity_type.as_i32())
+            .push(format!(
+                " AS entity_type, e.{name_column} AS entity_name, e.{id_column} AS entity_id, \
+                 e.unique_{name_column} AS sort_name FROM {table} e WHERE e.account_id = "
+            ))
+            .push_bind(query.account_id)
+            .push(" AND e.path LIKE ")
+            .push_bind(format!("{}%", &query.path_prefix))
+            .push(" AND (");
+        if query.include_permissions_policy {
+            // the attachment table references the entity by its primary key in the column with the same name
+            query_builder
+                .push(format!("e.id IN (SELECT a.{id_column} FROM {attachment_table} a WHERE a.policy_id = "))
+                .push_bind(query.policy_id)
+                .push(")");
+        }
+        if include_boundary {
+            if query.include_permissions_policy {
+                query_builder.push(" OR ");
+            }
+            query_builder.push("e.policy_id = ").push_bind(query.policy_id);
+        }
+        query_builder.push(")");
+    }
+    if is_first {
+        return Ok(vec![]);
+    }
+
+    let entities = query_builder
+        .push(") ORDER BY entity_type, sort_name")
+        .push(" LIMIT ")
+        .push_bind(query.limit + 1) // request more elements than we need to return. used to identify if NextPage token needs to be generated
+        .push(" OFFSET ")
+        .push_bind(query.skip)
+        .build()
+        .map(|row: SqliteRow| SelectPolicyEntity::from_row(&row).unwrap())
+        .fetch_all(executor)
+        .await?;
+    Ok(entities)
+}
+
+/// Counts users, groups and roles of the account the policy is attached to.
+pub(crate) async fn count_attachments<'a, E>(executor: E, account_id: i64, policy_id: i64) -> Result<usize, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        "SELECT \
+            (SELECT COUNT(*) FROM policy_users pu JOIN users u ON pu.user_id = u.id \
+                WHERE pu.policy_id = $1 AND u.account_id = $2) + \
+            (SELECT COUNT(*) FROM policy_groups pg JOIN groups g ON pg.group_id = g.id \
+                WHERE pg.policy_id = $1 AND g.account_id = $2) + \
+            (SELECT COUNT(*) FROM policy_roles pr JOIN roles r ON pr.role_id = r.id \
+                WHERE pr.policy_id = $1 AND r.account_id = $2) AS count",
+    )
+    .bind(policy_id)
+    .bind(account_id)
+    .map(|row: SqliteRow| row.get::<i64, &str>("count"))
+    .fetch_one(executor)
+    .await?;
+    Ok(result as usize)
+}
+
+pub(crate) async fn is_attached_to_user<'a, E>(executor: E, user_id: i64, policy_id: i64) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    is_attached(executor, "policy_users", "user_id", user_id, policy_id).await
+}
+
+pub(crate) async fn is_attached_to_group<'a, E>(executor: E, group_id: i64, policy_id: i64) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    is_attached(executor, "policy_groups", "group_id", group_id, policy_id).await
+}
+
+pub(crate) async fn is_attached_to_role<'a, E>(executor: E, role_id: i64, policy_id: i64) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    is_attached(executor, "policy_roles", "role_id", role_id, policy_id).await
+}
+
+/// Checks whether the managed policy is attached to the user, group or role identified by `parent_id`.
+async fn is_attached<'a, E>(
+    executor: E, link_table_name: &str, link_column_name: &str, parent_id: i64, policy_id: i64,
+) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(&format!(
+        "SELECT EXISTS(SELECT 1 FROM {link_table_name} WHERE {link_column_name} = $1 AND policy_id = $2) AS is_attached"
+    ))
+    .bind(parent_id)
+    .bind(policy_id)
+    .map(|row: SqliteRow| row.get::<bool, &str>("is_attached"))
+    .fetch_one(executor)
+    .await?;
+    Ok(result)
+}
+
+/// Counts users and roles which use the policy as a permissions boundary.
+pub(crate) async fn count_permissions_boundary_usages<'a, E>(executor: E, policy_id: i64) -> Result<usize, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        "SELECT \
+            (SELECT COUNT(*) FROM users WHERE policy_id = $1) + \
+            (SELECT COUNT(*) FROM roles WHERE policy_id = $1) AS count",
+    )
+    .bind(policy_id)
+    .map(|row: SqliteRow| row.get::<i64, &str>("count"))
+    .fetch_one(executor)
+    .await?;
+    Ok(result as usize)
+}
+
+/// Deletes the policy together with all its versions.
+pub(crate) async fn delete<'a>(tx: &mut Transaction<'a, Sqlite>, id: i64) -> Result<bool, Error> {
+    sqlx::query("DELETE FROM policy_versions WHERE policy_id=$1")
+        .bind(id)
+        .execute(tx.as_mut())
+        .await?;
+    let result = sqlx::query("DELETE FROM policies WHERE id=$1")
+        .bind(id)
+        .execute(tx.as_mut())
+        .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+/// Sets the update date of the policy to the creation date of its most recent version.
+pub(crate) async fn refresh_update_date<'a, E>(executor: E, policy_id: i64) -> Result<(), Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    sqlx::query(
+        "UPDATE policies \
+            SET update_date=(SELECT MAX(pv.create_date) FROM policy_versions pv WHERE pv.policy_id=$1) \
+            WHERE id=$1",
+    )
+    .bind(policy_id)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use core::fmt;
@@ -190,8 +498,9 @@ mod tests {
 
     use local_cloud_db::LocalDb;
 
-    use crate::http::aws::iam::db::policy::create;
-    use crate::http::aws::iam::db::types::policy::InsertPolicy;
+    use crate::http::aws::iam::constants;
+    use crate::http::aws::iam::db::policy::{create, find_by_arn, list};
+    use crate::http::aws::iam::db::types::policy::{InsertPolicy, ListPoliciesQuery};
     use crate::http::aws::iam::db::types::policy_type::PolicyType;
 
     #[tokio::test]
@@ -237,4 +546,100 @@ mod tests {
             _ => Err(fmt::Error),
         }
     }
+
+    #[tokio::test]
+    async fn test_create_preserves_policy_type() {
+        let db_file_name = Uuid::new_v4();
+        let database_url = format!("file:{}?mode=memory&cache=shared", db_file_name);
+
+        let db = LocalDb::new(&database_url, &sqlx::migrate!()).await.unwrap();
+
+        let mut tx = db.new_tx().await.unwrap();
+
+        for (policy_name, policy_type) in [
+            ("LocalCloudPolicy", PolicyType::LocalCloudManaged),
+            ("CustomerPolicy", PolicyType::CustomerManaged),
+        ] {
+            let mut policy = InsertPolicy {
+                id: None,
+                account_id: 1,
+                arn: format!("arn:aws:iam::000000000001:policy/{policy_name}"),
+                policy_id: format!("ANPA{:0>17}", policy_name.len()),
+                path: "/".to_string(),
+                create_date: Utc::now().timestamp(),
+                update_date: Utc::now().timestamp(),
+                policy_name: policy_name.to_string(),
+                policy_type: policy_type.clone(),
+                description: None,
+                attachable: true,
+            };
+            create(&mut tx, &mut policy).await.unwrap();
+
+            let found_policy = find_by_arn(tx.as_mut(), 1, &policy.arn).await.unwrap().unwrap();
+            assert_eq!(found_policy.policy_type.as_i32(), policy_type.as_i32());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_attachments_of_other_accounts_are_not_counted() {
+        let db_file_name = Uuid::new_v4();
+        let database_url = format!("file:{}?mode=memory&cache=shared", db_file_name);
+
+        let db = LocalDb::new(&database_url, &sqlx::migrate!()).await.unwrap();
+
+        let mut tx = db.new_tx().await.unwrap();
+
+        let mut policy = InsertPolicy {
+            id: None,
+            account_id: constants::policy::AWS_MANAGED_ACCOUNT_ID,
+            arn: "arn:aws:iam::aws:policy/SharedPolicy".to_string(),
+            policy_id: "ANPA1212121212SHARED0".to_string(),
+            path: "/".to_string(),
+            create_date: Utc::now().timestamp(),
+            update_date: Utc::now().timestamp(),
+            policy_name: "SharedPolicy".to_string(),
+            policy_type: PolicyType::LocalCloudManaged,
+            description: None,
+            attachable: true,
+        };
+        create(&mut tx, &mut policy).await.unwrap();
+
+        // the policy is attached to a user of another account
+        sqlx::query("INSERT INTO accounts(id, alias) VALUES (2, 'other-account')")
+            .execute(tx.as_mut())
+            .await
+            .unwrap();
+        let user_id: i64 = sqlx::query_scalar(
+            "INSERT INTO users(account_id, username, unique_username, arn, path, user_id, create_date) \
+             VALUES (2, 'user1', 'USER1', 'arn:aws:iam::000000000002:user/user1', '/', 'AIDA12121212121USER1', 0) \
+             RETURNING id",
+        )
+        .fetch_one(tx.as_mut())
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO policy_users(user_id, policy_id) VALUES ($1, $2)")
+            .bind(user_id)
+            .bind(policy.id.unwrap())
+            .execute(tx.as_mut())
+            .await
+            .unwrap();
+
+        let found_policy = find_by_arn(tx.as_mut(), 2, &policy.arn).await.unwrap().unwrap();
+        assert_eq!(found_policy.attachment_count, 1);
+        let found_policy = find_by_arn(tx.as_mut(), 1, &policy.arn).await.unwrap().unwrap();
+        assert_eq!(found_policy.attachment_count, 0);
+
+        let query = ListPoliciesQuery {
+            path_prefix: "/".to_string(),
+            limit: 10,
+            skip: 0,
+            is_attached: true,
+            policy_scope_types: vec![PolicyType::LocalCloudManaged, PolicyType::CustomerManaged],
+        };
+        let policies = list(tx.as_mut(), 2, &query).await.unwrap();
+        assert_eq!(policies.len(), 1);
+        assert_eq!(policies[0].attachment_count, 1);
+        let policies = list(tx.as_mut(), 1, &query).await.unwrap();
+        assert!(policies.is_empty());
+    }
 }
@@ -1,8 +1,9 @@
 use sqlx::sqlite::SqliteRow;
 use sqlx::{Error, Executor, FromRow, Row, Sqlite, Transaction};
 
+use crate::http::aws::iam::constants;
 use crate::http::aws::iam::db::types::policy_version::{
-    InsertPolicyVersion, ListPolicyVersionsQuery, SelectPolicyVersion,
+    InsertPolicyVersion, ListPolicyVersionsQuery, SelectPolicyDocument, SelectPolicyVersion,
 };
 
 pub(crate) async fn create<'a>(
@@ -15,9 +16,10 @@ pub(crate) async fn create<'a>(
                         policy_version_id,
                         policy_document,
                         create_date,
-                        is_default
+                        is_default,
+                        version
                 )
-                VALUES ($1, $2, $3, $4, $5, $6)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
                 RETURNING id, version"#,
     )
     .bind(policy_version.account_id)
@@ -26,6 +28,7 @@ pub(crate) async fn create<'a>(
     .bind(&policy_version.policy_document)
     .bind(policy_version.create_date)
     .bind(policy_version.is_default)
+    .bind(policy_version.version) // the version is populated by trigger when it is not specified
     .map(|row: SqliteRow| row.get::<i64, &str>("id"))
     .fetch_one(tx.as_mut())
     .await?;
@@ -98,11 +101,12 @@ where
                 pv.version AS version
               FROM policies p
               LEFT JOIN policy_versions pv ON p.id = pv.policy_id
-              WHERE pv.account_id = $1 AND p.arn = $2 AND pv.version = $3"#,
+              WHERE pv.account_id IN ($1, $4) AND p.arn = $2 AND pv.version = $3"#,
     )
     .bind(account_id)
     .bind(policy_arn)
     .bind(version)
+    .bind(constants::policy::AWS_MANAGED_ACCOUNT_ID)
     .map(|row: SqliteRow| SelectPolicyVersion::from_row(&row).unwrap())
     .fetch_optional(executor)
     .await?;
@@ -118,3 +122,102 @@ pub(crate) async fn disable_default_by_policy_id<'a>(
         .await?;
     Ok(())
 }
+
+/// Makes the version the only default version of the policy with a single statement.
+pub(crate) async fn set_default<'a, E>(executor: E, policy_id: i64, id: i64) -> Result<(), Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("UPDATE policy_versions SET is_default=(id=$2) WHERE policy_id=$1")
+        .bind(policy_id)
+        .bind(id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+pub(crate) async fn find_default_by_user_id<'a, E>(
+    executor: E, user_id: i64,
+) -> Result<Vec<SelectPolicyDocument>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    find_default_attached(executor, "policy_users", "user_id", user_id).await
+}
+
+pub(crate) async fn find_default_by_group_id<'a, E>(
+    executor: E, group_id: i64,
+) -> Result<Vec<SelectPolicyDocument>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    find_default_attached(executor, "policy_groups", "group_id", group_id).await
+}
+
+pub(crate) async fn find_default_by_role_id<'a, E>(
+    executor: E, role_id: i64,
+) -> Result<Vec<SelectPolicyDocument>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    find_default_attached(executor, "policy_roles", "role_id", role_id).await
+}
+
+async fn find_default_attached<'a, E>(
+    executor: E, link_table_name: &str, link_column_name: &str, parent_id: i64,
+) -> Result<Vec<SelectPolicyDocument>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        format!(
+            "SELECT \
+                p.arn AS policy_arn, \
+                pv.policy_document AS policy_document \
+             FROM {link_table_name} l \
+             LEFT JOIN policies p ON l.policy_id = p.id \
+             LEFT JOIN policy_versions pv ON p.id = pv.policy_id \
+             WHERE l.{link_column_name} = $1 AND pv.is_default = true \
+             ORDER BY p.arn"
+        )
+        .as_str(),
+    )
+    .bind(parent_id)
+    .map(|row: SqliteRow| SelectPolicyDocument::from_row(&row).unwrap())
+    .fetch_all(executor)
+    .await?;
+    Ok(result)
+}
+
+pub(crate) async fn find_default_by_policy_id<'a, E>(
+    executor: E, policy_id: i64,
+) -> Result<Option<SelectPolicyDocument>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"SELECT
+                p.arn AS policy_arn,
+                pv.policy_document AS policy_document
+              FROM policies p
+              LEFT JOIN policy_versions pv ON p.id = pv.policy_id
+              WHERE p.id = $1 AND pv.is_default = true"#,
+    )
+    .bind(policy_id)
+    .map(|row: SqliteRow| SelectPolicyDocument::from_row(&row).unwrap())
+    .fetch_optional(executor)
+    .await?;
+    Ok(result)
+}
+
+pub(crate) async fn delete<'a, E>(executor: E, id: i64) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("DELETE FROM policy_versions WHERE id=$1")
+        .bind(id)
+        .execute(executor)
+        .await?;
+
+    Ok(result.rows_affected() == 1)
+}
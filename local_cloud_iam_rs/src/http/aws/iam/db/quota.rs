@@ -0,0 +1,42 @@
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Error, Executor, FromRow, Row, Sqlite};
+
+use crate::http::aws::iam::db::types::quota::SelectQuota;
+
+/// Sets the value of the quota, replacing the existing one.
+pub(crate) async fn save<'a, E>(executor: E, name: &str, value: i32) -> Result<(), Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    sqlx::query(
+        "INSERT INTO quotas (name, value) VALUES ($1, $2) ON CONFLICT (name) DO UPDATE SET value = excluded.value",
+    )
+    .bind(name)
+    .bind(value)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+pub(crate) async fn find_by_name<'a, E>(executor: E, name: &str) -> Result<Option<i32>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let value = sqlx::query("SELECT value FROM quotas WHERE name = $1")
+        .bind(name)
+        .map(|row: SqliteRow| row.get::<i32, &str>("value"))
+        .fetch_optional(executor)
+        .await?;
+    Ok(value)
+}
+
+pub(crate) async fn find_all<'a, E>(executor: E) -> Result<Vec<SelectQuota>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let quotas = sqlx::query("SELECT name, value FROM quotas ORDER BY name")
+        .map(|row: SqliteRow| SelectQuota::from_row(&row).unwrap())
+        .fetch_all(executor)
+        .await?;
+    Ok(quotas)
+}
@@ -84,7 +84,7 @@ where
                 r.create_date AS create_date, \
                 r.last_used_date AS last_used_date, \
                 r.last_used_region_id AS last_used_region_id, \
-                rg.name AS last_used_region \
+                rg.region AS last_used_region \
             FROM roles r \
                 LEFT JOIN policies p ON r.policy_id = p.id \
                 LEFT JOIN regions rg ON r.last_used_region_id = rg.id \
@@ -149,6 +149,52 @@ where
     Ok(roles)
 }
 
+/// Lists roles together with their permissions boundaries and last usage, e.g. to report role details in bulk.
+pub(crate) async fn list_with_details<'a, E>(
+    executor: E, account_id: i64, query: &ListRolesQuery,
+) -> Result<Vec<SelectRoleWithDetails>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new(
+        "SELECT \
+                r.id AS id, \
+                r.account_id AS account_id, \
+                r.role_name AS role_name, \
+                r.unique_role_name AS unique_role_name, \
+                r.description AS description, \
+                r.max_session_duration AS max_session_duration, \
+                r.assume_role_policy_document AS assume_role_policy_document, \
+                r.arn AS arn, \
+                r.path AS path, \
+                r.role_id AS role_id, \
+                r.policy_id AS policy_id, \
+                p.arn AS policy_arn, \
+                r.create_date AS create_date, \
+                r.last_used_date AS last_used_date, \
+                r.last_used_region_id AS last_used_region_id, \
+                rg.region AS last_used_region \
+            FROM roles r \
+                LEFT JOIN policies p ON r.policy_id = p.id \
+                LEFT JOIN regions rg ON r.last_used_region_id = rg.id \
+            WHERE r.account_id = ",
+    );
+    let roles = query_builder
+        .push_bind(account_id)
+        .push(" AND r.path LIKE ")
+        .push_bind(format!("{}%", &query.path_prefix))
+        .push(" ORDER BY r.unique_role_name")
+        .push(" LIMIT ")
+        .push_bind(query.limit + 1) // request more elements than we need to return. used to identify if NextPage token needs to be generated
+        .push(" OFFSET ")
+        .push_bind(query.skip)
+        .build()
+        .map(|row: SqliteRow| SelectRoleWithDetails::from_row(&row).unwrap())
+        .fetch_all(executor)
+        .await?;
+    Ok(roles)
+}
+
 pub(crate) async fn update_permissions_boundary<'a, E>(
     executor: E, account_id: i64, role_name: &str, policy_id: i64,
 ) -> Result<bool, Error>
@@ -167,3 +213,95 @@ where
 
     Ok(result.rows_affected() == 1)
 }
+
+/// Updates the description and the maximum session duration of the role. `None` values leave the fields unchanged.
+pub(crate) async fn update<'a, E>(
+    executor: E, role_id: i64, description: Option<&str>, max_session_duration: Option<i64>,
+) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        "UPDATE roles SET description=COALESCE($1, description), \
+            max_session_duration=COALESCE($2, max_session_duration) \
+        WHERE id=$3",
+    )
+    .bind(description)
+    .bind(max_session_duration)
+    .bind(role_id)
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+/// Replaces the trust policy of the role.
+pub(crate) async fn update_assume_role_policy<'a, E>(
+    executor: E, role_id: i64, assume_role_policy_document: &str,
+) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("UPDATE roles SET assume_role_policy_document=$1 WHERE id=$2")
+        .bind(assume_role_policy_document)
+        .bind(role_id)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+/// Detaches the managed policy from the role. Returns `false` if the policy was not attached.
+pub(crate) async fn detach_policy_from_role<'a, E>(executor: E, role_id: i64, policy_id: i64) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("DELETE FROM policy_roles WHERE role_id=$1 AND policy_id=$2")
+        .bind(role_id)
+        .bind(policy_id)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+/// Counts managed policies attached to the role.
+pub(crate) async fn count_attached_policies<'a, E>(executor: E, role_id: i64) -> Result<usize, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("SELECT COUNT(*) AS count FROM policy_roles WHERE role_id = $1")
+        .bind(role_id)
+        .map(|row: SqliteRow| row.get::<i64, &str>("count"))
+        .fetch_one(executor)
+        .await?;
+    Ok(result as usize)
+}
+
+/// Counts instance profiles the role is added to.
+pub(crate) async fn count_instance_profiles<'a, E>(executor: E, role_id: i64) -> Result<usize, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("SELECT COUNT(*) AS count FROM instance_profile_roles WHERE role_id = $1")
+        .bind(role_id)
+        .map(|row: SqliteRow| row.get::<i64, &str>("count"))
+        .fetch_one(executor)
+        .await?;
+    Ok(result as usize)
+}
+
+/// Deletes the role together with its policy attachments and instance profile assignments.
+pub(crate) async fn delete<'a>(tx: &mut Transaction<'a, Sqlite>, id: i64) -> Result<bool, Error> {
+    sqlx::query("DELETE FROM policy_roles WHERE role_id=$1")
+        .bind(id)
+        .execute(tx.as_mut())
+        .await?;
+    sqlx::query("DELETE FROM instance_profile_roles WHERE role_id=$1")
+        .bind(id)
+        .execute(tx.as_mut())
+        .await?;
+    let result = sqlx::query("DELETE FROM roles WHERE id=$1")
+        .bind(id)
+        .execute(tx.as_mut())
+        .await?;
+
+    Ok(result.rows_affected() == 1)
+}
@@ -30,3 +30,33 @@ where
 {
     db::inline_policy::find_by_parent_id(executor, "roles", "role_inline_policies", query).await
 }
+
+pub(crate) async fn find_all_by_role_id<'a, E>(executor: E, role_id: i64) -> Result<Vec<DbInlinePolicy>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    db::inline_policy::find_all_by_parent_id(executor, "role_inline_policies", role_id).await
+}
+
+pub(crate) async fn count_by_role_id<'a, E>(executor: E, role_id: i64) -> Result<usize, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    db::inline_policy::count_by_parent_id(executor, "role_inline_policies", role_id).await
+}
+
+pub(crate) async fn delete_by_role_id_and_name<'a, E>(
+    executor: E, role_id: i64, policy_name: &str,
+) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    db::inline_policy::delete_by_parent_id_and_name(executor, "role_inline_policies", role_id, policy_name).await
+}
+
+pub(crate) async fn delete_by_role_id<'a, E>(executor: E, role_id: i64) -> Result<u64, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    db::inline_policy::delete_by_parent_id(executor, "role_inline_policies", role_id).await
+}
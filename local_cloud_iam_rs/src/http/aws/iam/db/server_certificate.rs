@@ -97,3 +97,15 @@ where
 
     Ok(result.rows_affected() == 1)
 }
+
+pub(crate) async fn delete<'a, E>(executor: E, id: i64) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("DELETE FROM server_certificates WHERE id=$1")
+        .bind(id)
+        .execute(executor)
+        .await?;
+
+    Ok(result.rows_affected() == 1)
+}
@@ -0,0 +1,301 @@
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Error, Executor, FromRow, Row, Sqlite, Transaction};
+
+use crate::http::aws::iam::db::types::service_last_accessed::{
+    InsertServiceLastAccess, InsertServiceLastAccessedJob, JobStateType, SelectReportEntity, SelectServiceLastAccess,
+    SelectServiceLastAccessedJob,
+};
+
+/// Records the access of the principal to the service action, and the last usage of the access key and the user.
+/// Regions which are unknown to the `regions` table are not recorded.
+pub(crate) async fn record<'a>(
+    tx: &mut Transaction<'a, Sqlite>, access: &InsertServiceLastAccess,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"INSERT INTO service_last_accesses
+                (account_id, principal_arn, service_namespace, action_name, region_id, last_accessed_date)
+            VALUES ($1, $2, $3, $4, (SELECT id FROM regions WHERE region = $5), $6)
+            ON CONFLICT (principal_arn, service_namespace, action_name) DO UPDATE SET
+                region_id = excluded.region_id,
+                last_accessed_date = excluded.last_accessed_date"#,
+    )
+    .bind(access.account_id)
+    .bind(&access.principal_arn)
+    .bind(&access.service_namespace)
+    .bind(&access.action_name)
+    .bind(&access.region)
+    .bind(access.last_accessed_date)
+    .execute(tx.as_mut())
+    .await?;
+
+    sqlx::query(
+        r#"UPDATE access_keys SET
+                last_used_date = $2,
+                last_used_region_id = (SELECT id FROM regions WHERE region = $3),
+                last_used_service = $4
+            WHERE access_key_id = $1"#,
+    )
+    .bind(&access.access_key_id)
+    .bind(access.last_accessed_date)
+    .bind(&access.region)
+    .bind(&access.service_namespace)
+    .execute(tx.as_mut())
+    .await?;
+
+    sqlx::query("UPDATE users SET last_used_date = $3 WHERE account_id = $1 AND arn = $2")
+        .bind(access.account_id)
+        .bind(&access.principal_arn)
+        .bind(access.last_accessed_date)
+        .execute(tx.as_mut())
+        .await?;
+    Ok(())
+}
+
+/// Records the last usage of the role and instance profiles which contain the role.
+pub(crate) async fn record_role_usage<'a>(
+    tx: &mut Transaction<'a, Sqlite>, account_id: i64, role_name: &str, region: &str, last_used_date: i64,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"UPDATE roles SET
+                last_used_date = $3,
+                last_used_region_id = (SELECT id FROM regions WHERE region = $4)
+            WHERE account_id = $1 AND unique_role_name = $2"#,
+    )
+    .bind(account_id)
+    .bind(role_name.to_uppercase())
+    .bind(last_used_date)
+    .bind(region)
+    .execute(tx.as_mut())
+    .await?;
+
+    sqlx::query(
+        r#"UPDATE instance_profiles SET
+                last_used_date = $3,
+                last_used_region_id = (SELECT id FROM regions WHERE region = $4)
+            WHERE id IN (
+                SELECT ipr.instance_profile_id
+                FROM instance_profile_roles ipr LEFT JOIN roles r ON ipr.role_id = r.id
+                WHERE r.account_id = $1 AND r.unique_role_name = $2
+            )"#,
+    )
+    .bind(account_id)
+    .bind(role_name.to_uppercase())
+    .bind(last_used_date)
+    .bind(region)
+    .execute(tx.as_mut())
+    .await?;
+    Ok(())
+}
+
+pub(crate) async fn list_accesses<'a, E>(executor: E, account_id: i64) -> Result<Vec<SelectServiceLastAccess>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"SELECT
+                a.principal_arn AS principal_arn,
+                a.service_namespace AS service_namespace,
+                a.action_name AS action_name,
+                r.region AS region,
+                a.last_accessed_date AS last_accessed_date
+            FROM service_last_accesses a LEFT JOIN regions r ON a.region_id = r.id
+            WHERE a.account_id = $1
+            ORDER BY a.last_accessed_date DESC, a.id DESC"#,
+    )
+    .bind(account_id)
+    .map(|row: SqliteRow| SelectServiceLastAccess::from_row(&row).unwrap())
+    .fetch_all(executor)
+    .await?;
+    Ok(result)
+}
+
+/// Lists entities which are reported for the user, group, role or policy identified by ARN:
+/// the user itself, users of the group, the role itself, or users, groups and roles the policy is attached to.
+pub(crate) async fn list_report_entities<'a, E>(
+    executor: E, account_id: i64, arn: &str,
+) -> Result<Vec<SelectReportEntity>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"SELECT 'USER' AS entity_type, u.username AS name, u.user_id AS id, u.arn AS arn, u.path AS path,
+                u.arn AS principal_arn
+            FROM users u
+            WHERE u.account_id = $1 AND u.arn = $2
+        UNION ALL
+        SELECT 'USER' AS entity_type, u.username AS name, u.user_id AS id, u.arn AS arn, u.path AS path,
+                u.arn AS principal_arn
+            FROM groups g
+                INNER JOIN group_users gu ON g.id = gu.group_id
+                INNER JOIN users u ON gu.user_id = u.id
+            WHERE g.account_id = $1 AND g.arn = $2
+        UNION ALL
+        SELECT 'ROLE' AS entity_type, r.role_name AS name, r.role_id AS id, r.arn AS arn, r.path AS path,
+                r.arn AS principal_arn
+            FROM roles r
+            WHERE r.account_id = $1 AND r.arn = $2
+        UNION ALL
+        SELECT 'USER' AS entity_type, u.username AS name, u.user_id AS id, u.arn AS arn, u.path AS path,
+                u.arn AS principal_arn
+            FROM policies p
+                INNER JOIN policy_users pu ON p.id = pu.policy_id
+                INNER JOIN users u ON pu.user_id = u.id
+            WHERE p.arn = $2 AND u.account_id = $1
+        UNION ALL
+        SELECT 'GROUP' AS entity_type, g.group_name AS name, g.group_id AS id, g.arn AS arn, g.path AS path,
+                u.arn AS principal_arn
+            FROM policies p
+                INNER JOIN policy_groups pg ON p.id = pg.policy_id
+                INNER JOIN groups g ON pg.group_id = g.id
+                LEFT JOIN group_users gu ON g.id = gu.group_id
+                LEFT JOIN users u ON gu.user_id = u.id
+            WHERE p.arn = $2 AND g.account_id = $1
+        UNION ALL
+        SELECT 'ROLE' AS entity_type, r.role_name AS name, r.role_id AS id, r.arn AS arn, r.path AS path,
+                r.arn AS principal_arn
+            FROM policies p
+                INNER JOIN policy_roles pr ON p.id = pr.policy_id
+                INNER JOIN roles r ON pr.role_id = r.id
+            WHERE p.arn = $2 AND r.account_id = $1
+        ORDER BY arn ASC"#,
+    )
+    .bind(account_id)
+    .bind(arn)
+    .map(|row: SqliteRow| SelectReportEntity::from_row(&row).unwrap())
+    .fetch_all(executor)
+    .await?;
+    Ok(result)
+}
+
+pub(crate) async fn create_job<'a>(
+    tx: &mut Transaction<'a, Sqlite>, job: &mut InsertServiceLastAccessedJob,
+) -> Result<(), Error> {
+    let result = sqlx::query(
+        r#"INSERT INTO service_last_accessed_jobs (account_id, job_id, arn, granularity, state, create_date)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                RETURNING id"#,
+    )
+    .bind(job.account_id)
+    .bind(&job.job_id)
+    .bind(&job.arn)
+    .bind(job.granularity.as_i32())
+    .bind(JobStateType::Started.as_i32())
+    .bind(job.create_date)
+    .map(|row: SqliteRow| row.get::<i64, &str>("id"))
+    .fetch_one(tx.as_mut())
+    .await?;
+    job.id = Some(result);
+    Ok(())
+}
+
+pub(crate) async fn find_job<'a, E>(
+    executor: E, account_id: i64, job_id: &str,
+) -> Result<Option<SelectServiceLastAccessedJob>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"SELECT id, account_id, arn, granularity, state, content, error_code, error_message, create_date,
+                completion_date
+            FROM service_last_accessed_jobs
+            WHERE account_id = $1 AND job_id = $2"#,
+    )
+    .bind(account_id)
+    .bind(job_id)
+    .map(|row: SqliteRow| SelectServiceLastAccessedJob::from_row(&row).unwrap())
+    .fetch_optional(executor)
+    .await?;
+    Ok(result)
+}
+
+/// Returns jobs which are waiting to be started.
+pub(crate) async fn find_started_jobs<'a, E>(executor: E) -> Result<Vec<SelectServiceLastAccessedJob>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"SELECT id, account_id, arn, granularity, state, NULL AS content, error_code, error_message, create_date,
+                completion_date
+            FROM service_last_accessed_jobs
+            WHERE state = $1"#,
+    )
+    .bind(JobStateType::Started.as_i32())
+    .map(|row: SqliteRow| SelectServiceLastAccessedJob::from_row(&row).unwrap())
+    .fetch_all(executor)
+    .await?;
+    Ok(result)
+}
+
+/// Moves the job from `Started` to `InProgress` state. Returns `false` if the job has been already taken.
+pub(crate) async fn mark_in_progress<'a, E>(executor: E, id: i64, start_date: i64) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result =
+        sqlx::query("UPDATE service_last_accessed_jobs SET state = $3, start_date = $4 WHERE id = $1 AND state = $2")
+            .bind(id)
+            .bind(JobStateType::Started.as_i32())
+            .bind(JobStateType::InProgress.as_i32())
+            .bind(start_date)
+            .execute(executor)
+            .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+/// Moves jobs which have been `InProgress` since before `start_date` back to `Started` state.
+pub(crate) async fn restart_stale<'a, E>(executor: E, start_date: i64) -> Result<u64, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"UPDATE service_last_accessed_jobs SET state = $2, start_date = NULL
+            WHERE state = $1 AND (start_date IS NULL OR start_date < $3)"#,
+    )
+    .bind(JobStateType::InProgress.as_i32())
+    .bind(JobStateType::Started.as_i32())
+    .bind(start_date)
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+pub(crate) async fn complete_job<'a, E>(
+    executor: E, id: i64, content: &[u8], completion_date: i64,
+) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"UPDATE service_last_accessed_jobs SET state = $2, content = $3, completion_date = $4
+            WHERE id = $1 AND state = $5"#,
+    )
+    .bind(id)
+    .bind(JobStateType::Completed.as_i32())
+    .bind(content)
+    .bind(completion_date)
+    .bind(JobStateType::InProgress.as_i32())
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+pub(crate) async fn fail_job<'a, E>(
+    executor: E, id: i64, error_code: &str, error_message: &str, completion_date: i64,
+) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"UPDATE service_last_accessed_jobs SET state = $2, error_code = $3, error_message = $4, completion_date = $5
+            WHERE id = $1 AND state = $6"#,
+    )
+    .bind(id)
+    .bind(JobStateType::Failed.as_i32())
+    .bind(error_code)
+    .bind(error_message)
+    .bind(completion_date)
+    .bind(JobStateType::InProgress.as_i32())
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected() == 1)
+}
@@ -0,0 +1,176 @@
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Error, Executor, FromRow, Row, Sqlite, Transaction};
+
+use crate::http::aws::iam::db::types::service_linked_role::{
+    DeletionTaskStateType, InsertDeletionTask, SelectDeletionTask,
+};
+
+pub(crate) async fn create_deletion_task<'a>(
+    tx: &mut Transaction<'a, Sqlite>, task: &mut InsertDeletionTask,
+) -> Result<(), Error> {
+    let result = sqlx::query(
+        r#"INSERT INTO service_linked_role_deletion_tasks (account_id, deletion_task_id, role_id, state, create_date)
+                VALUES ($1, $2, $3, $4, $5)
+                RETURNING id"#,
+    )
+    .bind(task.account_id)
+    .bind(&task.deletion_task_id)
+    .bind(task.role_id)
+    .bind(DeletionTaskStateType::NotStarted.as_i32())
+    .bind(task.create_date)
+    .map(|row: SqliteRow| row.get::<i64, &str>("id"))
+    .fetch_one(tx.as_mut())
+    .await?;
+    task.id = Some(result);
+    Ok(())
+}
+
+pub(crate) async fn find_deletion_task<'a, E>(
+    executor: E, account_id: i64, deletion_task_id: &str,
+) -> Result<Option<SelectDeletionTask>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"SELECT id, deletion_task_id, role_id, state, reason
+            FROM service_linked_role_deletion_tasks
+            WHERE account_id = $1 AND deletion_task_id = $2"#,
+    )
+    .bind(account_id)
+    .bind(deletion_task_id)
+    .map(|row: SqliteRow| SelectDeletionTask::from_row(&row).unwrap())
+    .fetch_optional(executor)
+    .await?;
+    Ok(result)
+}
+
+/// Returns the deletion task of the role which is not finished yet.
+pub(crate) async fn find_active_deletion_task<'a, E>(
+    executor: E, role_id: i64,
+) -> Result<Option<SelectDeletionTask>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"SELECT id, deletion_task_id, role_id, state, reason
+            FROM service_linked_role_deletion_tasks
+            WHERE role_id = $1 AND state IN ($2, $3)"#,
+    )
+    .bind(role_id)
+    .bind(DeletionTaskStateType::NotStarted.as_i32())
+    .bind(DeletionTaskStateType::InProgress.as_i32())
+    .map(|row: SqliteRow| SelectDeletionTask::from_row(&row).unwrap())
+    .fetch_optional(executor)
+    .await?;
+    Ok(result)
+}
+
+/// Returns deletion tasks which are waiting to be started.
+pub(crate) async fn find_not_started<'a, E>(executor: E) -> Result<Vec<SelectDeletionTask>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"SELECT id, deletion_task_id, role_id, state, reason
+            FROM service_linked_role_deletion_tasks
+            WHERE state = $1"#,
+    )
+    .bind(DeletionTaskStateType::NotStarted.as_i32())
+    .map(|row: SqliteRow| SelectDeletionTask::from_row(&row).unwrap())
+    .fetch_all(executor)
+    .await?;
+    Ok(result)
+}
+
+/// Moves the task from `NOT_STARTED` to `IN_PROGRESS` state. Returns `false` if the task has been already taken.
+pub(crate) async fn mark_in_progress<'a, E>(executor: E, id: i64, start_date: i64) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        "UPDATE service_linked_role_deletion_tasks SET state = $3, start_date = $4 WHERE id = $1 AND state = $2",
+    )
+    .bind(id)
+    .bind(DeletionTaskStateType::NotStarted.as_i32())
+    .bind(DeletionTaskStateType::InProgress.as_i32())
+    .bind(start_date)
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+/// Moves tasks which have been `IN_PROGRESS` since before `start_date` back to `NOT_STARTED` state.
+pub(crate) async fn restart_stale<'a, E>(executor: E, start_date: i64) -> Result<u64, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"UPDATE service_linked_role_deletion_tasks SET state = $2, start_date = NULL
+            WHERE state = $1 AND (start_date IS NULL OR start_date < $3)"#,
+    )
+    .bind(DeletionTaskStateType::InProgress.as_i32())
+    .bind(DeletionTaskStateType::NotStarted.as_i32())
+    .bind(start_date)
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Finishes the task which is in `IN_PROGRESS` state with either `SUCCEEDED` or `FAILED` state.
+pub(crate) async fn finish<'a, E>(
+    executor: E, id: i64, state: &DeletionTaskStateType, reason: Option<&str>,
+) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        "UPDATE service_linked_role_deletion_tasks SET state = $2, reason = $3 WHERE id = $1 AND state = $4",
+    )
+    .bind(id)
+    .bind(state.as_i32())
+    .bind(reason)
+    .bind(DeletionTaskStateType::InProgress.as_i32())
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use local_cloud_db::LocalDb;
+
+    use crate::http::aws::iam::db::service_linked_role::{
+        create_deletion_task, find_not_started, mark_in_progress, restart_stale,
+    };
+    use crate::http::aws::iam::db::types::service_linked_role::InsertDeletionTask;
+
+    #[tokio::test]
+    async fn test_restart_stale() {
+        let database_url = format!("file:{}?mode=memory&cache=shared", Uuid::new_v4());
+        let db = LocalDb::new(&database_url, &sqlx::migrate!()).await.unwrap();
+
+        let mut tx = db.new_tx().await.unwrap();
+        let mut task = InsertDeletionTask {
+            id: None,
+            account_id: 1,
+            deletion_task_id: "task/aws-service-role/es.amazonaws.com/role/1".to_owned(),
+            role_id: 1,
+            create_date: 100,
+        };
+        create_deletion_task(&mut tx, &mut task).await.unwrap();
+        tx.commit().await.unwrap();
+
+        let mut connection = db.new_connection().await.unwrap();
+        let id = task.id.unwrap();
+        assert!(mark_in_progress(connection.as_mut(), id, 200).await.unwrap());
+        assert!(!mark_in_progress(connection.as_mut(), id, 200).await.unwrap());
+        assert!(find_not_started(connection.as_mut()).await.unwrap().is_empty());
+
+        // the task has been taken after the deadline
+        assert_eq!(0, restart_stale(connection.as_mut(), 200).await.unwrap());
+        assert_eq!(1, restart_stale(connection.as_mut(), 201).await.unwrap());
+        assert_eq!(id, find_not_started(connection.as_mut()).await.unwrap()[0].id);
+    }
+}
@@ -0,0 +1,195 @@
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Error, Executor, FromRow, QueryBuilder, Row, Sqlite, Transaction};
+
+use crate::http::aws::iam::db::types::service_specific_credential::{
+    InsertServiceSpecificCredential, SelectServiceSpecificCredential, ServiceSpecificCredentialStatusType,
+};
+
+pub(crate) async fn create<'a>(
+    tx: &mut Transaction<'a, Sqlite>, credential: &mut InsertServiceSpecificCredential,
+) -> Result<(), Error> {
+    let result = sqlx::query(
+        r#"INSERT INTO service_specific_credentials (
+                    account_id,
+                    user_id,
+                    credential_id,
+                    service_name,
+                    service_user_name,
+                    password_hash,
+                    status,
+                    create_date
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                RETURNING id"#,
+    )
+    .bind(credential.account_id)
+    .bind(credential.user_id)
+    .bind(&credential.credential_id)
+    .bind(&credential.service_name)
+    .bind(&credential.service_user_name)
+    .bind(&credential.password_hash)
+    .bind(credential.status.as_i32())
+    .bind(credential.create_date)
+    .map(|row: SqliteRow| row.get::<i64, &str>("id"))
+    .fetch_one(tx.as_mut())
+    .await?;
+    credential.id = Some(result);
+    Ok(())
+}
+
+pub(crate) async fn find_by_credential_id<'a, E>(
+    executor: E, user_id: i64, credential_id: &str,
+) -> Result<Option<SelectServiceSpecificCredential>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"SELECT
+                ssc.id AS id,
+                ssc.account_id AS account_id,
+                u.username AS user_name,
+                ssc.credential_id AS credential_id,
+                ssc.service_name AS service_name,
+                ssc.service_user_name AS service_user_name,
+                ssc.password_hash AS password_hash,
+                ssc.status AS status,
+                ssc.create_date AS create_date
+            FROM service_specific_credentials ssc JOIN users u ON ssc.user_id = u.id
+            WHERE ssc.user_id = $1 AND ssc.credential_id = $2"#,
+    )
+    .bind(user_id)
+    .bind(credential_id)
+    .map(|row: SqliteRow| SelectServiceSpecificCredential::from_row(&row).unwrap())
+    .fetch_optional(executor)
+    .await?;
+    Ok(result)
+}
+
+/// Returns the credential which is used to sign in to the service with the service user name.
+pub(crate) async fn find_by_service_user_name<'a, E>(
+    executor: E, service_name: &str, service_user_name: &str,
+) -> Result<Option<SelectServiceSpecificCredential>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"SELECT
+                ssc.id AS id,
+                ssc.account_id AS account_id,
+                u.username AS user_name,
+                ssc.credential_id AS credential_id,
+                ssc.service_name AS service_name,
+                ssc.service_user_name AS service_user_name,
+                ssc.password_hash AS password_hash,
+                ssc.status AS status,
+                ssc.create_date AS create_date
+            FROM service_specific_credentials ssc JOIN users u ON ssc.user_id = u.id
+            WHERE ssc.service_name = $1 AND ssc.service_user_name = $2"#,
+    )
+    .bind(service_name)
+    .bind(service_user_name)
+    .map(|row: SqliteRow| SelectServiceSpecificCredential::from_row(&row).unwrap())
+    .fetch_optional(executor)
+    .await?;
+    Ok(result)
+}
+
+pub(crate) async fn list<'a, E>(
+    executor: E, user_id: i64, service_name: Option<&'a str>,
+) -> Result<Vec<SelectServiceSpecificCredential>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new(
+        r#"SELECT
+                ssc.id AS id,
+                ssc.account_id AS account_id,
+                u.username AS user_name,
+                ssc.credential_id AS credential_id,
+                ssc.service_name AS service_name,
+                ssc.service_user_name AS service_user_name,
+                ssc.password_hash AS password_hash,
+                ssc.status AS status,
+                ssc.create_date AS create_date
+            FROM service_specific_credentials ssc JOIN users u ON ssc.user_id = u.id
+            WHERE ssc.user_id = "#,
+    );
+    query_builder.push_bind(user_id);
+    if let Some(service_name) = service_name {
+        query_builder.push(" AND ssc.service_name = ").push_bind(service_name);
+    }
+    let result = query_builder
+        .push(" ORDER BY ssc.id ASC")
+        .build()
+        .map(|row: SqliteRow| SelectServiceSpecificCredential::from_row(&row).unwrap())
+        .fetch_all(executor)
+        .await?;
+    Ok(result)
+}
+
+pub(crate) async fn count_by_user_id<'a, E>(executor: E, user_id: i64) -> Result<usize, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("SELECT COUNT(*) AS count FROM service_specific_credentials WHERE user_id = $1")
+        .bind(user_id)
+        .map(|row: SqliteRow| row.get::<i64, &str>("count"))
+        .fetch_one(executor)
+        .await?;
+    Ok(result as usize)
+}
+
+pub(crate) async fn count_by_service_name<'a, E>(executor: E, user_id: i64, service_name: &str) -> Result<usize, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        "SELECT COUNT(*) AS count FROM service_specific_credentials WHERE user_id = $1 AND service_name = $2",
+    )
+    .bind(user_id)
+    .bind(service_name)
+    .map(|row: SqliteRow| row.get::<i64, &str>("count"))
+    .fetch_one(executor)
+    .await?;
+    Ok(result as usize)
+}
+
+pub(crate) async fn update_status<'a, E>(
+    executor: E, user_id: i64, credential_id: &str, status: &ServiceSpecificCredentialStatusType,
+) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result =
+        sqlx::query("UPDATE service_specific_credentials SET status = $1 WHERE user_id = $2 AND credential_id = $3")
+            .bind(status.as_i32())
+            .bind(user_id)
+            .bind(credential_id)
+            .execute(executor)
+            .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+pub(crate) async fn update_password_hash<'a, E>(executor: E, id: i64, password_hash: &str) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("UPDATE service_specific_credentials SET password_hash = $1 WHERE id = $2")
+        .bind(password_hash)
+        .bind(id)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+pub(crate) async fn delete<'a, E>(executor: E, user_id: i64, credential_id: &str) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("DELETE FROM service_specific_credentials WHERE user_id = $1 AND credential_id = $2")
+        .bind(user_id)
+        .bind(credential_id)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected() == 1)
+}
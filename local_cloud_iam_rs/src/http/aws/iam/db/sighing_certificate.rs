@@ -49,3 +49,28 @@ where
 
     Ok(result.rows_affected() == 1)
 }
+
+pub(crate) async fn count_by_user_id<'a, E>(executor: E, user_id: i64) -> Result<usize, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("SELECT COUNT(*) AS count FROM signing_certificates WHERE user_id = $1")
+        .bind(user_id)
+        .map(|row: SqliteRow| row.get::<i64, &str>("count"))
+        .fetch_one(executor)
+        .await?;
+    Ok(result as usize)
+}
+
+pub(crate) async fn delete<'a, E>(executor: E, user_id: i64, certificate_id: &str) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("DELETE FROM signing_certificates WHERE certificate_id=$1 AND user_id=$2")
+        .bind(certificate_id)
+        .bind(user_id)
+        .execute(executor)
+        .await?;
+
+    Ok(result.rows_affected() == 1)
+}
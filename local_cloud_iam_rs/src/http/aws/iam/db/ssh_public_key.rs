@@ -38,3 +38,28 @@ where
 
     Ok(result.rows_affected() == 1)
 }
+
+pub(crate) async fn count_by_user_id<'a, E>(executor: E, user_id: i64) -> Result<usize, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("SELECT COUNT(*) AS count FROM user_ssh_public_keys WHERE user_id = $1")
+        .bind(user_id)
+        .map(|row: SqliteRow| row.get::<i64, &str>("count"))
+        .fetch_one(executor)
+        .await?;
+    Ok(result as usize)
+}
+
+pub(crate) async fn delete<'a, E>(executor: E, user_id: i64, key_id: &str) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("DELETE FROM user_ssh_public_keys WHERE key_id=$1 AND user_id=$2")
+        .bind(key_id)
+        .bind(user_id)
+        .execute(executor)
+        .await?;
+
+    Ok(result.rows_affected() == 1)
+}
@@ -0,0 +1,120 @@
+use aws_sdk_iam::types::AccessKeyMetadata;
+use aws_smithy_types::DateTime;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Error, FromRow, Row};
+
+use crate::http::aws::iam::db::types::access_key_status_type::AccessKeyStatusType;
+use crate::http::aws::iam::db::types::common::Pageable;
+use crate::http::aws::iam::types::marker_type::MarkerType;
+
+#[derive(Debug)]
+pub(crate) struct InsertAccessKey {
+    pub(crate) id: Option<i64>,
+    pub(crate) account_id: i64,
+    pub(crate) user_id: i64,
+    pub(crate) access_key_id: String,
+    pub(crate) secret_access_key: String,
+    pub(crate) status: AccessKeyStatusType,
+    pub(crate) create_date: i64,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct SelectAccessKey {
+    pub(crate) account_id: i64,
+    pub(crate) user_name: String,
+    pub(crate) user_arn: String,
+    /// Unique ID of the user, e.g. `AIDA...`.
+    pub(crate) user_unique_id: String,
+    pub(crate) access_key_id: String,
+    pub(crate) secret_access_key: String,
+    pub(crate) status: AccessKeyStatusType,
+    pub(crate) create_date: i64,
+}
+
+impl<'r> FromRow<'r, SqliteRow> for SelectAccessKey {
+    fn from_row(row: &'r SqliteRow) -> Result<Self, Error> {
+        let account_id: i64 = row.try_get("account_id")?;
+        let user_name: String = row.try_get("user_name")?;
+        let user_arn: String = row.try_get("user_arn")?;
+        let user_unique_id: String = row.try_get("user_unique_id")?;
+        let access_key_id: String = row.try_get("access_key_id")?;
+        let secret_access_key: String = row.try_get("secret_access_key")?;
+        let status: i32 = row.try_get("status")?;
+        let create_date: i64 = row.try_get("create_date")?;
+        Ok(SelectAccessKey {
+            account_id,
+            user_name,
+            user_arn,
+            user_unique_id,
+            access_key_id,
+            secret_access_key,
+            status: status.into(),
+            create_date,
+        })
+    }
+}
+
+impl From<&SelectAccessKey> for AccessKeyMetadata {
+    fn from(value: &SelectAccessKey) -> Self {
+        AccessKeyMetadata::builder()
+            .user_name(&value.user_name)
+            .access_key_id(&value.access_key_id)
+            .status((&value.status).into())
+            .create_date(DateTime::from_secs(value.create_date))
+            .build()
+    }
+}
+
+/// Last usage of the access key. Region and service are `None` if the key has never been used.
+#[derive(Debug, FromRow)]
+pub(crate) struct SelectAccessKeyLastUsed {
+    pub(crate) user_name: String,
+    pub(crate) last_used_date: Option<i64>,
+    pub(crate) last_used_region: Option<String>,
+    pub(crate) last_used_service: Option<String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ListAccessKeysQuery {
+    pub(crate) user_id: i64,
+    pub(crate) limit: i32,
+    pub(crate) skip: i32,
+}
+
+impl ListAccessKeysQuery {
+    pub(crate) fn new(user_id: i64, max_items: Option<&i32>, marker_type: Option<&MarkerType>) -> Self {
+        let limit = match max_items {
+            None => 10,
+            Some(v) => *v,
+        };
+
+        let skip = match marker_type {
+            None => 0,
+            // unwrap is safe since marker must be validated before DB query preparation
+            Some(marker_type) => marker_type.marker().unwrap().truncate_amount,
+        };
+
+        ListAccessKeysQuery {
+            user_id,
+            limit: if limit < 1 { 10 } else { limit },
+            skip,
+        }
+    }
+}
+
+impl Pageable for &ListAccessKeysQuery {
+    fn limit(&self) -> i32 {
+        self.limit
+    }
+
+    fn skip(&self) -> i32 {
+        self.skip
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct UpdateAccessKeyQuery {
+    pub(crate) access_key_id: String,
+    pub(crate) status: AccessKeyStatusType,
+    pub(crate) user_id: i64,
+}
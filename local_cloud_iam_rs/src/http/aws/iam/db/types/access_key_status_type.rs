@@ -0,0 +1,52 @@
+use crate::http::aws::iam::types::status_type::StatusType;
+
+#[derive(Debug, Clone, PartialEq, sqlx::Type)]
+#[repr(i32)]
+pub enum AccessKeyStatusType {
+    Active,
+    Inactive,
+}
+
+impl AccessKeyStatusType {
+    pub(crate) fn as_i32(&self) -> i32 {
+        match self {
+            AccessKeyStatusType::Active => 1,
+            AccessKeyStatusType::Inactive => 2,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            AccessKeyStatusType::Active => "Active",
+            AccessKeyStatusType::Inactive => "Inactive",
+        }
+    }
+}
+
+impl From<i32> for AccessKeyStatusType {
+    fn from(value: i32) -> Self {
+        if value == 1 {
+            Self::Active
+        } else {
+            Self::Inactive
+        }
+    }
+}
+
+impl From<&StatusType> for AccessKeyStatusType {
+    fn from(value: &StatusType) -> Self {
+        match value {
+            StatusType::Inactive => Self::Inactive,
+            StatusType::Active => Self::Active,
+        }
+    }
+}
+
+impl From<&AccessKeyStatusType> for aws_sdk_iam::types::StatusType {
+    fn from(value: &AccessKeyStatusType) -> Self {
+        match value {
+            AccessKeyStatusType::Active => aws_sdk_iam::types::StatusType::Active,
+            AccessKeyStatusType::Inactive => aws_sdk_iam::types::StatusType::Inactive,
+        }
+    }
+}
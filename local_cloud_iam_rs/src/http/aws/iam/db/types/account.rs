@@ -0,0 +1,83 @@
+use sqlx::FromRow;
+
+use crate::http::aws::iam::db::types::common::Pageable;
+use crate::http::aws::iam::types::entity_type::EntityType;
+use crate::http::aws::iam::types::get_account_authorization_details::GetAccountAuthorizationDetailsRequest;
+
+#[derive(Debug)]
+pub(crate) struct AuthorizationDetailsQuery {
+    pub(crate) include_users: bool,
+    pub(crate) include_groups: bool,
+    pub(crate) include_roles: bool,
+    pub(crate) include_local_managed_policies: bool,
+    pub(crate) include_aws_managed_policies: bool,
+    pub(crate) limit: i32,
+    pub(crate) skip: i32,
+}
+
+impl Pageable for &AuthorizationDetailsQuery {
+    fn limit(&self) -> i32 {
+        self.limit
+    }
+
+    fn skip(&self) -> i32 {
+        self.skip
+    }
+}
+
+impl From<&GetAccountAuthorizationDetailsRequest> for AuthorizationDetailsQuery {
+    fn from(value: &GetAccountAuthorizationDetailsRequest) -> Self {
+        let limit = match value.max_items() {
+            None => 100,
+            Some(v) => *v,
+        };
+
+        let skip = match value.marker_type() {
+            None => 0,
+            // unwrap is safe since marker must be validated before DB query preparation
+            Some(marker_type) => marker_type.marker().unwrap().truncate_amount,
+        };
+
+        // all entities are included if the filter is not specified
+        let filter = value.filter().unwrap_or_default();
+        let includes = |entity_type: EntityType| filter.is_empty() || filter.contains(&entity_type);
+
+        AuthorizationDetailsQuery {
+            include_users: includes(EntityType::User),
+            include_groups: includes(EntityType::Group),
+            include_roles: includes(EntityType::Role),
+            include_local_managed_policies: includes(EntityType::LocalManagedPolicy),
+            include_aws_managed_policies: includes(EntityType::AwsManagedPolicy),
+            limit: if limit < 1 { 100 } else { limit },
+            skip,
+        }
+    }
+}
+
+/// Counts of IAM entities which are reported by the account summary and limited by quotas.
+#[derive(Debug, FromRow)]
+pub(crate) struct SelectAccountSummary {
+    pub(crate) users: i32,
+    pub(crate) groups: i32,
+    pub(crate) roles: i32,
+    pub(crate) policies: i32,
+    pub(crate) instance_profiles: i32,
+    pub(crate) server_certificates: i32,
+    pub(crate) providers: i32,
+    pub(crate) policy_versions_in_use: i32,
+    pub(crate) mfa_devices: i32,
+    pub(crate) mfa_devices_in_use: i32,
+    pub(crate) account_mfa_enabled: bool,
+    pub(crate) account_access_keys_present: bool,
+    pub(crate) account_signing_certificates_present: bool,
+}
+
+/// Counts of entities reported by `GetAccountAuthorizationDetails`.
+#[derive(Debug, FromRow)]
+pub(crate) struct SelectAuthorizationEntityCounts {
+    pub(crate) users: i32,
+    pub(crate) groups: i32,
+    pub(crate) roles: i32,
+    pub(crate) local_managed_policies: i32,
+    pub(crate) aws_managed_policies: i32,
+}
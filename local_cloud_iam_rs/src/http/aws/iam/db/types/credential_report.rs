@@ -0,0 +1,96 @@
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Error, FromRow, Row};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ReportStateType {
+    Started,
+    InProgress,
+    Complete,
+}
+
+impl ReportStateType {
+    pub(crate) fn as_i32(&self) -> i32 {
+        match self {
+            ReportStateType::Started => 1,
+            ReportStateType::InProgress => 2,
+            ReportStateType::Complete => 3,
+        }
+    }
+}
+
+impl From<i32> for ReportStateType {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => ReportStateType::Started,
+            2 => ReportStateType::InProgress,
+            _ => ReportStateType::Complete,
+        }
+    }
+}
+
+impl From<&ReportStateType> for aws_sdk_iam::types::ReportStateType {
+    fn from(value: &ReportStateType) -> Self {
+        match value {
+            ReportStateType::Started => aws_sdk_iam::types::ReportStateType::Started,
+            ReportStateType::InProgress => aws_sdk_iam::types::ReportStateType::Inprogress,
+            ReportStateType::Complete => aws_sdk_iam::types::ReportStateType::Complete,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct SelectCredentialReport {
+    pub(crate) id: i64,
+    pub(crate) account_id: i64,
+    pub(crate) state: ReportStateType,
+    pub(crate) content: Option<Vec<u8>>,
+    pub(crate) generated_time: Option<i64>,
+}
+
+impl<'r> FromRow<'r, SqliteRow> for SelectCredentialReport {
+    fn from_row(row: &'r SqliteRow) -> Result<Self, Error> {
+        let id: i64 = row.try_get("id")?;
+        let account_id: i64 = row.try_get("account_id")?;
+        let state: i32 = row.try_get("state")?;
+        let content: Option<Vec<u8>> = row.try_get("content")?;
+        let generated_time: Option<i64> = row.try_get("generated_time")?;
+        Ok(SelectCredentialReport {
+            id,
+            account_id,
+            state: state.into(),
+            content,
+            generated_time,
+        })
+    }
+}
+
+/// IAM user as it is presented in the credential report.
+#[derive(Debug, FromRow)]
+pub(crate) struct SelectReportUser {
+    pub(crate) id: i64,
+    pub(crate) username: String,
+    pub(crate) arn: String,
+    pub(crate) user_id: String,
+    pub(crate) create_date: i64,
+    pub(crate) password_change_date: Option<i64>,
+    pub(crate) mfa_active: bool,
+}
+
+/// Access key of IAM user as it is presented in the credential report.
+#[derive(Debug, FromRow)]
+pub(crate) struct SelectReportAccessKey {
+    pub(crate) user_id: i64,
+    pub(crate) is_active: bool,
+    pub(crate) create_date: i64,
+    pub(crate) last_used_date: Option<i64>,
+    pub(crate) last_used_region: Option<String>,
+    pub(crate) last_used_service: Option<String>,
+}
+
+/// Signing certificate of IAM user as it is presented in the credential report.
+#[derive(Debug, FromRow)]
+pub(crate) struct SelectReportCredential {
+    pub(crate) user_id: i64,
+    pub(crate) is_active: bool,
+    pub(crate) create_date: i64,
+}
@@ -1,4 +1,4 @@
-use aws_sdk_iam::types::InstanceProfile;
+use aws_sdk_iam::types::{InstanceProfile, Role};
 use aws_smithy_types::DateTime;
 use sqlx::FromRow;
 
@@ -37,6 +37,20 @@ pub(crate) struct SelectRoleForInstanceProfile {
     pub(crate) create_date: i64,
 }
 
+impl From<&SelectRoleForInstanceProfile> for Role {
+    fn from(value: &SelectRoleForInstanceProfile) -> Self {
+        Role::builder()
+            .arn(&value.arn)
+            .create_date(DateTime::from_secs(value.create_date))
+            .path(&value.path)
+            .assume_role_policy_document(urlencoding::encode(&value.assume_role_policy_document))
+            .role_name(&value.role_name)
+            .role_id(&value.role_id)
+            .build()
+            .unwrap()
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct ListInstanceProfilesQuery {
     pub(crate) path_prefix: String,
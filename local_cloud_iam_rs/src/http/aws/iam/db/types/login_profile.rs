@@ -1,3 +1,5 @@
+use sqlx::FromRow;
+
 #[derive(Debug)]
 pub(crate) struct InsertLoginProfile {
     pub(crate) id: Option<i64>,
@@ -6,3 +8,20 @@ pub(crate) struct InsertLoginProfile {
     pub(crate) password_reset_required: bool,
     pub(crate) create_date: i64,
 }
+
+/// Fields of the login profile to update. `None` keeps the stored value.
+#[derive(Debug)]
+pub(crate) struct UpdateLoginProfile {
+    pub(crate) user_id: i64,
+    pub(crate) password_hash: Option<String>,
+    pub(crate) password_change_date: Option<i64>,
+    pub(crate) password_reset_required: Option<bool>,
+}
+
+#[derive(Debug, FromRow)]
+pub(crate) struct SelectLoginProfile {
+    pub(crate) password_hash: String,
+    pub(crate) password_reset_required: bool,
+    pub(crate) create_date: i64,
+    pub(crate) password_change_date: i64,
+}
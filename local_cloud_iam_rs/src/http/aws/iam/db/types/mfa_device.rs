@@ -1,10 +1,11 @@
-use aws_sdk_iam::types::{User, VirtualMfaDevice};
+use aws_sdk_iam::types::{MfaDevice, User, VirtualMfaDevice};
 use aws_smithy_types::DateTime;
 use sqlx::FromRow;
 
 use crate::http::aws::iam::db::types::common::Pageable;
 use crate::http::aws::iam::types::assignment_status_type::AssignmentStatusType;
 use crate::http::aws::iam::types::list_virtual_mfa_devices::ListVirtualMfaDevicesRequest;
+use crate::http::aws::iam::types::marker_type::MarkerType;
 
 #[derive(Debug)]
 pub(crate) struct InsertMfaDevice {
@@ -28,6 +29,7 @@ pub(crate) struct SelectMfaDevice {
     pub(crate) create_date: i64,
     pub(crate) enable_date: Option<i64>,
     pub(crate) user_id: Option<i64>,
+    pub(crate) time_offset: i64,
     pub(crate) user_user_id: Option<String>,
     pub(crate) user_name: Option<String>,
     pub(crate) user_arn: Option<String>,
@@ -43,6 +45,7 @@ pub(crate) struct EnableMfaDeviceQuery {
     pub(crate) user_id: i64,
     pub(crate) code1: String,
     pub(crate) code2: String,
+    pub(crate) time_offset: i64,
 }
 
 #[derive(Debug)]
@@ -86,6 +89,55 @@ impl Into<ListVirtualMfaDevicesQuery> for &ListVirtualMfaDevicesRequest {
     }
 }
 
+#[derive(Debug)]
+pub(crate) struct ListMfaDevicesQuery {
+    pub(crate) user_id: i64,
+    pub(crate) limit: i32,
+    pub(crate) skip: i32,
+}
+
+impl ListMfaDevicesQuery {
+    pub(crate) fn new(user_id: i64, max_items: Option<&i32>, marker_type: Option<&MarkerType>) -> Self {
+        let limit = match max_items {
+            None => 10,
+            Some(v) => *v,
+        };
+
+        let skip = match marker_type {
+            None => 0,
+            // unwrap is safe since marker must be validated before DB query preparation
+            Some(marker_type) => marker_type.marker().unwrap().truncate_amount,
+        };
+
+        ListMfaDevicesQuery {
+            user_id,
+            limit: if limit < 1 { 10 } else { limit },
+            skip,
+        }
+    }
+}
+
+impl Pageable for &ListMfaDevicesQuery {
+    fn limit(&self) -> i32 {
+        self.limit
+    }
+
+    fn skip(&self) -> i32 {
+        self.skip
+    }
+}
+
+impl From<&SelectMfaDevice> for MfaDevice {
+    fn from(value: &SelectMfaDevice) -> Self {
+        MfaDevice::builder()
+            .user_name(value.user_name.as_deref().unwrap_or_default())
+            .serial_number(&value.serial_number)
+            .enable_date(DateTime::from_secs(value.enable_date.unwrap_or(value.create_date)))
+            .build()
+            .unwrap()
+    }
+}
+
 impl From<&SelectMfaDevice> for VirtualMfaDevice {
     fn from(value: &SelectMfaDevice) -> Self {
         let user = match value.user_id {
@@ -1,17 +1,26 @@
+pub(crate) mod access_key;
+pub(crate) mod access_key_status_type;
+pub(crate) mod account;
 pub(crate) mod common;
+pub(crate) mod credential_report;
 pub(crate) mod group;
 pub(crate) mod inline_policy;
 pub(crate) mod instance_profile;
 pub(crate) mod login_profile;
 pub(crate) mod mfa_device;
 pub(crate) mod open_id_connect_provider;
+pub(crate) mod password_policy;
 pub(crate) mod policy;
 pub(crate) mod policy_type;
 pub(crate) mod policy_version;
+pub(crate) mod quota;
 pub(crate) mod resource_identifier;
 pub(crate) mod role;
 pub(crate) mod saml_provider;
 pub(crate) mod server_certificate;
+pub(crate) mod service_last_accessed;
+pub(crate) mod service_linked_role;
+pub(crate) mod service_specific_credential;
 pub(crate) mod signing_certificate;
 pub(crate) mod signing_certificate_status_type;
 pub(crate) mod ssh_public_key;
@@ -0,0 +1,62 @@
+use aws_sdk_iam::types::PasswordPolicy;
+use sqlx::FromRow;
+
+#[derive(Debug)]
+pub(crate) struct InsertPasswordPolicy {
+    pub(crate) account_id: i64,
+    pub(crate) minimum_password_length: i32,
+    pub(crate) require_symbols: bool,
+    pub(crate) require_numbers: bool,
+    pub(crate) require_uppercase_characters: bool,
+    pub(crate) require_lowercase_characters: bool,
+    pub(crate) allow_users_to_change_password: bool,
+    pub(crate) max_password_age: i32,
+    pub(crate) password_reuse_prevention: i32,
+    pub(crate) hard_expiry: bool,
+}
+
+#[derive(Debug, FromRow)]
+pub(crate) struct SelectPasswordPolicy {
+    pub(crate) minimum_password_length: i32,
+    pub(crate) require_symbols: bool,
+    pub(crate) require_numbers: bool,
+    pub(crate) require_uppercase_characters: bool,
+    pub(crate) require_lowercase_characters: bool,
+    pub(crate) allow_users_to_change_password: bool,
+    pub(crate) max_password_age: i32,
+    pub(crate) password_reuse_prevention: i32,
+    pub(crate) hard_expiry: bool,
+}
+
+impl SelectPasswordPolicy {
+    /// Returns `true` if a password set at `password_change_date` (millis) has expired at `current_time` (millis).
+    pub(crate) fn is_expired(&self, password_change_date: i64, current_time: i64) -> bool {
+        self.max_password_age > 0
+            && current_time - password_change_date > self.max_password_age as i64 * 24 * 60 * 60 * 1000
+    }
+}
+
+impl From<&SelectPasswordPolicy> for PasswordPolicy {
+    fn from(value: &SelectPasswordPolicy) -> Self {
+        let builder = PasswordPolicy::builder()
+            .minimum_password_length(value.minimum_password_length)
+            .require_symbols(value.require_symbols)
+            .require_numbers(value.require_numbers)
+            .require_uppercase_characters(value.require_uppercase_characters)
+            .require_lowercase_characters(value.require_lowercase_characters)
+            .allow_users_to_change_password(value.allow_users_to_change_password)
+            .expire_passwords(value.max_password_age > 0)
+            .hard_expiry(value.hard_expiry);
+        let builder = if value.max_password_age > 0 {
+            builder.max_password_age(value.max_password_age)
+        } else {
+            builder
+        };
+        let builder = if value.password_reuse_prevention > 0 {
+            builder.password_reuse_prevention(value.password_reuse_prevention)
+        } else {
+            builder
+        };
+        builder.build()
+    }
+}
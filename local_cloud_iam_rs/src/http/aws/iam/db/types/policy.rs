@@ -1,4 +1,4 @@
-use aws_sdk_iam::types::Policy;
+use aws_sdk_iam::types::{AttachedPolicy, Policy, PolicyGroup, PolicyRole, PolicyUser};
 use aws_smithy_types::DateTime;
 use derive_builder::Builder;
 use sqlx::sqlite::SqliteRow;
@@ -8,6 +8,7 @@ use crate::http::aws::iam::db::types::common::Pageable;
 use crate::http::aws::iam::db::types::policy_type::PolicyType;
 use crate::http::aws::iam::db::types::tags::DbTag;
 use crate::http::aws::iam::types::list_policies::ListPoliciesRequest;
+use crate::http::aws::iam::types::marker_type::MarkerType;
 use crate::http::aws::iam::types::policy_scope_type::PolicyScopeType;
 
 #[derive(Debug, Builder)]
@@ -175,3 +176,158 @@ impl Into<ListPoliciesQuery> for &ListPoliciesRequest {
         }
     }
 }
+
+/// Managed policy attached to a user, group or role.
+#[derive(Debug, FromRow)]
+pub(crate) struct SelectAttachedPolicy {
+    pub(crate) policy_name: String,
+    pub(crate) arn: String,
+}
+
+impl From<&SelectAttachedPolicy> for AttachedPolicy {
+    fn from(value: &SelectAttachedPolicy) -> Self {
+        AttachedPolicy::builder()
+            .policy_name(&value.policy_name)
+            .policy_arn(&value.arn)
+            .build()
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ListAttachedPoliciesQuery {
+    pub(crate) parent_id: i64,
+    pub(crate) path_prefix: String,
+    pub(crate) limit: i32,
+    pub(crate) skip: i32,
+}
+
+impl Pageable for &ListAttachedPoliciesQuery {
+    fn limit(&self) -> i32 {
+        self.limit
+    }
+
+    fn skip(&self) -> i32 {
+        self.skip
+    }
+}
+
+impl ListAttachedPoliciesQuery {
+    pub(crate) fn new(
+        parent_id: i64, path_prefix: Option<&str>, max_items: Option<&i32>, marker_type: Option<&MarkerType>,
+    ) -> Self {
+        let limit = match max_items {
+            None => 10,
+            Some(v) => *v,
+        };
+
+        let skip = match marker_type {
+            None => 0,
+            // unwrap is safe since marker must be validated before DB query preparation
+            Some(marker_type) => marker_type.marker().unwrap().truncate_amount,
+        };
+
+        ListAttachedPoliciesQuery {
+            parent_id,
+            path_prefix: path_prefix.unwrap_or("/").to_owned(),
+            limit: if limit < 1 { 10 } else { limit },
+            skip,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum PolicyEntityType {
+    User,
+    Group,
+    Role,
+}
+
+impl PolicyEntityType {
+    pub(crate) fn as_i32(&self) -> i32 {
+        match self {
+            PolicyEntityType::User => 1,
+            PolicyEntityType::Group => 2,
+            PolicyEntityType::Role => 3,
+        }
+    }
+}
+
+impl From<i32> for PolicyEntityType {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => PolicyEntityType::User,
+            2 => PolicyEntityType::Group,
+            _ => PolicyEntityType::Role,
+        }
+    }
+}
+
+/// User, group or role which uses a managed policy either as a permissions policy or as a permissions boundary.
+#[derive(Debug)]
+pub(crate) struct SelectPolicyEntity {
+    pub(crate) entity_type: PolicyEntityType,
+    pub(crate) entity_name: String,
+    pub(crate) entity_id: String,
+}
+
+impl<'r> FromRow<'r, SqliteRow> for SelectPolicyEntity {
+    fn from_row(row: &'r SqliteRow) -> Result<Self, Error> {
+        let entity_type: i32 = row.try_get("entity_type")?;
+        let entity_name: String = row.try_get("entity_name")?;
+        let entity_id: String = row.try_get("entity_id")?;
+        Ok(SelectPolicyEntity {
+            entity_type: entity_type.into(),
+            entity_name,
+            entity_id,
+        })
+    }
+}
+
+impl From<&SelectPolicyEntity> for PolicyUser {
+    fn from(value: &SelectPolicyEntity) -> Self {
+        PolicyUser::builder()
+            .user_name(&value.entity_name)
+            .user_id(&value.entity_id)
+            .build()
+    }
+}
+
+impl From<&SelectPolicyEntity> for PolicyGroup {
+    fn from(value: &SelectPolicyEntity) -> Self {
+        PolicyGroup::builder()
+            .group_name(&value.entity_name)
+            .group_id(&value.entity_id)
+            .build()
+    }
+}
+
+impl From<&SelectPolicyEntity> for PolicyRole {
+    fn from(value: &SelectPolicyEntity) -> Self {
+        PolicyRole::builder()
+            .role_name(&value.entity_name)
+            .role_id(&value.entity_id)
+            .build()
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ListEntitiesForPolicyQuery {
+    pub(crate) account_id: i64,
+    pub(crate) policy_id: i64,
+    pub(crate) path_prefix: String,
+    pub(crate) entity_types: Vec<PolicyEntityType>,
+    pub(crate) include_permissions_policy: bool,
+    pub(crate) include_permissions_boundary: bool,
+    pub(crate) limit: i32,
+    pub(crate) skip: i32,
+}
+
+impl Pageable for &ListEntitiesForPolicyQuery {
+    fn limit(&self) -> i32 {
+        self.limit
+    }
+
+    fn skip(&self) -> i32 {
+        self.skip
+    }
+}
@@ -40,6 +40,13 @@ impl From<&SelectPolicyVersion> for PolicyVersion {
     }
 }
 
+/// Document of the default version of a managed policy.
+#[derive(Debug, FromRow)]
+pub(crate) struct SelectPolicyDocument {
+    pub(crate) policy_arn: String,
+    pub(crate) policy_document: String,
+}
+
 #[derive(Debug)]
 pub(crate) struct ListPolicyVersionsQuery {
     pub(crate) policy_id: i64,
@@ -0,0 +1,7 @@
+use sqlx::FromRow;
+
+#[derive(Debug, FromRow)]
+pub(crate) struct SelectQuota {
+    pub(crate) name: String,
+    pub(crate) value: i32,
+}
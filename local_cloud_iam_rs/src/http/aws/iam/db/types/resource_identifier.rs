@@ -16,6 +16,8 @@ pub(crate) enum ResourceType {
     SamlProvider,
     SshPublicKey,
     ServerCertificate,
+    AccessKey,
+    ServiceSpecificCredential,
 }
 
 impl Into<i16> for ResourceType {
@@ -30,6 +32,8 @@ impl Into<i16> for ResourceType {
             Self::SamlProvider => 7,
             Self::SshPublicKey => 8,
             Self::ServerCertificate => 9,
+            Self::AccessKey => 10,
+            Self::ServiceSpecificCredential => 11,
         }
     }
 }
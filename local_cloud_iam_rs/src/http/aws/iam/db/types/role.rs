@@ -1,3 +1,4 @@
+use aws_sdk_iam::types::builders::RoleBuilder;
 use aws_sdk_iam::types::{AttachedPermissionsBoundary, PermissionsBoundaryAttachmentType, Role, RoleLastUsed};
 use aws_smithy_types::DateTime;
 use derive_builder::Builder;
@@ -40,7 +41,7 @@ impl From<&SelectRole> for Role {
     fn from(value: &SelectRole) -> Self {
         Role::builder()
             .role_id(&value.role_id)
-            .assume_role_policy_document(&value.assume_role_policy_document)
+            .assume_role_policy_document(urlencoding::encode(&value.assume_role_policy_document))
             .role_name(&value.role_name)
             .path(&value.path)
             .arn(&value.arn)
@@ -98,39 +99,38 @@ pub(crate) struct SelectRoleWithDetails {
     pub(crate) last_used_region: Option<String>,
 }
 
-impl From<&SelectRoleWithDetails> for Role {
-    fn from(value: &SelectRoleWithDetails) -> Self {
-        let role_last_used = match value.last_used_date {
-            None => None,
-            Some(last_used_date) => Some(
-                RoleLastUsed::builder()
-                    .last_used_date(DateTime::from_secs(last_used_date))
-                    .set_region(value.last_used_region.clone())
-                    .build(),
-            ),
-        };
-        let permissions_boundary = match &value.policy_arn {
-            None => None,
-            Some(policy_arn) => Some(
-                AttachedPermissionsBoundary::builder()
-                    .permissions_boundary_type(PermissionsBoundaryAttachmentType::Policy)
-                    .permissions_boundary_arn(policy_arn)
-                    .build(),
-            ),
-        };
+impl SelectRoleWithDetails {
+    /// Prepares the output role. The trust policy is URL-encoded as AWS does in `GetRole`.
+    pub(crate) fn role_builder(&self) -> RoleBuilder {
+        let role_last_used = self.last_used_date.map(|last_used_date| {
+            RoleLastUsed::builder()
+                .last_used_date(DateTime::from_secs(last_used_date))
+                .set_region(self.last_used_region.clone())
+                .build()
+        });
+        let permissions_boundary = self.policy_arn.as_ref().map(|policy_arn| {
+            AttachedPermissionsBoundary::builder()
+                .permissions_boundary_type(PermissionsBoundaryAttachmentType::Policy)
+                .permissions_boundary_arn(policy_arn)
+                .build()
+        });
         Role::builder()
-            .role_id(&value.role_id)
-            .assume_role_policy_document(&value.assume_role_policy_document)
-            .role_name(&value.role_name)
-            .arn(&value.arn)
-            .path(&value.path)
+            .role_id(&self.role_id)
+            .assume_role_policy_document(urlencoding::encode(&self.assume_role_policy_document))
+            .role_name(&self.role_name)
+            .arn(&self.arn)
+            .path(&self.path)
             .set_permissions_boundary(permissions_boundary)
             .set_role_last_used(role_last_used)
-            .max_session_duration(value.max_session_duration)
-            .set_description(value.description.as_ref().map(|s| s.to_owned()))
-            .create_date(DateTime::from_secs(value.create_date))
-            .build()
-            .unwrap()
+            .max_session_duration(self.max_session_duration)
+            .set_description(self.description.as_ref().map(|s| s.to_owned()))
+            .create_date(DateTime::from_secs(self.create_date))
+    }
+}
+
+impl From<&SelectRoleWithDetails> for Role {
+    fn from(value: &SelectRoleWithDetails) -> Self {
+        value.role_builder().build().unwrap()
     }
 }
 
@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+
+use aws_sdk_iam::types::{AccessAdvisorUsageGranularityType, JobStatusType};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Error, FromRow, Row};
+
+use crate::http::aws::iam::types;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum JobStateType {
+    Started,
+    InProgress,
+    Completed,
+    Failed,
+}
+
+impl JobStateType {
+    pub(crate) fn as_i32(&self) -> i32 {
+        match self {
+            JobStateType::Started => 1,
+            JobStateType::InProgress => 2,
+            JobStateType::Completed => 3,
+            JobStateType::Failed => 4,
+        }
+    }
+}
+
+impl From<i32> for JobStateType {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => JobStateType::Started,
+            2 => JobStateType::InProgress,
+            3 => JobStateType::Completed,
+            _ => JobStateType::Failed,
+        }
+    }
+}
+
+impl From<&JobStateType> for JobStatusType {
+    fn from(value: &JobStateType) -> Self {
+        match value {
+            JobStateType::Started | JobStateType::InProgress => JobStatusType::InProgress,
+            JobStateType::Completed => JobStatusType::Completed,
+            JobStateType::Failed => JobStatusType::Failed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum GranularityType {
+    ServiceLevel,
+    ActionLevel,
+}
+
+impl GranularityType {
+    pub(crate) fn as_i32(&self) -> i32 {
+        match self {
+            GranularityType::ServiceLevel => 1,
+            GranularityType::ActionLevel => 2,
+        }
+    }
+}
+
+impl From<i32> for GranularityType {
+    fn from(value: i32) -> Self {
+        match value {
+            2 => GranularityType::ActionLevel,
+            _ => GranularityType::ServiceLevel,
+        }
+    }
+}
+
+impl From<&types::access_advisor_usage_granularity_type::AccessAdvisorUsageGranularityType> for GranularityType {
+    fn from(value: &types::access_advisor_usage_granularity_type::AccessAdvisorUsageGranularityType) -> Self {
+        match value {
+            types::access_advisor_usage_granularity_type::AccessAdvisorUsageGranularityType::ActionLevel => {
+                GranularityType::ActionLevel
+            }
+            types::access_advisor_usage_granularity_type::AccessAdvisorUsageGranularityType::ServiceLevel => {
+                GranularityType::ServiceLevel
+            }
+        }
+    }
+}
+
+impl From<&GranularityType> for AccessAdvisorUsageGranularityType {
+    fn from(value: &GranularityType) -> Self {
+        match value {
+            GranularityType::ServiceLevel => AccessAdvisorUsageGranularityType::ServiceLevel,
+            GranularityType::ActionLevel => AccessAdvisorUsageGranularityType::ActionLevel,
+        }
+    }
+}
+
+/// Access of a principal to an action of a service.
+#[derive(Debug)]
+pub(crate) struct InsertServiceLastAccess {
+    pub(crate) account_id: i64,
+    pub(crate) principal_arn: String,
+    pub(crate) access_key_id: String,
+    pub(crate) service_namespace: String,
+    pub(crate) action_name: String,
+    pub(crate) region: String,
+    pub(crate) last_accessed_date: i64,
+}
+
+#[derive(Debug, FromRow)]
+pub(crate) struct SelectServiceLastAccess {
+    pub(crate) principal_arn: String,
+    pub(crate) service_namespace: String,
+    pub(crate) action_name: String,
+    pub(crate) region: Option<String>,
+    pub(crate) last_accessed_date: i64,
+}
+
+/// User, group or role which takes part in the report, together with ARNs of principals which act on its behalf:
+/// the user itself, members of the group, or sessions of the role.
+#[derive(Debug, FromRow)]
+pub(crate) struct SelectReportEntity {
+    pub(crate) entity_type: String,
+    pub(crate) name: String,
+    pub(crate) id: String,
+    pub(crate) arn: String,
+    pub(crate) path: String,
+    pub(crate) principal_arn: Option<String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct InsertServiceLastAccessedJob {
+    pub(crate) id: Option<i64>,
+    pub(crate) account_id: i64,
+    pub(crate) job_id: String,
+    pub(crate) arn: String,
+    pub(crate) granularity: GranularityType,
+    pub(crate) create_date: i64,
+}
+
+#[derive(Debug)]
+pub(crate) struct SelectServiceLastAccessedJob {
+    pub(crate) id: i64,
+    pub(crate) account_id: i64,
+    pub(crate) arn: String,
+    pub(crate) granularity: GranularityType,
+    pub(crate) state: JobStateType,
+    pub(crate) content: Option<Vec<u8>>,
+    pub(crate) error_code: Option<String>,
+    pub(crate) error_message: Option<String>,
+    pub(crate) create_date: i64,
+    pub(crate) completion_date: Option<i64>,
+}
+
+impl<'r> FromRow<'r, SqliteRow> for SelectServiceLastAccessedJob {
+    fn from_row(row: &'r SqliteRow) -> Result<Self, Error> {
+        let id: i64 = row.try_get("id")?;
+        let account_id: i64 = row.try_get("account_id")?;
+        let arn: String = row.try_get("arn")?;
+        let granularity: i32 = row.try_get("granularity")?;
+        let state: i32 = row.try_get("state")?;
+        let content: Option<Vec<u8>> = row.try_get("content")?;
+        let error_code: Option<String> = row.try_get("error_code")?;
+        let error_message: Option<String> = row.try_get("error_message")?;
+        let create_date: i64 = row.try_get("create_date")?;
+        let completion_date: Option<i64> = row.try_get("completion_date")?;
+        Ok(SelectServiceLastAccessedJob {
+            id,
+            account_id,
+            arn,
+            granularity: granularity.into(),
+            state: state.into(),
+            content,
+            error_code,
+            error_message,
+            create_date,
+            completion_date,
+        })
+    }
+}
+
+/// Content of a completed job which is stored as JSON.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ServiceLastAccessedReport {
+    pub(crate) services: Vec<ReportService>,
+    pub(crate) entities: Vec<ReportEntity>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ReportService {
+    pub(crate) service_namespace: String,
+    pub(crate) last_authenticated: Option<i64>,
+    pub(crate) last_authenticated_entity: Option<String>,
+    pub(crate) last_authenticated_region: Option<String>,
+    pub(crate) total_authenticated_entities: i32,
+    /// Populated for `ACTION_LEVEL` reports only.
+    pub(crate) tracked_actions: Option<Vec<ReportTrackedAction>>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ReportTrackedAction {
+    pub(crate) action_name: String,
+    pub(crate) last_accessed_entity: String,
+    pub(crate) last_accessed_region: Option<String>,
+    pub(crate) last_accessed_time: i64,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ReportEntity {
+    pub(crate) entity_type: String,
+    pub(crate) name: String,
+    pub(crate) id: String,
+    pub(crate) arn: String,
+    pub(crate) path: String,
+    /// Last access of the entity to the service, by service namespace.
+    pub(crate) last_authenticated: HashMap<String, i64>,
+}
@@ -0,0 +1,79 @@
+use aws_sdk_iam::types::DeletionTaskStatusType;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Error, FromRow, Row};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum DeletionTaskStateType {
+    NotStarted,
+    InProgress,
+    Succeeded,
+    Failed,
+}
+
+impl DeletionTaskStateType {
+    pub(crate) fn as_i32(&self) -> i32 {
+        match self {
+            DeletionTaskStateType::NotStarted => 1,
+            DeletionTaskStateType::InProgress => 2,
+            DeletionTaskStateType::Succeeded => 3,
+            DeletionTaskStateType::Failed => 4,
+        }
+    }
+}
+
+impl From<i32> for DeletionTaskStateType {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => DeletionTaskStateType::NotStarted,
+            2 => DeletionTaskStateType::InProgress,
+            3 => DeletionTaskStateType::Succeeded,
+            _ => DeletionTaskStateType::Failed,
+        }
+    }
+}
+
+impl From<&DeletionTaskStateType> for DeletionTaskStatusType {
+    fn from(value: &DeletionTaskStateType) -> Self {
+        match value {
+            DeletionTaskStateType::NotStarted => DeletionTaskStatusType::NotStarted,
+            DeletionTaskStateType::InProgress => DeletionTaskStatusType::InProgress,
+            DeletionTaskStateType::Succeeded => DeletionTaskStatusType::Succeeded,
+            DeletionTaskStateType::Failed => DeletionTaskStatusType::Failed,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct InsertDeletionTask {
+    pub(crate) id: Option<i64>,
+    pub(crate) account_id: i64,
+    pub(crate) deletion_task_id: String,
+    pub(crate) role_id: i64,
+    pub(crate) create_date: i64,
+}
+
+#[derive(Debug)]
+pub(crate) struct SelectDeletionTask {
+    pub(crate) id: i64,
+    pub(crate) deletion_task_id: String,
+    pub(crate) role_id: i64,
+    pub(crate) state: DeletionTaskStateType,
+    pub(crate) reason: Option<String>,
+}
+
+impl<'r> FromRow<'r, SqliteRow> for SelectDeletionTask {
+    fn from_row(row: &'r SqliteRow) -> Result<Self, Error> {
+        let id: i64 = row.try_get("id")?;
+        let deletion_task_id: String = row.try_get("deletion_task_id")?;
+        let role_id: i64 = row.try_get("role_id")?;
+        let state: i32 = row.try_get("state")?;
+        let reason: Option<String> = row.try_get("reason")?;
+        Ok(SelectDeletionTask {
+            id,
+            deletion_task_id,
+            role_id,
+            state: state.into(),
+            reason,
+        })
+    }
+}
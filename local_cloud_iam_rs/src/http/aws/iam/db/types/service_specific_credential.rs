@@ -0,0 +1,114 @@
+use aws_sdk_iam::types::ServiceSpecificCredentialMetadata;
+use aws_smithy_types::DateTime;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Error, FromRow, Row};
+
+use crate::http::aws::iam::types::status_type::StatusType;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ServiceSpecificCredentialStatusType {
+    Active,
+    Inactive,
+}
+
+impl ServiceSpecificCredentialStatusType {
+    pub(crate) fn as_i32(&self) -> i32 {
+        match self {
+            ServiceSpecificCredentialStatusType::Active => 1,
+            ServiceSpecificCredentialStatusType::Inactive => 2,
+        }
+    }
+}
+
+impl From<i32> for ServiceSpecificCredentialStatusType {
+    fn from(value: i32) -> Self {
+        if value == 1 {
+            Self::Active
+        } else {
+            Self::Inactive
+        }
+    }
+}
+
+impl From<&StatusType> for ServiceSpecificCredentialStatusType {
+    fn from(value: &StatusType) -> Self {
+        match value {
+            StatusType::Inactive => Self::Inactive,
+            StatusType::Active => Self::Active,
+        }
+    }
+}
+
+impl From<&ServiceSpecificCredentialStatusType> for aws_sdk_iam::types::StatusType {
+    fn from(value: &ServiceSpecificCredentialStatusType) -> Self {
+        match value {
+            ServiceSpecificCredentialStatusType::Active => aws_sdk_iam::types::StatusType::Active,
+            ServiceSpecificCredentialStatusType::Inactive => aws_sdk_iam::types::StatusType::Inactive,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct InsertServiceSpecificCredential {
+    pub(crate) id: Option<i64>,
+    pub(crate) account_id: i64,
+    pub(crate) user_id: i64,
+    pub(crate) credential_id: String,
+    pub(crate) service_name: String,
+    pub(crate) service_user_name: String,
+    pub(crate) password_hash: String,
+    pub(crate) status: ServiceSpecificCredentialStatusType,
+    pub(crate) create_date: i64,
+}
+
+#[derive(Debug)]
+pub(crate) struct SelectServiceSpecificCredential {
+    pub(crate) id: i64,
+    pub(crate) account_id: i64,
+    pub(crate) user_name: String,
+    pub(crate) credential_id: String,
+    pub(crate) service_name: String,
+    pub(crate) service_user_name: String,
+    pub(crate) password_hash: String,
+    pub(crate) status: ServiceSpecificCredentialStatusType,
+    pub(crate) create_date: i64,
+}
+
+impl<'r> FromRow<'r, SqliteRow> for SelectServiceSpecificCredential {
+    fn from_row(row: &'r SqliteRow) -> Result<Self, Error> {
+        let id: i64 = row.try_get("id")?;
+        let account_id: i64 = row.try_get("account_id")?;
+        let user_name: String = row.try_get("user_name")?;
+        let credential_id: String = row.try_get("credential_id")?;
+        let service_name: String = row.try_get("service_name")?;
+        let service_user_name: String = row.try_get("service_user_name")?;
+        let password_hash: String = row.try_get("password_hash")?;
+        let status: i32 = row.try_get("status")?;
+        let create_date: i64 = row.try_get("create_date")?;
+        Ok(SelectServiceSpecificCredential {
+            id,
+            account_id,
+            user_name,
+            credential_id,
+            service_name,
+            service_user_name,
+            password_hash,
+            status: status.into(),
+            create_date,
+        })
+    }
+}
+
+impl From<&SelectServiceSpecificCredential> for ServiceSpecificCredentialMetadata {
+    fn from(value: &SelectServiceSpecificCredential) -> Self {
+        ServiceSpecificCredentialMetadata::builder()
+            .user_name(&value.user_name)
+            .status((&value.status).into())
+            .service_user_name(&value.service_user_name)
+            .create_date(DateTime::from_secs(value.create_date))
+            .service_specific_credential_id(&value.credential_id)
+            .service_name(&value.service_name)
+            .build()
+            .unwrap()
+    }
+}
@@ -1,9 +1,9 @@
 use sqlx::sqlite::SqliteRow;
 use sqlx::{Error, FromRow, Row};
 
-use crate::http::aws::iam::db;
 use crate::http::aws::iam::db::types::common::Pageable;
 use crate::http::aws::iam::types::marker_type::MarkerType;
+use crate::http::aws::iam::{constants, db};
 
 #[derive(Clone, FromRow, Debug)]
 pub(crate) struct DbTag {
@@ -105,4 +105,12 @@ impl ListTagsQuery {
             skip,
         }
     }
+
+    /// Query for all tags of the entity, used when tags are returned together with the entity.
+    pub(crate) fn all() -> Self {
+        ListTagsQuery {
+            limit: constants::tag::MAX_COUNT as i32,
+            skip: 0,
+        }
+    }
 }
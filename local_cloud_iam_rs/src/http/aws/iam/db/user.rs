@@ -1,6 +1,7 @@
 use sqlx::sqlite::SqliteRow;
 use sqlx::{Error, Executor, FromRow, QueryBuilder, Row, Sqlite, Transaction};
 
+use crate::http::aws::iam::constants;
 use crate::http::aws::iam::db::types::user::{
     InsertUser, ListUsersByGroupQuery, ListUsersQuery, SelectUser, UpdateUserQuery,
 };
@@ -65,6 +66,33 @@ where
     Ok(result)
 }
 
+/// Finds user by its unique ID, e.g. `AIDA...`.
+pub(crate) async fn find_by_unique_id<'a, E>(executor: E, unique_id: &str) -> Result<Option<SelectUser>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"
+        SELECT
+            u.id AS id,
+            u.account_id AS account_id,
+            u.username AS username,
+            u.arn AS arn,
+            u.path AS path,
+            u.user_id AS user_id,
+            u.policy_id AS policy_id,
+            p.arn AS policy_arn,
+            u.create_date AS create_date
+        FROM users u LEFT JOIN policies p ON u.policy_id = p.id
+        WHERE u.user_id = $1"#,
+    )
+    .bind(unique_id)
+    .map(|row: SqliteRow| SelectUser::from_row(&row).unwrap())
+    .fetch_optional(executor)
+    .await?;
+    Ok(result)
+}
+
 pub(crate) async fn find_by_group_id<'a, E>(
     executor: E, query: &ListUsersByGroupQuery,
 ) -> Result<Vec<SelectUser>, Error>
@@ -142,11 +170,55 @@ where
             u.path AS path,
             u.user_id AS user_id,
             u.create_date as create_date
-        FROM group_users gu LEFT JOIN users u ON gu.user_id = u.id
+        FROM users u
         WHERE u.account_id = "#,
     );
+    // the root user is not an IAM user of the account
     let users = query_builder
         .push_bind(account_id)
+        .push(" AND u.user_id != ")
+        .push_bind(constants::user::ROOT_USER_ID)
+        .push(" AND u.path LIKE ")
+        .push_bind(format!("{}%", &query.path_prefix))
+        .push(" ORDER BY u.unique_username ASC")
+        .push(" LIMIT ")
+        .push_bind(query.limit + 1) // request more elements than we need to return. used to identify if NextPage token needs to be generated
+        .push(" OFFSET ")
+        .push_bind(query.skip)
+        .build()
+        .map(|row: SqliteRow| SelectUser::from_row(&row).unwrap())
+        .fetch_all(executor)
+        .await?;
+    Ok(users)
+}
+
+/// Lists users together with their permissions boundaries, e.g. to report user details in bulk.
+pub(crate) async fn list_with_details<'a, E>(
+    executor: E, account_id: i64, query: &ListUsersQuery,
+) -> Result<Vec<SelectUser>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new(
+        r#"
+        SELECT 
+            u.id AS id,
+            u.account_id AS account_id,
+            u.username AS username,
+            u.arn AS arn,
+            u.path AS path,
+            u.user_id AS user_id,
+            u.policy_id AS policy_id,
+            p.arn AS policy_arn,
+            u.create_date as create_date
+        FROM users u LEFT JOIN policies p ON u.policy_id = p.id
+        WHERE u.account_id = "#,
+    );
+    // the root user is not an IAM user of the account
+    let users = query_builder
+        .push_bind(account_id)
+        .push(" AND u.user_id != ")
+        .push_bind(constants::user::ROOT_USER_ID)
         .push(" AND u.path LIKE ")
         .push_bind(format!("{}%", &query.path_prefix))
         .push(" ORDER BY u.unique_username ASC")
@@ -230,3 +302,64 @@ where
 
     Ok(result.rows_affected() == 1)
 }
+
+/// Counts groups the user is a member of.
+pub(crate) async fn count_groups<'a, E>(executor: E, user_id: i64) -> Result<usize, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("SELECT COUNT(*) AS count FROM group_users WHERE user_id = $1")
+        .bind(user_id)
+        .map(|row: SqliteRow| row.get::<i64, &str>("count"))
+        .fetch_one(executor)
+        .await?;
+    Ok(result as usize)
+}
+
+/// Detaches the managed policy from the user. Returns `false` if the policy was not attached.
+pub(crate) async fn detach_policy_from_user<'a, E>(executor: E, user_id: i64, policy_id: i64) -> Result<bool, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("DELETE FROM policy_users WHERE user_id=$1 AND policy_id=$2")
+        .bind(user_id)
+        .bind(policy_id)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+/// Counts managed policies attached to the user.
+pub(crate) async fn count_attached_policies<'a, E>(executor: E, user_id: i64) -> Result<usize, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("SELECT COUNT(*) AS count FROM policy_users WHERE user_id = $1")
+        .bind(user_id)
+        .map(|row: SqliteRow| row.get::<i64, &str>("count"))
+        .fetch_one(executor)
+        .await?;
+    Ok(result as usize)
+}
+
+/// Deletes the user together with its group memberships and policy attachments.
+pub(crate) async fn delete<'a>(tx: &mut Transaction<'a, Sqlite>, id: i64) -> Result<bool, Error> {
+    sqlx::query("DELETE FROM group_users WHERE user_id=$1")
+        .bind(id)
+        .execute(tx.as_mut())
+        .await?;
+    sqlx::query("DELETE FROM policy_users WHERE user_id=$1")
+        .bind(id)
+        .execute(tx.as_mut())
+        .await?;
+    sqlx::query("DELETE FROM password_history WHERE user_id=$1")
+        .bind(id)
+        .execute(tx.as_mut())
+        .await?;
+    let result = sqlx::query("DELETE FROM users WHERE id=$1")
+        .bind(id)
+        .execute(tx.as_mut())
+        .await?;
+
+    Ok(result.rows_affected() == 1)
+}
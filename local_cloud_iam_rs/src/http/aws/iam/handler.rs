@@ -8,17 +8,18 @@ macro_rules! action_handler {
     ) => {
         use axum::response::Response;
         use axum::extract::State;
+        use axum::Extension;
         use axum::http::StatusCode;
         use uuid::Uuid;
         use sqlx::{Sqlite, Transaction};
 
-        use local_cloud_axum::local::web::{AwsQueryBody, XmlResponse};
+        use local_cloud_axum::local::web::{AwsQueryBody, CallerIdentity, XmlResponse};
         use local_cloud_db::LocalDb;
 
         use crate::http::aws::iam::actions::action::Action;
         use crate::http::aws::iam::actions::error::ApiError;
         use crate::http::aws::iam::outputs::wrapper::OutputWrapper;
-        use crate::http::aws::iam::operations::ctx::OperationCtx;
+        use crate::http::aws::iam::operations::ctx::{OperationCtx, OperationSettings};
         use crate::http::aws::iam::operations::error::ActionError;
 
         $(
@@ -37,15 +38,17 @@ macro_rules! action_handler {
         const CONTENT_TYPE_HEADER: &str = "Content-Type";
         const CONTENT_TYPE_HEADER_VALUE: &str = "text/xml; charset=utf-8";
 
-        pub(crate) async fn handle(State(db): State<LocalDb>, aws_query: AwsQueryBody<AwsRequest>,) -> Response<String> {
-            let account_id = 1i64;
+        pub(crate) async fn handle(
+            State(db): State<LocalDb>, Extension(caller): Extension<CallerIdentity>,
+            Extension(settings): Extension<OperationSettings>, aws_query: AwsQueryBody<AwsRequest>,
+        ) -> Response<String> {
             let aws_request = aws_query.into_inner();
             let aws_request_id = Uuid::new_v4().to_string();
-
+            let ctx = OperationCtx::new(&caller, &aws_request_id).with_settings(&settings);
 
             let output: Result<XmlResponse, ActionError> = match aws_request {
                 $(
-                    $name::$variant(request) => handle_action_with_tx(&db, account_id, &aws_request_id, request).await,
+                    $name::$variant(request) => handle_action_with_tx(&db, &ctx, request).await,
                 )+
             };
             let output: Result<XmlResponse, ApiError> = output.map_err(|error| match error {
@@ -75,9 +78,9 @@ macro_rules! action_handler {
             }
         }
 
-        async fn handle_action_with_tx(db: &LocalDb, account_id: i64, aws_request_id: &str, action: impl Action) -> Result<XmlResponse, ActionError> {
+        async fn handle_action_with_tx(db: &LocalDb, ctx: &OperationCtx, action: impl Action) -> Result<XmlResponse, ActionError> {
             let mut tx = db.new_tx().await?;
-            let response = action.execute(&mut tx, account_id, &aws_request_id)
+            let response = action.execute(&mut tx, ctx)
                             .await
                             .map(|out| out.into())?;
             tx.commit().await?;
@@ -87,10 +90,9 @@ macro_rules! action_handler {
         $(
             impl Action for $request {
                 type Output = OutputWrapper<$response>;
-                async fn execute<'a>(&self, tx: &mut Transaction<'a, Sqlite>, account_id: i64, aws_request_id: &str) -> Result<Self::Output, ActionError> {
-                    let ctx = OperationCtx::new(account_id, aws_request_id);
-                    let output = crate::http::aws::iam::operations::$resource::$action(tx, &ctx, self).await?;
-                    Ok(OutputWrapper::new(output, aws_request_id))
+                async fn execute<'a>(&self, tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx) -> Result<Self::Output, ActionError> {
+                    let output = crate::http::aws::iam::operations::$resource::$action(tx, ctx, self).await?;
+                    Ok(OutputWrapper::new(output, &ctx.aws_request_id))
                 }
             }
         )+
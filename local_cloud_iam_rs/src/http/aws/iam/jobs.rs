@@ -0,0 +1,164 @@
+use chrono::Utc;
+use sqlx::{Sqlite, SqliteConnection, Transaction};
+use tokio::task::JoinHandle;
+
+use local_cloud_db::LocalDb;
+
+use crate::http::aws::iam::constants;
+use crate::http::aws::iam::db::types::credential_report::SelectCredentialReport;
+use crate::http::aws::iam::db::types::service_last_accessed::SelectServiceLastAccessedJob;
+use crate::http::aws::iam::db::types::service_linked_role::SelectDeletionTask;
+use crate::http::aws::iam::operations::error::ActionError;
+
+/// Asynchronous job (e.g. generation of a credential report) registered in the DB by an operation. The runner takes
+/// a started job by moving it to the in-progress state, does its work and either completes or fails it.
+pub(crate) trait Job: Sized {
+    /// Kind of the job used in logs.
+    const KIND: &'static str;
+
+    fn id(&self) -> i64;
+
+    /// Returns jobs which are waiting to be run.
+    async fn find_started(connection: &mut SqliteConnection) -> Result<Vec<Self>, sqlx::Error>;
+
+    /// Moves the job to the in-progress state. Returns `false` if the job has been already taken.
+    async fn take(connection: &mut SqliteConnection, id: i64, start_date: i64) -> Result<bool, sqlx::Error>;
+
+    /// Does the work of the job and completes it within the transaction.
+    async fn run(&self, tx: &mut Transaction<'_, Sqlite>) -> Result<(), ActionError>;
+
+    /// Records the failure of the job taken by the runner, so that it doesn't stay in the in-progress state.
+    async fn fail(
+        connection: &mut SqliteConnection, id: i64, error: &ActionError, completion_date: i64,
+    ) -> Result<(), sqlx::Error>;
+
+    /// Moves jobs which have been taken before `start_date` back to the started state, so that they are retried.
+    /// Returns the number of restarted jobs.
+    async fn restart_stale(connection: &mut SqliteConnection, start_date: i64) -> Result<u64, sqlx::Error>;
+}
+
+/// Handle of the background runner of asynchronous jobs. The runner is stopped when the handle is dropped.
+#[derive(Debug)]
+pub(crate) struct JobRunner(JoinHandle<()>);
+
+impl Drop for JobRunner {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Starts the background runner of asynchronous jobs (e.g. generation of credential reports, deletion of
+/// service-linked roles). Operations only register jobs in the DB, and the runner picks them up periodically.
+pub(crate) fn start(db: LocalDb) -> JobRunner {
+    let handle = tokio::spawn(async move {
+        let mut interval = tokio::time::interval(constants::job::POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(err) = run_pending(&db).await {
+                log::error!("Failed to run pending jobs: {}", err);
+            }
+        }
+    });
+    JobRunner(handle)
+}
+
+async fn run_pending(db: &LocalDb) -> Result<(), ActionError> {
+    run_jobs::<SelectCredentialReport>(db).await?;
+    run_jobs::<SelectDeletionTask>(db).await?;
+    run_jobs::<SelectServiceLastAccessedJob>(db).await?;
+    Ok(())
+}
+
+/// Runs started jobs of one kind. A failing job is marked as failed and doesn't prevent other jobs from running.
+async fn run_jobs<J: Job>(db: &LocalDb) -> Result<(), ActionError> {
+    let current_time = Utc::now().timestamp();
+    let stale_start_date = current_time - constants::job::IN_PROGRESS_TIMEOUT.as_secs() as i64;
+    let restarted = J::restart_stale(db.new_connection().await?.as_mut(), stale_start_date).await?;
+    if restarted > 0 {
+        log::warn!("Restarted {} stale {} job(s)", restarted, J::KIND);
+    }
+
+    let jobs = J::find_started(db.new_connection().await?.as_mut()).await?;
+    for job in jobs {
+        if !J::take(db.new_connection().await?.as_mut(), job.id(), Utc::now().timestamp()).await? {
+            continue;
+        }
+
+        if let Err(err) = run_job(db, &job).await {
+            log::error!("Failed to run {} job {}: {}", J::KIND, job.id(), err);
+            J::fail(db.new_connection().await?.as_mut(), job.id(), &err, Utc::now().timestamp()).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn run_job<J: Job>(db: &LocalDb, job: &J) -> Result<(), ActionError> {
+    let mut tx = db.new_tx().await?;
+    job.run(&mut tx).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicI64, Ordering};
+
+    use sqlx::{Sqlite, SqliteConnection, Transaction};
+
+    use local_cloud_db::LocalDb;
+
+    use crate::http::aws::iam::actions::error::ApiErrorKind;
+    use crate::http::aws::iam::operations::error::ActionError;
+
+    static FAILED_ID: AtomicI64 = AtomicI64::new(0);
+    static COMPLETED_ID: AtomicI64 = AtomicI64::new(0);
+
+    /// Job with ID 1 fails, and job with ID 2 succeeds.
+    struct TestJob(i64);
+
+    impl super::Job for TestJob {
+        const KIND: &'static str = "test";
+
+        fn id(&self) -> i64 {
+            self.0
+        }
+
+        async fn find_started(_connection: &mut SqliteConnection) -> Result<Vec<Self>, sqlx::Error> {
+            Ok(vec![TestJob(1), TestJob(2)])
+        }
+
+        async fn take(_connection: &mut SqliteConnection, _id: i64, _start_date: i64) -> Result<bool, sqlx::Error> {
+            Ok(true)
+        }
+
+        async fn run(&self, _tx: &mut Transaction<'_, Sqlite>) -> Result<(), ActionError> {
+            if self.0 == 1 {
+                return Err(ActionError::new(ApiErrorKind::ServiceFailure, "Job failed."));
+            }
+            COMPLETED_ID.store(self.0, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn fail(
+            _connection: &mut SqliteConnection, id: i64, _error: &ActionError, _completion_date: i64,
+        ) -> Result<(), sqlx::Error> {
+            FAILED_ID.store(id, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn restart_stale(_connection: &mut SqliteConnection, _start_date: i64) -> Result<u64, sqlx::Error> {
+            Ok(0)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_jobs_fails_failing_job_and_continues() {
+        let db_url = format!("file:{}?mode=memory&cache=shared", uuid::Uuid::new_v4());
+        let db = LocalDb::new(&db_url, &sqlx::migrate!()).await.unwrap();
+
+        super::run_jobs::<TestJob>(&db).await.unwrap();
+
+        assert_eq!(1, FAILED_ID.load(Ordering::SeqCst));
+        assert_eq!(2, COMPLETED_ID.load(Ordering::SeqCst));
+    }
+}
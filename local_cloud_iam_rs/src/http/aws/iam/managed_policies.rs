@@ -0,0 +1,125 @@
+use chrono::Utc;
+use serde::Deserialize;
+use sqlx::{Sqlite, Transaction};
+
+use local_cloud_db::LocalDb;
+
+use crate::http::aws::iam::actions::error::ApiErrorKind;
+use crate::http::aws::iam::db::types::policy::InsertPolicy;
+use crate::http::aws::iam::db::types::policy_type::PolicyType;
+use crate::http::aws::iam::db::types::policy_version::InsertPolicyVersion;
+use crate::http::aws::iam::db::types::resource_identifier::ResourceType;
+use crate::http::aws::iam::operations::common::create_resource_id;
+use crate::http::aws::iam::operations::error::ActionError;
+use crate::http::aws::iam::{constants, db};
+
+/// Catalog of AWS managed policies bundled with the service.
+const CATALOG: &str = include_str!("resources/aws_managed_policies.json");
+
+#[derive(Debug, Deserialize)]
+struct Catalog {
+    policies: Vec<CatalogPolicy>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CatalogPolicy {
+    name: String,
+    path: String,
+    description: String,
+    /// Number of the default version of the policy. Bumped every time the document is changed.
+    version: i16,
+    document: serde_json::Value,
+}
+
+impl CatalogPolicy {
+    fn arn(&self) -> String {
+        format!("{}{}{}", constants::policy::AWS_MANAGED_ARN_PREFIX, self.path, self.name)
+    }
+}
+
+/// Loads the catalog of AWS managed policies into the DB. Policies which are missing are created, and policies whose
+/// default version is older than the one in the catalog get a new default version.
+pub(crate) async fn load(db: &LocalDb) -> Result<(), ActionError> {
+    let catalog: Catalog = serde_json::from_str(CATALOG).map_err(|err| {
+        ActionError::new(
+            ApiErrorKind::ServiceFailure,
+            format!("Failed to parse catalog of AWS managed policies: {err}").as_str(),
+        )
+    })?;
+
+    let mut tx = db.new_tx().await?;
+    let current_time = Utc::now().timestamp();
+    for policy in &catalog.policies {
+        let account_id = constants::policy::AWS_MANAGED_ACCOUNT_ID;
+        let policy_id = match db::policy::find_by_arn(tx.as_mut(), account_id, &policy.arn()).await? {
+            None => create_policy(&mut tx, policy, current_time).await?,
+            Some(existing) if existing.version < policy.version as i32 => {
+                db::policy_version::disable_default_by_policy_id(&mut tx, existing.id).await?;
+                existing.id
+            }
+            Some(_) => continue,
+        };
+
+        let mut policy_version = InsertPolicyVersion {
+            id: None,
+            account_id,
+            policy_id,
+            policy_version_id: create_resource_id(
+                &mut tx,
+                constants::policy_version::PREFIX,
+                ResourceType::PolicyVersion,
+            )
+            .await?,
+            policy_document: policy.document.to_string(),
+            create_date: current_time,
+            version: Some(policy.version),
+            is_default: true,
+        };
+        db::policy_version::create(&mut tx, &mut policy_version).await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+async fn create_policy<'a>(
+    tx: &mut Transaction<'a, Sqlite>, policy: &CatalogPolicy, current_time: i64,
+) -> Result<i64, ActionError> {
+    let mut insert_policy = InsertPolicy {
+        id: None,
+        account_id: constants::policy::AWS_MANAGED_ACCOUNT_ID,
+        arn: policy.arn(),
+        policy_id: create_resource_id(tx, constants::policy::PREFIX, ResourceType::Policy).await?,
+        path: policy.path.clone(),
+        create_date: current_time,
+        update_date: current_time,
+        policy_name: policy.name.clone(),
+        policy_type: PolicyType::LocalCloudManaged,
+        description: Some(policy.description.clone()),
+        attachable: true,
+    };
+    db::policy::create(tx, &mut insert_policy).await?;
+    Ok(insert_policy.id.unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use local_cloud_iam_policy_document::types::LocalPolicyDocument;
+
+    use super::{Catalog, CATALOG};
+
+    #[test]
+    fn test_catalog_policies_are_valid() {
+        let catalog: Catalog = serde_json::from_str(CATALOG).unwrap();
+        assert!(!catalog.policies.is_empty());
+        for policy in &catalog.policies {
+            assert!(policy.path.starts_with('/') && policy.path.ends_with('/'), "{}", policy.name);
+            assert!(policy.version > 0, "{}", policy.name);
+            let document = policy.document.to_string();
+            assert!(
+                serde_json::from_str::<LocalPolicyDocument>(&document).is_ok(),
+                "Policy document of '{}' is not valid",
+                policy.name
+            );
+        }
+    }
+}
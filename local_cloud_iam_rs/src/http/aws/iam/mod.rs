@@ -1,7 +1,16 @@
+pub(crate) mod access_tracking;
+pub(crate) mod account_sign_in;
 pub(crate) mod actions;
+pub(crate) mod auth;
+pub(crate) mod authorization;
 pub(crate) mod constants;
 pub(crate) mod db;
 pub(crate) mod handler;
+pub(crate) mod jobs;
+pub(crate) mod managed_policies;
 pub(crate) mod operations;
 pub(crate) mod outputs;
+pub(crate) mod quotas;
+pub(crate) mod request_params;
+pub(crate) mod service_credentials;
 pub(crate) mod types;
@@ -1,28 +1,369 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
 use aws_sdk_iam::operation::generate_service_last_accessed_details::GenerateServiceLastAccessedDetailsOutput;
 use aws_sdk_iam::operation::get_access_key_last_used::GetAccessKeyLastUsedOutput;
-use sqlx::{Sqlite, Transaction};
+use aws_sdk_iam::types::AccessKeyLastUsed;
+use aws_smithy_types::DateTime;
+use chrono::Utc;
+use sqlx::{Sqlite, SqliteConnection, Transaction};
 
+use local_cloud_axum::local::web::CallerIdentity;
+use local_cloud_common::arn::assumed_role_name;
+use local_cloud_iam_policy_document::evaluator::PolicyKind;
 use local_cloud_validate::NamedValidator;
 
+use crate::http::aws::iam::actions::error::ApiErrorKind;
+use crate::http::aws::iam::db;
+use crate::http::aws::iam::db::types::service_last_accessed::{
+    GranularityType, InsertServiceLastAccessedJob, ReportEntity, ReportService, ReportTrackedAction,
+    SelectReportEntity, SelectServiceLastAccess, SelectServiceLastAccessedJob, ServiceLastAccessedReport,
+};
+use crate::http::aws::iam::jobs::Job;
 use crate::http::aws::iam::operations::ctx::OperationCtx;
 use crate::http::aws::iam::operations::error::ActionError;
 use crate::http::aws::iam::types::generate_service_last_accessed_details::GenerateServiceLastAccessedDetailsRequest;
 use crate::http::aws::iam::types::get_access_key_last_used::GetAccessKeyLastUsedRequest;
 
+pub(crate) const NOT_AVAILABLE: &str = "N/A";
+
+/// Registers a job which reports when the user, group, role or policy identified by ARN last accessed services.
+/// The report itself is generated asynchronously by the job runner.
 pub(crate) async fn generate_service_last_accessed_details<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &GenerateServiceLastAccessedDetailsRequest,
 ) -> Result<GenerateServiceLastAccessedDetailsOutput, ActionError> {
     input.validate("$")?;
 
-    let output = GenerateServiceLastAccessedDetailsOutput::builder().build();
+    let arn = input.arn().unwrap().trim();
+    let (entity_type, entity_name) = parse_entity_arn(arn)?;
+    let is_found = match entity_type {
+        "user" => db::user::find_by_name(tx.as_mut(), ctx.account_id, entity_name)
+            .await?
+            .is_some_and(|user| user.arn == arn),
+        "group" => db::group::find_by_name(tx.as_mut(), ctx.account_id, entity_name)
+            .await?
+            .is_some_and(|group| group.arn == arn),
+        "role" => db::role::find_by_name(tx.as_mut(), ctx.account_id, entity_name)
+            .await?
+            .is_some_and(|role| role.arn == arn),
+        _ => db::policy::find_id_by_arn(tx.as_mut(), ctx.account_id, arn)
+            .await?
+            .is_some(),
+    };
+    if !is_found {
+        return Err(no_such_entity(arn));
+    }
+
+    let mut job = InsertServiceLastAccessedJob {
+        id: None,
+        account_id: ctx.account_id,
+        job_id: uuid::Uuid::new_v4().to_string(),
+        arn: arn.to_owned(),
+        granularity: input
+            .granularity()
+            .map(|granularity| granularity.into())
+            .unwrap_or(GranularityType::ServiceLevel),
+        create_date: Utc::now().timestamp(),
+    };
+    db::service_last_accessed::create_job(tx, &mut job).await?;
+
+    let output = GenerateServiceLastAccessedDetailsOutput::builder()
+        .job_id(job.job_id)
+        .build();
     Ok(output)
 }
 
+/// Generation of the service last accessed report requested by [`generate_service_last_accessed_details`]. The job fails
+/// if the entity has been deleted in the meantime.
+impl Job for SelectServiceLastAccessedJob {
+    const KIND: &'static str = "service last accessed report";
+
+    fn id(&self) -> i64 {
+        self.id
+    }
+
+    async fn find_started(connection: &mut SqliteConnection) -> Result<Vec<Self>, sqlx::Error> {
+        db::service_last_accessed::find_started_jobs(connection).await
+    }
+
+    async fn take(connection: &mut SqliteConnection, id: i64, start_date: i64) -> Result<bool, sqlx::Error> {
+        db::service_last_accessed::mark_in_progress(connection, id, start_date).await
+    }
+
+    async fn run(&self, tx: &mut Transaction<'_, Sqlite>) -> Result<(), ActionError> {
+        let report = create_service_last_accessed_report(tx, self).await?;
+        let content = serde_json::to_vec(&report).map_err(|err| {
+            ActionError::new(ApiErrorKind::ServiceFailure, format!("Failed to store report: {err}").as_str())
+        })?;
+        db::service_last_accessed::complete_job(tx.as_mut(), self.id, &content, Utc::now().timestamp()).await?;
+        Ok(())
+    }
+
+    async fn fail(
+        connection: &mut SqliteConnection, id: i64, error: &ActionError, completion_date: i64,
+    ) -> Result<(), sqlx::Error> {
+        let (error_code, error_message) = match error {
+            ActionError::Service { kind, msg } => (kind.as_str().to_owned(), msg.clone()),
+            ActionError::Validation(error) => (ApiErrorKind::ServiceFailure.as_str().to_owned(), error.to_string()),
+        };
+        db::service_last_accessed::fail_job(connection, id, &error_code, &error_message, completion_date).await?;
+        Ok(())
+    }
+
+    async fn restart_stale(connection: &mut SqliteConnection, start_date: i64) -> Result<u64, sqlx::Error> {
+        db::service_last_accessed::restart_stale(connection, start_date).await
+    }
+}
+
+/// Entity of the report together with ARNs of the principals which act on its behalf.
+struct EntityPrincipals {
+    entity: ReportEntity,
+    principal_arns: HashSet<String>,
+}
+
+impl EntityPrincipals {
+    fn matches(&self, principal_arn: &str) -> bool {
+        // roles act via role sessions
+        self.principal_arns.contains(principal_arn)
+            || (self.entity.entity_type == "ROLE" && assumed_role_name(principal_arn) == Some(&self.entity.name))
+    }
+}
+
+async fn create_service_last_accessed_report<'a>(
+    tx: &mut Transaction<'a, Sqlite>, job: &SelectServiceLastAccessedJob,
+) -> Result<ServiceLastAccessedReport, ActionError> {
+    let (entity_type, _entity_name) = parse_entity_arn(&job.arn)?;
+    let documents = match entity_type {
+        "user" | "group" | "role" => {
+            let ctx = OperationCtx::new(&CallerIdentity::new("", job.account_id, None), "");
+            super::simulate::find_principal_policies(tx, &ctx, &job.arn)
+                .await?
+                .into_iter()
+                .filter(|policy| policy.kind == PolicyKind::Identity)
+                .map(|policy| policy.document)
+                .collect()
+        }
+        _ => {
+            let policy_id = db::policy::find_id_by_arn(tx.as_mut(), job.account_id, &job.arn)
+                .await?
+                .ok_or_else(|| no_such_entity(&job.arn))?;
+            db::policy_version::find_default_by_policy_id(tx.as_mut(), policy_id)
+                .await?
+                .map(|policy| vec![policy.policy_document])
+                .unwrap_or_default()
+        }
+    };
+    let mut service_namespaces = BTreeSet::new();
+    for document in &documents {
+        service_namespaces.extend(granted_service_namespaces(document));
+    }
+
+    let entities =
+        group_entities(db::service_last_accessed::list_report_entities(tx.as_mut(), job.account_id, &job.arn).await?);
+    let accesses: Vec<SelectServiceLastAccess> = db::service_last_accessed::list_accesses(tx.as_mut(), job.account_id)
+        .await?
+        .into_iter()
+        .filter(|access| entities.iter().any(|entity| entity.matches(&access.principal_arn)))
+        .collect();
+    service_namespaces.extend(accesses.iter().map(|access| access.service_namespace.to_owned()));
+
+    let mut services = vec![];
+    for service_namespace in service_namespaces {
+        // accesses are sorted from the latest to the oldest one
+        let service_accesses: Vec<&SelectServiceLastAccess> = accesses
+            .iter()
+            .filter(|access| access.service_namespace == service_namespace)
+            .collect();
+        let latest = service_accesses.first();
+        let authenticated_entities: HashSet<&str> = service_accesses
+            .iter()
+            .map(|access| authenticated_entity(&entities, &access.principal_arn))
+            .collect();
+
+        let tracked_actions = match job.granularity {
+            GranularityType::ServiceLevel => None,
+            GranularityType::ActionLevel => {
+                let mut actions: BTreeMap<&str, ReportTrackedAction> = BTreeMap::new();
+                for access in &service_accesses {
+                    actions
+                        .entry(&access.action_name)
+                        .or_insert_with(|| ReportTrackedAction {
+                            action_name: access.action_name.to_owned(),
+                            last_accessed_entity: authenticated_entity(&entities, &access.principal_arn).to_owned(),
+                            last_accessed_region: access.region.to_owned(),
+                            last_accessed_time: access.last_accessed_date,
+                        });
+                }
+                Some(actions.into_values().collect())
+            }
+        };
+
+        services.push(ReportService {
+            service_namespace: service_namespace.to_owned(),
+            last_authenticated: latest.map(|access| access.last_accessed_date),
+            last_authenticated_entity: latest
+                .map(|access| authenticated_entity(&entities, &access.principal_arn).to_owned()),
+            last_authenticated_region: latest.and_then(|access| access.region.to_owned()),
+            total_authenticated_entities: authenticated_entities.len() as i32,
+            tracked_actions,
+        });
+    }
+
+    let mut report_entities = vec![];
+    for mut entity in entities {
+        let entity_accesses: Vec<&SelectServiceLastAccess> = accesses
+            .iter()
+            .filter(|access| entity.matches(&access.principal_arn))
+            .collect();
+        for access in entity_accesses {
+            entity
+                .entity
+                .last_authenticated
+                .entry(access.service_namespace.to_owned())
+                .or_insert(access.last_accessed_date);
+        }
+        report_entities.push(entity.entity);
+    }
+
+    Ok(ServiceLastAccessedReport {
+        services,
+        entities: report_entities,
+    })
+}
+
+fn group_entities(rows: Vec<SelectReportEntity>) -> Vec<EntityPrincipals> {
+    let mut entities: BTreeMap<String, EntityPrincipals> = BTreeMap::new();
+    for row in rows {
+        let entity = entities.entry(row.arn.to_owned()).or_insert_with(|| EntityPrincipals {
+            entity: ReportEntity {
+                entity_type: row.entity_type,
+                name: row.name,
+                id: row.id,
+                arn: row.arn,
+                path: row.path,
+                last_authenticated: Default::default(),
+            },
+            principal_arns: HashSet::new(),
+        });
+        if let Some(principal_arn) = row.principal_arn {
+            entity.principal_arns.insert(principal_arn);
+        }
+    }
+    entities.into_values().collect()
+}
+
+/// Returns ARN of the user or role which has been authenticated. Role sessions are reported as the role itself.
+fn authenticated_entity<'a>(entities: &'a [EntityPrincipals], principal_arn: &'a str) -> &'a str {
+    entities
+        .iter()
+        .find(|entity| entity.entity.entity_type != "GROUP" && entity.matches(principal_arn))
+        .map(|entity| entity.entity.arn.as_str())
+        .unwrap_or(principal_arn)
+}
+
+/// Collects namespaces of services which actions are allowed by the policy document. Actions like `*` don't identify
+/// a service and are skipped.
+fn granted_service_namespaces(document: &str) -> BTreeSet<String> {
+    let document: serde_json::Value = match serde_json::from_str(document) {
+        Ok(document) => document,
+        Err(_err) => return BTreeSet::new(),
+    };
+    let statements = match &document["Statement"] {
+        serde_json::Value::Array(statements) => statements.iter().collect(),
+        statement => vec![statement],
+    };
+
+    let mut namespaces = BTreeSet::new();
+    for statement in statements
+        .into_iter()
+        .filter(|statement| statement["Effect"] == "Allow")
+    {
+        let actions = match &statement["Action"] {
+            serde_json::Value::String(action) => vec![action.as_str()],
+            serde_json::Value::Array(actions) => actions.iter().filter_map(|action| action.as_str()).collect(),
+            _ => vec![],
+        };
+        for action in actions {
+            if let Some((namespace, _action_name)) = action.split_once(':') {
+                if !namespace.is_empty() && !namespace.contains(['*', '?']) {
+                    namespaces.insert(namespace.to_lowercase());
+                }
+            }
+        }
+    }
+    namespaces
+}
+
+/// Splits ARN like `arn:aws:iam::123456789012:user/division/name` into entity type and name. Only users, groups,
+/// roles and policies are supported.
+fn parse_entity_arn(arn: &str) -> Result<(&str, &str), ActionError> {
+    let parts: Vec<&str> = arn.splitn(6, ':').collect();
+    if parts.len() == 6 && parts[0] == "arn" && parts[2] == "iam" {
+        let resource = parts[5];
+        if let Some((entity_type, path_and_name)) = resource.split_once('/') {
+            let entity_name = path_and_name.rsplit('/').next().unwrap_or(path_and_name);
+            if ["user", "group", "role", "policy"].contains(&entity_type) && !entity_name.is_empty() {
+                return Ok((entity_type, entity_name));
+            }
+        }
+    }
+    Err(ActionError::new(
+        ApiErrorKind::InvalidInput,
+        format!("ARN {arn} must identify an IAM user, group, role or policy.").as_str(),
+    ))
+}
+
+fn no_such_entity(arn: &str) -> ActionError {
+    ActionError::new(ApiErrorKind::NoSuchEntity, format!("The entity with ARN {arn} cannot be found.").as_str())
+}
+
+/// Keys which have never been used are reported with `N/A` region and service name, as AWS does.
 pub(crate) async fn get_access_key_last_used<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &GetAccessKeyLastUsedRequest,
 ) -> Result<GetAccessKeyLastUsedOutput, ActionError> {
     input.validate("$")?;
 
-    let output = GetAccessKeyLastUsedOutput::builder().build();
+    let access_key_id = input.access_key_id().unwrap().trim();
+    let last_used = match db::access_key::find_last_used(tx.as_mut(), ctx.account_id, access_key_id).await? {
+        Some(last_used) => last_used,
+        None => {
+            return Err(ActionError::new(
+                ApiErrorKind::NoSuchEntity,
+                format!("The Access Key with id {access_key_id} cannot be found.").as_str(),
+            ))
+        }
+    };
+
+    // keys which have never been used have no usage details, see the output serialization
+    let access_key_last_used = last_used.last_used_date.map(|last_used_date| {
+        AccessKeyLastUsed::builder()
+            .last_used_date(DateTime::from_secs(last_used_date))
+            .service_name(last_used.last_used_service.as_deref().unwrap_or(NOT_AVAILABLE))
+            .region(last_used.last_used_region.as_deref().unwrap_or(NOT_AVAILABLE))
+            .build()
+            .unwrap()
+    });
+    let output = GetAccessKeyLastUsedOutput::builder()
+        .user_name(last_used.user_name)
+        .set_access_key_last_used(access_key_last_used)
+        .build();
     Ok(output)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::granted_service_namespaces;
+
+    #[test]
+    fn collect_granted_service_namespaces() {
+        let document = r#"{
+            "Version": "2012-10-17",
+            "Statement": [
+                {"Effect": "Allow", "Action": ["s3:GetObject", "IAM:ListUsers", "*"], "Resource": "*"},
+                {"Effect": "Allow", "Action": "sqs:*", "Resource": "*"},
+                {"Effect": "Deny", "Action": "ec2:*", "Resource": "*"}
+            ]
+        }"#;
+        let namespaces: Vec<String> = granted_service_namespaces(document).into_iter().collect();
+        assert_eq!(namespaces, vec!["iam", "s3", "sqs"]);
+    }
+}
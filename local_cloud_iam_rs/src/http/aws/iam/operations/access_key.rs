@@ -2,23 +2,67 @@ use aws_sdk_iam::operation::create_access_key::CreateAccessKeyOutput;
 use aws_sdk_iam::operation::delete_access_key::DeleteAccessKeyOutput;
 use aws_sdk_iam::operation::list_access_keys::ListAccessKeysOutput;
 use aws_sdk_iam::operation::update_access_key::UpdateAccessKeyOutput;
+use aws_sdk_iam::types::AccessKey;
+use aws_smithy_types::DateTime;
+use chrono::Utc;
 use sqlx::{Sqlite, Transaction};
 
 use local_cloud_validate::NamedValidator;
 
+use crate::http::aws::iam::actions::error::ApiErrorKind;
+use crate::http::aws::iam::db::types::access_key::{InsertAccessKey, ListAccessKeysQuery, UpdateAccessKeyQuery};
+use crate::http::aws::iam::db::types::access_key_status_type::AccessKeyStatusType;
+use crate::http::aws::iam::db::types::resource_identifier::ResourceType;
+use crate::http::aws::iam::operations::common::create_resource_id_with_length;
 use crate::http::aws::iam::operations::ctx::OperationCtx;
 use crate::http::aws::iam::operations::error::ActionError;
+use crate::http::aws::iam::quotas::Quota;
 use crate::http::aws::iam::types::create_access_key::CreateAccessKeyRequest;
 use crate::http::aws::iam::types::delete_access_key::DeleteAccessKeyRequest;
 use crate::http::aws::iam::types::list_access_keys::ListAccessKeysRequest;
 use crate::http::aws::iam::types::update_access_key::UpdateAccessKeyRequest;
+use crate::http::aws::iam::{constants, db, quotas};
 
 pub(crate) async fn create_access_key<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &CreateAccessKeyRequest,
 ) -> Result<CreateAccessKeyOutput, ActionError> {
     input.validate("$")?;
 
-    let output = CreateAccessKeyOutput::builder().build();
+    let current_time = Utc::now().timestamp();
+    let user = super::user::find_by_name_or_caller(ctx, tx.as_mut(), input.user_name()).await?;
+
+    let count = db::access_key::count_by_user_id(tx.as_mut(), user.id).await?;
+    quotas::check(tx, Quota::AccessKeysPerUser, count).await?;
+
+    let access_key_id = create_resource_id_with_length(
+        tx,
+        constants::access_key::PREFIX,
+        constants::access_key::ID_LENGTH,
+        ResourceType::AccessKey,
+    )
+    .await?;
+
+    let mut insert_access_key = InsertAccessKey {
+        id: None,
+        account_id: ctx.account_id,
+        user_id: user.id,
+        access_key_id,
+        secret_access_key: super::secure::generate_secret_access_key(),
+        status: AccessKeyStatusType::Active,
+        create_date: current_time,
+    };
+    db::access_key::create(tx, &mut insert_access_key).await?;
+
+    let access_key = AccessKey::builder()
+        .user_name(&user.username)
+        .access_key_id(&insert_access_key.access_key_id)
+        .status((&insert_access_key.status).into())
+        .secret_access_key(&insert_access_key.secret_access_key)
+        .create_date(DateTime::from_secs(current_time))
+        .build()
+        .unwrap();
+
+    let output = CreateAccessKeyOutput::builder().access_key(access_key).build();
     Ok(output)
 }
 
@@ -27,6 +71,19 @@ pub(crate) async fn update_access_key<'a>(
 ) -> Result<UpdateAccessKeyOutput, ActionError> {
     input.validate("$")?;
 
+    let user_id = super::user::find_id_by_name_or_caller(ctx, tx.as_mut(), input.user_name()).await?;
+
+    let access_key_id = input.access_key_id().unwrap();
+    let query = UpdateAccessKeyQuery {
+        access_key_id: access_key_id.to_owned(),
+        status: input.status().unwrap().into(),
+        user_id,
+    };
+    let result = db::access_key::update(tx.as_mut(), &query).await?;
+    if !result {
+        return Err(no_such_access_key(access_key_id));
+    }
+
     let output = UpdateAccessKeyOutput::builder().build();
     Ok(output)
 }
@@ -36,7 +93,20 @@ pub(crate) async fn list_access_keys<'a>(
 ) -> Result<ListAccessKeysOutput, ActionError> {
     input.validate("$")?;
 
-    let output = ListAccessKeysOutput::builder().build().unwrap();
+    let user_id = super::user::find_id_by_name_or_caller(ctx, tx.as_mut(), input.user_name()).await?;
+
+    let query = ListAccessKeysQuery::new(user_id, input.max_items(), input.marker_type());
+    let found_access_keys = db::access_key::list(tx.as_mut(), &query).await?;
+
+    let access_keys = super::common::convert_and_limit(&found_access_keys, query.limit);
+    let marker = super::common::create_encoded_marker(&query, found_access_keys.len())?;
+
+    let output = ListAccessKeysOutput::builder()
+        .set_access_key_metadata(access_keys)
+        .set_is_truncated(marker.as_ref().map(|_v| true))
+        .set_marker(marker)
+        .build()
+        .unwrap();
     Ok(output)
 }
 
@@ -45,6 +115,21 @@ pub(crate) async fn delete_access_key<'a>(
 ) -> Result<DeleteAccessKeyOutput, ActionError> {
     input.validate("$")?;
 
+    let user_id = super::user::find_id_by_name_or_caller(ctx, tx.as_mut(), input.user_name()).await?;
+
+    let access_key_id = input.access_key_id().unwrap();
+    let is_deleted = db::access_key::delete(tx.as_mut(), user_id, access_key_id).await?;
+    if !is_deleted {
+        return Err(no_such_access_key(access_key_id));
+    }
+
     let output = DeleteAccessKeyOutput::builder().build();
     Ok(output)
 }
+
+fn no_such_access_key(access_key_id: &str) -> ActionError {
+    ActionError::new(
+        ApiErrorKind::NoSuchEntity,
+        format!("The Access Key with id {} cannot be found.", access_key_id).as_str(),
+    )
+}
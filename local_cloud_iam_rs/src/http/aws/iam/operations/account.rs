@@ -1,12 +1,31 @@
+use std::collections::HashMap;
+
 use aws_sdk_iam::operation::get_account_authorization_details::GetAccountAuthorizationDetailsOutput;
 use aws_sdk_iam::operation::get_account_password_policy::GetAccountPasswordPolicyOutput;
 use aws_sdk_iam::operation::get_account_summary::GetAccountSummaryOutput;
+use aws_sdk_iam::types::{
+    AttachedPermissionsBoundary, GroupDetail, InstanceProfile, ManagedPolicyDetail, PermissionsBoundaryAttachmentType,
+    PolicyDetail, PolicyVersion, Role, RoleDetail, SummaryKeyType, UserDetail,
+};
+use aws_smithy_types::DateTime;
 use sqlx::{Sqlite, Transaction};
 
 use local_cloud_validate::NamedValidator;
 
+use crate::http::aws::iam::constants;
+use crate::http::aws::iam::db;
+use crate::http::aws::iam::db::types::account::AuthorizationDetailsQuery;
+use crate::http::aws::iam::db::types::group::{ListGroupsQuery, SelectGroup};
+use crate::http::aws::iam::db::types::inline_policy::DbInlinePolicy;
+use crate::http::aws::iam::db::types::policy::{ListAttachedPoliciesQuery, ListPoliciesQuery, SelectPolicy};
+use crate::http::aws::iam::db::types::policy_type::PolicyType;
+use crate::http::aws::iam::db::types::policy_version::ListPolicyVersionsQuery;
+use crate::http::aws::iam::db::types::role::{ListRolesQuery, SelectRoleWithDetails};
+use crate::http::aws::iam::db::types::tags::ListTagsQuery;
+use crate::http::aws::iam::db::types::user::{ListUsersQuery, SelectUser};
 use crate::http::aws::iam::operations::ctx::OperationCtx;
 use crate::http::aws::iam::operations::error::ActionError;
+use crate::http::aws::iam::quotas::Quota;
 use crate::http::aws::iam::types::get_account_authorization_details::GetAccountAuthorizationDetailsRequest;
 use crate::http::aws::iam::types::get_account_password_policy::GetAccountPasswordPolicyRequest;
 use crate::http::aws::iam::types::get_account_summary::GetAccountSummaryRequest;
@@ -16,16 +35,293 @@ pub(crate) async fn get_account_authorization_details<'a>(
 ) -> Result<GetAccountAuthorizationDetailsOutput, ActionError> {
     input.validate("$")?;
 
-    let output = GetAccountAuthorizationDetailsOutput::builder().build();
+    let query: AuthorizationDetailsQuery = input.into();
+    let counts = db::account::count_authorization_entities(tx.as_mut(), ctx.account_id).await?;
+    let mut page = AuthorizationDetailsPage::new(&query);
+
+    let mut users = vec![];
+    if let Some((skip, limit)) = page.next(query.include_users, counts.users) {
+        let users_query = ListUsersQuery {
+            path_prefix: "/".to_owned(),
+            limit,
+            skip,
+        };
+        let found_users = db::user::list_with_details(tx.as_mut(), ctx.account_id, &users_query).await?;
+        for user in found_users.iter().take(limit as usize) {
+            users.push(user_detail(tx, user).await?);
+        }
+    }
+    let mut groups = vec![];
+    if let Some((skip, limit)) = page.next(query.include_groups, counts.groups) {
+        let groups_query = ListGroupsQuery {
+            path_prefix: "/".to_owned(),
+            limit,
+            skip,
+        };
+        let found_groups = db::group::list(tx.as_mut(), ctx.account_id, &groups_query).await?;
+        for group in found_groups.iter().take(limit as usize) {
+            groups.push(group_detail(tx, group).await?);
+        }
+    }
+    let mut roles = vec![];
+    if let Some((skip, limit)) = page.next(query.include_roles, counts.roles) {
+        let roles_query = ListRolesQuery {
+            path_prefix: "/".to_owned(),
+            limit,
+            skip,
+        };
+        let found_roles = db::role::list_with_details(tx.as_mut(), ctx.account_id, &roles_query).await?;
+        for role in found_roles.iter().take(limit as usize) {
+            roles.push(role_detail(tx, role).await?);
+        }
+    }
+    let mut policies = vec![];
+    // local policies are returned regardless of their attachments
+    if let Some((skip, limit)) = page.next(query.include_local_managed_policies, counts.local_managed_policies) {
+        for policy in find_policies(tx, ctx, PolicyType::CustomerManaged, false, skip, limit).await? {
+            policies.push(managed_policy_detail(tx, &policy).await?);
+        }
+    }
+    // only AWS managed policies which are in use are reported
+    if let Some((skip, limit)) = page.next(query.include_aws_managed_policies, counts.aws_managed_policies) {
+        for policy in find_policies(tx, ctx, PolicyType::LocalCloudManaged, true, skip, limit).await? {
+            policies.push(managed_policy_detail(tx, &policy).await?);
+        }
+    }
+
+    let marker = super::common::create_encoded_marker(&query, page.found_items())?;
+
+    let output = GetAccountAuthorizationDetailsOutput::builder()
+        .set_user_detail_list(Some(users))
+        .set_group_detail_list(Some(groups))
+        .set_role_detail_list(Some(roles))
+        .set_policies(Some(policies))
+        .set_is_truncated(Some(marker.is_some()))
+        .set_marker(marker)
+        .build();
     Ok(output)
 }
 
+/// Upper bound for loading all entities of a kind, since list queries fetch one extra row.
+const ALL_ITEMS: i32 = i32::MAX - 1;
+
+/// Page of authorization details. Entities are listed kind by kind (users, groups, roles, local managed policies and
+/// AWS managed policies), so the page is split into a part of each kind.
+struct AuthorizationDetailsPage {
+    skip: i32,
+    limit: i32,
+    total: i32,
+    initial_skip: i32,
+}
+
+impl AuthorizationDetailsPage {
+    fn new(query: &AuthorizationDetailsQuery) -> Self {
+        AuthorizationDetailsPage {
+            skip: query.skip,
+            limit: query.limit,
+            total: 0,
+            initial_skip: query.skip,
+        }
+    }
+
+    /// Returns skip and limit of the next kind which has `count` entities, or `None` if none of them is on the page.
+    fn next(&mut self, is_included: bool, count: i32) -> Option<(i32, i32)> {
+        if !is_included {
+            return None;
+        }
+        self.total += count;
+        let skip = self.skip.min(count);
+        self.skip -= skip;
+        let limit = self.limit.min(count - skip);
+        self.limit -= limit;
+        if limit > 0 {
+            Some((skip, limit))
+        } else {
+            None
+        }
+    }
+
+    /// Number of entities starting from the page.
+    fn found_items(&self) -> usize {
+        (self.total - self.initial_skip).max(0) as usize
+    }
+}
+
+async fn find_policies<'a>(
+    tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, policy_type: PolicyType, is_attached: bool, skip: i32,
+    limit: i32,
+) -> Result<Vec<SelectPolicy>, ActionError> {
+    let policies_query = ListPoliciesQuery {
+        path_prefix: "/".to_owned(),
+        limit,
+        skip,
+        is_attached,
+        policy_scope_types: vec![policy_type],
+    };
+    let mut policies = db::policy::list(tx.as_mut(), ctx.account_id, &policies_query).await?;
+    policies.truncate(limit as usize);
+    Ok(policies)
+}
+
+fn all_attached(parent_id: i64) -> ListAttachedPoliciesQuery {
+    ListAttachedPoliciesQuery {
+        parent_id,
+        path_prefix: "/".to_owned(),
+        limit: ALL_ITEMS,
+        skip: 0,
+    }
+}
+
+fn policy_details(inline_policies: &[DbInlinePolicy]) -> Vec<PolicyDetail> {
+    inline_policies
+        .iter()
+        .map(|policy| {
+            PolicyDetail::builder()
+                .policy_name(&policy.policy_name)
+                .policy_document(urlencoding::encode(&policy.policy_document))
+                .build()
+        })
+        .collect()
+}
+
+fn permissions_boundary(policy_arn: &Option<String>) -> Option<AttachedPermissionsBoundary> {
+    policy_arn.as_ref().map(|policy_arn| {
+        AttachedPermissionsBoundary::builder()
+            .permissions_boundary_type(PermissionsBoundaryAttachmentType::Policy)
+            .permissions_boundary_arn(policy_arn)
+            .build()
+    })
+}
+
+async fn user_detail<'a>(tx: &mut Transaction<'a, Sqlite>, user: &SelectUser) -> Result<UserDetail, ActionError> {
+    let groups = db::group::find_all_by_user_id(tx.as_mut(), user.id).await?;
+    let inline_policies = db::user_inline_policy::find_all_by_user_id(tx.as_mut(), user.id).await?;
+    let attached_policies = db::policy::list_attached_to_user(tx.as_mut(), &all_attached(user.id)).await?;
+    let tags = db::Tags::User.list(tx.as_mut(), user.id, &ListTagsQuery::all()).await?;
+
+    let detail = UserDetail::builder()
+        .path(&user.path)
+        .user_name(&user.username)
+        .user_id(&user.user_id)
+        .arn(&user.arn)
+        .create_date(DateTime::from_secs(user.create_date))
+        .set_user_policy_list(Some(policy_details(&inline_policies)))
+        .set_group_list(Some(groups.into_iter().map(|group| group.group_name).collect()))
+        .set_attached_managed_policies(Some(attached_policies.iter().map(|policy| policy.into()).collect()))
+        .set_permissions_boundary(permissions_boundary(&user.policy_arn))
+        .set_tags(Some(tags.iter().map(|tag| tag.into()).collect()))
+        .build();
+    Ok(detail)
+}
+
+async fn group_detail<'a>(tx: &mut Transaction<'a, Sqlite>, group: &SelectGroup) -> Result<GroupDetail, ActionError> {
+    let inline_policies = db::group_inline_policy::find_all_by_group_id(tx.as_mut(), group.id).await?;
+    let attached_policies = db::policy::list_attached_to_group(tx.as_mut(), &all_attached(group.id)).await?;
+
+    let detail = GroupDetail::builder()
+        .path(&group.path)
+        .group_name(&group.group_name)
+        .group_id(&group.group_id)
+        .arn(&group.arn)
+        .create_date(DateTime::from_secs(group.create_date))
+        .set_group_policy_list(Some(policy_details(&inline_policies)))
+        .set_attached_managed_policies(Some(attached_policies.iter().map(|policy| policy.into()).collect()))
+        .build();
+    Ok(detail)
+}
+
+async fn role_detail<'a>(
+    tx: &mut Transaction<'a, Sqlite>, select_role: &SelectRoleWithDetails,
+) -> Result<RoleDetail, ActionError> {
+    let role_id = select_role.id;
+    let role: Role = select_role.into();
+    let inline_policies = db::role_inline_policy::find_all_by_role_id(tx.as_mut(), role_id).await?;
+    let attached_policies = db::policy::list_attached_to_role(tx.as_mut(), &all_attached(role_id)).await?;
+    let tags = db::Tags::Role.list(tx.as_mut(), role_id, &ListTagsQuery::all()).await?;
+
+    let mut instance_profiles = vec![];
+    for select_profile in db::instance_profile::find_by_role_id(tx.as_mut(), role_id).await? {
+        let select_roles = db::instance_profile::list_roles(tx.as_mut(), select_profile.id).await?;
+        let instance_profile = InstanceProfile::builder()
+            .path(&select_profile.path)
+            .instance_profile_name(&select_profile.instance_profile_name)
+            .instance_profile_id(&select_profile.instance_profile_id)
+            .arn(&select_profile.arn)
+            .create_date(DateTime::from_secs(select_profile.create_date))
+            .set_roles(Some(select_roles.iter().map(|role| role.into()).collect()))
+            .build()
+            .unwrap();
+        instance_profiles.push(instance_profile);
+    }
+
+    let detail = RoleDetail::builder()
+        .path(role.path())
+        .role_name(role.role_name())
+        .role_id(role.role_id())
+        .arn(role.arn())
+        .create_date(*role.create_date())
+        .set_assume_role_policy_document(role.assume_role_policy_document().map(|doc| doc.to_owned()))
+        .set_instance_profile_list(Some(instance_profiles))
+        .set_role_policy_list(Some(policy_details(&inline_policies)))
+        .set_attached_managed_policies(Some(attached_policies.iter().map(|policy| policy.into()).collect()))
+        .set_permissions_boundary(role.permissions_boundary().cloned())
+        .set_tags(Some(tags.iter().map(|tag| tag.into()).collect()))
+        .set_role_last_used(role.role_last_used().cloned())
+        .build();
+    Ok(detail)
+}
+
+async fn managed_policy_detail<'a>(
+    tx: &mut Transaction<'a, Sqlite>, policy: &SelectPolicy,
+) -> Result<ManagedPolicyDetail, ActionError> {
+    let versions_query = ListPolicyVersionsQuery {
+        policy_id: policy.id,
+        limit: ALL_ITEMS,
+        skip: 0,
+    };
+    let versions = db::policy_version::find_by_policy_id(tx.as_mut(), &versions_query).await?;
+    let policy_versions = versions
+        .iter()
+        .map(|version| {
+            PolicyVersion::builder()
+                .version_id(format!("v{}", version.version))
+                .document(urlencoding::encode(&version.policy_document))
+                .is_default_version(version.is_default)
+                .create_date(DateTime::from_secs(version.create_date))
+                .build()
+        })
+        .collect();
+
+    let detail = ManagedPolicyDetail::builder()
+        .policy_name(&policy.policy_name)
+        .policy_id(&policy.policy_id)
+        .arn(&policy.arn)
+        .path(&policy.path)
+        .default_version_id(format!("v{}", policy.version))
+        .attachment_count(policy.attachment_count)
+        .permissions_boundary_usage_count(policy.permissions_boundary_usage_count)
+        .is_attachable(policy.is_attachable)
+        .set_description(policy.description.clone())
+        .create_date(DateTime::from_secs(policy.create_date))
+        .update_date(DateTime::from_secs(policy.update_date))
+        .set_policy_version_list(Some(policy_versions))
+        .build();
+    Ok(detail)
+}
+
 pub(crate) async fn get_account_password_policy<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &GetAccountPasswordPolicyRequest,
 ) -> Result<GetAccountPasswordPolicyOutput, ActionError> {
     input.validate("$")?;
 
-    let output = GetAccountPasswordPolicyOutput::builder().build();
+    let policy = match db::password_policy::find_by_account_id(tx.as_mut(), ctx.account_id).await? {
+        Some(policy) => policy,
+        None => return Err(super::account_password_policy::password_policy_not_found(ctx.account_id)),
+    };
+
+    let output = GetAccountPasswordPolicyOutput::builder()
+        .password_policy((&policy).into())
+        .build();
     Ok(output)
 }
 
@@ -34,6 +330,31 @@ pub(crate) async fn get_account_summary<'a>(
 ) -> Result<GetAccountSummaryOutput, ActionError> {
     input.validate("$")?;
 
-    let output = GetAccountSummaryOutput::builder().build();
+    let summary = db::account::find_summary(tx.as_mut(), ctx.account_id).await?;
+
+    let mut summary_map = HashMap::new();
+    for quota in Quota::ALL {
+        if let Some(count) = quota.account_count(&summary) {
+            summary_map.insert(SummaryKeyType::from(quota.as_str()), count);
+        }
+    }
+    for select_quota in db::quota::find_all(tx.as_mut()).await? {
+        summary_map.insert(SummaryKeyType::from(select_quota.name.as_str()), select_quota.value);
+    }
+    summary_map.insert(SummaryKeyType::MfaDevices, summary.mfa_devices);
+    summary_map.insert(SummaryKeyType::MfaDevicesInUse, summary.mfa_devices_in_use);
+    summary_map.insert(SummaryKeyType::AccountMfaEnabled, summary.account_mfa_enabled as i32);
+    summary_map.insert(SummaryKeyType::AccountAccessKeysPresent, summary.account_access_keys_present as i32);
+    summary_map
+        .insert(SummaryKeyType::AccountSigningCertificatesPresent, summary.account_signing_certificates_present as i32);
+    summary_map.insert(SummaryKeyType::GlobalEndpointTokenVersion, 1);
+    // the summary map holds only numbers, so the alias is reported as the count of account aliases
+    let account_aliases = db::account::find_alias(tx.as_mut(), ctx.account_id).await?.is_some() as i32;
+    summary_map.insert(SummaryKeyType::from("AccountAliases"), account_aliases);
+    summary_map.insert(SummaryKeyType::from("AccountAliasesPerAccountQuota"), constants::account::MAX_ALIASES);
+
+    let output = GetAccountSummaryOutput::builder()
+        .set_summary_map(Some(summary_map))
+        .build();
     Ok(output)
 }
@@ -1,10 +1,13 @@
 use aws_sdk_iam::operation::create_account_alias::CreateAccountAliasOutput;
 use aws_sdk_iam::operation::delete_account_alias::DeleteAccountAliasOutput;
 use aws_sdk_iam::operation::list_account_aliases::ListAccountAliasesOutput;
-use sqlx::{Sqlite, Transaction};
+use sqlx::{Executor, Sqlite, Transaction};
 
 use local_cloud_validate::NamedValidator;
 
+use crate::http::aws::iam::actions::error::ApiErrorKind;
+use crate::http::aws::iam::constants;
+use crate::http::aws::iam::db;
 use crate::http::aws::iam::operations::ctx::OperationCtx;
 use crate::http::aws::iam::operations::error::ActionError;
 use crate::http::aws::iam::types::create_account_alias::CreateAccountAliasRequest;
@@ -16,6 +19,28 @@ pub(crate) async fn create_account_alias<'a>(
 ) -> Result<CreateAccountAliasOutput, ActionError> {
     input.validate("$")?;
 
+    let alias = input.account_alias().unwrap().trim();
+    match db::account::find_id_by_alias(tx.as_mut(), alias).await? {
+        // creation of the existing alias is idempotent
+        Some(account_id) if account_id == ctx.account_id => {
+            return Ok(CreateAccountAliasOutput::builder().build());
+        }
+        Some(_) => {
+            return Err(ActionError::new(
+                ApiErrorKind::EntityAlreadyExists,
+                format!("The account alias {alias} already exists.").as_str(),
+            ));
+        }
+        None => {}
+    }
+    if db::account::find_alias(tx.as_mut(), ctx.account_id).await?.is_some() {
+        return Err(ActionError::new(
+            ApiErrorKind::LimitExceeded,
+            format!("Cannot exceed quota for AccountAliasesPerAccount: {}", constants::account::MAX_ALIASES).as_str(),
+        ));
+    }
+    db::account::update_alias(tx.as_mut(), ctx.account_id, Some(alias)).await?;
+
     let output = CreateAccountAliasOutput::builder().build();
     Ok(output)
 }
@@ -25,7 +50,17 @@ pub(crate) async fn list_account_aliases<'a>(
 ) -> Result<ListAccountAliasesOutput, ActionError> {
     input.validate("$")?;
 
-    let output = ListAccountAliasesOutput::builder().build().unwrap();
+    // an account could have only one alias, so the result is never truncated
+    let aliases: Vec<String> = db::account::find_alias(tx.as_mut(), ctx.account_id)
+        .await?
+        .into_iter()
+        .collect();
+
+    let output = ListAccountAliasesOutput::builder()
+        .set_account_aliases(Some(aliases))
+        .is_truncated(false)
+        .build()
+        .unwrap();
     Ok(output)
 }
 
@@ -34,6 +69,33 @@ pub(crate) async fn delete_account_alias<'a>(
 ) -> Result<DeleteAccountAliasOutput, ActionError> {
     input.validate("$")?;
 
+    let alias = input.account_alias().unwrap().trim();
+    let current_alias = db::account::find_alias(tx.as_mut(), ctx.account_id).await?;
+    if current_alias.as_deref() != Some(alias) {
+        return Err(ActionError::new(
+            ApiErrorKind::NoSuchEntity,
+            format!("The account alias {alias} cannot be found.").as_str(),
+        ));
+    }
+    db::account::update_alias(tx.as_mut(), ctx.account_id, None).await?;
+
     let output = DeleteAccountAliasOutput::builder().build();
     Ok(output)
 }
+
+/// Returns the alias of the account, or its 12-digit ID if the account has no alias.
+pub(crate) async fn find_account_name<'a, E>(executor: E, account_id: i64) -> Result<String, ActionError>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let account_name = match db::account::find_alias(executor, account_id).await? {
+        Some(alias) => alias,
+        None => format!("{:0>12}", account_id),
+    };
+    Ok(account_name)
+}
+
+/// Returns the console sign-in URL of the account identified by its alias or ID.
+pub(crate) fn sign_in_url(account_name: &str) -> String {
+    format!("https://{account_name}.signin.aws.amazon.com/console")
+}
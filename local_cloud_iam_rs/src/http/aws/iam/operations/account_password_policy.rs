@@ -2,18 +2,42 @@ use aws_sdk_iam::operation::delete_account_password_policy::DeleteAccountPasswor
 use aws_sdk_iam::operation::update_account_password_policy::UpdateAccountPasswordPolicyOutput;
 use sqlx::{Sqlite, Transaction};
 
-use local_cloud_validate::NamedValidator;
+use local_cloud_validate::{NamedValidator, ValidationError, ValidationErrorKind};
 
+use crate::http::aws::iam::actions::error::ApiErrorKind;
+use crate::http::aws::iam::db;
+use crate::http::aws::iam::db::types::password_policy::{InsertPasswordPolicy, SelectPasswordPolicy};
 use crate::http::aws::iam::operations::ctx::OperationCtx;
 use crate::http::aws::iam::operations::error::ActionError;
 use crate::http::aws::iam::types::delete_account_password_policy::DeleteAccountPasswordPolicyRequest;
 use crate::http::aws::iam::types::update_account_password_policy::UpdateAccountPasswordPolicyRequest;
 
+const DEFAULT_MINIMUM_PASSWORD_LENGTH: i32 = 6;
+const SYMBOLS: &str = "!@#$%^&*()_+-=[]{}|'";
+
 pub(crate) async fn update_account_password_policy<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &UpdateAccountPasswordPolicyRequest,
 ) -> Result<UpdateAccountPasswordPolicyOutput, ActionError> {
     input.validate("$")?;
 
+    // parameters which are not specified are reverted to their default values
+    let policy = InsertPasswordPolicy {
+        account_id: ctx.account_id,
+        minimum_password_length: input
+            .minimum_password_length()
+            .copied()
+            .unwrap_or(DEFAULT_MINIMUM_PASSWORD_LENGTH),
+        require_symbols: input.require_symbols().unwrap_or(false),
+        require_numbers: input.require_numbers().unwrap_or(false),
+        require_uppercase_characters: input.require_uppercase_characters().unwrap_or(false),
+        require_lowercase_characters: input.require_lowercase_characters().unwrap_or(false),
+        allow_users_to_change_password: input.allow_users_to_change_password().unwrap_or(false),
+        max_password_age: input.max_password_age().copied().unwrap_or(0),
+        password_reuse_prevention: input.password_reuse_prevention().copied().unwrap_or(0),
+        hard_expiry: input.hard_expiry().unwrap_or(false),
+    };
+    db::password_policy::save(tx.as_mut(), &policy).await?;
+
     let output = UpdateAccountPasswordPolicyOutput::builder().build();
     Ok(output)
 }
@@ -23,6 +47,95 @@ pub(crate) async fn delete_account_password_policy<'a>(
 ) -> Result<DeleteAccountPasswordPolicyOutput, ActionError> {
     input.validate("$")?;
 
+    let is_deleted = db::password_policy::delete(tx.as_mut(), ctx.account_id).await?;
+    if !is_deleted {
+        return Err(password_policy_not_found(ctx.account_id));
+    }
+
     let output = DeleteAccountPasswordPolicyOutput::builder().build();
     Ok(output)
 }
+
+pub(crate) fn password_policy_not_found(account_id: i64) -> ActionError {
+    ActionError::new(
+        ApiErrorKind::NoSuchEntity,
+        format!("The Password Policy with domain name {account_id:0>12} cannot be found.").as_str(),
+    )
+}
+
+/// Checks the new password of the IAM user against the password policy of the account (if any),
+/// including the password history of the user when password reuse prevention is enabled.
+pub(crate) async fn check_password<'a>(
+    tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, user_id: i64, password: &str,
+) -> Result<(), ActionError> {
+    let policy = match db::password_policy::find_by_account_id(tx.as_mut(), ctx.account_id).await? {
+        Some(policy) => policy,
+        None => return Ok(()),
+    };
+    validate_password(&policy, password)?;
+
+    if policy.password_reuse_prevention > 0 {
+        let hashes =
+            db::password_history::find_recent_hashes(tx.as_mut(), user_id, policy.password_reuse_prevention).await?;
+        if hashes.iter().any(|hash| super::secure::verify_password(password, hash)) {
+            return Err(ActionError::from(ValidationError::new(
+                ValidationErrorKind::Password,
+                format!("Password should not be one of the last {} passwords.", policy.password_reuse_prevention),
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_password(policy: &SelectPasswordPolicy, password: &str) -> Result<(), ValidationError> {
+    let violation = |message: String| Err(ValidationError::new(ValidationErrorKind::Password, message));
+
+    if password.chars().count() < policy.minimum_password_length as usize {
+        return violation(format!("Password should have a minimum length of {}.", policy.minimum_password_length));
+    }
+    if policy.require_uppercase_characters && !password.chars().any(|ch| ch.is_ascii_uppercase()) {
+        return violation("Password should have at least one uppercase letter.".to_owned());
+    }
+    if policy.require_lowercase_characters && !password.chars().any(|ch| ch.is_ascii_lowercase()) {
+        return violation("Password should have at least one lowercase letter.".to_owned());
+    }
+    if policy.require_numbers && !password.chars().any(|ch| ch.is_ascii_digit()) {
+        return violation("Password should have at least one number.".to_owned());
+    }
+    if policy.require_symbols && !password.chars().any(|ch| SYMBOLS.contains(ch)) {
+        return violation(format!("Password should have at least one of the following symbols: {SYMBOLS}"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use local_cloud_validate::ValidationErrorKind;
+
+    use crate::http::aws::iam::db::types::password_policy::SelectPasswordPolicy;
+
+    fn policy() -> SelectPasswordPolicy {
+        SelectPasswordPolicy {
+            minimum_password_length: 10,
+            require_symbols: true,
+            require_numbers: true,
+            require_uppercase_characters: true,
+            require_lowercase_characters: true,
+            allow_users_to_change_password: true,
+            max_password_age: 0,
+            password_reuse_prevention: 0,
+            hard_expiry: false,
+        }
+    }
+
+    #[test]
+    fn test_validate_password() {
+        let policy = policy();
+        assert!(super::validate_password(&policy, "Abcdefgh1!").is_ok());
+
+        for password in ["Abcdefg1!", "abcdefgh1!", "ABCDEFGH1!", "Abcdefghi!", "Abcdefghi1"] {
+            let error = super::validate_password(&policy, password).unwrap_err();
+            assert_eq!(ValidationErrorKind::Password, error.kind, "{password}");
+        }
+    }
+}
@@ -9,9 +9,15 @@ use crate::http::aws::iam::types::marker_type::Marker;
 
 pub(crate) async fn create_resource_id<'a>(
     tx: &mut Transaction<'a, Sqlite>, prefix: &str, resource_type: ResourceType,
+) -> Result<String, ActionError> {
+    create_resource_id_with_length(tx, prefix, 21, resource_type).await
+}
+
+pub(crate) async fn create_resource_id_with_length<'a>(
+    tx: &mut Transaction<'a, Sqlite>, prefix: &str, length: usize, resource_type: ResourceType,
 ) -> Result<String, ActionError> {
     loop {
-        let id = local_cloud_common::naming::generate_id(prefix, 21)
+        let id = local_cloud_common::naming::generate_id(prefix, length)
             .map_err(|err| ActionError::new(ApiErrorKind::ServiceFailure, err.to_string().as_str()))?;
 
         let mut resource_identifier = ResourceIdentifier::new(&id, resource_type);
@@ -53,3 +59,20 @@ pub(crate) fn convert_and_limit<S: Sized, T: Sized + for<'a> From<&'a S>>(
         Some(result)
     }
 }
+
+/// Fails with `EntityAlreadyExists` if the entity which is about to be created has been found. Duplicates are detected
+/// before quotas are checked, so that a full account reports the duplicate rather than the exceeded quota.
+pub(crate) fn check_not_exists<T>(found: Option<T>, message: &str) -> Result<(), ActionError> {
+    if found.is_some() {
+        return Err(ActionError::new(ApiErrorKind::EntityAlreadyExists, message));
+    }
+    Ok(())
+}
+
+/// Fails with `DeleteConflict` if the entity which is about to be deleted still has dependent resources.
+pub(crate) fn check_delete_conflict(dependents_count: usize, message: &str) -> Result<(), ActionError> {
+    if dependents_count > 0 {
+        return Err(ActionError::new(ApiErrorKind::DeleteConflict, message));
+    }
+    Ok(())
+}
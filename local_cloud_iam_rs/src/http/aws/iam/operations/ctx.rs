@@ -1,15 +1,43 @@
+use local_cloud_axum::local::web::CallerIdentity;
+
+use crate::http::aws::iam::constants;
+
 #[derive(Debug)]
 #[allow(dead_code)]
 pub struct OperationCtx {
     pub(crate) account_id: i64,
     pub(crate) aws_request_id: String,
+    pub(crate) caller: CallerIdentity,
+    pub(crate) settings: OperationSettings,
 }
 
 impl OperationCtx {
-    pub fn new(account_id: i64, aws_request_id: impl Into<String>) -> OperationCtx {
+    pub fn new(caller: &CallerIdentity, aws_request_id: impl Into<String>) -> OperationCtx {
         OperationCtx {
-            account_id,
+            account_id: caller.account_id,
             aws_request_id: aws_request_id.into(),
+            caller: caller.clone(),
+            settings: OperationSettings::default(),
+        }
+    }
+
+    pub(crate) fn with_settings(mut self, settings: &OperationSettings) -> OperationCtx {
+        self.settings = settings.clone();
+        self
+    }
+}
+
+/// Configurable settings of the service which are used by operations.
+#[derive(Clone, Debug)]
+pub(crate) struct OperationSettings {
+    /// Number of time steps before and after the current one in which MFA codes are accepted.
+    pub(crate) mfa_clock_skew_steps: u16,
+}
+
+impl Default for OperationSettings {
+    fn default() -> Self {
+        OperationSettings {
+            mfa_clock_skew_steps: constants::mfa::DEFAULT_CLOCK_SKEW_STEPS,
         }
     }
 }
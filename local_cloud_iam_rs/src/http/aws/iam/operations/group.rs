@@ -18,6 +18,7 @@ use aws_smithy_types::DateTime;
 use chrono::Utc;
 use sqlx::{Executor, Sqlite, Transaction};
 
+use local_cloud_iam_policy_document::types::PolicyDocumentKind;
 use local_cloud_validate::NamedValidator;
 
 use crate::http::aws::iam::actions::error::ApiErrorKind;
@@ -26,11 +27,13 @@ use crate::http::aws::iam::db::types::group::{
     UpdateGroupQuery,
 };
 use crate::http::aws::iam::db::types::inline_policy::{DbInlinePolicy, ListInlinePoliciesQuery};
+use crate::http::aws::iam::db::types::policy::ListAttachedPoliciesQuery;
 use crate::http::aws::iam::db::types::resource_identifier::ResourceType;
 use crate::http::aws::iam::db::types::user::ListUsersByGroupQuery;
-use crate::http::aws::iam::operations::common::create_resource_id;
+use crate::http::aws::iam::operations::common::{check_delete_conflict, create_resource_id};
 use crate::http::aws::iam::operations::ctx::OperationCtx;
 use crate::http::aws::iam::operations::error::ActionError;
+use crate::http::aws::iam::quotas::Quota;
 use crate::http::aws::iam::types::add_user_to_group::AddUserToGroupRequest;
 use crate::http::aws::iam::types::attach_group_policy::AttachGroupPolicyRequest;
 use crate::http::aws::iam::types::create_group::CreateGroupRequest;
@@ -46,12 +49,18 @@ use crate::http::aws::iam::types::list_groups_for_user::ListGroupsForUserRequest
 use crate::http::aws::iam::types::put_group_policy::PutGroupPolicyRequest;
 use crate::http::aws::iam::types::remove_user_from_group::RemoveUserFromGroupRequest;
 use crate::http::aws::iam::types::update_group::UpdateGroupRequest;
-use crate::http::aws::iam::{constants, db};
+use crate::http::aws::iam::{constants, db, quotas};
 
 pub(crate) async fn create_group<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &CreateGroupRequest,
 ) -> Result<CreateGroupOutput, ActionError> {
     input.validate("$")?;
+    let group_name = input.group_name().unwrap().trim();
+    super::common::check_not_exists(
+        db::group::find_id_by_name(tx.as_mut(), ctx.account_id, group_name).await?,
+        format!("Group with name {group_name} already exists.").as_str(),
+    )?;
+    quotas::check_account(tx, ctx.account_id, Quota::Groups).await?;
 
     let current_time = Utc::now().timestamp();
 
@@ -151,7 +160,12 @@ pub(crate) async fn add_user_to_group<'a>(
 
     let found_group = find_by_name(ctx, tx.as_mut(), input.group_name().unwrap().trim()).await?;
     let found_user = super::user::find_by_name(ctx, tx.as_mut(), input.user_name().unwrap().trim()).await?;
-    db::group::assign_user_to_group(tx, found_group.id, found_user.id).await?;
+    // adding the member again is a no-op which is not limited by quotas
+    if !db::group::is_member(tx.as_mut(), found_group.id, found_user.id).await? {
+        let groups_count = db::user::count_groups(tx.as_mut(), found_user.id).await?;
+        quotas::check(tx, Quota::GroupsPerUser, groups_count).await?;
+        db::group::assign_user_to_group(tx, found_group.id, found_user.id).await?;
+    }
     let output = AddUserToGroupOutput::builder().build();
     Ok(output)
 }
@@ -199,7 +213,13 @@ pub(crate) async fn attach_group_policy<'a>(
     let found_policy_id =
         super::policy::find_id_by_arn(tx.as_mut(), ctx.account_id, input.policy_arn().unwrap().trim()).await?;
 
-    db::group::assign_policy_to_group(tx, found_group.id, found_policy_id).await?;
+    // attaching the policy again is a no-op which is not limited by quotas
+    if !db::policy::is_attached_to_group(tx.as_mut(), found_group.id, found_policy_id).await? {
+        let attached_count = db::group::count_attached_policies(tx.as_mut(), found_group.id).await?;
+        quotas::check(tx, Quota::AttachedPoliciesPerGroup, attached_count).await?;
+        quotas::check_account(tx, ctx.account_id, Quota::PolicyVersionsInUse).await?;
+        db::group::assign_policy_to_group(tx, found_group.id, found_policy_id).await?;
+    }
 
     let output = AttachGroupPolicyOutput::builder().build();
     Ok(output)
@@ -291,6 +311,10 @@ pub(crate) async fn put_group_policy<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &PutGroupPolicyRequest,
 ) -> Result<PutGroupPolicyOutput, ActionError> {
     input.validate("$")?;
+    input
+        .policy_document_type()
+        .unwrap()
+        .validate_as(PolicyDocumentKind::Identity)?;
 
     let group_id = find_id_by_name(tx.as_mut(), ctx.account_id, input.group_name().unwrap().trim()).await?;
 
@@ -331,7 +355,19 @@ pub(crate) async fn list_attached_group_policies<'a>(
 ) -> Result<ListAttachedGroupPoliciesOutput, ActionError> {
     input.validate("$")?;
 
-    let output = ListAttachedGroupPoliciesOutput::builder().build();
+    let group_id = find_id_by_name(tx.as_mut(), ctx.account_id, input.group_name().unwrap().trim()).await?;
+
+    let query = ListAttachedPoliciesQuery::new(group_id, input.path_prefix(), input.max_items(), input.marker_type());
+    let found_policies = db::policy::list_attached_to_group(tx.as_mut(), &query).await?;
+
+    let policies = super::common::convert_and_limit(&found_policies, query.limit);
+    let marker = super::common::create_encoded_marker(&query, found_policies.len())?;
+
+    let output = ListAttachedGroupPoliciesOutput::builder()
+        .set_attached_policies(policies)
+        .set_is_truncated(marker.as_ref().map(|_v| true))
+        .set_marker(marker)
+        .build();
     Ok(output)
 }
 
@@ -359,6 +395,18 @@ pub(crate) async fn remove_user_from_group<'a>(
 ) -> Result<RemoveUserFromGroupOutput, ActionError> {
     input.validate("$")?;
 
+    let group_name = input.group_name().unwrap().trim();
+    let user_name = input.user_name().unwrap().trim();
+    let group_id = find_id_by_name(tx.as_mut(), ctx.account_id, group_name).await?;
+    let user_id = super::user::find_id_by_name(tx.as_mut(), ctx.account_id, user_name).await?;
+    let is_removed = db::group::remove_user_from_group(tx.as_mut(), group_id, user_id).await?;
+    if !is_removed {
+        return Err(ActionError::new(
+            ApiErrorKind::NoSuchEntity,
+            format!("User {user_name} is not a member of group {group_name}.").as_str(),
+        ));
+    }
+
     let output = RemoveUserFromGroupOutput::builder().build();
     Ok(output)
 }
@@ -368,6 +416,23 @@ pub(crate) async fn delete_group<'a>(
 ) -> Result<DeleteGroupOutput, ActionError> {
     input.validate("$")?;
 
+    let group_id = find_id_by_name(tx.as_mut(), ctx.account_id, input.group_name().unwrap().trim()).await?;
+
+    check_delete_conflict(
+        db::group::count_users(tx.as_mut(), group_id).await?,
+        "Cannot delete entity, must remove users from group first.",
+    )?;
+    check_delete_conflict(
+        db::group_inline_policy::count_by_group_id(tx.as_mut(), group_id).await?,
+        "Cannot delete entity, must delete policies first.",
+    )?;
+    check_delete_conflict(
+        db::group::count_attached_policies(tx.as_mut(), group_id).await?,
+        "Cannot delete entity, must detach all policies first.",
+    )?;
+
+    db::group::delete(tx, group_id).await?;
+
     let output = DeleteGroupOutput::builder().build();
     Ok(output)
 }
@@ -377,6 +442,18 @@ pub(crate) async fn detach_group_policy<'a>(
 ) -> Result<DetachGroupPolicyOutput, ActionError> {
     input.validate("$")?;
 
+    let group_id = find_id_by_name(tx.as_mut(), ctx.account_id, input.group_name().unwrap().trim()).await?;
+    let policy_arn = input.policy_arn().unwrap().trim();
+    let policy_id = super::policy::find_id_by_arn(tx.as_mut(), ctx.account_id, policy_arn).await?;
+
+    let is_detached = db::group::detach_policy_from_group(tx.as_mut(), group_id, policy_id).await?;
+    if !is_detached {
+        return Err(ActionError::new(
+            ApiErrorKind::NoSuchEntity,
+            format!("Policy {policy_arn} was not found.").as_str(),
+        ));
+    }
+
     let output = DetachGroupPolicyOutput::builder().build();
     Ok(output)
 }
@@ -386,6 +463,16 @@ pub(crate) async fn delete_group_policy<'a>(
 ) -> Result<DeleteGroupPolicyOutput, ActionError> {
     input.validate("$")?;
 
+    let group_id = find_id_by_name(tx.as_mut(), ctx.account_id, input.group_name().unwrap().trim()).await?;
+    let policy_name = input.policy_name().unwrap();
+    let is_deleted = db::group_inline_policy::delete_by_group_id_and_name(tx.as_mut(), group_id, policy_name).await?;
+    if !is_deleted {
+        return Err(ActionError::new(
+            ApiErrorKind::NoSuchEntity,
+            format!("The group policy with name {policy_name} cannot be found.").as_str(),
+        ));
+    }
+
     let output = DeleteGroupPolicyOutput::builder().build();
     Ok(output)
 }
@@ -19,9 +19,10 @@ use crate::http::aws::iam::actions::error::ApiErrorKind;
 use crate::http::aws::iam::db::types::instance_profile::{InsertInstanceProfile, ListInstanceProfilesQuery};
 use crate::http::aws::iam::db::types::resource_identifier::ResourceType;
 use crate::http::aws::iam::db::types::tags::ListTagsQuery;
-use crate::http::aws::iam::operations::common::create_resource_id;
+use crate::http::aws::iam::operations::common::{check_delete_conflict, create_resource_id};
 use crate::http::aws::iam::operations::ctx::OperationCtx;
 use crate::http::aws::iam::operations::error::ActionError;
+use crate::http::aws::iam::quotas::Quota;
 use crate::http::aws::iam::types::add_role_to_instance_profile::AddRoleToInstanceProfileRequest;
 use crate::http::aws::iam::types::create_instance_profile::CreateInstanceProfileRequest;
 use crate::http::aws::iam::types::delete_instance_profile::DeleteInstanceProfileRequest;
@@ -32,17 +33,22 @@ use crate::http::aws::iam::types::list_instance_profiles_for_role::ListInstanceP
 use crate::http::aws::iam::types::remove_role_from_instance_profile::RemoveRoleFromInstanceProfileRequest;
 use crate::http::aws::iam::types::tag_instance_profile::TagInstanceProfileRequest;
 use crate::http::aws::iam::types::untag_instance_profile::UntagInstanceProfileRequest;
-use crate::http::aws::iam::{constants, db};
+use crate::http::aws::iam::{constants, db, quotas};
 
 pub(crate) async fn create_instance_profile<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &CreateInstanceProfileRequest,
 ) -> Result<CreateInstanceProfileOutput, ActionError> {
     input.validate("$")?;
+    let instance_profile_name = input.instance_profile_name().unwrap();
+    super::common::check_not_exists(
+        db::instance_profile::find_id_by_name(tx.as_mut(), ctx.account_id, instance_profile_name).await?,
+        format!("Instance Profile {instance_profile_name} already exists.").as_str(),
+    )?;
+    quotas::check_account(tx, ctx.account_id, Quota::InstanceProfiles).await?;
 
     let current_time = Utc::now().timestamp();
 
     let path = input.path().unwrap_or("/");
-    let instance_profile_name = input.instance_profile_name().unwrap();
     let arn = format!("arn:aws:iam::{:0>12}:instance-profile{}{}", ctx.account_id, path, instance_profile_name);
     let instance_profile_id =
         create_resource_id(tx, constants::instance_profile::PREFIX, ResourceType::InstanceProfile).await?;
@@ -180,6 +186,20 @@ pub(crate) async fn remove_role_from_instance_profile<'a>(
 ) -> Result<RemoveRoleFromInstanceProfileOutput, ActionError> {
     input.validate("$")?;
 
+    let instance_profile_name = input.instance_profile_name().unwrap().trim();
+    let role_name = input.role_name().unwrap().trim();
+    let instance_profile_id = find_id_by_name(ctx, tx.as_mut(), instance_profile_name).await?;
+    let role_id = super::role::find_id_by_name(tx.as_mut(), ctx.account_id, role_name).await?;
+
+    let is_removed =
+        db::instance_profile::remove_role_from_instance_profile(tx.as_mut(), instance_profile_id, role_id).await?;
+    if !is_removed {
+        return Err(ActionError::new(
+            ApiErrorKind::NoSuchEntity,
+            format!("Role {role_name} in Instance Profile {instance_profile_name} cannot be found.").as_str(),
+        ));
+    }
+
     let output = RemoveRoleFromInstanceProfileOutput::builder().build();
     Ok(output)
 }
@@ -189,6 +209,18 @@ pub(crate) async fn delete_instance_profile<'a>(
 ) -> Result<DeleteInstanceProfileOutput, ActionError> {
     input.validate("$")?;
 
+    let instance_profile_id = find_id_by_name(ctx, tx.as_mut(), input.instance_profile_name().unwrap().trim()).await?;
+
+    check_delete_conflict(
+        db::instance_profile::count_roles(tx.as_mut(), instance_profile_id).await?,
+        "Cannot delete entity, must remove roles from instance profile first.",
+    )?;
+
+    db::Tags::InstanceProfile
+        .delete_by_parent_id(tx, instance_profile_id)
+        .await?;
+    db::instance_profile::delete(tx, instance_profile_id).await?;
+
     let output = DeleteInstanceProfileOutput::builder().build();
     Ok(output)
 }
@@ -219,19 +251,7 @@ pub(crate) async fn list_instance_profiles<'a>(
             None => break,
             Some(select_profile) => {
                 let select_roles = db::instance_profile::list_roles(tx.as_mut(), select_profile.id).await?;
-                let mut roles = vec![];
-                for select_role in select_roles {
-                    let role = Role::builder()
-                        .arn(&select_role.arn)
-                        .create_date(DateTime::from_secs(select_role.create_date))
-                        .path(&select_role.path)
-                        .assume_role_policy_document(&select_role.assume_role_policy_document)
-                        .role_name(&select_role.role_name)
-                        .role_id(&select_role.role_id)
-                        .build()
-                        .unwrap();
-                    roles.push(role);
-                }
+                let roles: Vec<Role> = select_roles.iter().map(|role| role.into()).collect();
                 let profile = InstanceProfile::builder()
                     .path(&select_profile.path)
                     .set_roles(Some(roles))
@@ -8,10 +8,13 @@ use aws_smithy_types::DateTime;
 use chrono::Utc;
 use sqlx::{Sqlite, Transaction};
 
-use local_cloud_validate::NamedValidator;
+use local_cloud_validate::{NamedValidator, ValidationError, ValidationErrorKind};
 
+use crate::http::aws::iam::actions::error::ApiErrorKind;
+use crate::http::aws::iam::constants;
 use crate::http::aws::iam::db;
-use crate::http::aws::iam::db::types::login_profile::InsertLoginProfile;
+use crate::http::aws::iam::db::types::login_profile::{InsertLoginProfile, SelectLoginProfile, UpdateLoginProfile};
+use crate::http::aws::iam::db::types::user::SelectUser;
 use crate::http::aws::iam::operations::ctx::OperationCtx;
 use crate::http::aws::iam::operations::error::ActionError;
 use crate::http::aws::iam::types::change_password::ChangePasswordRequest;
@@ -26,8 +29,11 @@ pub(crate) async fn create_login_profile<'a>(
     input.validate("$")?;
     let found_user = super::user::find_by_name(ctx, tx.as_mut(), input.user_name().unwrap().trim()).await?;
 
+    let password = input.password().unwrap();
+    super::account_password_policy::check_password(tx, ctx, found_user.id, password).await?;
+
     let current_time = Utc::now().timestamp_millis();
-    let password_hash = super::secure::password_hash(input.password().unwrap())?;
+    let password_hash = super::secure::password_hash(password)?;
     let password_reset_required = input.password_reset_required().unwrap_or(false);
     let mut insert_login_profile = InsertLoginProfile {
         id: None,
@@ -37,6 +43,7 @@ pub(crate) async fn create_login_profile<'a>(
         create_date: current_time,
     };
     db::login_profile::create(tx, &mut insert_login_profile).await?;
+    db::password_history::create(tx.as_mut(), found_user.id, &insert_login_profile.password_hash, current_time).await?;
     let login_profile = LoginProfile::builder()
         .create_date(DateTime::from_millis(current_time))
         .user_name(&found_user.username)
@@ -49,34 +56,152 @@ pub(crate) async fn create_login_profile<'a>(
 }
 
 pub(crate) async fn change_password<'a>(
-    tx: &mut Transaction<'a, Sqlite>, _ctx: &OperationCtx, input: &ChangePasswordRequest,
+    tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &ChangePasswordRequest,
 ) -> Result<ChangePasswordOutput, ActionError> {
     input.validate("$")?;
-    // TODO:
+
+    let user = find_caller_user(tx, ctx).await?;
+    let password_policy = db::password_policy::find_by_account_id(tx.as_mut(), ctx.account_id).await?;
+    if password_policy
+        .as_ref()
+        .is_some_and(|policy| !policy.allow_users_to_change_password)
+    {
+        return Err(ActionError::new(
+            ApiErrorKind::AccessDenied,
+            "The account password policy does not allow users to change their own password.",
+        ));
+    }
+    let login_profile = find_login_profile(tx, user.id, &user.username).await?;
+    if !super::secure::verify_password(input.old_password().unwrap(), &login_profile.password_hash) {
+        return Err(ActionError::new(
+            ApiErrorKind::EntityTemporarilyUnmodifiable,
+            "The old password provided is incorrect.",
+        ));
+    }
+
+    let current_time = Utc::now().timestamp_millis();
+    if let Some(policy) = password_policy {
+        if policy.hard_expiry && policy.is_expired(login_profile.password_change_date, current_time) {
+            return Err(ActionError::from(ValidationError::new(
+                ValidationErrorKind::Password,
+                "Password has expired and can only be reset by an administrator.",
+            )));
+        }
+    }
+    let new_password = input.new_password().unwrap();
+    super::account_password_policy::check_password(tx, ctx, user.id, new_password).await?;
+
+    let password_hash = super::secure::password_hash(new_password)?;
+    let update_login_profile = UpdateLoginProfile {
+        user_id: user.id,
+        password_hash: Some(password_hash),
+        password_change_date: Some(current_time),
+        password_reset_required: Some(false),
+    };
+    db::login_profile::update(tx.as_mut(), &update_login_profile).await?;
+    db::password_history::create(
+        tx.as_mut(),
+        user.id,
+        update_login_profile.password_hash.as_ref().unwrap(),
+        current_time,
+    )
+    .await?;
+
     let output = ChangePasswordOutput::builder().build();
     Ok(output)
 }
 
 pub(crate) async fn update_login_profile<'a>(
-    tx: &mut Transaction<'a, Sqlite>, _ctx: &OperationCtx, input: &UpdateLoginProfileRequest,
+    tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &UpdateLoginProfileRequest,
 ) -> Result<UpdateLoginProfileOutput, ActionError> {
     input.validate("$")?;
+    let found_user = super::user::find_by_name(ctx, tx.as_mut(), input.user_name().unwrap().trim()).await?;
+
+    find_login_profile(tx, found_user.id, &found_user.username).await?;
+
+    let current_time = Utc::now().timestamp_millis();
+    let password_hash = match input.password() {
+        Some(password) => {
+            super::account_password_policy::check_password(tx, ctx, found_user.id, password).await?;
+            Some(super::secure::password_hash(password)?)
+        }
+        None => None,
+    };
+    let update_login_profile = UpdateLoginProfile {
+        user_id: found_user.id,
+        password_change_date: password_hash.as_ref().map(|_| current_time),
+        password_hash,
+        password_reset_required: input.password_reset_required(),
+    };
+    db::login_profile::update(tx.as_mut(), &update_login_profile).await?;
+    if let Some(password_hash) = &update_login_profile.password_hash {
+        db::password_history::create(tx.as_mut(), found_user.id, password_hash, current_time).await?;
+    }
+
     let output = UpdateLoginProfileOutput::builder().build();
     Ok(output)
 }
 
 pub(crate) async fn get_login_profile<'a>(
-    tx: &mut Transaction<'a, Sqlite>, _ctx: &OperationCtx, input: &GetLoginProfileRequest,
+    tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &GetLoginProfileRequest,
 ) -> Result<GetLoginProfileOutput, ActionError> {
     input.validate("$")?;
-    let output = GetLoginProfileOutput::builder().build();
+    let found_user = super::user::find_by_name(ctx, tx.as_mut(), input.user_name().unwrap().trim()).await?;
+    let login_profile = find_login_profile(tx, found_user.id, &found_user.username).await?;
+
+    let login_profile = LoginProfile::builder()
+        .create_date(DateTime::from_millis(login_profile.create_date))
+        .user_name(&found_user.username)
+        .password_reset_required(login_profile.password_reset_required)
+        .build()
+        .unwrap();
+
+    let output = GetLoginProfileOutput::builder().login_profile(login_profile).build();
     Ok(output)
 }
 
 pub(crate) async fn delete_login_profile<'a>(
-    tx: &mut Transaction<'a, Sqlite>, _ctx: &OperationCtx, input: &DeleteLoginProfileRequest,
+    tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &DeleteLoginProfileRequest,
 ) -> Result<DeleteLoginProfileOutput, ActionError> {
     input.validate("$")?;
+    let found_user = super::user::find_by_name(ctx, tx.as_mut(), input.user_name().unwrap().trim()).await?;
+
+    let is_deleted = db::login_profile::delete_by_user_id(tx.as_mut(), found_user.id).await?;
+    if !is_deleted {
+        return Err(login_profile_not_found(&found_user.username));
+    }
+
     let output = DeleteLoginProfileOutput::builder().build();
     Ok(output)
 }
+
+/// Finds IAM user signing the request. Only IAM users (except the `Root` user) are able to change their own password.
+async fn find_caller_user<'a>(tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx) -> Result<SelectUser, ActionError> {
+    let invalid_user_type =
+        || ActionError::new(ApiErrorKind::InvalidUserType, "Only IAM users can change their own password.");
+    let principal = match &ctx.caller.principal {
+        Some(principal) if principal.arn.contains(":user/") => principal,
+        _ => return Err(invalid_user_type()),
+    };
+    match db::user::find_by_unique_id(tx.as_mut(), &principal.id).await? {
+        Some(user) if user.user_id == constants::user::ROOT_USER_ID => Err(invalid_user_type()),
+        Some(user) => Ok(user),
+        None => Err(ActionError::new(ApiErrorKind::NoSuchEntity, "IAM user of the caller doesn't exist.")),
+    }
+}
+
+async fn find_login_profile<'a>(
+    tx: &mut Transaction<'a, Sqlite>, user_id: i64, user_name: &str,
+) -> Result<SelectLoginProfile, ActionError> {
+    match db::login_profile::find_by_user_id(tx.as_mut(), user_id).await? {
+        Some(login_profile) => Ok(login_profile),
+        None => Err(login_profile_not_found(user_name)),
+    }
+}
+
+fn login_profile_not_found(user_name: &str) -> ActionError {
+    ActionError::new(
+        ApiErrorKind::NoSuchEntity,
+        format!("Login Profile for User {user_name} cannot be found.").as_str(),
+    )
+}
@@ -14,7 +14,7 @@ use aws_sdk_iam::operation::untag_mfa_device::UntagMfaDeviceOutput;
 use aws_sdk_iam::types::VirtualMfaDevice;
 use aws_smithy_types::{Blob, DateTime};
 use chrono::Utc;
-use data_encoding::BASE64;
+use data_encoding::BASE32_NOPAD;
 use image::{ImageOutputFormat, Luma};
 use qrcode::QrCode;
 use sqlx::{Executor, Sqlite, Transaction};
@@ -23,7 +23,7 @@ use local_cloud_validate::NamedValidator;
 
 use crate::http::aws::iam::actions::error::ApiErrorKind;
 use crate::http::aws::iam::db::types::mfa_device::{
-    EnableMfaDeviceQuery, InsertMfaDevice, ListVirtualMfaDevicesQuery, SelectMfaDevice,
+    EnableMfaDeviceQuery, InsertMfaDevice, ListMfaDevicesQuery, ListVirtualMfaDevicesQuery, SelectMfaDevice,
 };
 use crate::http::aws::iam::db::types::tags::ListTagsQuery;
 use crate::http::aws::iam::operations::ctx::OperationCtx;
@@ -41,6 +41,50 @@ use crate::http::aws::iam::types::tag_mfa_device::TagMfaDeviceRequest;
 use crate::http::aws::iam::types::untag_mfa_device::UntagMfaDeviceRequest;
 use crate::http::aws::iam::{constants, db};
 
+fn invalid_authentication_code() -> ActionError {
+    ActionError::new(ApiErrorKind::InvalidAuthenticationCode, "Authentication code for device is not valid.")
+}
+
+/// Verifies two consecutive codes generated by the device and returns the clock drift of the device in time steps.
+/// Codes are looked up in `window_steps` time steps around the current time of the device.
+fn verify_consecutive_codes(
+    mfa_device: &SelectMfaDevice, code1: &str, code2: &str, window_steps: i64,
+) -> Result<i64, ActionError> {
+    let current_step = local_cloud_common::totp::time_step(Utc::now().timestamp());
+    let device_step = current_step + mfa_device.time_offset;
+    let second_code_step =
+        local_cloud_common::totp::verify_consecutive(&mfa_device.seed, code1, code2, device_step, window_steps)
+            .ok_or_else(invalid_authentication_code)?;
+    // the drift is measured from the step of the first code, so that the offset isn't shifted when codes are entered
+    // without waiting for a new code
+    Ok(second_code_step - 1 - current_step)
+}
+
+/// Creates the key URI which is used by authenticator apps to register a device, in the same format as AWS does:
+/// `otpauth://totp/<issuer>:<device name>@<account>?secret=<base32 seed>&issuer=<issuer>`.
+fn create_otpauth_uri(device_name: &str, account_name: &str, base32_seed: &str) -> String {
+    let issuer = urlencoding::encode(constants::mfa::ISSUER);
+    let label = urlencoding::encode(&format!("{device_name}@{account_name}")).into_owned();
+    format!("otpauth://totp/{issuer}:{label}?secret={base32_seed}&issuer={issuer}")
+}
+
+fn render_qr_code_png(otpauth_uri: &str) -> Result<Vec<u8>, ActionError> {
+    let qr_code_failure = || {
+        ActionError::new(
+            ApiErrorKind::ServiceFailure,
+            "Failed to generate QRCode image. Please contact service team for resolution.",
+        )
+    };
+
+    let code = QrCode::new(otpauth_uri).map_err(|_| qr_code_failure())?;
+    let image = code.render::<Luma<u8>>().build();
+    let mut image_bytes_cursor = Cursor::new(Vec::new());
+    image
+        .write_to(&mut image_bytes_cursor, ImageOutputFormat::Png)
+        .map_err(|_| qr_code_failure())?;
+    Ok(image_bytes_cursor.into_inner())
+}
+
 pub(crate) async fn create_virtual_mfa_device<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &CreateVirtualMfaDeviceRequest,
 ) -> Result<CreateVirtualMfaDeviceOutput, ActionError> {
@@ -80,28 +124,16 @@ pub(crate) async fn create_virtual_mfa_device<'a>(
     let mut device_tags = super::tag::prepare_for_db(input.tags(), insert_mfa_device.id.unwrap());
     db::Tags::MfaDevice.save_all(tx, &mut device_tags).await?;
 
-    // Using account ID since User is not available when we register a new MFA device.
-    let account_name = format!("{:0>12}", ctx.account_id);
-    let secret = BASE64.encode(&insert_mfa_device.seed);
-    let qr_code_str = format!("otpauth://totp/{device_name}@{account_name}?secret={secret}");
-
-    let code = QrCode::new(&qr_code_str).unwrap();
-    let image = code.render::<Luma<u8>>().build();
-    let mut image_bytes_cursor = Cursor::new(Vec::new());
-
-    image
-        .write_to(&mut image_bytes_cursor, ImageOutputFormat::Png)
-        .map_err(|_| {
-            ActionError::new(
-                ApiErrorKind::ServiceFailure,
-                "Failed to generate QRCode image. Please contact service team for resolution.",
-            )
-        })?;
+    // Using account alias or ID since User is not available when we register a new MFA device.
+    let account_name = super::account_alias::find_account_name(tx.as_mut(), ctx.account_id).await?;
+    let base32_seed = BASE32_NOPAD.encode(&insert_mfa_device.seed);
+    let otpauth_uri = create_otpauth_uri(device_name, &account_name, &base32_seed);
+    let qr_code_png = render_qr_code_png(&otpauth_uri)?;
 
     let device = VirtualMfaDevice::builder()
         .serial_number(&arn)
-        .base32_string_seed(Blob::new(insert_mfa_device.seed.as_slice()))
-        .qr_code_png(Blob::new(image_bytes_cursor.into_inner()))
+        .base32_string_seed(Blob::new(base32_seed.into_bytes()))
+        .qr_code_png(Blob::new(qr_code_png))
         .set_tags(super::tag::prepare_for_output(&device_tags))
         .build()
         .unwrap();
@@ -193,12 +225,18 @@ pub(crate) async fn enable_mfa_device<'a>(
         return Err(ActionError::new(ApiErrorKind::EntityAlreadyExists, "MFA device is already activated."));
     }
 
+    let code1 = input.authentication_code_1().unwrap();
+    let code2 = input.authentication_code_2().unwrap();
+    let clock_skew_steps = ctx.settings.mfa_clock_skew_steps as i64;
+    let time_offset = verify_consecutive_codes(&mfa_device, code1, code2, clock_skew_steps)?;
+
     let query = EnableMfaDeviceQuery {
         id: mfa_device.id,
         enable_date: current_time,
         user_id,
-        code1: input.authentication_code_1().unwrap().to_owned(),
-        code2: input.authentication_code_2().unwrap().to_owned(),
+        code1: code1.to_owned(),
+        code2: code2.to_owned(),
+        time_offset,
     };
 
     db::mfa_device::enable(tx, &query).await?;
@@ -315,8 +353,20 @@ pub(crate) async fn list_mfa_devices<'a>(
 ) -> Result<ListMfaDevicesOutput, ActionError> {
     input.validate("$")?;
 
-    let output = ListMfaDevicesOutput::builder().build().unwrap();
+    let user_id = super::user::find_id_by_name_or_caller(ctx, tx.as_mut(), input.user_name()).await?;
+
+    let query = ListMfaDevicesQuery::new(user_id, input.max_items(), input.marker_type());
+    let found_mfa_devices = db::mfa_device::list_by_user_id(tx.as_mut(), &query).await?;
+
+    let mfa_devices = super::common::convert_and_limit(&found_mfa_devices, query.limit).unwrap_or_default();
+    let marker = super::common::create_encoded_marker(&query, found_mfa_devices.len())?;
 
+    let output = ListMfaDevicesOutput::builder()
+        .set_mfa_devices(Some(mfa_devices))
+        .set_is_truncated(marker.as_ref().map(|_v| true))
+        .set_marker(marker)
+        .build()
+        .unwrap();
     Ok(output)
 }
 
@@ -325,6 +375,26 @@ pub(crate) async fn resync_mfa_device<'a>(
 ) -> Result<ResyncMfaDeviceOutput, ActionError> {
     input.validate("$")?;
 
+    let user_name = input.user_name().unwrap();
+    super::user::find_id_by_name(tx.as_mut(), ctx.account_id, user_name).await?;
+
+    let serial_number = input.serial_number().unwrap().trim();
+    let mfa_device = find_by_serial_number(tx.as_mut(), ctx.account_id, serial_number, Some(user_name)).await?;
+    if mfa_device.enable_date.is_none() {
+        return Err(ActionError::new(
+            ApiErrorKind::NoSuchEntity,
+            format!("IAM MFA device with serial number '{}' is not enabled.", serial_number).as_str(),
+        ));
+    }
+
+    let code1 = input.authentication_code_1().unwrap();
+    let code2 = input.authentication_code_2().unwrap();
+    // the resync window is never narrower than the configured clock skew
+    let window_steps = constants::mfa::RESYNC_WINDOW_STEPS.max(ctx.settings.mfa_clock_skew_steps as i64);
+    let time_offset = verify_consecutive_codes(&mfa_device, code1, code2, window_steps)?;
+
+    db::mfa_device::resync(tx, mfa_device.id, code1, code2, time_offset).await?;
+
     let output = ResyncMfaDeviceOutput::builder().build();
     Ok(output)
 }
@@ -334,6 +404,29 @@ pub(crate) async fn delete_virtual_mfa_device<'a>(
 ) -> Result<DeleteVirtualMfaDeviceOutput, ActionError> {
     input.validate("$")?;
 
+    let serial_number = input.serial_number().unwrap().trim();
+    let mfa_device = find_by_serial_number(tx.as_mut(), ctx.account_id, serial_number, None).await?;
+    if mfa_device.user_id.is_some() {
+        return Err(ActionError::new(
+            ApiErrorKind::DeleteConflict,
+            "MFA device is enabled for a user, it must be deactivated first.",
+        ));
+    }
+
+    db::Tags::MfaDevice.delete_by_parent_id(tx, mfa_device.id).await?;
+    db::mfa_device::delete(tx, mfa_device.id).await?;
+
     let output = DeleteVirtualMfaDeviceOutput::builder().build();
     Ok(output)
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_create_otpauth_uri() {
+        assert_eq!(
+            super::create_otpauth_uri("TestDevice", "000000000001", "JBSWY3DPEHPK3PXP"),
+            "otpauth://totp/Amazon%20Web%20Services:TestDevice%40000000000001?secret=JBSWY3DPEHPK3PXP&issuer=Amazon%20Web%20Services"
+        );
+    }
+}
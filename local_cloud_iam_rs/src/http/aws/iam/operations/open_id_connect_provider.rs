@@ -18,6 +18,7 @@ use crate::http::aws::iam::db::types::open_id_connect_provider::InsertOpenIdConn
 use crate::http::aws::iam::db::types::tags::ListTagsQuery;
 use crate::http::aws::iam::operations::ctx::OperationCtx;
 use crate::http::aws::iam::operations::error::ActionError;
+use crate::http::aws::iam::quotas::Quota;
 use crate::http::aws::iam::types::add_client_id_to_open_id_connect_provider::AddClientIdToOpenIdConnectProviderRequest;
 use crate::http::aws::iam::types::create_open_id_connect_provider::CreateOpenIdConnectProviderRequest;
 use crate::http::aws::iam::types::delete_open_id_connect_provider::DeleteOpenIdConnectProviderRequest;
@@ -28,7 +29,7 @@ use crate::http::aws::iam::types::remove_client_id_from_open_id_connect_provider
 use crate::http::aws::iam::types::tag_open_id_connect_provider::TagOpenIdConnectProviderRequest;
 use crate::http::aws::iam::types::untag_open_id_connect_provider::UntagOpenIdConnectProviderRequest;
 use crate::http::aws::iam::types::update_open_id_connect_provider_thumbprint::UpdateOpenIdConnectProviderThumbprintRequest;
-use crate::http::aws::iam::{constants, db};
+use crate::http::aws::iam::{constants, db, quotas};
 
 pub(crate) async fn add_client_id_to_open_id_connect_provider<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &AddClientIdToOpenIdConnectProviderRequest,
@@ -72,6 +73,11 @@ pub(crate) async fn create_open_id_connect_provider<'a>(
             .strip_prefix(constants::open_id_connect_provider::URL_PREFIX)
             .unwrap()
     );
+    super::common::check_not_exists(
+        db::open_id_connect_provider::find_id_by_arn(tx.as_mut(), ctx.account_id, &arn).await?,
+        format!("Provider with url {} already exists.", input.url().unwrap()).as_str(),
+    )?;
+    quotas::check_account(tx, ctx.account_id, Quota::Providers).await?;
 
     let mut insert_provider = InsertOpenIdConnectProvider {
         id: None,
@@ -188,6 +194,14 @@ pub(crate) async fn delete_open_id_connect_provider<'a>(
 ) -> Result<DeleteOpenIdConnectProviderOutput, ActionError> {
     input.validate("$")?;
 
+    let provider_arn = input.open_id_connect_provider_arn().unwrap().trim();
+    let provider_id = find_id_by_arn(tx.as_mut(), ctx.account_id, provider_arn).await?;
+
+    db::Tags::OpenIdConnectProvider
+        .delete_by_parent_id(tx, provider_id)
+        .await?;
+    db::open_id_connect_provider::delete(tx, provider_id).await?;
+
     let output = DeleteOpenIdConnectProviderOutput::builder().build();
     Ok(output)
 }
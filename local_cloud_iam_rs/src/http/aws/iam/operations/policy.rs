@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use aws_sdk_iam::operation::create_policy::CreatePolicyOutput;
 use aws_sdk_iam::operation::create_policy_version::CreatePolicyVersionOutput;
 use aws_sdk_iam::operation::delete_policy::DeletePolicyOutput;
@@ -19,25 +21,32 @@ use aws_smithy_types::DateTime;
 use chrono::Utc;
 use sqlx::{Executor, Sqlite, Transaction};
 
+use local_cloud_iam_policy_document::evaluator::PolicyKind;
+use local_cloud_iam_policy_document::types::{LocalPolicyDocument, PolicyDocumentKind};
 use local_cloud_validate::NamedValidator;
 
 use crate::http::aws::iam::actions::error::ApiErrorKind;
 use crate::http::aws::iam::db::types::policy::{
-    InsertPolicy, InsertPolicyBuilder, InsertPolicyBuilderError, SelectPolicy,
+    InsertPolicy, InsertPolicyBuilder, InsertPolicyBuilderError, ListEntitiesForPolicyQuery, PolicyEntityType,
+    SelectPolicy,
 };
 use crate::http::aws::iam::db::types::policy_type::PolicyType;
 use crate::http::aws::iam::db::types::policy_version::{
     InsertPolicyVersion, InsertPolicyVersionBuilder, InsertPolicyVersionBuilderError, ListPolicyVersionsQuery,
+    SelectPolicyVersion,
 };
 use crate::http::aws::iam::db::types::resource_identifier::ResourceType;
 use crate::http::aws::iam::db::types::tags::ListTagsQuery;
-use crate::http::aws::iam::operations::common::create_resource_id;
+use crate::http::aws::iam::operations::common::{check_delete_conflict, create_resource_id};
 use crate::http::aws::iam::operations::ctx::OperationCtx;
 use crate::http::aws::iam::operations::error::ActionError;
+use crate::http::aws::iam::operations::simulate::SimulationPolicy;
+use crate::http::aws::iam::quotas::Quota;
 use crate::http::aws::iam::types::create_policy::CreatePolicyRequest;
 use crate::http::aws::iam::types::create_policy_version::CreatePolicyVersionRequest;
 use crate::http::aws::iam::types::delete_policy::DeletePolicyRequest;
 use crate::http::aws::iam::types::delete_policy_version::DeletePolicyVersionRequest;
+use crate::http::aws::iam::types::entity_type::EntityType;
 use crate::http::aws::iam::types::get_context_keys_for_custom_policy::GetContextKeysForCustomPolicyRequest;
 use crate::http::aws::iam::types::get_context_keys_for_principal_policy::GetContextKeysForPrincipalPolicyRequest;
 use crate::http::aws::iam::types::get_policy::GetPolicyRequest;
@@ -47,10 +56,11 @@ use crate::http::aws::iam::types::list_policies::ListPoliciesRequest;
 use crate::http::aws::iam::types::list_policies_granting_service_access::ListPoliciesGrantingServiceAccessRequest;
 use crate::http::aws::iam::types::list_policy_tags::ListPolicyTagsRequest;
 use crate::http::aws::iam::types::list_policy_versions::ListPolicyVersionsRequest;
+use crate::http::aws::iam::types::policy_usage_type::PolicyUsageType;
 use crate::http::aws::iam::types::set_default_policy_version::SetDefaultPolicyVersionRequest;
 use crate::http::aws::iam::types::tag_policy::TagPolicyRequest;
 use crate::http::aws::iam::types::untag_policy::UntagPolicyRequest;
-use crate::http::aws::iam::{constants, db};
+use crate::http::aws::iam::{constants, db, quotas};
 
 pub(crate) async fn find_id_by_arn<'a, E>(executor: E, account_id: i64, arn: &str) -> Result<i64, ActionError>
 where
@@ -82,11 +92,32 @@ where
     }
 }
 
+/// AWS managed policies are shared by all accounts, so they can't be changed by any of them.
+fn check_customer_managed(policy_arn: &str) -> Result<(), ActionError> {
+    if policy_arn.starts_with(constants::policy::AWS_MANAGED_ARN_PREFIX) {
+        return Err(ActionError::new(
+            ApiErrorKind::AccessDenied,
+            format!("Policy {policy_arn} is an AWS managed policy and can't be modified.").as_str(),
+        ));
+    }
+    Ok(())
+}
+
 pub(crate) async fn create_policy<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &CreatePolicyRequest,
 ) -> Result<CreatePolicyOutput, ActionError> {
     // validate
     input.validate("$")?;
+    input
+        .policy_document_type()
+        .unwrap()
+        .validate_as(PolicyDocumentKind::Identity)?;
+    let policy_name = input.policy_name().unwrap().trim();
+    super::common::check_not_exists(
+        db::policy::find_id_by_name(tx.as_mut(), ctx.account_id, policy_name).await?,
+        format!("A policy called {policy_name} already exists. Duplicate names are not allowed.").as_str(),
+    )?;
+    quotas::check_account(tx, ctx.account_id, Quota::Policies).await?;
     let policy_document = input.policy_document().unwrap();
 
     let policy_id = create_resource_id(tx, constants::policy::PREFIX, ResourceType::Policy).await?;
@@ -136,7 +167,12 @@ pub(crate) async fn create_policy_version<'a>(
 ) -> Result<CreatePolicyVersionOutput, ActionError> {
     // validate
     input.validate("$")?;
+    input
+        .policy_document_type()
+        .unwrap()
+        .validate_as(PolicyDocumentKind::Identity)?;
     let policy_document = input.policy_document().unwrap();
+    check_customer_managed(input.policy_arn().unwrap().trim())?;
 
     let policy_id = db::policy::find_id_by_arn(tx.as_mut(), ctx.account_id, input.policy_arn().unwrap()).await?;
     if policy_id.is_none() {
@@ -151,24 +187,19 @@ pub(crate) async fn create_policy_version<'a>(
 
     // check whether new policy version should be set as default. True by default
     let set_as_default = input.set_as_default().unwrap_or(true);
-    if set_as_default {
-        // find and disable previous default policy version
-        db::policy_version::disable_default_by_policy_id(tx, policy_id).await?;
-    }
 
     let current_time = Utc::now().timestamp();
     let policy_version_id =
         create_resource_id(tx, constants::policy_version::PREFIX, ResourceType::PolicyVersion).await?;
-    let mut insert_policy_version = prepare_policy_version_for_insert(
-        ctx,
-        policy_document,
-        policy_id,
-        policy_version_id,
-        set_as_default,
-        current_time,
-    )
-    .map_err(|err| ActionError::new(ApiErrorKind::ServiceFailure, err.to_string().as_str()))?;
+    let mut insert_policy_version =
+        prepare_policy_version_for_insert(ctx, policy_document, policy_id, policy_version_id, false, current_time)
+            .map_err(|err| ActionError::new(ApiErrorKind::ServiceFailure, err.to_string().as_str()))?;
     db::policy_version::create(tx, &mut insert_policy_version).await?;
+    if set_as_default {
+        // the previous default version is switched off by the same statement
+        db::policy_version::set_default(tx.as_mut(), policy_id, insert_policy_version.id.unwrap()).await?;
+    }
+    db::policy::refresh_update_date(tx.as_mut(), policy_id).await?;
 
     let policy_version = PolicyVersion::builder()
         .is_default_version(set_as_default)
@@ -218,13 +249,13 @@ pub(crate) async fn list_policy_versions<'a>(
 
 async fn check_policy_version_count<'a>(tx: &mut Transaction<'a, Sqlite>, policy_id: i64) -> Result<(), ActionError> {
     let policy_version_count = db::policy_version::count_by_policy_id(tx, policy_id).await?;
-    if policy_version_count >= constants::policy_version::POLICY_VERSION_MAX_COUNT {
+    let versions_per_policy = quotas::find(tx, Quota::VersionsPerPolicy).await?;
+    if policy_version_count >= versions_per_policy.max(0) as usize {
         return Err(ActionError::new(
             ApiErrorKind::LimitExceeded,
             format!(
                 "Number of Policy Versions cannot be greater than '{}'. Actual count: '{}'.",
-                constants::policy_version::POLICY_VERSION_MAX_COUNT,
-                policy_version_count
+                versions_per_policy, policy_version_count
             )
             .as_str(),
         ));
@@ -278,6 +309,7 @@ pub(crate) async fn tag_policy<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &TagPolicyRequest,
 ) -> Result<TagPolicyOutput, ActionError> {
     input.validate("$")?;
+    check_customer_managed(input.policy_arn().unwrap().trim())?;
 
     let policy_id = find_id_by_arn(tx.as_mut(), ctx.account_id, input.policy_arn().unwrap().trim()).await?;
     let mut policy_tags = super::tag::prepare_for_db(input.tags(), policy_id);
@@ -338,6 +370,7 @@ pub(crate) async fn untag_policy<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &UntagPolicyRequest,
 ) -> Result<UntagPolicyOutput, ActionError> {
     input.validate("$")?;
+    check_customer_managed(input.policy_arn().unwrap().trim())?;
 
     let policy_id = find_id_by_arn(tx.as_mut(), ctx.account_id, input.policy_arn().unwrap().trim()).await?;
 
@@ -400,6 +433,12 @@ pub(crate) async fn set_default_policy_version<'a>(
 ) -> Result<SetDefaultPolicyVersionOutput, ActionError> {
     input.validate("$")?;
 
+    let policy_arn = input.policy_arn().unwrap().trim();
+    check_customer_managed(policy_arn)?;
+    let policy_version = find_policy_version(tx, ctx, policy_arn, input.version_id().unwrap().trim()).await?;
+
+    db::policy_version::set_default(tx.as_mut(), policy_version.policy_id, policy_version.id).await?;
+
     let output = SetDefaultPolicyVersionOutput::builder().build();
     Ok(output)
 }
@@ -408,6 +447,29 @@ pub(crate) async fn delete_policy<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &DeletePolicyRequest,
 ) -> Result<DeletePolicyOutput, ActionError> {
     input.validate("$")?;
+    check_customer_managed(input.policy_arn().unwrap().trim())?;
+
+    let policy_id = find_id_by_arn(tx.as_mut(), ctx.account_id, input.policy_arn().unwrap().trim()).await?;
+
+    check_delete_conflict(
+        db::policy::count_attachments(tx.as_mut(), ctx.account_id, policy_id).await?,
+        "Cannot delete a policy attached to entities.",
+    )?;
+    check_delete_conflict(
+        db::policy::count_permissions_boundary_usages(tx.as_mut(), policy_id).await?,
+        "Cannot delete a policy attached to entities.",
+    )?;
+    // the default version is deleted together with the policy
+    check_delete_conflict(
+        db::policy_version::count_by_policy_id(tx, policy_id)
+            .await?
+            .saturating_sub(1),
+        "This policy has more than one version. Before you delete a policy, you must delete the policy's versions. \
+        The default version is deleted with the policy.",
+    )?;
+
+    db::Tags::Policy.delete_by_parent_id(tx, policy_id).await?;
+    db::policy::delete(tx, policy_id).await?;
 
     let output = DeletePolicyOutput::builder().build();
     Ok(output)
@@ -418,16 +480,31 @@ pub(crate) async fn delete_policy_version<'a>(
 ) -> Result<DeletePolicyVersionOutput, ActionError> {
     input.validate("$")?;
 
+    let policy_arn = input.policy_arn().unwrap().trim();
+    check_customer_managed(policy_arn)?;
+    let policy_version = find_policy_version(tx, ctx, policy_arn, input.version_id().unwrap().trim()).await?;
+    if policy_version.is_default {
+        return Err(ActionError::new(ApiErrorKind::DeleteConflict, "Cannot delete the default version of a policy."));
+    }
+
+    db::policy_version::delete(tx.as_mut(), policy_version.id).await?;
+    db::policy::refresh_update_date(tx.as_mut(), policy_version.policy_id).await?;
+
     let output = DeletePolicyVersionOutput::builder().build();
     Ok(output)
 }
 
 pub(crate) async fn get_context_keys_for_custom_policy<'a>(
-    tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &GetContextKeysForCustomPolicyRequest,
+    _tx: &mut Transaction<'a, Sqlite>, _ctx: &OperationCtx, input: &GetContextKeysForCustomPolicyRequest,
 ) -> Result<GetContextKeysForCustomPolicyOutput, ActionError> {
     input.validate("$")?;
 
-    let output = GetContextKeysForCustomPolicyOutput::builder().build();
+    let policies = super::simulate::input_policies(input.policy_input_list(), None);
+    let context_keys = collect_context_keys(&policies)?;
+
+    let output = GetContextKeysForCustomPolicyOutput::builder()
+        .set_context_key_names(Some(context_keys))
+        .build();
     Ok(output)
 }
 
@@ -436,16 +513,103 @@ pub(crate) async fn get_context_keys_for_principal_policy<'a>(
 ) -> Result<GetContextKeysForPrincipalPolicyOutput, ActionError> {
     input.validate("$")?;
 
-    let output = GetContextKeysForPrincipalPolicyOutput::builder().build();
+    let policy_source_arn = input.policy_source_arn().unwrap().trim();
+    let mut policies = super::simulate::find_principal_policies(tx, ctx, policy_source_arn).await?;
+    // only policies which grant permissions are taken into account
+    policies.retain(|policy| policy.kind == PolicyKind::Identity);
+    policies.extend(super::simulate::input_policies(input.policy_input_list(), None));
+    let context_keys = collect_context_keys(&policies)?;
+
+    let output = GetContextKeysForPrincipalPolicyOutput::builder()
+        .set_context_key_names(Some(context_keys))
+        .build();
     Ok(output)
 }
 
+/// Collects sorted and de-duplicated condition keys of all policies. Keys are case-insensitive, the first spelling
+/// of a key is returned.
+fn collect_context_keys(policies: &[SimulationPolicy]) -> Result<Vec<String>, ActionError> {
+    let mut context_keys = BTreeMap::new();
+    for policy in policies {
+        let document: LocalPolicyDocument = serde_json::from_str(&policy.document).map_err(|_err| {
+            ActionError::new(
+                ApiErrorKind::InvalidInput,
+                format!("Failed to parse policy document '{}'.", policy.id).as_str(),
+            )
+        })?;
+        for key in document.condition_keys() {
+            context_keys.entry(key.to_lowercase()).or_insert_with(|| key.to_owned());
+        }
+    }
+    Ok(context_keys.into_values().collect())
+}
+
 pub(crate) async fn list_entities_for_policy<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &ListEntitiesForPolicyRequest,
 ) -> Result<ListEntitiesForPolicyOutput, ActionError> {
     input.validate("$")?;
 
-    let output = ListEntitiesForPolicyOutput::builder().build();
+    let policy = find_by_arn(tx.as_mut(), ctx.account_id, input.policy_arn().unwrap().trim()).await?;
+
+    let entity_types = match input.entity_filter() {
+        None => vec![PolicyEntityType::User, PolicyEntityType::Group, PolicyEntityType::Role],
+        Some(EntityType::User) => vec![PolicyEntityType::User],
+        Some(EntityType::Group) => vec![PolicyEntityType::Group],
+        Some(EntityType::Role) => vec![PolicyEntityType::Role],
+        // policies are not attached to other policies, so the filter only checks the type of the policy itself
+        Some(EntityType::LocalManagedPolicy) => match policy.policy_type {
+            PolicyType::CustomerManaged => {
+                vec![PolicyEntityType::User, PolicyEntityType::Group, PolicyEntityType::Role]
+            }
+            PolicyType::LocalCloudManaged => vec![],
+        },
+        Some(EntityType::AwsManagedPolicy) => match policy.policy_type {
+            PolicyType::LocalCloudManaged => {
+                vec![PolicyEntityType::User, PolicyEntityType::Group, PolicyEntityType::Role]
+            }
+            PolicyType::CustomerManaged => vec![],
+        },
+    };
+    let usage_filter = input.policy_usage_filter();
+    let query = ListEntitiesForPolicyQuery {
+        account_id: ctx.account_id,
+        policy_id: policy.id,
+        path_prefix: input.path_prefix().unwrap_or("/").to_owned(),
+        entity_types,
+        include_permissions_policy: usage_filter != Some(&PolicyUsageType::PermissionsBoundary),
+        include_permissions_boundary: usage_filter != Some(&PolicyUsageType::PermissionsPolicy),
+        limit: match input.max_items() {
+            None => 10,
+            Some(v) => *v,
+        },
+        skip: match input.marker_type() {
+            None => 0,
+            // unwrap is safe since marker must be validated before DB query preparation
+            Some(marker_type) => marker_type.marker().unwrap().truncate_amount,
+        },
+    };
+
+    let found_entities = db::policy::list_entities(tx.as_mut(), &query).await?;
+    let marker = super::common::create_encoded_marker(&query, found_entities.len())?;
+
+    let mut policy_users = vec![];
+    let mut policy_groups = vec![];
+    let mut policy_roles = vec![];
+    for entity in found_entities.iter().take(query.limit as usize) {
+        match entity.entity_type {
+            PolicyEntityType::User => policy_users.push(entity.into()),
+            PolicyEntityType::Group => policy_groups.push(entity.into()),
+            PolicyEntityType::Role => policy_roles.push(entity.into()),
+        }
+    }
+
+    let output = ListEntitiesForPolicyOutput::builder()
+        .set_policy_users(Some(policy_users))
+        .set_policy_groups(Some(policy_groups))
+        .set_policy_roles(Some(policy_roles))
+        .set_is_truncated(marker.as_ref().map(|_v| true))
+        .set_marker(marker)
+        .build();
     Ok(output)
 }
 
@@ -457,3 +621,25 @@ pub(crate) async fn list_policies_granting_service_access<'a>(
     let output = ListPoliciesGrantingServiceAccessOutput::builder().build().unwrap();
     Ok(output)
 }
+
+async fn find_policy_version<'a>(
+    tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, policy_arn: &str, version_id: &str,
+) -> Result<SelectPolicyVersion, ActionError> {
+    let policy_version = match parse_version(version_id) {
+        Some(version) => {
+            db::policy_version::find_by_policy_arn_and_version(tx.as_mut(), ctx.account_id, policy_arn, version).await?
+        }
+        None => None,
+    };
+    policy_version.ok_or_else(|| {
+        ActionError::new(
+            ApiErrorKind::NoSuchEntity,
+            format!("Policy {policy_arn} version {version_id} does not exist or is not attachable.").as_str(),
+        )
+    })
+}
+
+/// Parses policy version identifier like `v2`.
+fn parse_version(version_id: &str) -> Option<u16> {
+    version_id.strip_prefix('v').and_then(|version| version.parse().ok())
+}
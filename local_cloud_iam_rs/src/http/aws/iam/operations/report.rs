@@ -2,35 +2,240 @@ use aws_sdk_iam::operation::generate_credential_report::GenerateCredentialReport
 use aws_sdk_iam::operation::generate_organizations_access_report::GenerateOrganizationsAccessReportOutput;
 use aws_sdk_iam::operation::get_credential_report::GetCredentialReportOutput;
 use aws_sdk_iam::operation::get_organizations_access_report::GetOrganizationsAccessReportOutput;
-use sqlx::{Sqlite, Transaction};
+use aws_sdk_iam::types::ReportFormatType;
+use aws_smithy_types::{Blob, DateTime};
+use chrono::{SecondsFormat, Utc};
+use sqlx::{Sqlite, SqliteConnection, Transaction};
 
 use local_cloud_validate::NamedValidator;
 
+use crate::http::aws::iam::actions::error::ApiErrorKind;
+use crate::http::aws::iam::db::types::credential_report::{
+    ReportStateType, SelectCredentialReport, SelectReportAccessKey, SelectReportCredential,
+};
+use crate::http::aws::iam::jobs::Job;
 use crate::http::aws::iam::operations::ctx::OperationCtx;
 use crate::http::aws::iam::operations::error::ActionError;
 use crate::http::aws::iam::types::generate_credential_report::GenerateCredentialReportRequest;
 use crate::http::aws::iam::types::generate_organizations_access_report::GenerateOrganizationsAccessReportRequest;
 use crate::http::aws::iam::types::get_credential_report::GetCredentialReportRequest;
 use crate::http::aws::iam::types::get_organizations_access_report::GetOrganizationsAccessReportRequest;
+use crate::http::aws::iam::{constants, db};
+
+const CREDENTIAL_REPORT_HEADER: &str = "user,arn,user_creation_time,password_enabled,password_last_used,\
+password_last_changed,password_next_rotation,mfa_active,access_key_1_active,access_key_1_last_rotated,\
+access_key_1_last_used_date,access_key_1_last_used_region,access_key_1_last_used_service,access_key_2_active,\
+access_key_2_last_rotated,access_key_2_last_used_date,access_key_2_last_used_region,access_key_2_last_used_service,\
+cert_1_active,cert_1_last_rotated,cert_2_active,cert_2_last_rotated";
 
 pub(crate) async fn get_credential_report<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &GetCredentialReportRequest,
 ) -> Result<GetCredentialReportOutput, ActionError> {
     input.validate("$")?;
 
-    let output = GetCredentialReportOutput::builder().build();
+    let report = match db::credential_report::find_by_account_id(tx.as_mut(), ctx.account_id).await? {
+        Some(report) => report,
+        None => {
+            return Err(ActionError::new(
+                ApiErrorKind::CredentialReportNotPresent,
+                "Credential report is not present. Use GenerateCredentialReport to generate a report.",
+            ))
+        }
+    };
+    let (content, generated_time) = match (report.state, report.content, report.generated_time) {
+        (ReportStateType::Complete, Some(content), Some(generated_time)) => (content, generated_time),
+        _ => {
+            return Err(ActionError::new(
+                ApiErrorKind::CredentialReportNotReady,
+                "Credential report is still being generated.",
+            ))
+        }
+    };
+    if is_expired(generated_time, Utc::now().timestamp()) {
+        return Err(ActionError::new(
+            ApiErrorKind::CredentialReportExpired,
+            "Credential report is expired. Use GenerateCredentialReport to generate a new report.",
+        ));
+    }
+
+    let output = GetCredentialReportOutput::builder()
+        .content(Blob::new(content))
+        .report_format(ReportFormatType::TextCsv)
+        .generated_time(DateTime::from_secs(generated_time))
+        .build();
     Ok(output)
 }
 
+/// Starts generation of a new credential report unless the current one is still being generated or is not expired.
+/// The report itself is generated asynchronously by the [job runner](crate::http::aws::iam::jobs).
 pub(crate) async fn generate_credential_report<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &GenerateCredentialReportRequest,
 ) -> Result<GenerateCredentialReportOutput, ActionError> {
     input.validate("$")?;
 
-    let output = GenerateCredentialReportOutput::builder().build();
+    let current_time = Utc::now().timestamp();
+    let report = db::credential_report::find_by_account_id(tx.as_mut(), ctx.account_id).await?;
+    let (state, description) = match report {
+        Some(report) if report.state != ReportStateType::Complete => {
+            (ReportStateType::InProgress, Some("Report generation task is in progress."))
+        }
+        Some(report) if !report.generated_time.is_some_and(|time| is_expired(time, current_time)) => {
+            (ReportStateType::Complete, None)
+        }
+        _ => {
+            db::credential_report::start(tx.as_mut(), ctx.account_id, current_time).await?;
+            (ReportStateType::Started, Some("No report exists. Starting a new report generation task."))
+        }
+    };
+
+    let output = GenerateCredentialReportOutput::builder()
+        .state((&state).into())
+        .set_description(description.map(|v| v.to_owned()))
+        .build();
     Ok(output)
 }
 
+/// Credential report which has been requested by [`generate_credential_report`]. A report which fails to be generated
+/// is discarded, so that a new one could be requested.
+impl Job for SelectCredentialReport {
+    const KIND: &'static str = "credential report";
+
+    fn id(&self) -> i64 {
+        self.id
+    }
+
+    async fn find_started(connection: &mut SqliteConnection) -> Result<Vec<Self>, sqlx::Error> {
+        db::credential_report::find_started(connection).await
+    }
+
+    async fn take(connection: &mut SqliteConnection, id: i64, start_date: i64) -> Result<bool, sqlx::Error> {
+        db::credential_report::mark_in_progress(connection, id, start_date).await
+    }
+
+    async fn run(&self, tx: &mut Transaction<'_, Sqlite>) -> Result<(), ActionError> {
+        let content = create_credential_report(tx, self.account_id).await?;
+        db::credential_report::complete(tx.as_mut(), self.id, content.as_bytes(), Utc::now().timestamp()).await?;
+        Ok(())
+    }
+
+    async fn fail(
+        connection: &mut SqliteConnection, id: i64, _error: &ActionError, _completion_date: i64,
+    ) -> Result<(), sqlx::Error> {
+        db::credential_report::discard(connection, id).await?;
+        Ok(())
+    }
+
+    async fn restart_stale(connection: &mut SqliteConnection, start_date: i64) -> Result<u64, sqlx::Error> {
+        db::credential_report::restart_stale(connection, start_date).await
+    }
+}
+
+fn is_expired(generated_time: i64, current_time: i64) -> bool {
+    current_time - generated_time > constants::report::CREDENTIAL_REPORT_EXPIRATION.as_secs() as i64
+}
+
+/// Builds the credential report of the account in the AWS CSV format.
+async fn create_credential_report<'a>(
+    tx: &mut Transaction<'a, Sqlite>, account_id: i64,
+) -> Result<String, ActionError> {
+    let users = db::credential_report::list_users(tx.as_mut(), account_id).await?;
+    let access_keys = db::credential_report::list_access_keys(tx.as_mut(), account_id).await?;
+    let certificates = db::credential_report::list_signing_certificates(tx.as_mut(), account_id).await?;
+    let password_policy = db::password_policy::find_by_account_id(tx.as_mut(), account_id).await?;
+
+    let mut report = String::from(CREDENTIAL_REPORT_HEADER);
+    report.push('\n');
+    for user in &users {
+        let is_root = user.user_id == constants::user::ROOT_USER_ID;
+        let mut row: Vec<String> = vec![];
+        if is_root {
+            row.push("<root_account>".to_owned());
+            row.push(format!("arn:aws:iam::{account_id:0>12}:root"));
+        } else {
+            row.push(user.username.to_owned());
+            row.push(user.arn.to_owned());
+        }
+        row.push(format_secs(user.create_date));
+
+        // console password
+        match (is_root, user.password_change_date) {
+            (true, _) => {
+                row.extend(["not_supported", "no_information", "not_supported", "not_supported"].map(String::from))
+            }
+            (false, None) => row.extend(["false", "N/A", "N/A", "N/A"].map(String::from)),
+            (false, Some(password_change_date)) => {
+                let next_rotation = match &password_policy {
+                    Some(policy) if policy.max_password_age > 0 => {
+                        format_millis(password_change_date + policy.max_password_age as i64 * 24 * 60 * 60 * 1000)
+                    }
+                    _ => "N/A".to_owned(),
+                };
+                row.push("true".to_owned());
+                row.push("no_information".to_owned());
+                row.push(format_millis(password_change_date));
+                row.push(next_rotation);
+            }
+        }
+        row.push(user.mfa_active.to_string());
+
+        let user_access_keys: Vec<&SelectReportAccessKey> = access_keys
+            .iter()
+            .filter(|key| key.user_id == user.id)
+            .take(2)
+            .collect();
+        for index in 0..2 {
+            match user_access_keys.get(index) {
+                Some(key) => {
+                    row.push(key.is_active.to_string());
+                    row.push(format_secs(key.create_date));
+                    row.push(key.last_used_date.map(format_secs).unwrap_or_else(|| "N/A".to_owned()));
+                    row.push(key.last_used_region.to_owned().unwrap_or_else(|| "N/A".to_owned()));
+                    row.push(key.last_used_service.to_owned().unwrap_or_else(|| "N/A".to_owned()));
+                }
+                None => row.extend(["false", "N/A", "N/A", "N/A", "N/A"].map(String::from)),
+            }
+        }
+
+        let user_certificates: Vec<&SelectReportCredential> = certificates
+            .iter()
+            .filter(|cert| cert.user_id == user.id)
+            .take(2)
+            .collect();
+        for index in 0..2 {
+            match user_certificates.get(index) {
+                Some(cert) => {
+                    row.push(cert.is_active.to_string());
+                    row.push(format_secs(cert.create_date));
+                }
+                None => row.extend(["false", "N/A"].map(String::from)),
+            }
+        }
+
+        let row: Vec<String> = row.iter().map(|value| escape_csv_value(value)).collect();
+        report.push_str(&row.join(","));
+        report.push('\n');
+    }
+    Ok(report)
+}
+
+fn format_secs(secs: i64) -> String {
+    chrono::DateTime::<Utc>::from_timestamp(secs, 0)
+        .map(|time| time.to_rfc3339_opts(SecondsFormat::Secs, false))
+        .unwrap_or_else(|| "N/A".to_owned())
+}
+
+fn format_millis(millis: i64) -> String {
+    format_secs(millis / 1000)
+}
+
+fn escape_csv_value(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
 pub(crate) async fn generate_organizations_access_report<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &GenerateOrganizationsAccessReportRequest,
 ) -> Result<GenerateOrganizationsAccessReportOutput, ActionError> {
@@ -22,18 +22,21 @@ use aws_smithy_types::DateTime;
 use chrono::Utc;
 use sqlx::{Executor, Sqlite, Transaction};
 
+use local_cloud_iam_policy_document::types::PolicyDocumentKind;
 use local_cloud_validate::NamedValidator;
 
 use crate::http::aws::iam::actions::error::ApiErrorKind;
 use crate::http::aws::iam::db::types::inline_policy::{DbInlinePolicy, ListInlinePoliciesQuery};
+use crate::http::aws::iam::db::types::policy::ListAttachedPoliciesQuery;
 use crate::http::aws::iam::db::types::resource_identifier::ResourceType;
 use crate::http::aws::iam::db::types::role::{
     InsertRole, InsertRoleBuilder, InsertRoleBuilderError, SelectRole, SelectRoleWithDetails,
 };
 use crate::http::aws::iam::db::types::tags::ListTagsQuery;
-use crate::http::aws::iam::operations::common::create_resource_id;
+use crate::http::aws::iam::operations::common::{check_delete_conflict, create_resource_id};
 use crate::http::aws::iam::operations::ctx::OperationCtx;
 use crate::http::aws::iam::operations::error::ActionError;
+use crate::http::aws::iam::quotas::Quota;
 use crate::http::aws::iam::types::attach_role_policy::AttachRolePolicyRequest;
 use crate::http::aws::iam::types::create_role::CreateRoleRequest;
 use crate::http::aws::iam::types::delete_role::DeleteRoleRequest;
@@ -53,12 +56,22 @@ use crate::http::aws::iam::types::untag_role::UntagRoleRequest;
 use crate::http::aws::iam::types::update_assume_role_policy::UpdateAssumeRolePolicyRequest;
 use crate::http::aws::iam::types::update_role::UpdateRoleRequest;
 use crate::http::aws::iam::types::update_role_description::UpdateRoleDescriptionRequest;
-use crate::http::aws::iam::{constants, db};
+use crate::http::aws::iam::{constants, db, quotas};
 
 pub async fn create_role<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &CreateRoleRequest,
 ) -> Result<CreateRoleOutput, ActionError> {
     input.validate("$")?;
+    input
+        .assume_role_policy_document_type()
+        .unwrap()
+        .validate_as(PolicyDocumentKind::Trust)?;
+    let role_name = input.role_name().unwrap().trim();
+    super::common::check_not_exists(
+        db::role::find_id_by_name(tx.as_mut(), ctx.account_id, role_name).await?,
+        format!("Role with name {role_name} already exists.").as_str(),
+    )?;
+    quotas::check_account(tx, ctx.account_id, Quota::Roles).await?;
 
     let current_time = Utc::now().timestamp();
 
@@ -160,11 +173,19 @@ pub(crate) async fn attach_role_policy<'a>(
 ) -> Result<AttachRolePolicyOutput, ActionError> {
     input.validate("$")?;
 
-    let found_role_id = find_id_by_name(tx.as_mut(), ctx.account_id, input.role_name().unwrap().trim()).await?;
+    let role = find_by_name(tx.as_mut(), ctx.account_id, input.role_name().unwrap().trim()).await?;
+    check_modifiable(&role)?;
+    let found_role_id = role.id;
     let policy_arn = input.policy_arn().unwrap();
     let found_policy_id = super::policy::find_id_by_arn(tx.as_mut(), ctx.account_id, policy_arn).await?;
 
-    db::role::assign_policy_to_role(tx, found_role_id, found_policy_id).await?;
+    // attaching the policy again is a no-op which is not limited by quotas
+    if !db::policy::is_attached_to_role(tx.as_mut(), found_role_id, found_policy_id).await? {
+        let attached_count = db::role::count_attached_policies(tx.as_mut(), found_role_id).await?;
+        quotas::check(tx, Quota::AttachedPoliciesPerRole, attached_count).await?;
+        quotas::check_account(tx, ctx.account_id, Quota::PolicyVersionsInUse).await?;
+        db::role::assign_policy_to_role(tx, found_role_id, found_policy_id).await?;
+    }
 
     let output = AttachRolePolicyOutput::builder().build();
     Ok(output)
@@ -295,11 +316,16 @@ pub(crate) async fn put_role_policy<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &PutRolePolicyRequest,
 ) -> Result<PutRolePolicyOutput, ActionError> {
     input.validate("$")?;
+    input
+        .policy_document_type()
+        .unwrap()
+        .validate_as(PolicyDocumentKind::Identity)?;
 
-    let role_id = find_id_by_name(tx.as_mut(), ctx.account_id, input.role_name().unwrap().trim()).await?;
+    let role = find_by_name(tx.as_mut(), ctx.account_id, input.role_name().unwrap().trim()).await?;
+    check_modifiable(&role)?;
 
     let mut inline_policy =
-        DbInlinePolicy::new(role_id, input.policy_name().unwrap(), input.policy_document().unwrap());
+        DbInlinePolicy::new(role.id, input.policy_name().unwrap(), input.policy_document().unwrap());
 
     db::role_inline_policy::save(tx, &mut inline_policy).await?;
 
@@ -324,9 +350,16 @@ pub(crate) async fn get_role<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &GetRoleRequest,
 ) -> Result<GetRoleOutput, ActionError> {
     input.validate("$")?;
-    let role_name = input.role_name().unwrap();
+    let role_name = input.role_name().unwrap().trim();
     let role = find_by_name(tx.as_mut(), ctx.account_id, role_name).await?;
-    let output = GetRoleOutput::builder().role((&role).into()).build();
+    let tags = db::Tags::Role.list(tx.as_mut(), role.id, &ListTagsQuery::all()).await?;
+
+    let role = role
+        .role_builder()
+        .set_tags(super::tag::prepare_for_output(&tags))
+        .build()
+        .unwrap();
+    let output = GetRoleOutput::builder().role(role).build();
     Ok(output)
 }
 
@@ -334,6 +367,21 @@ pub(crate) async fn update_role<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &UpdateRoleRequest,
 ) -> Result<UpdateRoleOutput, ActionError> {
     input.validate("$")?;
+    let max_session_duration = input.max_session_duration().copied();
+    local_cloud_validate::validate_min(
+        max_session_duration,
+        constants::policy::ROLE_SESSION_DURATION_MIN_SEC.as_secs() as i32,
+        "$.MaxSessionDuration",
+    )?;
+    local_cloud_validate::validate_max(
+        max_session_duration,
+        constants::policy::ROLE_SESSION_DURATION_MAX_SEC.as_secs() as i32,
+        "$.MaxSessionDuration",
+    )?;
+
+    let role = find_by_name(tx.as_mut(), ctx.account_id, input.role_name().unwrap().trim()).await?;
+    check_modifiable(&role)?;
+    db::role::update(tx.as_mut(), role.id, input.description(), max_session_duration.map(|v| v as i64)).await?;
 
     let output = UpdateRoleOutput::builder().build();
     Ok(output)
@@ -343,17 +391,52 @@ pub(crate) async fn update_assume_role_policy<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &UpdateAssumeRolePolicyRequest,
 ) -> Result<UpdateAssumeRolePolicyOutput, ActionError> {
     input.validate("$")?;
+    input
+        .policy_document_type()
+        .unwrap()
+        .validate_as(PolicyDocumentKind::Trust)?;
+
+    let role = find_by_name(tx.as_mut(), ctx.account_id, input.role_name().unwrap().trim()).await?;
+    check_modifiable(&role)?;
+    db::role::update_assume_role_policy(tx.as_mut(), role.id, input.policy_document().unwrap()).await?;
 
     let output = UpdateAssumeRolePolicyOutput::builder().build();
     Ok(output)
 }
 
+/// Service-linked roles are only modifiable by the linked service.
+fn check_modifiable(role: &SelectRoleWithDetails) -> Result<(), ActionError> {
+    if super::service_linked_role::is_service_linked(&role.path) {
+        return Err(ActionError::new(
+            ApiErrorKind::UnmodifiableEntity,
+            format!(
+                "Cannot perform the operation on the protected role '{}' - this role is only modifiable by AWS",
+                role.role_name
+            )
+            .as_str(),
+        ));
+    }
+    Ok(())
+}
+
 pub(crate) async fn list_attached_role_policies<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &ListAttachedRolePoliciesRequest,
 ) -> Result<ListAttachedRolePoliciesOutput, ActionError> {
     input.validate("$")?;
 
-    let output = ListAttachedRolePoliciesOutput::builder().build();
+    let role_id = find_id_by_name(tx.as_mut(), ctx.account_id, input.role_name().unwrap().trim()).await?;
+
+    let query = ListAttachedPoliciesQuery::new(role_id, input.path_prefix(), input.max_items(), input.marker_type());
+    let found_policies = db::policy::list_attached_to_role(tx.as_mut(), &query).await?;
+
+    let policies = super::common::convert_and_limit(&found_policies, query.limit);
+    let marker = super::common::create_encoded_marker(&query, found_policies.len())?;
+
+    let output = ListAttachedRolePoliciesOutput::builder()
+        .set_attached_policies(policies)
+        .set_is_truncated(marker.as_ref().map(|_v| true))
+        .set_marker(marker)
+        .build();
     Ok(output)
 }
 
@@ -362,6 +445,19 @@ pub(crate) async fn detach_role_policy<'a>(
 ) -> Result<DetachRolePolicyOutput, ActionError> {
     input.validate("$")?;
 
+    let role = find_by_name(tx.as_mut(), ctx.account_id, input.role_name().unwrap().trim()).await?;
+    check_modifiable(&role)?;
+    let policy_arn = input.policy_arn().unwrap().trim();
+    let policy_id = super::policy::find_id_by_arn(tx.as_mut(), ctx.account_id, policy_arn).await?;
+
+    let is_detached = db::role::detach_policy_from_role(tx.as_mut(), role.id, policy_id).await?;
+    if !is_detached {
+        return Err(ActionError::new(
+            ApiErrorKind::NoSuchEntity,
+            format!("Policy {policy_arn} was not found.").as_str(),
+        ));
+    }
+
     let output = DetachRolePolicyOutput::builder().build();
     Ok(output)
 }
@@ -370,18 +466,14 @@ pub(crate) async fn put_role_permissions_boundary<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &PutRolePermissionsBoundaryRequest,
 ) -> Result<PutRolePermissionsBoundaryOutput, ActionError> {
     input.validate("$")?;
+    let role_name = input.role_name().unwrap();
+    let role = find_by_name(tx.as_mut(), ctx.account_id, role_name).await?;
+    check_modifiable(&role)?;
+
     let policy_arn = input.permissions_boundary().unwrap();
     let policy_id = super::policy::find_id_by_arn(tx.as_mut(), ctx.account_id, policy_arn).await?;
+    db::role::update_permissions_boundary(tx.as_mut(), ctx.account_id, role_name, policy_id).await?;
 
-    let role_name = input.role_name().unwrap();
-    let is_updated = db::role::update_permissions_boundary(tx.as_mut(), ctx.account_id, role_name, policy_id).await?;
-    if !is_updated {
-        // There is only one reason why `is_updated == false` - role doesn't exist.
-        return Err(ActionError::new(
-            ApiErrorKind::NoSuchEntity,
-            format!("IAM role with name '{}' doesn't exist.", role_name).as_str(),
-        ));
-    }
     let output = PutRolePermissionsBoundaryOutput::builder().build();
     Ok(output)
 }
@@ -391,7 +483,18 @@ pub(crate) async fn update_role_description<'a>(
 ) -> Result<UpdateRoleDescriptionOutput, ActionError> {
     input.validate("$")?;
 
-    let output = UpdateRoleDescriptionOutput::builder().build();
+    let role_name = input.role_name().unwrap().trim();
+    let role_id = find_id_by_name(tx.as_mut(), ctx.account_id, role_name).await?;
+    db::role::update(tx.as_mut(), role_id, input.description(), None).await?;
+
+    let role = find_by_name(tx.as_mut(), ctx.account_id, role_name).await?;
+    let tags = db::Tags::Role.list(tx.as_mut(), role.id, &ListTagsQuery::all()).await?;
+    let role = role
+        .role_builder()
+        .set_tags(super::tag::prepare_for_output(&tags))
+        .build()
+        .unwrap();
+    let output = UpdateRoleDescriptionOutput::builder().role(role).build();
     Ok(output)
 }
 
@@ -400,6 +503,26 @@ pub(crate) async fn delete_role<'a>(
 ) -> Result<DeleteRoleOutput, ActionError> {
     input.validate("$")?;
 
+    let role = find_by_name(tx.as_mut(), ctx.account_id, input.role_name().unwrap().trim()).await?;
+    check_modifiable(&role)?;
+    let role_id = role.id;
+
+    check_delete_conflict(
+        db::role::count_instance_profiles(tx.as_mut(), role_id).await?,
+        "Cannot delete entity, must remove roles from instance profile first.",
+    )?;
+    check_delete_conflict(
+        db::role_inline_policy::count_by_role_id(tx.as_mut(), role_id).await?,
+        "Cannot delete entity, must delete policies first.",
+    )?;
+    check_delete_conflict(
+        db::role::count_attached_policies(tx.as_mut(), role_id).await?,
+        "Cannot delete entity, must detach all policies first.",
+    )?;
+
+    db::Tags::Role.delete_by_parent_id(tx, role_id).await?;
+    db::role::delete(tx, role_id).await?;
+
     let output = DeleteRoleOutput::builder().build();
     Ok(output)
 }
@@ -418,6 +541,17 @@ pub(crate) async fn delete_role_policy<'a>(
 ) -> Result<DeleteRolePolicyOutput, ActionError> {
     input.validate("$")?;
 
+    let role = find_by_name(tx.as_mut(), ctx.account_id, input.role_name().unwrap().trim()).await?;
+    check_modifiable(&role)?;
+    let policy_name = input.policy_name().unwrap();
+    let is_deleted = db::role_inline_policy::delete_by_role_id_and_name(tx.as_mut(), role.id, policy_name).await?;
+    if !is_deleted {
+        return Err(ActionError::new(
+            ApiErrorKind::NoSuchEntity,
+            format!("The role policy with name {policy_name} cannot be found.").as_str(),
+        ));
+    }
+
     let output = DeleteRolePolicyOutput::builder().build();
     Ok(output)
 }
@@ -18,6 +18,7 @@ use crate::http::aws::iam::db::types::saml_provider::{InsertSamlProvider, Select
 use crate::http::aws::iam::db::types::tags::ListTagsQuery;
 use crate::http::aws::iam::operations::ctx::OperationCtx;
 use crate::http::aws::iam::operations::error::ActionError;
+use crate::http::aws::iam::quotas::Quota;
 use crate::http::aws::iam::types::create_saml_provider::CreateSamlProviderRequest;
 use crate::http::aws::iam::types::delete_saml_provider::DeleteSamlProviderRequest;
 use crate::http::aws::iam::types::get_saml_provider::GetSamlProviderRequest;
@@ -26,7 +27,7 @@ use crate::http::aws::iam::types::list_saml_providers::ListSamlProvidersRequest;
 use crate::http::aws::iam::types::tag_saml_provider::TagSamlProviderRequest;
 use crate::http::aws::iam::types::untag_saml_provider::UntagSamlProviderRequest;
 use crate::http::aws::iam::types::update_saml_provider::UpdateSamlProviderRequest;
-use crate::http::aws::iam::{constants, db};
+use crate::http::aws::iam::{constants, db, quotas};
 
 pub(crate) async fn find_id_by_arn<'a, E>(executor: E, account_id: i64, arn: &str) -> Result<i64, ActionError>
 where
@@ -64,15 +65,21 @@ pub(crate) async fn create_saml_provider<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &CreateSamlProviderRequest,
 ) -> Result<CreateSamlProviderOutput, ActionError> {
     input.validate("$")?;
+    let saml_provider_name = input.name().unwrap().trim();
+    let arn = format!("arn:aws:iam::{:0>12}:saml-provider/{saml_provider_name}", ctx.account_id);
+    super::common::check_not_exists(
+        db::saml_provider::find_id_by_arn(tx.as_mut(), ctx.account_id, &arn).await?,
+        format!("SAML provider with name {saml_provider_name} already exists.").as_str(),
+    )?;
+    quotas::check_account(tx, ctx.account_id, Quota::Providers).await?;
 
     let current_time = Utc::now().timestamp();
-    let saml_provider_name = input.name().unwrap().trim();
 
     let mut insert_saml_provider = InsertSamlProvider {
         id: None,
         account_id: ctx.account_id,
         name: saml_provider_name.to_owned(),
-        arn: format!("arn:aws:iam::{:0>12}:saml-provider/{saml_provider_name}", ctx.account_id),
+        arn,
         create_date: current_time,
         valid_until: None,
         metadata_document: input.saml_metadata_document().unwrap().to_owned(),
@@ -213,6 +220,11 @@ pub(crate) async fn delete_saml_provider<'a>(
 ) -> Result<DeleteSamlProviderOutput, ActionError> {
     input.validate("$")?;
 
+    let provider_id = find_id_by_arn(tx.as_mut(), ctx.account_id, input.saml_provider_arn().unwrap().trim()).await?;
+
+    db::Tags::SamlProvider.delete_by_parent_id(tx, provider_id).await?;
+    db::saml_provider::delete(tx.as_mut(), provider_id).await?;
+
     let output = DeleteSamlProviderOutput::builder().build();
     Ok(output)
 }
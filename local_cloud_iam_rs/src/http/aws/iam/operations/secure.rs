@@ -1,10 +1,13 @@
 use argon2::Argon2;
 use password_hash::rand_core::OsRng;
-use password_hash::{PasswordHasher, SaltString};
+use password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
 
 use crate::http::aws::iam::actions::error::ApiErrorKind;
+use crate::http::aws::iam::constants;
 use crate::http::aws::iam::operations::error::ActionError;
 
+const SECRET_ACCESS_KEY_CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
 pub(crate) fn password_hash(password: impl AsRef<[u8]>) -> Result<String, ActionError> {
     let salt = SaltString::generate(OsRng);
 
@@ -15,3 +18,24 @@ pub(crate) fn password_hash(password: impl AsRef<[u8]>) -> Result<String, Action
             ActionError::new(ApiErrorKind::ServiceFailure, "Password hashing error. Please contact support team.")
         })
 }
+
+/// Verifies the password against the hash produced by [`password_hash`]. Malformed hashes never match.
+pub(crate) fn verify_password(password: impl AsRef<[u8]>, hash: &str) -> bool {
+    match PasswordHash::new(hash) {
+        Ok(parsed_hash) => Argon2::default()
+            .verify_password(password.as_ref(), &parsed_hash)
+            .is_ok(),
+        Err(_) => false,
+    }
+}
+
+pub(crate) fn generate_secret_access_key() -> String {
+    local_cloud_common::random::generate_char_sequence(SECRET_ACCESS_KEY_CHARSET, constants::access_key::SECRET_LENGTH)
+}
+
+pub(crate) fn generate_service_password() -> String {
+    local_cloud_common::random::generate_char_sequence(
+        SECRET_ACCESS_KEY_CHARSET,
+        constants::service_specific_credential::PASSWORD_LENGTH,
+    )
+}
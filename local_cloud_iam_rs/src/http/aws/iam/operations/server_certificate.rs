@@ -21,6 +21,7 @@ use crate::http::aws::iam::db::types::tags::ListTagsQuery;
 use crate::http::aws::iam::operations::common::create_resource_id;
 use crate::http::aws::iam::operations::ctx::OperationCtx;
 use crate::http::aws::iam::operations::error::ActionError;
+use crate::http::aws::iam::quotas::Quota;
 use crate::http::aws::iam::types::delete_server_certificate::DeleteServerCertificateRequest;
 use crate::http::aws::iam::types::get_server_certificate::GetServerCertificateRequest;
 use crate::http::aws::iam::types::list_server_certificate_tags::ListServerCertificateTagsRequest;
@@ -29,16 +30,21 @@ use crate::http::aws::iam::types::tag_server_certificate::TagServerCertificateRe
 use crate::http::aws::iam::types::untag_server_certificate::UntagServerCertificateRequest;
 use crate::http::aws::iam::types::update_server_certificate::UpdateServerCertificateRequest;
 use crate::http::aws::iam::types::upload_server_certificate::UploadServerCertificateRequest;
-use crate::http::aws::iam::{constants, db};
+use crate::http::aws::iam::{constants, db, quotas};
 
 pub(crate) async fn upload_server_certificate<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &UploadServerCertificateRequest,
 ) -> Result<UploadServerCertificateOutput, ActionError> {
     input.validate("$")?;
+    let server_certificate_name = input.server_certificate_name().unwrap().trim();
+    super::common::check_not_exists(
+        db::server_certificate::find_id_by_name(tx.as_mut(), ctx.account_id, server_certificate_name).await?,
+        format!("The Server Certificate with name {server_certificate_name} already exists.").as_str(),
+    )?;
+    quotas::check_account(tx, ctx.account_id, Quota::ServerCertificates).await?;
 
     let current_time = Utc::now().timestamp();
     let path = input.path().unwrap_or("/").trim();
-    let server_certificate_name = input.server_certificate_name().unwrap().trim();
     let arn = format!("arn:aws:iam::{:0>12}:server-certificate{}{}", ctx.account_id, path, server_certificate_name);
     let certificate_body = input.certificate_body().unwrap();
     let pem = parse_x509_pem(certificate_body.as_bytes()).unwrap().1;
@@ -211,6 +217,14 @@ pub(crate) async fn delete_server_certificate<'a>(
 ) -> Result<DeleteServerCertificateOutput, ActionError> {
     input.validate("$")?;
 
+    let server_certificate_id =
+        find_id_by_name(tx.as_mut(), ctx.account_id, input.server_certificate_name().unwrap().trim()).await?;
+
+    db::Tags::ServerCertificate
+        .delete_by_parent_id(tx, server_certificate_id)
+        .await?;
+    db::server_certificate::delete(tx.as_mut(), server_certificate_id).await?;
+
     let output = DeleteServerCertificateOutput::builder().build();
     Ok(output)
 }
@@ -1,20 +1,152 @@
 use aws_sdk_iam::operation::get_service_last_accessed_details::GetServiceLastAccessedDetailsOutput;
 use aws_sdk_iam::operation::get_service_last_accessed_details_with_entities::GetServiceLastAccessedDetailsWithEntitiesOutput;
+use aws_sdk_iam::types::{
+    EntityDetails, EntityInfo, ErrorDetails, JobStatusType, PolicyOwnerEntityType, ServiceLastAccessed,
+    TrackedActionLastAccessed,
+};
+use aws_smithy_types::DateTime;
 use sqlx::{Sqlite, Transaction};
 
 use local_cloud_validate::NamedValidator;
 
+use crate::http::aws::iam::actions::error::ApiErrorKind;
+use crate::http::aws::iam::db::types::common::Pageable;
+use crate::http::aws::iam::db::types::service_last_accessed::{
+    JobStateType, ReportService, SelectServiceLastAccessedJob, ServiceLastAccessedReport,
+};
 use crate::http::aws::iam::operations::ctx::OperationCtx;
 use crate::http::aws::iam::operations::error::ActionError;
 use crate::http::aws::iam::types::get_service_last_accessed_details::GetServiceLastAccessedDetailsRequest;
 use crate::http::aws::iam::types::get_service_last_accessed_details_with_entities::GetServiceLastAccessedDetailsWithEntitiesRequest;
+use crate::http::aws::iam::types::marker_type::MarkerType;
+use crate::http::aws::iam::{constants, db};
 
+/// Page of the report items. Reports are stored as a whole, so pages are cut from the loaded report.
+struct ReportQuery {
+    limit: i32,
+    skip: i32,
+}
+
+impl ReportQuery {
+    fn new(max_items: Option<&i32>, marker_type: Option<&MarkerType>) -> Self {
+        ReportQuery {
+            limit: match max_items {
+                None => 100,
+                Some(v) => *v,
+            },
+            skip: match marker_type {
+                None => 0,
+                // unwrap is safe since marker must be validated before
+                Some(marker_type) => marker_type.marker().unwrap().truncate_amount,
+            },
+        }
+    }
+
+    fn paginate<T>(&self, items: Vec<T>) -> Result<(Vec<T>, Option<String>), ActionError> {
+        let remaining: Vec<T> = items.into_iter().skip(self.skip.max(0) as usize).collect();
+        let marker = super::common::create_encoded_marker(self, remaining.len())?;
+        let page = remaining.into_iter().take(self.limit.max(0) as usize).collect();
+        Ok((page, marker))
+    }
+}
+
+impl Pageable for &ReportQuery {
+    fn limit(&self) -> i32 {
+        self.limit
+    }
+
+    fn skip(&self) -> i32 {
+        self.skip
+    }
+}
+
+async fn find_job<'a>(
+    tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, job_id: &str,
+) -> Result<(SelectServiceLastAccessedJob, ServiceLastAccessedReport), ActionError> {
+    let job = match db::service_last_accessed::find_job(tx.as_mut(), ctx.account_id, job_id).await? {
+        Some(job) => job,
+        None => {
+            return Err(ActionError::new(
+                ApiErrorKind::NoSuchEntity,
+                format!("The job with ID '{job_id}' cannot be found.").as_str(),
+            ))
+        }
+    };
+    let report = match &job.content {
+        Some(content) => serde_json::from_slice(content).map_err(|err| {
+            ActionError::new(ApiErrorKind::ServiceFailure, format!("Failed to read report: {err}").as_str())
+        })?,
+        None => ServiceLastAccessedReport::default(),
+    };
+    Ok((job, report))
+}
+
+fn job_error(job: &SelectServiceLastAccessedJob) -> Option<ErrorDetails> {
+    if job.state != JobStateType::Failed {
+        return None;
+    }
+    ErrorDetails::builder()
+        .code(job.error_code.as_deref().unwrap_or_default())
+        .message(job.error_message.as_deref().unwrap_or_default())
+        .build()
+        .ok()
+}
+
+fn service_name(service_namespace: &str) -> &str {
+    constants::service_last_accessed::SERVICE_NAMES
+        .iter()
+        .find(|(namespace, _name)| *namespace == service_namespace)
+        .map(|(_namespace, name)| *name)
+        .unwrap_or(service_namespace)
+}
+
+fn service_last_accessed(service: &ReportService) -> ServiceLastAccessed {
+    let tracked_actions = service.tracked_actions.as_ref().map(|actions| {
+        actions
+            .iter()
+            .map(|action| {
+                TrackedActionLastAccessed::builder()
+                    .action_name(&action.action_name)
+                    .last_accessed_entity(&action.last_accessed_entity)
+                    .set_last_accessed_region(action.last_accessed_region.clone())
+                    .last_accessed_time(DateTime::from_secs(action.last_accessed_time))
+                    .build()
+            })
+            .collect()
+    });
+    ServiceLastAccessed::builder()
+        .service_name(service_name(&service.service_namespace))
+        .service_namespace(&service.service_namespace)
+        .set_last_authenticated(service.last_authenticated.map(DateTime::from_secs))
+        .set_last_authenticated_entity(service.last_authenticated_entity.clone())
+        .set_last_authenticated_region(service.last_authenticated_region.clone())
+        .total_authenticated_entities(service.total_authenticated_entities)
+        .set_tracked_actions_last_accessed(tracked_actions)
+        .build()
+        .unwrap()
+}
+
+/// The completion date is reported only for finished jobs, the creation date is used as a placeholder otherwise.
 pub(crate) async fn get_service_last_accessed_details<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &GetServiceLastAccessedDetailsRequest,
 ) -> Result<GetServiceLastAccessedDetailsOutput, ActionError> {
     input.validate("$")?;
 
-    let output = GetServiceLastAccessedDetailsOutput::builder().build().unwrap();
+    let (job, report) = find_job(tx, ctx, input.job_id().unwrap().trim()).await?;
+    let query = ReportQuery::new(input.max_items(), input.marker_type());
+    let (services, marker) = query.paginate(report.services)?;
+
+    let output = GetServiceLastAccessedDetailsOutput::builder()
+        .job_status(JobStatusType::from(&job.state))
+        .job_type((&job.granularity).into())
+        .job_creation_date(DateTime::from_secs(job.create_date))
+        .set_services_last_accessed(Some(services.iter().map(service_last_accessed).collect()))
+        .job_completion_date(DateTime::from_secs(job.completion_date.unwrap_or(job.create_date)))
+        .is_truncated(marker.is_some())
+        .set_marker(marker)
+        .set_error(job_error(&job))
+        .build()
+        .unwrap();
     Ok(output)
 }
 
@@ -23,7 +155,40 @@ pub(crate) async fn get_service_last_accessed_details_with_entities<'a>(
 ) -> Result<GetServiceLastAccessedDetailsWithEntitiesOutput, ActionError> {
     input.validate("$")?;
 
+    let (job, report) = find_job(tx, ctx, input.job_id().unwrap().trim()).await?;
+    let service_namespace = input.service_namespace().unwrap().trim();
+    let mut entities = vec![];
+    for entity in &report.entities {
+        let entity_info = EntityInfo::builder()
+            .arn(&entity.arn)
+            .name(&entity.name)
+            .r#type(PolicyOwnerEntityType::from(entity.entity_type.as_str()))
+            .id(&entity.id)
+            .path(&entity.path)
+            .build()
+            .unwrap();
+        let entity_details = EntityDetails::builder()
+            .entity_info(entity_info)
+            .set_last_authenticated(
+                entity
+                    .last_authenticated
+                    .get(service_namespace)
+                    .map(|date| DateTime::from_secs(*date)),
+            )
+            .build();
+        entities.push(entity_details);
+    }
+    let query = ReportQuery::new(input.max_items(), input.marker_type());
+    let (entities, marker) = query.paginate(entities)?;
+
     let output = GetServiceLastAccessedDetailsWithEntitiesOutput::builder()
+        .job_status(JobStatusType::from(&job.state))
+        .job_creation_date(DateTime::from_secs(job.create_date))
+        .set_entity_details_list(Some(entities))
+        .job_completion_date(DateTime::from_secs(job.completion_date.unwrap_or(job.create_date)))
+        .is_truncated(marker.is_some())
+        .set_marker(marker)
+        .set_error(job_error(&job))
         .build()
         .unwrap();
     Ok(output)
@@ -1,22 +1,184 @@
 use aws_sdk_iam::operation::create_service_linked_role::CreateServiceLinkedRoleOutput;
 use aws_sdk_iam::operation::delete_service_linked_role::DeleteServiceLinkedRoleOutput;
 use aws_sdk_iam::operation::get_service_linked_role_deletion_status::GetServiceLinkedRoleDeletionStatusOutput;
-use sqlx::{Sqlite, Transaction};
+use aws_sdk_iam::types::{DeletionTaskFailureReasonType, Role};
+use aws_smithy_types::DateTime;
+use chrono::Utc;
+use sqlx::{Sqlite, SqliteConnection, Transaction};
+use uuid::Uuid;
 
 use local_cloud_validate::NamedValidator;
 
+use crate::http::aws::iam::actions::error::ApiErrorKind;
+use crate::http::aws::iam::db::types::resource_identifier::ResourceType;
+use crate::http::aws::iam::db::types::role::InsertRoleBuilder;
+use crate::http::aws::iam::db::types::service_linked_role::{
+    DeletionTaskStateType, InsertDeletionTask, SelectDeletionTask,
+};
+use crate::http::aws::iam::jobs::Job;
+use crate::http::aws::iam::operations::common::create_resource_id;
 use crate::http::aws::iam::operations::ctx::OperationCtx;
 use crate::http::aws::iam::operations::error::ActionError;
+use crate::http::aws::iam::quotas::Quota;
 use crate::http::aws::iam::types::create_service_linked_role::CreateServiceLinkedRoleRequest;
 use crate::http::aws::iam::types::delete_service_linked_role::DeleteServiceLinkedRoleRequest;
 use crate::http::aws::iam::types::get_service_linked_role_deletion_status::GetServiceLinkedRoleDeletionStatusRequest;
+use crate::http::aws::iam::{constants, db, quotas};
+
+/// Service-linked role of an AWS service: name of the role, the AWS managed policy attached to it and whether the
+/// service allows several roles distinguished by a custom suffix.
+struct ServiceLinkedRoleTemplate {
+    service_name: &'static str,
+    role_name: &'static str,
+    policy_name: &'static str,
+    description: &'static str,
+    custom_suffix_allowed: bool,
+}
+
+const SERVICE_LINKED_ROLES: &[ServiceLinkedRoleTemplate] = &[
+    ServiceLinkedRoleTemplate {
+        service_name: "ecs.amazonaws.com",
+        role_name: "AWSServiceRoleForECS",
+        policy_name: "AmazonECSServiceRolePolicy",
+        description: "Role to enable Amazon ECS to manage your cluster.",
+        custom_suffix_allowed: false,
+    },
+    ServiceLinkedRoleTemplate {
+        service_name: "es.amazonaws.com",
+        role_name: "AWSServiceRoleForAmazonElasticsearchService",
+        policy_name: "AmazonElasticsearchServiceRolePolicy",
+        description: "Allows Amazon ES to manage AWS resources for a domain on your behalf.",
+        custom_suffix_allowed: false,
+    },
+    ServiceLinkedRoleTemplate {
+        service_name: "opensearchservice.amazonaws.com",
+        role_name: "AWSServiceRoleForAmazonOpenSearchService",
+        policy_name: "AmazonOpenSearchServiceRolePolicy",
+        description: "Allows Amazon OpenSearch Service to manage AWS resources for a domain on your behalf.",
+        custom_suffix_allowed: false,
+    },
+    ServiceLinkedRoleTemplate {
+        service_name: "elasticloadbalancing.amazonaws.com",
+        role_name: "AWSServiceRoleForElasticLoadBalancing",
+        policy_name: "AWSElasticLoadBalancingServiceRolePolicy",
+        description: "Allows ELB to call AWS services on your behalf.",
+        custom_suffix_allowed: false,
+    },
+    ServiceLinkedRoleTemplate {
+        service_name: "autoscaling.amazonaws.com",
+        role_name: "AWSServiceRoleForAutoScaling",
+        policy_name: "AutoScalingServiceRolePolicy",
+        description: "Default Service-Linked Role enables access to AWS Services and Resources used or managed by \
+                      Auto Scaling",
+        custom_suffix_allowed: true,
+    },
+];
+
+fn find_template(service_name: &str) -> Result<&'static ServiceLinkedRoleTemplate, ActionError> {
+    SERVICE_LINKED_ROLES
+        .iter()
+        .find(|template| template.service_name == service_name)
+        .ok_or_else(|| {
+            ActionError::new(
+                ApiErrorKind::InvalidInput,
+                format!("Service '{service_name}' doesn't support service-linked roles.").as_str(),
+            )
+        })
+}
+
+/// Path of service-linked roles of the service, e.g. `/aws-service-role/ecs.amazonaws.com/`.
+fn service_path(service_name: &str) -> String {
+    format!("{}{service_name}/", constants::role::SERVICE_LINKED_PATH_PREFIX)
+}
+
+pub(crate) fn is_service_linked(role_path: &str) -> bool {
+    role_path.starts_with(constants::role::SERVICE_LINKED_PATH_PREFIX)
+}
+
+/// Trust policy which allows the service to assume the role.
+fn create_trust_policy(service_name: &str) -> String {
+    serde_json::json!({
+        "Version": "2012-10-17",
+        "Statement": [
+            {
+                "Effect": "Allow",
+                "Principal": {"Service": service_name},
+                "Action": "sts:AssumeRole"
+            }
+        ]
+    })
+    .to_string()
+}
 
 pub(crate) async fn create_service_linked_role<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &CreateServiceLinkedRoleRequest,
 ) -> Result<CreateServiceLinkedRoleOutput, ActionError> {
     input.validate("$")?;
 
-    let output = CreateServiceLinkedRoleOutput::builder().build();
+    let template = find_template(input.aws_service_name().unwrap().trim())?;
+    let role_name = match input.custom_suffix() {
+        None => template.role_name.to_owned(),
+        Some(suffix) if template.custom_suffix_allowed => format!("{}_{suffix}", template.role_name),
+        Some(_) => {
+            return Err(ActionError::new(
+                ApiErrorKind::InvalidInput,
+                format!("Custom suffix is not allowed for {}.", template.service_name).as_str(),
+            ));
+        }
+    };
+    if db::role::find_id_by_name(tx.as_mut(), ctx.account_id, &role_name)
+        .await?
+        .is_some()
+    {
+        return Err(ActionError::new(
+            ApiErrorKind::InvalidInput,
+            format!("Service role name {role_name} has been taken in this account, please try a different suffix.")
+                .as_str(),
+        ));
+    }
+    quotas::check_account(tx, ctx.account_id, Quota::Roles).await?;
+
+    let current_time = Utc::now().timestamp();
+    let role_id = create_resource_id(tx, constants::role::PREFIX, ResourceType::Role).await?;
+    let path = service_path(template.service_name);
+    let description = input.description().unwrap_or(template.description);
+    let mut insert_role = InsertRoleBuilder::default()
+        .id(None)
+        .account_id(ctx.account_id)
+        .role_name(role_name.to_owned())
+        .assume_role_policy_document(create_trust_policy(template.service_name))
+        .description(Some(description.to_owned()))
+        .max_session_duration(constants::role::DEFAULT_MAX_SESSION_DURATION as i64)
+        .arn(format!("arn:aws:iam::{:0>12}:role{}{}", ctx.account_id, &path, &role_name))
+        .path(path)
+        .role_id(role_id)
+        .policy_id(None)
+        .create_date(current_time)
+        .build()
+        .map_err(|err| ActionError::new(ApiErrorKind::ServiceFailure, err.to_string().as_str()))?;
+    db::role::create(tx, &mut insert_role).await?;
+
+    let policy_arn = format!(
+        "{}{}{}",
+        constants::policy::AWS_MANAGED_ARN_PREFIX,
+        constants::role::SERVICE_LINKED_PATH_PREFIX,
+        template.policy_name
+    );
+    let policy_id = super::policy::find_id_by_arn(tx.as_mut(), ctx.account_id, &policy_arn).await?;
+    db::role::assign_policy_to_role(tx, insert_role.id.unwrap(), policy_id).await?;
+
+    let role = Role::builder()
+        .role_id(&insert_role.role_id)
+        .assume_role_policy_document(urlencoding::encode(&insert_role.assume_role_policy_document))
+        .role_name(&insert_role.role_name)
+        .path(&insert_role.path)
+        .arn(&insert_role.arn)
+        .set_description(insert_role.description)
+        .max_session_duration(insert_role.max_session_duration as i32)
+        .create_date(DateTime::from_secs(insert_role.create_date))
+        .build()
+        .unwrap();
+    let output = CreateServiceLinkedRoleOutput::builder().role(role).build();
     Ok(output)
 }
 
@@ -25,15 +187,118 @@ pub(crate) async fn get_service_linked_role_deletion_status<'a>(
 ) -> Result<GetServiceLinkedRoleDeletionStatusOutput, ActionError> {
     input.validate("$")?;
 
-    let output = GetServiceLinkedRoleDeletionStatusOutput::builder().build().unwrap();
+    let deletion_task_id = input.deletion_task_id().unwrap().trim();
+    let task = match db::service_linked_role::find_deletion_task(tx.as_mut(), ctx.account_id, deletion_task_id).await? {
+        Some(task) => task,
+        None => {
+            return Err(ActionError::new(
+                ApiErrorKind::NoSuchEntity,
+                format!("Deletion task with ID '{deletion_task_id}' doesn't exist.").as_str(),
+            ));
+        }
+    };
+
+    let reason = task.reason.as_ref().map(|reason| {
+        DeletionTaskFailureReasonType::builder()
+            .reason(reason)
+            .set_role_usage_list(Some(vec![]))
+            .build()
+    });
+    let output = GetServiceLinkedRoleDeletionStatusOutput::builder()
+        .status((&task.state).into())
+        .set_reason(reason)
+        .build()
+        .unwrap();
     Ok(output)
 }
 
+/// Registers a deletion task of the service-linked role. The role is deleted asynchronously by
+/// the job runner. If the role is already being deleted, the ID of the existing task is returned.
 pub(crate) async fn delete_service_linked_role<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &DeleteServiceLinkedRoleRequest,
 ) -> Result<DeleteServiceLinkedRoleOutput, ActionError> {
     input.validate("$")?;
 
-    let output = DeleteServiceLinkedRoleOutput::builder().build().unwrap();
+    let role_name = input.role_name().unwrap().trim();
+    let role = super::role::find_by_name(tx.as_mut(), ctx.account_id, role_name).await?;
+    if !is_service_linked(&role.path) {
+        return Err(ActionError::new(
+            ApiErrorKind::InvalidInput,
+            format!("Role '{}' is not a service-linked role.", role.role_name).as_str(),
+        ));
+    }
+
+    let deletion_task_id = match db::service_linked_role::find_active_deletion_task(tx.as_mut(), role.id).await? {
+        Some(task) => task.deletion_task_id,
+        None => {
+            let mut task = InsertDeletionTask {
+                id: None,
+                account_id: ctx.account_id,
+                deletion_task_id: format!("task{}{}/{}", role.path, role.role_name, Uuid::new_v4()),
+                role_id: role.id,
+                create_date: Utc::now().timestamp(),
+            };
+            db::service_linked_role::create_deletion_task(tx, &mut task).await?;
+            task.deletion_task_id
+        }
+    };
+
+    let output = DeleteServiceLinkedRoleOutput::builder()
+        .deletion_task_id(deletion_task_id)
+        .build()
+        .unwrap();
     Ok(output)
 }
+
+/// Deletion of the service-linked role requested by [`delete_service_linked_role`].
+impl Job for SelectDeletionTask {
+    const KIND: &'static str = "service-linked role deletion";
+
+    fn id(&self) -> i64 {
+        self.id
+    }
+
+    async fn find_started(connection: &mut SqliteConnection) -> Result<Vec<Self>, sqlx::Error> {
+        db::service_linked_role::find_not_started(connection).await
+    }
+
+    async fn take(connection: &mut SqliteConnection, id: i64, start_date: i64) -> Result<bool, sqlx::Error> {
+        db::service_linked_role::mark_in_progress(connection, id, start_date).await
+    }
+
+    async fn run(&self, tx: &mut Transaction<'_, Sqlite>) -> Result<(), ActionError> {
+        let (state, reason) = match delete_role(tx, self).await? {
+            None => (DeletionTaskStateType::Succeeded, None),
+            Some(reason) => (DeletionTaskStateType::Failed, Some(reason)),
+        };
+        db::service_linked_role::finish(tx.as_mut(), self.id, &state, reason.as_deref()).await?;
+        Ok(())
+    }
+
+    async fn fail(
+        connection: &mut SqliteConnection, id: i64, _error: &ActionError, _completion_date: i64,
+    ) -> Result<(), sqlx::Error> {
+        let reason = "Failed to delete the service-linked role.";
+        db::service_linked_role::finish(connection, id, &DeletionTaskStateType::Failed, Some(reason)).await?;
+        Ok(())
+    }
+
+    async fn restart_stale(connection: &mut SqliteConnection, start_date: i64) -> Result<u64, sqlx::Error> {
+        db::service_linked_role::restart_stale(connection, start_date).await
+    }
+}
+
+/// Deletes the role of the task. Returns the failure reason if the role is still in use.
+async fn delete_role<'a>(
+    tx: &mut Transaction<'a, Sqlite>, task: &SelectDeletionTask,
+) -> Result<Option<String>, ActionError> {
+    if db::role::count_instance_profiles(tx.as_mut(), task.role_id).await? > 0 {
+        return Ok(Some("Service-linked role is used by instance profiles.".to_owned()));
+    }
+
+    // policies of the service-linked role are owned by the service, so they are deleted together with the role
+    db::role_inline_policy::delete_by_role_id(tx.as_mut(), task.role_id).await?;
+    db::Tags::Role.delete_by_parent_id(tx, task.role_id).await?;
+    db::role::delete(tx, task.role_id).await?;
+    Ok(None)
+}
@@ -3,10 +3,19 @@ use aws_sdk_iam::operation::delete_service_specific_credential::DeleteServiceSpe
 use aws_sdk_iam::operation::list_service_specific_credentials::ListServiceSpecificCredentialsOutput;
 use aws_sdk_iam::operation::reset_service_specific_credential::ResetServiceSpecificCredentialOutput;
 use aws_sdk_iam::operation::update_service_specific_credential::UpdateServiceSpecificCredentialOutput;
-use sqlx::{Sqlite, Transaction};
+use aws_sdk_iam::types::ServiceSpecificCredential;
+use aws_smithy_types::DateTime;
+use chrono::Utc;
+use sqlx::{Executor, Sqlite, Transaction};
 
 use local_cloud_validate::NamedValidator;
 
+use crate::http::aws::iam::actions::error::ApiErrorKind;
+use crate::http::aws::iam::db::types::resource_identifier::ResourceType;
+use crate::http::aws::iam::db::types::service_specific_credential::{
+    InsertServiceSpecificCredential, SelectServiceSpecificCredential, ServiceSpecificCredentialStatusType,
+};
+use crate::http::aws::iam::operations::common::create_resource_id;
 use crate::http::aws::iam::operations::ctx::OperationCtx;
 use crate::http::aws::iam::operations::error::ActionError;
 use crate::http::aws::iam::types::create_service_specific_credential::CreateServiceSpecificCredentialRequest;
@@ -14,12 +23,69 @@ use crate::http::aws::iam::types::delete_service_specific_credential::DeleteServ
 use crate::http::aws::iam::types::list_service_specific_credentials::ListServiceSpecificCredentialsRequest;
 use crate::http::aws::iam::types::reset_service_specific_credential::ResetServiceSpecificCredentialRequest;
 use crate::http::aws::iam::types::update_service_specific_credential::UpdateServiceSpecificCredentialRequest;
+use crate::http::aws::iam::{constants, db};
+
+const BEDROCK_SERVICE_NAME: &str = "bedrock.amazonaws.com";
+const BEDROCK_USER_NAME_CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+/// Generates the user name which is used to sign in to the service, e.g. `jane-at-000000000001` for CodeCommit and
+/// Keyspaces, or `BedrockAPIKey-K2RT-at-000000000001` for Bedrock API keys. The second credential of the user for the
+/// same service gets a suffix, e.g. `jane+1-at-000000000001`, and Bedrock API keys get another random suffix, so
+/// that service user names are unique.
+async fn generate_service_user_name<'a>(
+    tx: &mut Transaction<'a, Sqlite>, service_name: &str, user_name: &str, account_id: i64,
+) -> Result<String, ActionError> {
+    let mut index = 0;
+    loop {
+        let service_user_name = if service_name == BEDROCK_SERVICE_NAME {
+            let suffix = local_cloud_common::random::generate_char_sequence(BEDROCK_USER_NAME_CHARSET, 4);
+            format!("BedrockAPIKey-{suffix}-at-{account_id:0>12}")
+        } else if index == 0 {
+            format!("{user_name}-at-{account_id:0>12}")
+        } else {
+            format!("{user_name}+{index}-at-{account_id:0>12}")
+        };
+        if db::service_specific_credential::find_by_service_user_name(tx.as_mut(), service_name, &service_user_name)
+            .await?
+            .is_none()
+        {
+            return Ok(service_user_name);
+        }
+        index += 1;
+    }
+}
+
+async fn find_by_credential_id<'a>(
+    tx: &mut Transaction<'a, Sqlite>, user_id: i64, credential_id: &str,
+) -> Result<SelectServiceSpecificCredential, ActionError> {
+    match db::service_specific_credential::find_by_credential_id(tx.as_mut(), user_id, credential_id).await? {
+        Some(credential) => Ok(credential),
+        None => Err(no_such_credential(credential_id)),
+    }
+}
+
+fn no_such_credential(credential_id: &str) -> ActionError {
+    ActionError::new(
+        ApiErrorKind::NoSuchEntity,
+        format!("The service-specific credential with ID '{credential_id}' cannot be found.").as_str(),
+    )
+}
 
 pub(crate) async fn update_service_specific_credential<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &UpdateServiceSpecificCredentialRequest,
 ) -> Result<UpdateServiceSpecificCredentialOutput, ActionError> {
     input.validate("$")?;
 
+    let user_id = super::user::find_id_by_name_or_caller(ctx, tx.as_mut(), input.user_name()).await?;
+
+    let credential_id = input.service_specific_credential_id().unwrap().trim();
+    let status = input.status().unwrap().into();
+    let is_updated =
+        db::service_specific_credential::update_status(tx.as_mut(), user_id, credential_id, &status).await?;
+    if !is_updated {
+        return Err(no_such_credential(credential_id));
+    }
+
     let output = UpdateServiceSpecificCredentialOutput::builder().build();
     Ok(output)
 }
@@ -29,7 +95,60 @@ pub(crate) async fn create_service_specific_credential<'a>(
 ) -> Result<CreateServiceSpecificCredentialOutput, ActionError> {
     input.validate("$")?;
 
-    let output = CreateServiceSpecificCredentialOutput::builder().build();
+    let service_name = input.service_name().unwrap().trim();
+    if !constants::service_specific_credential::SUPPORTED_SERVICES.contains(&service_name) {
+        return Err(ActionError::new(
+            ApiErrorKind::ServiceNotSupported,
+            format!("Service '{service_name}' doesn't support service-specific credentials.").as_str(),
+        ));
+    }
+
+    let user = super::user::find_by_name(ctx, tx.as_mut(), input.user_name().unwrap().trim()).await?;
+    let count = db::service_specific_credential::count_by_service_name(tx.as_mut(), user.id, service_name).await?;
+    if count >= constants::service_specific_credential::MAX_COUNT_PER_SERVICE {
+        return Err(ActionError::new(
+            ApiErrorKind::LimitExceeded,
+            format!(
+                "Cannot exceed quota for ServiceSpecificCredentialsPerUserPerService: {}",
+                constants::service_specific_credential::MAX_COUNT_PER_SERVICE
+            )
+            .as_str(),
+        ));
+    }
+
+    let current_time = Utc::now().timestamp();
+    let credential_id =
+        create_resource_id(tx, constants::service_specific_credential::PREFIX, ResourceType::ServiceSpecificCredential)
+            .await?;
+    let password = super::secure::generate_service_password();
+    let service_user_name = generate_service_user_name(tx, service_name, &user.username, ctx.account_id).await?;
+
+    let mut insert_credential = InsertServiceSpecificCredential {
+        id: None,
+        account_id: ctx.account_id,
+        user_id: user.id,
+        credential_id,
+        service_name: service_name.to_owned(),
+        service_user_name,
+        password_hash: super::secure::password_hash(&password)?,
+        status: ServiceSpecificCredentialStatusType::Active,
+        create_date: current_time,
+    };
+    db::service_specific_credential::create(tx, &mut insert_credential).await?;
+
+    let credential = ServiceSpecificCredential::builder()
+        .create_date(DateTime::from_secs(current_time))
+        .service_name(&insert_credential.service_name)
+        .service_user_name(&insert_credential.service_user_name)
+        .service_password(password)
+        .service_specific_credential_id(&insert_credential.credential_id)
+        .user_name(&user.username)
+        .status((&insert_credential.status).into())
+        .build()
+        .unwrap();
+    let output = CreateServiceSpecificCredentialOutput::builder()
+        .service_specific_credential(credential)
+        .build();
     Ok(output)
 }
 
@@ -38,16 +157,45 @@ pub(crate) async fn list_service_specific_credentials<'a>(
 ) -> Result<ListServiceSpecificCredentialsOutput, ActionError> {
     input.validate("$")?;
 
-    let output = ListServiceSpecificCredentialsOutput::builder().build();
+    let user_id = super::user::find_id_by_name_or_caller(ctx, tx.as_mut(), input.user_name()).await?;
+
+    let service_name = input.service_name().map(|name| name.trim());
+    let found_credentials = db::service_specific_credential::list(tx.as_mut(), user_id, service_name).await?;
+
+    let output = ListServiceSpecificCredentialsOutput::builder()
+        .set_service_specific_credentials(Some(found_credentials.iter().map(|v| v.into()).collect()))
+        .build();
     Ok(output)
 }
 
+/// Generates a new password of the credential. The previous password stops working immediately.
 pub(crate) async fn reset_service_specific_credential<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &ResetServiceSpecificCredentialRequest,
 ) -> Result<ResetServiceSpecificCredentialOutput, ActionError> {
     input.validate("$")?;
 
-    let output = ResetServiceSpecificCredentialOutput::builder().build();
+    let user_id = super::user::find_id_by_name_or_caller(ctx, tx.as_mut(), input.user_name()).await?;
+
+    let credential_id = input.service_specific_credential_id().unwrap().trim();
+    let found_credential = find_by_credential_id(tx, user_id, credential_id).await?;
+
+    let password = super::secure::generate_service_password();
+    let password_hash = super::secure::password_hash(&password)?;
+    db::service_specific_credential::update_password_hash(tx.as_mut(), found_credential.id, &password_hash).await?;
+
+    let credential = ServiceSpecificCredential::builder()
+        .create_date(DateTime::from_secs(found_credential.create_date))
+        .service_name(&found_credential.service_name)
+        .service_user_name(&found_credential.service_user_name)
+        .service_password(password)
+        .service_specific_credential_id(&found_credential.credential_id)
+        .user_name(&found_credential.user_name)
+        .status((&found_credential.status).into())
+        .build()
+        .unwrap();
+    let output = ResetServiceSpecificCredentialOutput::builder()
+        .service_specific_credential(credential)
+        .build();
     Ok(output)
 }
 
@@ -56,6 +204,117 @@ pub(crate) async fn delete_service_specific_credential<'a>(
 ) -> Result<DeleteServiceSpecificCredentialOutput, ActionError> {
     input.validate("$")?;
 
+    let user_id = super::user::find_id_by_name_or_caller(ctx, tx.as_mut(), input.user_name()).await?;
+
+    let credential_id = input.service_specific_credential_id().unwrap().trim();
+    let is_deleted = db::service_specific_credential::delete(tx.as_mut(), user_id, credential_id).await?;
+    if !is_deleted {
+        return Err(no_such_credential(credential_id));
+    }
+
     let output = DeleteServiceSpecificCredentialOutput::builder().build();
     Ok(output)
 }
+
+/// Verifies the service user name and password which are used to sign in to the service, e.g. by a local stand-in of
+/// CodeCommit. Returns the credential if it is active and the password matches.
+pub(crate) async fn verify_service_specific_credential<'a, E>(
+    executor: E, service_name: &str, service_user_name: &str, password: &str,
+) -> Result<SelectServiceSpecificCredential, ActionError>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let invalid_credential = || ActionError::new(ApiErrorKind::AccessDenied, "Invalid service user name or password.");
+    let credential =
+        db::service_specific_credential::find_by_service_user_name(executor, service_name, service_user_name)
+            .await?
+            .ok_or_else(invalid_credential)?;
+    if !super::secure::verify_password(password, &credential.password_hash) {
+        return Err(invalid_credential());
+    }
+    if credential.status != ServiceSpecificCredentialStatusType::Active {
+        return Err(ActionError::new(
+            ApiErrorKind::AccessDenied,
+            format!("The service-specific credential with ID '{}' is inactive.", credential.credential_id).as_str(),
+        ));
+    }
+    Ok(credential)
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use local_cloud_db::LocalDb;
+
+    use crate::http::aws::iam::actions::error::ApiErrorKind;
+    use crate::http::aws::iam::db;
+    use crate::http::aws::iam::db::types::service_specific_credential::{
+        InsertServiceSpecificCredential, ServiceSpecificCredentialStatusType,
+    };
+    use crate::http::aws::iam::operations::error::ActionError;
+
+    const SERVICE_NAME: &str = "codecommit.amazonaws.com";
+    const SERVICE_USER_NAME: &str = "Root-at-000000000001";
+
+    fn assert_access_denied(result: Result<impl std::fmt::Debug, ActionError>) {
+        match result.unwrap_err() {
+            ActionError::Service { kind, .. } => assert_eq!(kind, ApiErrorKind::AccessDenied),
+            error => panic!("Unexpected error: {error}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_service_specific_credential() {
+        let database_url = format!("file:{}?mode=memory&cache=shared", Uuid::new_v4());
+        let db = LocalDb::new(&database_url, &sqlx::migrate!()).await.unwrap();
+
+        let mut tx = db.new_tx().await.unwrap();
+        let mut credential = InsertServiceSpecificCredential {
+            id: None,
+            account_id: 1,
+            user_id: 1,
+            credential_id: "ACCAEXAMPLE1".to_owned(),
+            service_name: SERVICE_NAME.to_owned(),
+            service_user_name: SERVICE_USER_NAME.to_owned(),
+            password_hash: super::super::secure::password_hash("password").unwrap(),
+            status: ServiceSpecificCredentialStatusType::Active,
+            create_date: 100,
+        };
+        db::service_specific_credential::create(&mut tx, &mut credential)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let mut connection = db.new_connection().await.unwrap();
+        let verified =
+            super::verify_service_specific_credential(connection.as_mut(), SERVICE_NAME, SERVICE_USER_NAME, "password")
+                .await
+                .unwrap();
+        assert_eq!(verified.credential_id, "ACCAEXAMPLE1");
+        assert_eq!(verified.user_name, "Root");
+
+        assert_access_denied(
+            super::verify_service_specific_credential(connection.as_mut(), SERVICE_NAME, SERVICE_USER_NAME, "wrong")
+                .await,
+        );
+        assert_access_denied(
+            super::verify_service_specific_credential(
+                connection.as_mut(),
+                "cassandra.amazonaws.com",
+                SERVICE_USER_NAME,
+                "password",
+            )
+            .await,
+        );
+
+        let status = ServiceSpecificCredentialStatusType::Inactive;
+        db::service_specific_credential::update_status(connection.as_mut(), 1, "ACCAEXAMPLE1", &status)
+            .await
+            .unwrap();
+        assert_access_denied(
+            super::verify_service_specific_credential(connection.as_mut(), SERVICE_NAME, SERVICE_USER_NAME, "password")
+                .await,
+        );
+    }
+}
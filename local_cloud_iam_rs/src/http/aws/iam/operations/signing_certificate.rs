@@ -11,15 +11,16 @@ use uuid::Uuid;
 use local_cloud_validate::NamedValidator;
 
 use crate::http::aws::iam::actions::error::ApiErrorKind;
-use crate::http::aws::iam::db;
 use crate::http::aws::iam::db::types::signing_certificate::{InsertSigningCertificate, UpdateSigningCertificateQuery};
 use crate::http::aws::iam::db::types::signing_certificate_status_type::SigningCertificateStatusType;
 use crate::http::aws::iam::operations::ctx::OperationCtx;
 use crate::http::aws::iam::operations::error::ActionError;
+use crate::http::aws::iam::quotas::Quota;
 use crate::http::aws::iam::types::delete_signing_certificate::DeleteSigningCertificateRequest;
 use crate::http::aws::iam::types::list_signing_certificates::ListSigningCertificatesRequest;
 use crate::http::aws::iam::types::update_signing_certificate::UpdateSigningCertificateRequest;
 use crate::http::aws::iam::types::upload_signing_certificate::UploadSigningCertificateRequest;
+use crate::http::aws::iam::{db, quotas};
 
 pub(crate) async fn upload_signing_certificate<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &UploadSigningCertificateRequest,
@@ -29,6 +30,8 @@ pub(crate) async fn upload_signing_certificate<'a>(
     let current_time = Utc::now().timestamp();
 
     let user = super::user::find_by_name(ctx, tx.as_mut(), input.user_name().unwrap().trim()).await?;
+    let count = db::sighing_certificate::count_by_user_id(tx.as_mut(), user.id).await?;
+    quotas::check(tx, Quota::SigningCertificatesPerUser, count).await?;
     let cert_content = input.certificate_body().unwrap();
     let x509 = input.certificate_body_type().unwrap().metadata().unwrap();
 
@@ -105,6 +108,16 @@ pub(crate) async fn delete_signing_certificate<'a>(
 ) -> Result<DeleteSigningCertificateOutput, ActionError> {
     input.validate("$")?;
 
+    let user_id = super::user::find_id_by_name_or_caller(ctx, tx.as_mut(), input.user_name()).await?;
+    let certificate_id = input.certificate_id().unwrap();
+    let is_deleted = db::sighing_certificate::delete(tx.as_mut(), user_id, certificate_id).await?;
+    if !is_deleted {
+        return Err(ActionError::new(
+            ApiErrorKind::NoSuchEntity,
+            format!("The Certificate with id {certificate_id} cannot be found.").as_str(),
+        ));
+    }
+
     let output = DeleteSigningCertificateOutput::builder().build();
     Ok(output)
 }
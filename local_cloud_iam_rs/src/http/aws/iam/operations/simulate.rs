@@ -1,20 +1,108 @@
+use std::collections::{BTreeSet, HashMap};
+use std::ops::Deref;
+
 use aws_sdk_iam::operation::simulate_custom_policy::SimulateCustomPolicyOutput;
 use aws_sdk_iam::operation::simulate_principal_policy::SimulatePrincipalPolicyOutput;
+use aws_sdk_iam::types::{
+    EvaluationResult, PermissionsBoundaryDecisionDetail, PolicyEvaluationDecisionType, PolicySourceType,
+    ResourceSpecificResult, Statement,
+};
 use sqlx::{Sqlite, Transaction};
 
+use local_cloud_iam_policy_document::evaluator::{self, Decision, PolicyKind, PolicySet, RequestContext};
+use local_cloud_iam_policy_document::types::LocalPolicyDocument;
 use local_cloud_validate::NamedValidator;
 
+use crate::http::aws::iam::actions::error::ApiErrorKind;
+use crate::http::aws::iam::db;
+use crate::http::aws::iam::db::types::common::Pageable;
 use crate::http::aws::iam::operations::ctx::OperationCtx;
 use crate::http::aws::iam::operations::error::ActionError;
+use crate::http::aws::iam::types::action_name_type::ActionNameType;
+use crate::http::aws::iam::types::context_entry::ContextEntry;
+use crate::http::aws::iam::types::marker_type::MarkerType;
+use crate::http::aws::iam::types::policy_document_type::PolicyDocumentType;
+use crate::http::aws::iam::types::resource_name_type::ResourceNameType;
 use crate::http::aws::iam::types::simulate_custom_policy::SimulateCustomPolicyRequest;
 use crate::http::aws::iam::types::simulate_principal_policy::SimulatePrincipalPolicyRequest;
 
+const ANY_RESOURCE: &str = "*";
+
+/// Policy which takes part in the simulation.
+#[derive(Debug)]
+pub(crate) struct SimulationPolicy {
+    /// Unique key of the policy in the policy set, the ARN for managed policies.
+    pub(crate) id: String,
+    /// Policy identifier reported in the simulation results.
+    pub(crate) name: String,
+    pub(crate) kind: PolicyKind,
+    pub(crate) source_type: PolicySourceType,
+    pub(crate) document: String,
+}
+
+impl SimulationPolicy {
+    fn new(id: impl Into<String>, kind: PolicyKind, source_type: PolicySourceType, document: &str) -> Self {
+        let id = id.into();
+        SimulationPolicy {
+            name: id.clone(),
+            id,
+            kind,
+            source_type,
+            document: document.to_owned(),
+        }
+    }
+}
+
+struct SimulationQuery {
+    limit: i32,
+    skip: i32,
+}
+
+impl SimulationQuery {
+    fn new(max_items: Option<&i32>, marker_type: Option<&MarkerType>) -> Self {
+        SimulationQuery {
+            limit: match max_items {
+                None => 100,
+                Some(v) => *v,
+            },
+            skip: match marker_type {
+                None => 0,
+                Some(marker_type) => marker_type.marker().unwrap().truncate_amount,
+            },
+        }
+    }
+}
+
+impl Pageable for &SimulationQuery {
+    fn limit(&self) -> i32 {
+        self.limit
+    }
+
+    fn skip(&self) -> i32 {
+        self.skip
+    }
+}
+
 pub(crate) async fn simulate_custom_policy<'a>(
-    tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &SimulateCustomPolicyRequest,
+    _tx: &mut Transaction<'a, Sqlite>, _ctx: &OperationCtx, input: &SimulateCustomPolicyRequest,
 ) -> Result<SimulateCustomPolicyOutput, ActionError> {
     input.validate("$")?;
 
-    let output = SimulateCustomPolicyOutput::builder().build();
+    let mut policies = input_policies(input.policy_input_list(), input.permissions_boundary_policy_input_list());
+    if let Some(resource_policy) = input.resource_policy() {
+        policies.push(resource_policy_input(resource_policy));
+    }
+
+    let query = SimulationQuery::new(input.max_items(), input.marker_type());
+    let results =
+        simulate(&policies, input.action_names(), input.resource_arns(), input.caller_arn(), input.context_entries())?;
+    let (evaluation_results, marker) = paginate(&query, results)?;
+
+    let output = SimulateCustomPolicyOutput::builder()
+        .set_evaluation_results(evaluation_results)
+        .set_is_truncated(marker.as_ref().map(|_v| true))
+        .set_marker(marker)
+        .build();
     Ok(output)
 }
 
@@ -23,6 +111,365 @@ pub(crate) async fn simulate_principal_policy<'a>(
 ) -> Result<SimulatePrincipalPolicyOutput, ActionError> {
     input.validate("$")?;
 
-    let output = SimulatePrincipalPolicyOutput::builder().build();
+    let policy_source_arn = input.policy_source_arn().unwrap().trim();
+    let mut policies = find_principal_policies(tx, ctx, policy_source_arn).await?;
+    if input.permissions_boundary_policy_input_list().is_some() {
+        // boundaries from the input replace the boundary which is attached to the principal
+        policies.retain(|policy| policy.kind != PolicyKind::PermissionsBoundary);
+    }
+    policies.extend(input_policies(input.policy_input_list(), input.permissions_boundary_policy_input_list()));
+    if let Some(resource_policy) = input.resource_policy() {
+        policies.push(resource_policy_input(resource_policy));
+    }
+
+    let caller_arn = match input.caller_arn() {
+        Some(caller_arn) => Some(caller_arn),
+        None if is_user_arn(policy_source_arn) => Some(policy_source_arn),
+        None => None,
+    };
+
+    let query = SimulationQuery::new(input.max_items(), input.marker_type());
+    let results =
+        simulate(&policies, input.action_names(), input.resource_arns(), caller_arn, input.context_entries())?;
+    let (evaluation_results, marker) = paginate(&query, results)?;
+
+    let output = SimulatePrincipalPolicyOutput::builder()
+        .set_evaluation_results(evaluation_results)
+        .set_is_truncated(marker.as_ref().map(|_v| true))
+        .set_marker(marker)
+        .build();
     Ok(output)
 }
+
+/// Loads inline, attached, group and permissions boundary policies of the user, group or role identified by ARN.
+pub(crate) async fn find_principal_policies<'a>(
+    tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, policy_source_arn: &str,
+) -> Result<Vec<SimulationPolicy>, ActionError> {
+    let (entity_type, entity_name) = parse_entity_arn(policy_source_arn)?;
+    let mut policies = vec![];
+    match entity_type {
+        "user" => {
+            let user = db::user::find_by_name(tx.as_mut(), ctx.account_id, entity_name)
+                .await?
+                .filter(|user| user.arn == policy_source_arn)
+                .ok_or_else(|| no_such_entity(policy_source_arn))?;
+
+            for policy in db::user_inline_policy::find_all_by_user_id(tx.as_mut(), user.id).await? {
+                let id = format!("user_{}_{}", user.username, policy.policy_name);
+                policies.push(SimulationPolicy::new(
+                    id,
+                    PolicyKind::Identity,
+                    PolicySourceType::User,
+                    &policy.policy_document,
+                ));
+            }
+            for policy in db::policy_version::find_default_by_user_id(tx.as_mut(), user.id).await? {
+                policies.push(managed_policy(&policy.policy_arn, PolicyKind::Identity, &policy.policy_document));
+            }
+            for group in db::group::find_all_by_user_id(tx.as_mut(), user.id).await? {
+                policies.extend(find_group_policies(tx, group.id, &group.group_name).await?);
+            }
+            if let Some(policy_id) = user.policy_id {
+                if let Some(policy) = db::policy_version::find_default_by_policy_id(tx.as_mut(), policy_id).await? {
+                    policies.push(managed_policy(
+                        &policy.policy_arn,
+                        PolicyKind::PermissionsBoundary,
+                        &policy.policy_document,
+                    ));
+                }
+            }
+        }
+        "group" => {
+            let group = db::group::find_by_name(tx.as_mut(), ctx.account_id, entity_name)
+                .await?
+                .filter(|group| group.arn == policy_source_arn)
+                .ok_or_else(|| no_such_entity(policy_source_arn))?;
+            policies.extend(find_group_policies(tx, group.id, &group.group_name).await?);
+        }
+        "role" => {
+            let role = db::role::find_by_name(tx.as_mut(), ctx.account_id, entity_name)
+                .await?
+                .filter(|role| role.arn == policy_source_arn)
+                .ok_or_else(|| no_such_entity(policy_source_arn))?;
+
+            for policy in db::role_inline_policy::find_all_by_role_id(tx.as_mut(), role.id).await? {
+                let id = format!("role_{}_{}", role.role_name, policy.policy_name);
+                policies.push(SimulationPolicy::new(
+                    id,
+                    PolicyKind::Identity,
+                    PolicySourceType::Role,
+                    &policy.policy_document,
+                ));
+            }
+            for policy in db::policy_version::find_default_by_role_id(tx.as_mut(), role.id).await? {
+                policies.push(managed_policy(&policy.policy_arn, PolicyKind::Identity, &policy.policy_document));
+            }
+            if let Some(policy_id) = role.policy_id {
+                if let Some(policy) = db::policy_version::find_default_by_policy_id(tx.as_mut(), policy_id).await? {
+                    policies.push(managed_policy(
+                        &policy.policy_arn,
+                        PolicyKind::PermissionsBoundary,
+                        &policy.policy_document,
+                    ));
+                }
+            }
+        }
+        _ => {
+            return Err(ActionError::new(
+                ApiErrorKind::InvalidInput,
+                format!("PolicySourceArn {policy_source_arn} must identify an IAM user, group or role.").as_str(),
+            ))
+        }
+    }
+    Ok(policies)
+}
+
+async fn find_group_policies<'a>(
+    tx: &mut Transaction<'a, Sqlite>, group_id: i64, group_name: &str,
+) -> Result<Vec<SimulationPolicy>, ActionError> {
+    let mut policies = vec![];
+    for policy in db::group_inline_policy::find_all_by_group_id(tx.as_mut(), group_id).await? {
+        let id = format!("group_{}_{}", group_name, policy.policy_name);
+        policies.push(SimulationPolicy::new(
+            id,
+            PolicyKind::Identity,
+            PolicySourceType::Group,
+            &policy.policy_document,
+        ));
+    }
+    for policy in db::policy_version::find_default_by_group_id(tx.as_mut(), group_id).await? {
+        policies.push(managed_policy(&policy.policy_arn, PolicyKind::Identity, &policy.policy_document));
+    }
+    Ok(policies)
+}
+
+fn managed_policy(policy_arn: &str, kind: PolicyKind, document: &str) -> SimulationPolicy {
+    // managed policies owned by the `aws` namespace have ARNs like `arn:aws:iam::aws:policy/...`
+    let source_type = match policy_arn.split(':').nth(4) {
+        Some("aws") => PolicySourceType::AwsManaged,
+        _ => PolicySourceType::UserManaged,
+    };
+    let policy_name = policy_arn.rsplit('/').next().unwrap_or(policy_arn);
+    SimulationPolicy {
+        name: policy_name.to_owned(),
+        ..SimulationPolicy::new(policy_arn, kind, source_type, document)
+    }
+}
+
+pub(crate) fn input_policies(
+    policy_input_list: Option<&[PolicyDocumentType]>,
+    permissions_boundary_policy_input_list: Option<&[PolicyDocumentType]>,
+) -> Vec<SimulationPolicy> {
+    let mut policies = vec![];
+    for (id, policy) in policy_input_list.unwrap_or_default().iter().enumerate() {
+        policies.push(SimulationPolicy::new(
+            format!("PolicyInputList.{}", id + 1),
+            PolicyKind::Identity,
+            PolicySourceType::None,
+            policy.document().unwrap(),
+        ));
+    }
+    for (id, policy) in permissions_boundary_policy_input_list
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+    {
+        policies.push(SimulationPolicy::new(
+            format!("PermissionsBoundaryPolicyInputList.{}", id + 1),
+            PolicyKind::PermissionsBoundary,
+            PolicySourceType::None,
+            policy.document().unwrap(),
+        ));
+    }
+    policies
+}
+
+fn resource_policy_input(document: &str) -> SimulationPolicy {
+    SimulationPolicy::new("ResourcePolicy", PolicyKind::Resource, PolicySourceType::Resource, document)
+}
+
+pub(crate) fn create_policy_set(policies: &[SimulationPolicy]) -> Result<PolicySet, ActionError> {
+    let mut policy_set = PolicySet::new();
+    for policy in policies {
+        let document: LocalPolicyDocument = serde_json::from_str(&policy.document).map_err(|_err| {
+            ActionError::new(
+                ApiErrorKind::PolicyEvaluation,
+                format!("Failed to parse policy document '{}'.", policy.name).as_str(),
+            )
+        })?;
+        match policy.kind {
+            PolicyKind::Identity => policy_set.add_identity_policy(&policy.id, document),
+            PolicyKind::Resource => policy_set.add_resource_policy(&policy.id, document),
+            PolicyKind::PermissionsBoundary => policy_set.add_permissions_boundary(&policy.id, document),
+        };
+    }
+    Ok(policy_set)
+}
+
+/// Evaluates every action against every resource. Returns one result per action.
+fn simulate(
+    policies: &[SimulationPolicy], action_names: Option<&[ActionNameType]>, resource_arns: Option<&[ResourceNameType]>,
+    caller_arn: Option<&str>, context_entries: Option<&[ContextEntry]>,
+) -> Result<Vec<EvaluationResult>, ActionError> {
+    let policy_set = create_policy_set(policies)?;
+    let mut policies_by_id = HashMap::new();
+    for policy in policies {
+        policies_by_id.entry(policy.id.as_str()).or_insert(policy);
+    }
+
+    let resources: Vec<&str> = match resource_arns {
+        Some(resource_arns) if !resource_arns.is_empty() => resource_arns.iter().map(|arn| arn.deref()).collect(),
+        _ => vec![],
+    };
+
+    let mut results = vec![];
+    for action_name in action_names.unwrap_or_default() {
+        let action_name: &str = action_name;
+        if resources.is_empty() {
+            let request = request_context(action_name, ANY_RESOURCE, caller_arn, context_entries);
+            let evaluation = policy_set.evaluate(&request);
+            let result = EvaluationResult::builder()
+                .eval_action_name(action_name)
+                .eval_resource_name(ANY_RESOURCE)
+                .eval_decision(decision_type(&evaluation.decision))
+                .set_matched_statements(Some(statements(&evaluation, &policies_by_id)))
+                .set_missing_context_values(Some(evaluation.missing_context_values.clone()))
+                .set_permissions_boundary_decision_detail(boundary_decision_detail(&evaluation))
+                .build()
+                .unwrap();
+            results.push(result);
+            continue;
+        }
+
+        let mut decision = Decision::Allow;
+        let mut matched_statements = vec![];
+        let mut missing_context_values = BTreeSet::new();
+        let mut resource_specific_results = vec![];
+        for resource in &resources {
+            let request = request_context(action_name, resource, caller_arn, context_entries);
+            let evaluation = policy_set.evaluate(&request);
+            decision = combine(decision, &evaluation.decision);
+
+            let resource_statements = statements(&evaluation, &policies_by_id);
+            for statement in &resource_statements {
+                if !matched_statements.contains(statement) {
+                    matched_statements.push(statement.clone());
+                }
+            }
+            missing_context_values.extend(evaluation.missing_context_values.iter().cloned());
+
+            let resource_result = ResourceSpecificResult::builder()
+                .eval_resource_name(*resource)
+                .eval_resource_decision(decision_type(&evaluation.decision))
+                .set_matched_statements(Some(resource_statements))
+                .set_missing_context_values(Some(evaluation.missing_context_values.clone()))
+                .set_permissions_boundary_decision_detail(boundary_decision_detail(&evaluation))
+                .build()
+                .unwrap();
+            resource_specific_results.push(resource_result);
+        }
+
+        let result = EvaluationResult::builder()
+            .eval_action_name(action_name)
+            .eval_decision(decision_type(&decision))
+            .set_matched_statements(Some(matched_statements))
+            .set_missing_context_values(Some(missing_context_values.into_iter().collect()))
+            .set_resource_specific_results(Some(resource_specific_results))
+            .build()
+            .unwrap();
+        results.push(result);
+    }
+    Ok(results)
+}
+
+fn request_context(
+    action_name: &str, resource: &str, caller_arn: Option<&str>, context_entries: Option<&[ContextEntry]>,
+) -> RequestContext {
+    let mut request = RequestContext::new(action_name, resource);
+    if let Some(caller_arn) = caller_arn {
+        request = request.with_principal(caller_arn);
+    }
+    for entry in context_entries.unwrap_or_default() {
+        if let Some(key) = entry.context_key_name() {
+            let values = entry
+                .context_key_values()
+                .unwrap_or_default()
+                .iter()
+                .map(|value| value.to_string())
+                .collect();
+            request = request.with_context_values(key, values);
+        }
+    }
+    request
+}
+
+/// Decision for the action on all the resources: any explicit deny wins, otherwise every resource must be allowed.
+fn combine(current: Decision, next: &Decision) -> Decision {
+    match (current, next) {
+        (Decision::ExplicitDeny, _) | (_, Decision::ExplicitDeny) => Decision::ExplicitDeny,
+        (Decision::ImplicitDeny, _) | (_, Decision::ImplicitDeny) => Decision::ImplicitDeny,
+        _ => Decision::Allow,
+    }
+}
+
+fn decision_type(decision: &Decision) -> PolicyEvaluationDecisionType {
+    PolicyEvaluationDecisionType::from(decision.as_str())
+}
+
+fn statements(
+    evaluation: &evaluator::EvaluationResult, policies_by_id: &HashMap<&str, &SimulationPolicy>,
+) -> Vec<Statement> {
+    evaluation
+        .matched_statements
+        .iter()
+        .map(|statement| {
+            let policy = policies_by_id.get(statement.source_policy_id.as_str());
+            Statement::builder()
+                .source_policy_id(policy.map_or(statement.source_policy_id.as_str(), |policy| policy.name.as_str()))
+                .set_source_policy_type(policy.map(|policy| policy.source_type.clone()))
+                .build()
+        })
+        .collect()
+}
+
+fn boundary_decision_detail(evaluation: &evaluator::EvaluationResult) -> Option<PermissionsBoundaryDecisionDetail> {
+    evaluation.allowed_by_permissions_boundary.map(|allowed| {
+        PermissionsBoundaryDecisionDetail::builder()
+            .allowed_by_permissions_boundary(allowed)
+            .build()
+    })
+}
+
+fn paginate(
+    query: &SimulationQuery, results: Vec<EvaluationResult>,
+) -> Result<(Option<Vec<EvaluationResult>>, Option<String>), ActionError> {
+    let remaining: Vec<EvaluationResult> = results.into_iter().skip(query.skip.max(0) as usize).collect();
+    let marker = super::common::create_encoded_marker(query, remaining.len())?;
+    let evaluation_results = remaining.into_iter().take(query.limit.max(0) as usize).collect();
+    Ok((Some(evaluation_results), marker))
+}
+
+/// Splits ARN like `arn:aws:iam::123456789012:user/division/name` into entity type and name.
+fn parse_entity_arn(arn: &str) -> Result<(&str, &str), ActionError> {
+    let invalid_arn = || ActionError::new(ApiErrorKind::InvalidInput, format!("Invalid ARN: {arn}.").as_str());
+    let parts: Vec<&str> = arn.splitn(6, ':').collect();
+    if parts.len() != 6 || parts[0] != "arn" || parts[2] != "iam" {
+        return Err(invalid_arn());
+    }
+    let resource = parts[5];
+    let entity_type = resource.split('/').next().ok_or_else(invalid_arn)?;
+    let entity_name = resource
+        .rsplit('/')
+        .next()
+        .filter(|name| *name != resource)
+        .ok_or_else(invalid_arn)?;
+    Ok((entity_type, entity_name))
+}
+
+fn is_user_arn(arn: &str) -> bool {
+    matches!(parse_entity_arn(arn), Ok(("user", _)))
+}
+
+fn no_such_entity(arn: &str) -> ActionError {
+    ActionError::new(ApiErrorKind::NoSuchEntity, format!("The entity with ARN {arn} cannot be found.").as_str())
+}
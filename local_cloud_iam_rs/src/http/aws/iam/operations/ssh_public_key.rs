@@ -112,6 +112,16 @@ pub(crate) async fn delete_ssh_public_key<'a>(
 ) -> Result<DeleteSshPublicKeyOutput, ActionError> {
     input.validate("$")?;
 
+    let user_id = super::user::find_id_by_name(tx.as_mut(), ctx.account_id, input.user_name().unwrap().trim()).await?;
+    let key_id = input.ssh_public_key_id().unwrap();
+    let is_deleted = db::ssh_public_key::delete(tx.as_mut(), user_id, key_id).await?;
+    if !is_deleted {
+        return Err(ActionError::new(
+            ApiErrorKind::NoSuchEntity,
+            format!("The Public Key with id {key_id} cannot be found.").as_str(),
+        ));
+    }
+
     let output = DeleteSshPublicKeyOutput::builder().build();
     Ok(output)
 }
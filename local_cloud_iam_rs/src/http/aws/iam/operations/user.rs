@@ -20,18 +20,21 @@ use aws_smithy_types::DateTime;
 use chrono::Utc;
 use sqlx::{Executor, Sqlite, Transaction};
 
+use local_cloud_iam_policy_document::types::PolicyDocumentKind;
 use local_cloud_validate::NamedValidator;
 
 use crate::http::aws::iam::actions::error::ApiErrorKind;
 use crate::http::aws::iam::db::types::inline_policy::{DbInlinePolicy, ListInlinePoliciesQuery};
+use crate::http::aws::iam::db::types::policy::ListAttachedPoliciesQuery;
 use crate::http::aws::iam::db::types::resource_identifier::ResourceType;
 use crate::http::aws::iam::db::types::tags::ListTagsQuery;
 use crate::http::aws::iam::db::types::user::{
     InsertUser, InsertUserBuilder, InsertUserBuilderError, SelectUser, UpdateUserQuery,
 };
-use crate::http::aws::iam::operations::common::create_resource_id;
+use crate::http::aws::iam::operations::common::{check_delete_conflict, create_resource_id};
 use crate::http::aws::iam::operations::ctx::OperationCtx;
 use crate::http::aws::iam::operations::error::ActionError;
+use crate::http::aws::iam::quotas::Quota;
 use crate::http::aws::iam::types::attach_user_policy::AttachUserPolicyRequest;
 use crate::http::aws::iam::types::create_user::CreateUserRequest;
 use crate::http::aws::iam::types::delete_user::DeleteUserRequest;
@@ -49,12 +52,18 @@ use crate::http::aws::iam::types::put_user_policy::PutUserPolicyRequest;
 use crate::http::aws::iam::types::tag_user::TagUserRequest;
 use crate::http::aws::iam::types::untag_user::UntagUserRequest;
 use crate::http::aws::iam::types::update_user::UpdateUserRequest;
-use crate::http::aws::iam::{constants, db};
+use crate::http::aws::iam::{constants, db, quotas};
 
 pub async fn create_user<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &CreateUserRequest,
 ) -> Result<CreateUserOutput, ActionError> {
     input.validate("$")?;
+    let user_name = input.user_name().unwrap().trim();
+    super::common::check_not_exists(
+        db::user::find_id_by_name(tx.as_mut(), ctx.account_id, user_name).await?,
+        format!("User with name {user_name} already exists.").as_str(),
+    )?;
+    quotas::check_account(tx, ctx.account_id, Quota::Users).await?;
     let current_time = Utc::now().timestamp();
 
     let user_id = create_resource_id(tx, constants::user::PREFIX, ResourceType::User).await?;
@@ -133,6 +142,49 @@ where
     }
 }
 
+/// Finds IAM user by name. If the name is not specified, the user is determined implicitly
+/// based on the access key ID signing the request.
+pub(crate) async fn find_by_name_or_caller<'a, E>(
+    ctx: &OperationCtx, executor: E, user_name: Option<&str>,
+) -> Result<SelectUser, ActionError>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    match user_name {
+        Some(user_name) => find_by_name(ctx, executor, user_name.trim()).await,
+        None => {
+            let principal_id = caller_unique_id(ctx)?;
+            match db::user::find_by_unique_id(executor, principal_id).await? {
+                Some(user) => Ok(user),
+                None => Err(ActionError::new(ApiErrorKind::NoSuchEntity, "IAM user of the caller doesn't exist.")),
+            }
+        }
+    }
+}
+
+/// Same as [`find_by_name_or_caller`] but returns internal ID of the user only.
+pub(crate) async fn find_id_by_name_or_caller<'a, E>(
+    ctx: &OperationCtx, executor: E, user_name: Option<&str>,
+) -> Result<i64, ActionError>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    match user_name {
+        Some(user_name) => find_id_by_name(executor, ctx.account_id, user_name.trim()).await,
+        None => find_by_name_or_caller(ctx, executor, None).await.map(|user| user.id),
+    }
+}
+
+fn caller_unique_id(ctx: &OperationCtx) -> Result<&str, ActionError> {
+    match &ctx.caller.principal {
+        Some(principal) => Ok(&principal.id),
+        None => Err(ActionError::new(
+            ApiErrorKind::InvalidInput,
+            "Must specify userName when calling with non-User credentials",
+        )),
+    }
+}
+
 pub(crate) async fn find_id_by_name<'a, E>(executor: E, account_id: i64, user_name: &str) -> Result<i64, ActionError>
 where
     E: 'a + Executor<'a, Database = Sqlite>,
@@ -159,7 +211,13 @@ pub(crate) async fn attach_user_policy<'a>(
     let policy_arn = input.policy_arn().unwrap();
     let found_policy_id = super::policy::find_id_by_arn(tx.as_mut(), ctx.account_id, policy_arn).await?;
 
-    db::user::assign_policy_to_user(tx.as_mut(), found_user_id, found_policy_id).await?;
+    // attaching the policy again is a no-op which is not limited by quotas
+    if !db::policy::is_attached_to_user(tx.as_mut(), found_user_id, found_policy_id).await? {
+        let attached_count = db::user::count_attached_policies(tx.as_mut(), found_user_id).await?;
+        quotas::check(tx, Quota::AttachedPoliciesPerUser, attached_count).await?;
+        quotas::check_account(tx, ctx.account_id, Quota::PolicyVersionsInUse).await?;
+        db::user::assign_policy_to_user(tx.as_mut(), found_user_id, found_policy_id).await?;
+    }
 
     let output = AttachUserPolicyOutput::builder().build();
     Ok(output)
@@ -261,6 +319,10 @@ pub(crate) async fn put_user_policy<'a>(
     tx: &mut Transaction<'a, Sqlite>, ctx: &OperationCtx, input: &PutUserPolicyRequest,
 ) -> Result<PutUserPolicyOutput, ActionError> {
     input.validate("$")?;
+    input
+        .policy_document_type()
+        .unwrap()
+        .validate_as(PolicyDocumentKind::Identity)?;
 
     let user_id = find_id_by_name(tx.as_mut(), ctx.account_id, input.user_name().unwrap().trim()).await?;
 
@@ -382,7 +444,19 @@ pub(crate) async fn list_attached_user_policies<'a>(
 ) -> Result<ListAttachedUserPoliciesOutput, ActionError> {
     input.validate("$")?;
 
-    let output = ListAttachedUserPoliciesOutput::builder().build();
+    let user_id = find_id_by_name(tx.as_mut(), ctx.account_id, input.user_name().unwrap().trim()).await?;
+
+    let query = ListAttachedPoliciesQuery::new(user_id, input.path_prefix(), input.max_items(), input.marker_type());
+    let found_policies = db::policy::list_attached_to_user(tx.as_mut(), &query).await?;
+
+    let policies = super::common::convert_and_limit(&found_policies, query.limit);
+    let marker = super::common::create_encoded_marker(&query, found_policies.len())?;
+
+    let output = ListAttachedUserPoliciesOutput::builder()
+        .set_attached_policies(policies)
+        .set_is_truncated(marker.as_ref().map(|_v| true))
+        .set_marker(marker)
+        .build();
     Ok(output)
 }
 
@@ -391,6 +465,48 @@ pub(crate) async fn delete_user<'a>(
 ) -> Result<DeleteUserOutput, ActionError> {
     input.validate("$")?;
 
+    let user_id = find_id_by_name(tx.as_mut(), ctx.account_id, input.user_name().unwrap().trim()).await?;
+
+    check_delete_conflict(
+        db::login_profile::count_by_user_id(tx.as_mut(), user_id).await?,
+        "Cannot delete entity, must delete login profile first.",
+    )?;
+    check_delete_conflict(
+        db::access_key::count_by_user_id(tx.as_mut(), user_id).await?,
+        "Cannot delete entity, must delete access keys first.",
+    )?;
+    check_delete_conflict(
+        db::sighing_certificate::count_by_user_id(tx.as_mut(), user_id).await?,
+        "Cannot delete entity, must delete signing certificates first.",
+    )?;
+    check_delete_conflict(
+        db::ssh_public_key::count_by_user_id(tx.as_mut(), user_id).await?,
+        "Cannot delete entity, must delete SSH public keys first.",
+    )?;
+    check_delete_conflict(
+        db::service_specific_credential::count_by_user_id(tx.as_mut(), user_id).await?,
+        "Cannot delete entity, must delete service specific credentials first.",
+    )?;
+    check_delete_conflict(
+        db::mfa_device::count_by_user_id(tx.as_mut(), user_id).await?,
+        "Cannot delete entity, must delete MFA device first.",
+    )?;
+    check_delete_conflict(
+        db::user_inline_policy::count_by_user_id(tx.as_mut(), user_id).await?,
+        "Cannot delete entity, must delete policies first.",
+    )?;
+    check_delete_conflict(
+        db::user::count_attached_policies(tx.as_mut(), user_id).await?,
+        "Cannot delete entity, must detach all policies first.",
+    )?;
+    check_delete_conflict(
+        db::user::count_groups(tx.as_mut(), user_id).await?,
+        "Cannot delete entity, must remove user from all groups first.",
+    )?;
+
+    db::Tags::User.delete_by_parent_id(tx, user_id).await?;
+    db::user::delete(tx, user_id).await?;
+
     let output = DeleteUserOutput::builder().build();
     Ok(output)
 }
@@ -400,6 +516,16 @@ pub(crate) async fn delete_user_policy<'a>(
 ) -> Result<DeleteUserPolicyOutput, ActionError> {
     input.validate("$")?;
 
+    let user_id = find_id_by_name(tx.as_mut(), ctx.account_id, input.user_name().unwrap().trim()).await?;
+    let policy_name = input.policy_name().unwrap();
+    let is_deleted = db::user_inline_policy::delete_by_user_id_and_name(tx.as_mut(), user_id, policy_name).await?;
+    if !is_deleted {
+        return Err(ActionError::new(
+            ApiErrorKind::NoSuchEntity,
+            format!("The user policy with name {policy_name} cannot be found.").as_str(),
+        ));
+    }
+
     let output = DeleteUserPolicyOutput::builder().build();
     Ok(output)
 }
@@ -409,6 +535,18 @@ pub(crate) async fn detach_user_policy<'a>(
 ) -> Result<DetachUserPolicyOutput, ActionError> {
     input.validate("$")?;
 
+    let user_id = find_id_by_name(tx.as_mut(), ctx.account_id, input.user_name().unwrap().trim()).await?;
+    let policy_arn = input.policy_arn().unwrap().trim();
+    let policy_id = super::policy::find_id_by_arn(tx.as_mut(), ctx.account_id, policy_arn).await?;
+
+    let is_detached = db::user::detach_policy_from_user(tx.as_mut(), user_id, policy_id).await?;
+    if !is_detached {
+        return Err(ActionError::new(
+            ApiErrorKind::NoSuchEntity,
+            format!("Policy {policy_arn} was not found.").as_str(),
+        ));
+    }
+
     let output = DetachUserPolicyOutput::builder().build();
     Ok(output)
 }
@@ -5,6 +5,7 @@ use local_cloud_axum::local::web::XmlResponse;
 use local_cloud_xml::{write_iso8061_datetime_value_tag, write_request_metadata_tag, write_tag_with_value};
 
 use crate::http::aws::iam::constants;
+use crate::http::aws::iam::operations::access_details::NOT_AVAILABLE;
 use crate::http::aws::iam::outputs::wrapper::OutputWrapper;
 
 pub type LocalGetAccessKeyLastUsedOutput = OutputWrapper<GetAccessKeyLastUsedOutput>;
@@ -22,17 +23,24 @@ impl From<LocalGetAccessKeyLastUsedOutput> for XmlResponse {
         let mut result_tag = response_tag.start_el("GetAccessKeyLastUsedResult").finish();
 
         write_tag_with_value(&mut result_tag, "UserName", val.inner.user_name());
-        if let Some(key_last_used) = val.inner.access_key_last_used() {
-            let mut key_last_used_tag = result_tag.start_el("AccessKeyLastUsed").finish();
-
-            write_tag_with_value(&mut key_last_used_tag, "Region", Some(&key_last_used.region));
-            write_iso8061_datetime_value_tag(
-                &mut key_last_used_tag,
-                "LastUsedDate",
-                Some(&key_last_used.last_used_date()),
-            );
-            write_tag_with_value(&mut key_last_used_tag, "ServiceName", Some(&key_last_used.service_name));
+        let mut key_last_used_tag = result_tag.start_el("AccessKeyLastUsed").finish();
+        match val.inner.access_key_last_used() {
+            Some(key_last_used) => {
+                write_tag_with_value(&mut key_last_used_tag, "Region", Some(&key_last_used.region));
+                write_iso8061_datetime_value_tag(
+                    &mut key_last_used_tag,
+                    "LastUsedDate",
+                    Some(key_last_used.last_used_date()),
+                );
+                write_tag_with_value(&mut key_last_used_tag, "ServiceName", Some(&key_last_used.service_name));
+            }
+            // keys which have never been used have no date
+            None => {
+                write_tag_with_value(&mut key_last_used_tag, "Region", Some(NOT_AVAILABLE));
+                write_tag_with_value(&mut key_last_used_tag, "ServiceName", Some(NOT_AVAILABLE));
+            }
         }
+        key_last_used_tag.finish();
         result_tag.finish();
 
         write_request_metadata_tag(&mut response_tag, "ResponseMetadata", "RequestId", val.request_id);
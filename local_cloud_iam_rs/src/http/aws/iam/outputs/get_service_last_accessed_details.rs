@@ -1,4 +1,5 @@
 use aws_sdk_iam::operation::get_service_last_accessed_details::GetServiceLastAccessedDetailsOutput;
+use aws_sdk_iam::types::JobStatusType;
 use aws_smithy_xml::encode::XmlWriter;
 
 use local_cloud_axum::local::web::XmlResponse;
@@ -29,7 +30,13 @@ impl From<LocalGetServiceLastAccessedDetailsOutput> for XmlResponse {
             "ServicesLastAccessed",
             val.inner.services_last_accessed(),
         );
-        write_iso8061_datetime_value_tag(&mut result_tag, "JobCompletionDate", Some(val.inner.job_completion_date()));
+        if val.inner.job_status != JobStatusType::InProgress {
+            write_iso8061_datetime_value_tag(
+                &mut result_tag,
+                "JobCompletionDate",
+                Some(val.inner.job_completion_date()),
+            );
+        }
         if let Some(token) = val.inner.marker() {
             write_tag_with_value(&mut result_tag, "Marker", Some(token));
         }
@@ -1,4 +1,5 @@
 use aws_sdk_iam::operation::get_service_last_accessed_details_with_entities::GetServiceLastAccessedDetailsWithEntitiesOutput;
+use aws_sdk_iam::types::JobStatusType;
 use aws_smithy_xml::encode::XmlWriter;
 
 use local_cloud_axum::local::web::XmlResponse;
@@ -27,7 +28,13 @@ impl From<LocalGetServiceLastAccessedDetailsWithEntitiesOutput> for XmlResponse
         write_tag_with_value(&mut result_tag, "JobStatus", Some(val.inner.job_status.as_str()));
         write_iso8061_datetime_value_tag(&mut result_tag, "JobCreationDate", Some(val.inner.job_creation_date()));
         super::entity_details::write_slice(&mut result_tag, "EntityDetailsList", val.inner.entity_details_list());
-        write_iso8061_datetime_value_tag(&mut result_tag, "JobCompletionDate", Some(val.inner.job_completion_date()));
+        if val.inner.job_status != JobStatusType::InProgress {
+            write_iso8061_datetime_value_tag(
+                &mut result_tag,
+                "JobCompletionDate",
+                Some(val.inner.job_completion_date()),
+            );
+        }
         if let Some(token) = val.inner.marker() {
             write_tag_with_value(&mut result_tag, "Marker", Some(token));
         }
@@ -26,7 +26,7 @@ impl From<LocalGetServiceLinkedRoleDeletionStatusOutput> for XmlResponse {
         write_tag_with_value(&mut result_tag, "Status", Some(val.inner.status().as_str()));
 
         if let Some(reason_type) = val.inner.reason() {
-            let mut reason_type_tag = result_tag.start_el("DeletionTaskFailureReasonType").finish();
+            let mut reason_type_tag = result_tag.start_el("Reason").finish();
             write_tag_with_value(&mut reason_type_tag, "Reason", reason_type.reason());
             super::role_usages::write_slice(&mut reason_type_tag, "RoleUsageList", reason_type.role_usage_list());
             reason_type_tag.finish();
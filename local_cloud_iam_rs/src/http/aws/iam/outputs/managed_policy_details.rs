@@ -4,7 +4,7 @@ use aws_smithy_xml::encode::ScopeWriter;
 use local_cloud_xml::{write_iso8061_datetime_value_tag, write_tag_with_value};
 
 pub(crate) fn write_slice(parent_tag: &mut ScopeWriter, policies: &[ManagedPolicyDetail]) {
-    let mut policies_tag = parent_tag.start_el("GroupDetailList").finish();
+    let mut policies_tag = parent_tag.start_el("Policies").finish();
     for policy in policies {
         write(&mut policies_tag, "member", policy);
     }
@@ -19,7 +19,13 @@ pub(crate) fn write(parent_tag: &mut ScopeWriter, wrapper_tag_name: &str, policy
     write_tag_with_value(&mut wrapper_tag, "Path", policy.path());
     write_tag_with_value(&mut wrapper_tag, "Arn", policy.arn());
     write_tag_with_value(&mut wrapper_tag, "AttachmentCount", policy.attachment_count().map(|v| v.to_string()));
+    write_tag_with_value(
+        &mut wrapper_tag,
+        "PermissionsBoundaryUsageCount",
+        policy.permissions_boundary_usage_count().map(|v| v.to_string()),
+    );
     write_tag_with_value(&mut wrapper_tag, "IsAttachable", Some(policy.is_attachable.to_string()));
+    write_tag_with_value(&mut wrapper_tag, "Description", policy.description());
     write_iso8061_datetime_value_tag(&mut wrapper_tag, "CreateDate", policy.create_date());
     write_iso8061_datetime_value_tag(&mut wrapper_tag, "UpdateDate", policy.update_date());
     super::policy_versions::write_slice(&mut wrapper_tag, "PolicyVersionList", policy.policy_version_list());
@@ -36,6 +36,7 @@ pub(crate) fn write(parent_tag: &mut ScopeWriter, wrapper_tag_name: &str, role:
         "AttachedManagedPolicies",
         role.attached_managed_policies(),
     );
+    super::policy_details::write_slice(&mut wrapper_tag, "RolePolicyList", role.role_policy_list());
     super::instance_profiles::write_slice(&mut wrapper_tag, "InstanceProfileList", role.instance_profile_list());
     super::tags::write_slice(&mut wrapper_tag, role.tags());
     wrapper_tag.finish();
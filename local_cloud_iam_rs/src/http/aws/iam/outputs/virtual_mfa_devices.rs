@@ -1,6 +1,6 @@
 use aws_sdk_iam::types::VirtualMfaDevice;
 use aws_smithy_xml::encode::ScopeWriter;
-use data_encoding::{BASE32, BASE64};
+use data_encoding::BASE64;
 
 use local_cloud_xml::{write_iso8061_datetime_value_tag, write_tag_with_value};
 
@@ -16,10 +16,11 @@ pub(crate) fn write(parent_tag: &mut ScopeWriter, wrapper_tag_name: &str, device
     let mut wrapper_tag = parent_tag.start_el(wrapper_tag_name).finish();
     write_iso8061_datetime_value_tag(&mut wrapper_tag, "EnableDate", device.enable_date());
     write_tag_with_value(&mut wrapper_tag, "SerialNumber", Some(device.serial_number()));
+    // the seed blob already holds the base32 string, like any other blob it is written base64 encoded
     write_tag_with_value(
         &mut wrapper_tag,
         "Base32StringSeed",
-        device.base32_string_seed().map(|v| BASE32.encode(v.as_ref())),
+        device.base32_string_seed().map(|v| BASE64.encode(v.as_ref())),
     );
     write_tag_with_value(&mut wrapper_tag, "QRCodePNG", device.qr_code_png().map(|v| BASE64.encode(v.as_ref())));
     if let Some(user) = device.user() {
@@ -0,0 +1,166 @@
+use sqlx::{Sqlite, Transaction};
+
+use local_cloud_db::LocalDb;
+
+use crate::http::aws::iam::actions::error::ApiErrorKind;
+use crate::http::aws::iam::db;
+use crate::http::aws::iam::db::types::account::SelectAccountSummary;
+use crate::http::aws::iam::operations::error::ActionError;
+
+/// IAM quotas which are reported by `GetAccountSummary` and enforced by create operations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Quota {
+    Users,
+    Groups,
+    Roles,
+    Policies,
+    InstanceProfiles,
+    ServerCertificates,
+    Providers,
+    PolicyVersionsInUse,
+    VersionsPerPolicy,
+    AccessKeysPerUser,
+    SigningCertificatesPerUser,
+    GroupsPerUser,
+    AttachedPoliciesPerUser,
+    AttachedPoliciesPerGroup,
+    AttachedPoliciesPerRole,
+}
+
+impl Quota {
+    pub(crate) const ALL: [Quota; 15] = [
+        Quota::Users,
+        Quota::Groups,
+        Quota::Roles,
+        Quota::Policies,
+        Quota::InstanceProfiles,
+        Quota::ServerCertificates,
+        Quota::Providers,
+        Quota::PolicyVersionsInUse,
+        Quota::VersionsPerPolicy,
+        Quota::AccessKeysPerUser,
+        Quota::SigningCertificatesPerUser,
+        Quota::GroupsPerUser,
+        Quota::AttachedPoliciesPerUser,
+        Quota::AttachedPoliciesPerGroup,
+        Quota::AttachedPoliciesPerRole,
+    ];
+
+    /// Name of the quota without the `Quota` suffix, as it is used in error messages.
+    pub(crate) fn as_str(&self) -> &str {
+        match self {
+            Quota::Users => "Users",
+            Quota::Groups => "Groups",
+            Quota::Roles => "Roles",
+            Quota::Policies => "Policies",
+            Quota::InstanceProfiles => "InstanceProfiles",
+            Quota::ServerCertificates => "ServerCertificates",
+            Quota::Providers => "Providers",
+            Quota::PolicyVersionsInUse => "PolicyVersionsInUse",
+            Quota::VersionsPerPolicy => "VersionsPerPolicy",
+            Quota::AccessKeysPerUser => "AccessKeysPerUser",
+            Quota::SigningCertificatesPerUser => "SigningCertificatesPerUser",
+            Quota::GroupsPerUser => "GroupsPerUser",
+            Quota::AttachedPoliciesPerUser => "AttachedPoliciesPerUser",
+            Quota::AttachedPoliciesPerGroup => "AttachedPoliciesPerGroup",
+            Quota::AttachedPoliciesPerRole => "AttachedPoliciesPerRole",
+        }
+    }
+
+    /// Key of the quota in the summary map, e.g. `UsersQuota`. The same key is used in the configuration.
+    pub(crate) fn summary_key(&self) -> String {
+        format!("{}Quota", self.as_str())
+    }
+
+    /// Default values match the default quotas of AWS.
+    pub(crate) fn default_value(&self) -> i32 {
+        match self {
+            Quota::Users => 5000,
+            Quota::Groups => 300,
+            Quota::Roles => 1000,
+            Quota::Policies => 1500,
+            Quota::InstanceProfiles => 1000,
+            Quota::ServerCertificates => 20,
+            Quota::Providers => 100,
+            Quota::PolicyVersionsInUse => 10000,
+            Quota::VersionsPerPolicy => 5,
+            Quota::AccessKeysPerUser => 2,
+            Quota::SigningCertificatesPerUser => 2,
+            Quota::GroupsPerUser => 10,
+            Quota::AttachedPoliciesPerUser => 10,
+            Quota::AttachedPoliciesPerGroup => 10,
+            Quota::AttachedPoliciesPerRole => 10,
+        }
+    }
+
+    /// Number of the account entities which are limited by the quota. Quotas which limit entities per user, group,
+    /// role or policy are not counted for the whole account.
+    pub(crate) fn account_count(&self, summary: &SelectAccountSummary) -> Option<i32> {
+        match self {
+            Quota::Users => Some(summary.users),
+            Quota::Groups => Some(summary.groups),
+            Quota::Roles => Some(summary.roles),
+            Quota::Policies => Some(summary.policies),
+            Quota::InstanceProfiles => Some(summary.instance_profiles),
+            Quota::ServerCertificates => Some(summary.server_certificates),
+            Quota::Providers => Some(summary.providers),
+            Quota::PolicyVersionsInUse => Some(summary.policy_versions_in_use),
+            _ => None,
+        }
+    }
+
+    fn from_summary_key(key: &str) -> Option<Quota> {
+        Quota::ALL.into_iter().find(|quota| quota.summary_key() == key)
+    }
+}
+
+/// Stores quota values into the DB. Quotas which are not configured get their default values.
+pub(crate) async fn load(db: &LocalDb, configured: &[(String, i32)]) -> Result<(), ActionError> {
+    for (key, _) in configured {
+        if Quota::from_summary_key(key).is_none() {
+            log::warn!("Unknown IAM quota '{}' is ignored", key);
+        }
+    }
+
+    let mut tx = db.new_tx().await?;
+    for quota in Quota::ALL {
+        let key = quota.summary_key();
+        let value = configured
+            .iter()
+            .find(|(configured_key, _)| configured_key == &key)
+            .map(|(_, value)| *value)
+            .unwrap_or(quota.default_value());
+        db::quota::save(tx.as_mut(), &key, value).await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Returns the current value of the quota.
+pub(crate) async fn find<'a>(tx: &mut Transaction<'a, Sqlite>, quota: Quota) -> Result<i32, ActionError> {
+    let value = db::quota::find_by_name(tx.as_mut(), &quota.summary_key()).await?;
+    Ok(value.unwrap_or(quota.default_value()))
+}
+
+/// Fails with `LimitExceeded` if `count` existing entities have already reached the quota.
+pub(crate) async fn check<'a>(tx: &mut Transaction<'a, Sqlite>, quota: Quota, count: usize) -> Result<(), ActionError> {
+    let value = find(tx, quota).await?;
+    if count >= value.max(0) as usize {
+        return Err(ActionError::new(
+            ApiErrorKind::LimitExceeded,
+            format!("Cannot exceed quota for {}: {}", quota.as_str(), value).as_str(),
+        ));
+    }
+    Ok(())
+}
+
+/// Fails with `LimitExceeded` if the account has already reached the quota.
+pub(crate) async fn check_account<'a>(
+    tx: &mut Transaction<'a, Sqlite>, account_id: i64, quota: Quota,
+) -> Result<(), ActionError> {
+    // only quotas which limit entities of the whole account are passed here
+    let count = db::account::count_for_quota(tx.as_mut(), account_id, quota)
+        .await?
+        .unwrap_or_default();
+    check(tx, quota, count as usize).await
+}
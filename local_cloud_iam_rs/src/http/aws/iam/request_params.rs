@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+
+use axum::body::Body;
+use axum::extract::Request;
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use uuid::Uuid;
+
+use local_cloud_axum::local::web::XmlResponse;
+
+use crate::http::aws::iam::actions::error::{ApiError, ApiErrorKind};
+
+const MAX_BODY_SIZE: usize = 2 * 1024 * 1024;
+const CONTENT_TYPE_HEADER: &str = "Content-Type";
+const CONTENT_TYPE_HEADER_VALUE: &str = "text/xml; charset=utf-8";
+
+/// Form parameters of an AWS Query request, parsed once and shared by the middlewares through request extensions.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RequestParams(HashMap<String, String>);
+
+impl RequestParams {
+    pub(crate) fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    pub(crate) fn action(&self) -> Option<&str> {
+        self.get("Action")
+    }
+}
+
+impl FromIterator<(String, String)> for RequestParams {
+    fn from_iter<T: IntoIterator<Item = (String, String)>>(iter: T) -> Self {
+        RequestParams(iter.into_iter().collect())
+    }
+}
+
+/// Middleware which buffers the request body, puts its parameters into request extensions as [`RequestParams`]
+/// and passes the body on to the handler. Requests with a body larger than 2 MiB are rejected.
+pub(crate) async fn parse(request: Request, next: Next) -> Response {
+    let (mut parts, body) = request.into_parts();
+    let body = match axum::body::to_bytes(body, MAX_BODY_SIZE).await {
+        Ok(body) => body,
+        Err(_err) => {
+            let error = ApiError::new(
+                ApiErrorKind::InvalidInput,
+                format!("Request body must not be larger than {MAX_BODY_SIZE} bytes."),
+                Uuid::new_v4().to_string(),
+            );
+            return error_response(error);
+        }
+    };
+
+    let params: RequestParams = url::form_urlencoded::parse(&body).into_owned().collect();
+    parts.extensions.insert(params);
+    next.run(Request::from_parts(parts, Body::from(body))).await
+}
+
+pub(crate) fn error_response(error: ApiError) -> Response {
+    let status_code: StatusCode = error.kind.status_code();
+    let body: XmlResponse = error.into();
+    (status_code, [(CONTENT_TYPE_HEADER, CONTENT_TYPE_HEADER_VALUE)], body.0).into_response()
+}
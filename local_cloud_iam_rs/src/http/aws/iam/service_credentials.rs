@@ -0,0 +1,79 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use local_cloud_db::LocalDb;
+
+use crate::http::aws::iam::operations;
+use crate::http::aws::iam::operations::error::ActionError;
+
+/// Request of a local stand-in of a service (e.g. git over HTTPS for CodeCommit) to verify the service-specific
+/// credential which is used to sign in to the service.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct VerifyServiceSpecificCredentialRequest {
+    service_name: String,
+    service_user_name: String,
+    service_password: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct VerifyServiceSpecificCredentialResponse {
+    account_id: String,
+    user_name: String,
+    service_specific_credential_id: String,
+}
+
+/// Error of the local endpoints which are not a part of the AWS API.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct ErrorResponse {
+    pub(crate) code: String,
+    pub(crate) message: String,
+}
+
+/// Verifies the service user name and password of an active service-specific credential. Returns the owner of the
+/// credential, or the `AccessDenied` error if the credential is not valid.
+pub(crate) async fn verify(
+    State(db): State<LocalDb>, Json(request): Json<VerifyServiceSpecificCredentialRequest>,
+) -> Response {
+    let result = match db.new_connection().await {
+        Ok(mut connection) => {
+            operations::service_specific_credential::verify_service_specific_credential(
+                connection.as_mut(),
+                request.service_name.trim(),
+                request.service_user_name.trim(),
+                &request.service_password,
+            )
+            .await
+        }
+        Err(err) => Err(err.into()),
+    };
+    match result {
+        Ok(credential) => {
+            let response = VerifyServiceSpecificCredentialResponse {
+                account_id: format!("{:0>12}", credential.account_id),
+                user_name: credential.user_name,
+                service_specific_credential_id: credential.credential_id,
+            };
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(ActionError::Service { kind, msg }) => {
+            let response = ErrorResponse {
+                code: kind.as_str().to_owned(),
+                message: msg,
+            };
+            (kind.status_code(), Json(response)).into_response()
+        }
+        Err(ActionError::Validation(error)) => {
+            let response = ErrorResponse {
+                code: "ValidationError".to_owned(),
+                message: error.to_string(),
+            };
+            (StatusCode::BAD_REQUEST, Json(response)).into_response()
+        }
+    }
+}
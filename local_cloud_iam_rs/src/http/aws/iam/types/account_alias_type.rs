@@ -1,8 +1,10 @@
 use std::ops::Deref;
 
 lazy_static::lazy_static! {
+    // equivalent of `^[a-z0-9]([a-z0-9]|-(?!-)){1,61}[a-z0-9]$` without look-ahead, which is not supported by `regex`;
+    // the length is validated separately
     static ref REGEX : regex::Regex =
-    regex::Regex::new(r"^[a-z0-9]([a-z0-9]|-(?!-)){1,61}[a-z0-9]$").unwrap();
+    regex::Regex::new(r"^[a-z0-9]+(-[a-z0-9]+)*$").unwrap();
 }
 #[derive(Debug, PartialEq, serde::Deserialize)]
 pub(crate) struct AccountAliasType(String);
@@ -1,11 +1,8 @@
-use local_cloud_validate::{NamedValidator, ValidationError};
-
 #[derive(Debug, PartialEq, serde::Deserialize)]
+pub(crate) struct GenerateCredentialReportRequest {}
 
-pub(crate) struct GenerateCredentialReportRequest(());
-
-impl NamedValidator for GenerateCredentialReportRequest {
-    fn validate(&self, _at: &str) -> Result<(), ValidationError> {
+impl local_cloud_validate::NamedValidator for &GenerateCredentialReportRequest {
+    fn validate(&self, _at: &str) -> Result<(), local_cloud_validate::ValidationError> {
         Ok(())
     }
 }
@@ -14,6 +14,9 @@ impl GetAccountAuthorizationDetailsRequest {
     pub(crate) fn marker(&self) -> Option<&str> {
         self.marker.as_deref()
     }
+    pub(crate) fn marker_type(&self) -> Option<&types::marker_type::MarkerType> {
+        self.marker.as_ref()
+    }
     pub(crate) fn filter(&self) -> Option<&[types::entity_type::EntityType]> {
         self.filter.as_deref()
     }
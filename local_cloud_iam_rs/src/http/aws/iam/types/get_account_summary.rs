@@ -1,10 +1,8 @@
-use local_cloud_validate::{NamedValidator, ValidationError};
-
 #[derive(Debug, PartialEq, serde::Deserialize)]
-pub(crate) struct GetAccountSummaryRequest(());
+pub(crate) struct GetAccountSummaryRequest {}
 
-impl NamedValidator for GetAccountSummaryRequest {
-    fn validate(&self, _at: &str) -> Result<(), ValidationError> {
+impl local_cloud_validate::NamedValidator for &GetAccountSummaryRequest {
+    fn validate(&self, _at: &str) -> Result<(), local_cloud_validate::ValidationError> {
         Ok(())
     }
 }
@@ -1,10 +1,8 @@
-use local_cloud_validate::{NamedValidator, ValidationError};
-
 #[derive(Debug, PartialEq, serde::Deserialize)]
-pub(crate) struct GetCredentialReportRequest(());
+pub(crate) struct GetCredentialReportRequest {}
 
-impl NamedValidator for GetCredentialReportRequest {
-    fn validate(&self, _at: &str) -> Result<(), ValidationError> {
+impl local_cloud_validate::NamedValidator for &GetCredentialReportRequest {
+    fn validate(&self, _at: &str) -> Result<(), local_cloud_validate::ValidationError> {
         Ok(())
     }
 }
@@ -17,6 +17,9 @@ impl GetServiceLastAccessedDetailsRequest {
     pub(crate) fn marker(&self) -> Option<&str> {
         self.marker.as_deref()
     }
+    pub(crate) fn marker_type(&self) -> Option<&types::marker_type::MarkerType> {
+        self.marker.as_ref()
+    }
     pub(crate) fn job_id(&self) -> Option<&str> {
         self.job_id.as_deref()
     }
@@ -16,6 +16,9 @@ impl GetServiceLastAccessedDetailsWithEntitiesRequest {
     pub(crate) fn marker(&self) -> Option<&str> {
         self.marker.as_deref()
     }
+    pub(crate) fn marker_type(&self) -> Option<&types::marker_type::MarkerType> {
+        self.marker.as_ref()
+    }
     pub(crate) fn service_namespace(&self) -> Option<&str> {
         self.service_namespace.as_deref()
     }
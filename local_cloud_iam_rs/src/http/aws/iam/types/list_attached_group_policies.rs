@@ -13,6 +13,9 @@ pub(crate) struct ListAttachedGroupPoliciesRequest {
 }
 
 impl ListAttachedGroupPoliciesRequest {
+    pub(crate) fn marker_type(&self) -> Option<&types::marker_type::MarkerType> {
+        self.marker.as_ref()
+    }
     pub(crate) fn path_prefix(&self) -> Option<&str> {
         self.path_prefix.as_deref()
     }
@@ -13,6 +13,9 @@ pub(crate) struct ListAttachedRolePoliciesRequest {
 }
 
 impl ListAttachedRolePoliciesRequest {
+    pub(crate) fn marker_type(&self) -> Option<&types::marker_type::MarkerType> {
+        self.marker.as_ref()
+    }
     pub(crate) fn role_name(&self) -> Option<&str> {
         self.role_name.as_deref()
     }
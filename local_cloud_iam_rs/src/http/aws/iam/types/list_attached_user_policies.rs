@@ -13,6 +13,9 @@ pub(crate) struct ListAttachedUserPoliciesRequest {
 }
 
 impl ListAttachedUserPoliciesRequest {
+    pub(crate) fn marker_type(&self) -> Option<&types::marker_type::MarkerType> {
+        self.marker.as_ref()
+    }
     pub(crate) fn marker(&self) -> Option<&str> {
         self.marker.as_deref()
     }
@@ -1,26 +1,30 @@
 use std::ops::Deref;
 
 use serde::{Deserialize, Deserializer};
-use validator::Validate;
 
-use local_cloud_iam_policy_document::types::LocalPolicyDocument;
+use local_cloud_iam_policy_document::types::{LocalPolicyDocument, PolicyDocumentKind};
 use local_cloud_validate::{ValidationError, ValidationErrorKind};
 
 lazy_static::lazy_static! {
     static ref REGEX : regex::Regex =
     regex::Regex::new(r"^[\u0009\u000A\u000D\u0020-\u00FF]+$").unwrap();
 }
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub(crate) struct PolicyDocumentType {
     raw_value: String,
     minified_value: Result<String, ValidationError>,
+    // parsed document, present if the elements of the document are valid
+    document: Option<LocalPolicyDocument>,
 }
 
 impl PolicyDocumentType {
-    fn new(raw_value: String, minified_value: Result<String, ValidationError>) -> Self {
+    fn new(
+        raw_value: String, minified_value: Result<String, ValidationError>, document: Option<LocalPolicyDocument>,
+    ) -> Self {
         PolicyDocumentType {
             raw_value,
             minified_value,
+            document,
         }
     }
 
@@ -30,6 +34,20 @@ impl PolicyDocumentType {
             Err(err) => Err(err.clone()),
         }
     }
+
+    /// Validates the statement elements which are required or prohibited for the kind of the policy document.
+    pub(crate) fn validate_as(&self, kind: PolicyDocumentKind) -> Result<(), ValidationError> {
+        self.document()?;
+        let document = match &self.document {
+            None => {
+                return Err(ValidationError::new(ValidationErrorKind::PolicyDocument, "Malformed Policy Document."))
+            }
+            Some(document) => document,
+        };
+        document
+            .validate_as(kind)
+            .map_err(|message| ValidationError::new(ValidationErrorKind::PolicyDocument, message))
+    }
 }
 
 impl<'de> Deserialize<'de> for PolicyDocumentType {
@@ -43,25 +61,32 @@ impl<'de> Deserialize<'de> for PolicyDocumentType {
             .map_err(|_err| ValidationError::new(ValidationErrorKind::Other, "Malformed Policy Document."));
 
         let result = match policy_document {
-            Ok(document) => match document.validate() {
+            Ok(document) => match document.validate_elements() {
                 Ok(_) => {
                     let minified_value = serde_json::to_string(&document).map_err(|_err| {
                         ValidationError::new(ValidationErrorKind::Other, "Failed to minimize Policy Document.")
                     });
-                    PolicyDocumentType::new(raw_value, minified_value)
+                    PolicyDocumentType::new(raw_value, minified_value, Some(document))
                 }
-                Err(err) => {
-                    log::debug!("Policy Document validation failed. Errors: {:?}", err);
-                    let error = ValidationError::new(ValidationErrorKind::Other, "Malformed policy document.");
-                    PolicyDocumentType::new(raw_value, Err(error))
+                Err(message) => {
+                    log::debug!("Policy Document validation failed. Error: {}", message);
+                    let error = ValidationError::new(ValidationErrorKind::PolicyDocument, message);
+                    PolicyDocumentType::new(raw_value, Err(error), None)
                 }
             },
-            Err(err) => PolicyDocumentType::new(raw_value, Err(err)),
+            Err(err) => PolicyDocumentType::new(raw_value, Err(err), None),
         };
         Ok(result)
     }
 }
 
+impl PartialEq for PolicyDocumentType {
+    fn eq(&self, other: &Self) -> bool {
+        // the parsed document is derived from the raw value
+        self.raw_value == other.raw_value && self.minified_value == other.minified_value
+    }
+}
+
 impl Deref for PolicyDocumentType {
     type Target = str;
     fn deref(&self) -> &Self::Target {
@@ -64,6 +64,9 @@ impl SimulateCustomPolicyRequest {
     pub(crate) fn marker(&self) -> Option<&str> {
         self.marker.as_deref()
     }
+    pub(crate) fn marker_type(&self) -> Option<&types::marker_type::MarkerType> {
+        self.marker.as_ref()
+    }
     pub(crate) fn resource_arns(&self) -> Option<&[types::resource_name_type::ResourceNameType]> {
         self.resource_arns.as_deref()
     }
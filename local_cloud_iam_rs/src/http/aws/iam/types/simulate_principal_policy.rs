@@ -45,6 +45,9 @@ impl SimulatePrincipalPolicyRequest {
     pub(crate) fn marker(&self) -> Option<&str> {
         self.marker.as_deref()
     }
+    pub(crate) fn marker_type(&self) -> Option<&types::marker_type::MarkerType> {
+        self.marker.as_ref()
+    }
     pub(crate) fn context_entries(&self) -> Option<&[types::context_entry::ContextEntry]> {
         self.context_entries.as_deref()
     }
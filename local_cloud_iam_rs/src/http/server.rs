@@ -1,8 +1,17 @@
+use std::sync::Arc;
+
 use crate::config::AppConfig;
-use axum::routing::post;
-use axum::Router;
+use axum::routing::{get, post};
+use axum::{middleware, Extension, Router};
+
+use local_cloud_axum::local::web::sigv4;
+use local_cloud_axum::local::web::SigV4Auth;
 
 use crate::http::aws;
+use crate::http::aws::iam::auth::IamCredentialsResolver;
+use crate::http::aws::iam::constants;
+use crate::http::aws::iam::operations::ctx::OperationSettings;
+use crate::http::aws::iam::{access_tracking, authorization, request_params};
 
 pub(crate) async fn router(app_config: &AppConfig) -> std::io::Result<Router> {
     // connect to DB
@@ -13,11 +22,51 @@ pub(crate) async fn router(app_config: &AppConfig) -> std::io::Result<Router> {
             err
         })
         .unwrap();
+    aws::iam::auth::configure_root_access_key(&iam_db, &app_config.root_secret_access_key)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to configure the bootstrap access key: {}", err);
+            std::io::Error::other(err)
+        })?;
+    aws::iam::managed_policies::load(&iam_db).await.map_err(|err| {
+        log::error!("Failed to load AWS managed policies: {:?}", err);
+        std::io::Error::other(format!("{:?}", err))
+    })?;
+    aws::iam::quotas::load(&iam_db, &app_config.quotas)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load IAM quotas: {:?}", err);
+            std::io::Error::other(format!("{:?}", err))
+        })?;
+    let settings = OperationSettings {
+        mfa_clock_skew_steps: app_config.mfa_clock_skew_steps,
+    };
+    let job_runner = aws::iam::jobs::start(iam_db.clone());
+
+    let auth = SigV4Auth::new(IamCredentialsResolver::new(iam_db.clone()), constants::xml::IAM_XMLNS)
+        .trust_any_key(app_config.trust_any_access_key);
 
     // setting up HTTP Router
-    let app = Router::new()
+    let mut app = Router::new()
         .route("/iam/", post(aws::iam::handler::handle))
-        .route("/iam", post(aws::iam::handler::handle))
+        .route("/iam", post(aws::iam::handler::handle));
+    if app_config.enforce_policies {
+        // runs after the authentication layer which provides identity of the caller
+        app = app.route_layer(middleware::from_fn_with_state(iam_db.clone(), authorization::authorize));
+    }
+    // runs before authorization, so that denied requests are tracked as well
+    let app = app
+        .route_layer(middleware::from_fn_with_state(iam_db.clone(), access_tracking::track))
+        .route_layer(middleware::from_fn_with_state(auth, sigv4::authenticate::<IamCredentialsResolver>))
+        // runs first: rejects too large requests and parses the parameters once for tracking and authorization
+        .route_layer(middleware::from_fn(request_params::parse))
+        // verified with the service password of the credential instead of SigV4
+        .route("/iam/service-specific-credentials/verify", post(aws::iam::service_credentials::verify))
+        // unsigned, the same as the console sign-in page which the URL points to
+        .route("/iam/accounts/:account_id/sign-in-url", get(aws::iam::account_sign_in::get_sign_in_url))
+        .layer(Extension(settings))
+        // the job runner lives as long as the router, so it is stopped together with the server
+        .layer(Extension(Arc::new(job_runner)))
         .with_state(iam_db);
 
     Ok(app)
@@ -37,3 +37,46 @@ async fn attach_role_policy() {
 
     ctx.stop_server().await;
 }
+
+#[tokio::test]
+async fn attach_aws_managed_role_policy() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    client
+        .create_role()
+        .role_name("Test-Role")
+        .assume_role_policy_document(CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY)
+        .send()
+        .await
+        .expect("Failed to create IAM role");
+
+    client
+        .attach_role_policy()
+        .role_name("Test-Role")
+        .policy_arn("arn:aws:iam::aws:policy/ReadOnlyAccess")
+        .send()
+        .await
+        .expect("Failed to attach AWS managed policy");
+
+    let response = client
+        .list_attached_role_policies()
+        .role_name("Test-Role")
+        .send()
+        .await
+        .expect("Failed to list attached role policies");
+    assert_eq!(response.attached_policies().len(), 1);
+    assert_eq!(response.attached_policies()[0].policy_name().unwrap(), "ReadOnlyAccess");
+
+    let response = client
+        .get_policy()
+        .policy_arn("arn:aws:iam::aws:policy/ReadOnlyAccess")
+        .send()
+        .await
+        .expect("Failed to get AWS managed policy");
+    assert_eq!(response.policy().unwrap().attachment_count().unwrap(), 1);
+
+    ctx.stop_server().await;
+}
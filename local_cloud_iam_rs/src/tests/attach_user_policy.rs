@@ -32,3 +32,64 @@ async fn attach_user_policy() {
 
     ctx.stop_server().await;
 }
+
+#[tokio::test]
+async fn attach_user_policy_exceeding_quota() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server_with_low_quotas).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, "user1", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+
+    let mut policy_arns = vec![];
+    for policy_name in ["policy1", "policy2"] {
+        let policy_output = super::fixture::create_policy(
+            &client,
+            policy_name,
+            "policy description",
+            "/",
+            CREATE_USER_PERMISSIONS_BOUNDARY,
+            None,
+        )
+        .await
+        .expect("Failed to create IAM policy");
+        policy_arns.push(policy_output.policy().unwrap().arn().unwrap().to_owned());
+    }
+
+    client
+        .attach_user_policy()
+        .user_name("user1")
+        .policy_arn(&policy_arns[0])
+        .send()
+        .await
+        .expect("Failed to attach IAM user policy");
+
+    let response = client
+        .attach_user_policy()
+        .user_name("user1")
+        .policy_arn(&policy_arns[1])
+        .send()
+        .await;
+
+    assert!(response.is_err());
+    let sdk_error = response.unwrap_err();
+    assert_eq!(409u16, sdk_error.raw_response().unwrap().status().as_u16());
+
+    let error = sdk_error.into_service_error();
+    assert!(error.is_limit_exceeded_exception());
+    assert_eq!(error.meta().message().unwrap(), "Cannot exceed quota for AttachedPoliciesPerUser: 1");
+
+    // attaching the same policy again succeeds even if the quota is exhausted
+    client
+        .attach_user_policy()
+        .user_name("user1")
+        .policy_arn(&policy_arns[0])
+        .send()
+        .await
+        .expect("Failed to attach IAM user policy again");
+
+    ctx.stop_server().await;
+}
@@ -1 +1,156 @@
+use aws_credential_types::Credentials;
 
+const USER_NAME: &str = "test-user1";
+const OLD_PASSWORD: &str = "fvrmweioyt43y8989JK9*(&#@*(";
+const NEW_PASSWORD: &str = "kdfjg8934589*&#njkjk32JJ_";
+
+async fn create_user_client(client: &aws_sdk_iam::Client, port: u16) -> aws_sdk_iam::Client {
+    super::fixture::create_user(client, USER_NAME, "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    client
+        .create_login_profile()
+        .user_name(USER_NAME)
+        .password(OLD_PASSWORD)
+        .password_reset_required(true)
+        .send()
+        .await
+        .expect("Failed to create login profile");
+    let response = client
+        .create_access_key()
+        .user_name(USER_NAME)
+        .send()
+        .await
+        .expect("Failed to create access key");
+    let access_key = response.access_key().unwrap();
+
+    let credentials =
+        Credentials::new(access_key.access_key_id(), access_key.secret_access_key(), None, None, "provider_name");
+    aws_sdk_iam::Client::new(&super::aws_config_with_credentials(port, credentials))
+}
+
+#[tokio::test]
+async fn change_password() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+    let user_client = create_user_client(&client, port).await;
+
+    user_client
+        .change_password()
+        .old_password(OLD_PASSWORD)
+        .new_password(NEW_PASSWORD)
+        .send()
+        .await
+        .expect("Failed to change password");
+
+    let response = client
+        .get_login_profile()
+        .user_name(USER_NAME)
+        .send()
+        .await
+        .expect("Failed to get login profile");
+    assert!(!response.login_profile().unwrap().password_reset_required());
+
+    // the old password is not valid anymore
+    let response = user_client
+        .change_password()
+        .old_password(OLD_PASSWORD)
+        .new_password(NEW_PASSWORD)
+        .send()
+        .await;
+    assert!(response.is_err());
+    let sdk_error = response.unwrap_err();
+    assert_eq!(409u16, sdk_error.raw_response().unwrap().status().as_u16());
+    assert_eq!("EntityTemporarilyUnmodifiable", sdk_error.into_service_error().meta().code().unwrap());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn change_password_root_user() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let response = client
+        .change_password()
+        .old_password(OLD_PASSWORD)
+        .new_password(NEW_PASSWORD)
+        .send()
+        .await;
+
+    assert!(response.is_err());
+    let sdk_error = response.unwrap_err();
+    assert_eq!(400u16, sdk_error.raw_response().unwrap().status().as_u16());
+    assert_eq!("InvalidUserType", sdk_error.into_service_error().meta().code().unwrap());
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn change_password_reuse_prevention() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+    client
+        .update_account_password_policy()
+        .password_reuse_prevention(2)
+        .allow_users_to_change_password(true)
+        .send()
+        .await
+        .expect("Failed to update account password policy");
+    let user_client = create_user_client(&client, port).await;
+
+    user_client
+        .change_password()
+        .old_password(OLD_PASSWORD)
+        .new_password(NEW_PASSWORD)
+        .send()
+        .await
+        .expect("Failed to change password");
+
+    // the old password is one of the last 2 passwords
+    let response = user_client
+        .change_password()
+        .old_password(NEW_PASSWORD)
+        .new_password(OLD_PASSWORD)
+        .send()
+        .await;
+    assert!(response.is_err());
+    let sdk_error = response.unwrap_err();
+    assert_eq!(400u16, sdk_error.raw_response().unwrap().status().as_u16());
+    assert_eq!("PasswordPolicyViolation", sdk_error.into_service_error().meta().code().unwrap());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn change_password_not_allowed_by_password_policy() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+    client
+        .update_account_password_policy()
+        .allow_users_to_change_password(false)
+        .send()
+        .await
+        .expect("Failed to update account password policy");
+    let user_client = create_user_client(&client, port).await;
+
+    let response = user_client
+        .change_password()
+        .old_password(OLD_PASSWORD)
+        .new_password(NEW_PASSWORD)
+        .send()
+        .await;
+    assert!(response.is_err());
+    let sdk_error = response.unwrap_err();
+    assert_eq!(403u16, sdk_error.raw_response().unwrap().status().as_u16());
+    assert_eq!("AccessDenied", sdk_error.into_service_error().meta().code().unwrap());
+
+    ctx.stop_server().await;
+}
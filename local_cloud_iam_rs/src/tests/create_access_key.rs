@@ -1 +1,82 @@
+use aws_sdk_iam::types::StatusType;
 
+const USER_NAME: &str = "test-user1";
+
+#[tokio::test]
+async fn create_access_key() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, USER_NAME, "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+
+    let response = client
+        .create_access_key()
+        .user_name(USER_NAME)
+        .send()
+        .await
+        .expect("Failed to create access key");
+
+    assert!(response.access_key().is_some());
+    let access_key = response.access_key().unwrap();
+    assert_eq!(access_key.user_name(), USER_NAME);
+    assert_eq!(access_key.access_key_id().len(), 20);
+    assert!(access_key.access_key_id().starts_with("AKIA"));
+    assert_eq!(access_key.secret_access_key().len(), 40);
+    assert_eq!(access_key.status(), &StatusType::Active);
+    assert!(access_key.create_date().is_some());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn create_access_key_limit_exceeded() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, USER_NAME, "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+
+    for _i in 0..2 {
+        client
+            .create_access_key()
+            .user_name(USER_NAME)
+            .send()
+            .await
+            .expect("Failed to create access key");
+    }
+
+    let result = client.create_access_key().user_name(USER_NAME).send().await;
+
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(409u16, sdk_error.raw_response().unwrap().status().as_u16());
+
+    let error = sdk_error.into_service_error();
+    assert!(error.is_limit_exceeded_exception());
+    assert_eq!(error.meta().message().unwrap(), "Cannot exceed quota for AccessKeysPerUser: 2");
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn create_access_key_no_such_user() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let result = client.create_access_key().user_name(USER_NAME).send().await;
+
+    assert!(result.is_err());
+    let error = result.unwrap_err().into_service_error();
+    assert!(error.is_no_such_entity_exception());
+
+    ctx.stop_server().await;
+}
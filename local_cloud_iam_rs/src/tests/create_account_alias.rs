@@ -1 +1,98 @@
+#[tokio::test]
+async fn create_account_alias() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
 
+    client
+        .create_account_alias()
+        .account_alias("home-cloud")
+        .send()
+        .await
+        .expect("Failed to create account alias");
+    // creation of the same alias is idempotent
+    client
+        .create_account_alias()
+        .account_alias("home-cloud")
+        .send()
+        .await
+        .expect("Failed to create account alias");
+
+    let response = client
+        .list_account_aliases()
+        .send()
+        .await
+        .expect("Failed to list account aliases");
+    assert_eq!(response.account_aliases(), &["home-cloud".to_owned()]);
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn create_account_alias_invalid() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let response = client.create_account_alias().account_alias("Home Cloud").send().await;
+
+    assert!(response.is_err());
+    let sdk_error = response.unwrap_err();
+    assert_eq!(400u16, sdk_error.raw_response().unwrap().status().as_u16());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn create_account_alias_second_alias() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    client
+        .create_account_alias()
+        .account_alias("home-cloud")
+        .send()
+        .await
+        .expect("Failed to create account alias");
+
+    let response = client.create_account_alias().account_alias("other-cloud").send().await;
+
+    assert!(response.is_err());
+    let sdk_error = response.unwrap_err();
+    assert_eq!(409u16, sdk_error.raw_response().unwrap().status().as_u16());
+
+    let error = sdk_error.into_service_error();
+    assert!(error.is_limit_exceeded_exception());
+    assert_eq!(error.meta().message().unwrap(), "Cannot exceed quota for AccountAliasesPerAccount: 1");
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn create_account_alias_aws() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    // the account which owns AWS managed policies doesn't reserve the alias
+    client
+        .create_account_alias()
+        .account_alias("aws")
+        .send()
+        .await
+        .expect("Failed to create account alias");
+
+    let response = client
+        .list_account_aliases()
+        .send()
+        .await
+        .expect("Failed to list account aliases");
+    assert_eq!(response.account_aliases(), &["aws".to_owned()]);
+
+    ctx.stop_server().await;
+}
@@ -91,3 +91,49 @@ async fn create_policy_version_limit_exceeded() {
 
     ctx.stop_server().await;
 }
+
+#[tokio::test]
+async fn create_policy_version_as_default() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let policy_output =
+        super::fixture::create_policy(&client, "some-policy-name", "", "/", CREATE_USER_PERMISSIONS_BOUNDARY, None)
+            .await
+            .expect("Failed to create IAM policy");
+    let policy_arn = policy_output.policy().unwrap().arn().unwrap();
+
+    let response = client
+        .create_policy_version()
+        .policy_arn(policy_arn)
+        .policy_document(CREATE_USER_PERMISSIONS_BOUNDARY)
+        .send()
+        .await
+        .expect("Failed to create IAM policy version");
+    let policy_version = response.policy_version().unwrap();
+    assert_eq!(policy_version.version_id().unwrap(), "v2");
+    assert!(policy_version.is_default_version());
+
+    let response = client
+        .get_policy()
+        .policy_arn(policy_arn)
+        .send()
+        .await
+        .expect("Failed to get IAM policy");
+    let policy = response.policy().unwrap();
+    assert_eq!(policy.default_version_id().unwrap(), "v2");
+    assert_eq!(policy.update_date(), policy_version.create_date());
+
+    let response = client
+        .get_policy_version()
+        .policy_arn(policy_arn)
+        .version_id("v1")
+        .send()
+        .await
+        .expect("Failed to get IAM policy version");
+    assert!(!response.policy_version().unwrap().is_default_version());
+
+    ctx.stop_server().await;
+}
@@ -42,3 +42,55 @@ async fn create_role() {
 
     ctx.stop_server().await;
 }
+
+#[tokio::test]
+async fn create_role_invalid_principal() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let config = super::aws_config(ctx.port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let response = client
+        .create_role()
+        .role_name("Test-Role")
+        .assume_role_policy_document(
+            r#"{"Version": "2012-10-17", "Statement": [
+                {"Effect": "Allow", "Principal": {"AWS": "arn:aws:iam::1234:root"}, "Action": "sts:AssumeRole"}
+            ]}"#,
+        )
+        .send()
+        .await;
+
+    assert!(response.is_err());
+    let error = response.unwrap_err().into_service_error();
+    assert!(error.is_malformed_policy_document_exception());
+    assert_eq!(
+        error.meta().message().unwrap(),
+        r#"Statement[0].Principal: Invalid principal in policy: "AWS":"arn:aws:iam::1234:root""#
+    );
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn create_role_trust_policy_with_resource() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let config = super::aws_config(ctx.port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let response = client
+        .create_role()
+        .role_name("Test-Role")
+        .assume_role_policy_document(
+            r#"{"Version": "2012-10-17", "Statement": [
+                {"Effect": "Allow", "Principal": {"Service": "ec2.amazonaws.com"}, "Action": "sts:AssumeRole"},
+                {"Effect": "Allow", "Principal": "*", "Action": "sts:AssumeRole", "Resource": "*"}
+            ]}"#,
+        )
+        .send()
+        .await;
+
+    assert!(response.is_err());
+    let error = response.unwrap_err().into_service_error();
+    assert!(error.is_malformed_policy_document_exception());
+    assert_eq!(error.meta().message().unwrap(), "Statement[1].Resource: Has prohibited field Resource.");
+    ctx.stop_server().await;
+}
@@ -1 +1,97 @@
+#[tokio::test]
+async fn create_service_linked_role() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
 
+    let response = client
+        .create_service_linked_role()
+        .aws_service_name("ecs.amazonaws.com")
+        .send()
+        .await
+        .expect("Failed to create service-linked role");
+
+    let role = response.role().unwrap();
+    assert_eq!(role.role_name(), "AWSServiceRoleForECS");
+    assert_eq!(role.path(), "/aws-service-role/ecs.amazonaws.com/");
+    assert_eq!(role.arn(), "arn:aws:iam::000000000001:role/aws-service-role/ecs.amazonaws.com/AWSServiceRoleForECS");
+    assert_eq!(role.description(), Some("Role to enable Amazon ECS to manage your cluster."));
+
+    let trust_policy = urlencoding::decode(role.assume_role_policy_document().unwrap()).unwrap();
+    let trust_policy: serde_json::Value = serde_json::from_str(&trust_policy).unwrap();
+    assert_eq!(trust_policy["Statement"][0]["Principal"]["Service"], "ecs.amazonaws.com");
+    assert_eq!(trust_policy["Statement"][0]["Action"], "sts:AssumeRole");
+
+    let response = client
+        .list_attached_role_policies()
+        .role_name("AWSServiceRoleForECS")
+        .send()
+        .await
+        .expect("Failed to list attached role policies");
+    assert_eq!(response.attached_policies().len(), 1);
+    assert_eq!(
+        response.attached_policies()[0].policy_arn(),
+        Some("arn:aws:iam::aws:policy/aws-service-role/AmazonECSServiceRolePolicy")
+    );
+
+    let result = client
+        .create_service_linked_role()
+        .aws_service_name("ecs.amazonaws.com")
+        .send()
+        .await;
+    assert!(result.is_err());
+    let error = result.unwrap_err().into_service_error();
+    assert!(error.is_invalid_input_exception());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn create_service_linked_role_with_custom_suffix() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let response = client
+        .create_service_linked_role()
+        .aws_service_name("autoscaling.amazonaws.com")
+        .custom_suffix("web")
+        .description("Auto Scaling of web servers")
+        .send()
+        .await
+        .expect("Failed to create service-linked role");
+    let role = response.role().unwrap();
+    assert_eq!(role.role_name(), "AWSServiceRoleForAutoScaling_web");
+    assert_eq!(role.description(), Some("Auto Scaling of web servers"));
+
+    let result = client
+        .create_service_linked_role()
+        .aws_service_name("es.amazonaws.com")
+        .custom_suffix("search")
+        .send()
+        .await;
+    assert!(result.is_err());
+    assert!(result.unwrap_err().into_service_error().is_invalid_input_exception());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn create_service_linked_role_unknown_service() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let result = client
+        .create_service_linked_role()
+        .aws_service_name("unknown.amazonaws.com")
+        .send()
+        .await;
+    assert!(result.is_err());
+    assert!(result.unwrap_err().into_service_error().is_invalid_input_exception());
+
+    ctx.stop_server().await;
+}
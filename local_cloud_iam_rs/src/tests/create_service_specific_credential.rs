@@ -1 +1,111 @@
+use aws_sdk_iam::types::StatusType;
 
+const USER_NAME: &str = "test-user-1";
+const SERVICE_NAME: &str = "codecommit.amazonaws.com";
+
+#[tokio::test]
+async fn create_service_specific_credential() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, USER_NAME, "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+
+    let response = client
+        .create_service_specific_credential()
+        .user_name(USER_NAME)
+        .service_name(SERVICE_NAME)
+        .send()
+        .await
+        .expect("Failed to create service-specific credential");
+
+    let credential = response.service_specific_credential().unwrap();
+    assert_eq!(credential.user_name(), USER_NAME);
+    assert_eq!(credential.service_name(), SERVICE_NAME);
+    assert_eq!(credential.service_user_name(), "test-user-1-at-000000000001");
+    assert_eq!(credential.service_password().len(), 44);
+    assert!(credential.service_specific_credential_id().starts_with("ACCA"));
+    assert_eq!(credential.status(), &StatusType::Active);
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn create_service_specific_credential_limit_exceeded() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, USER_NAME, "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+
+    let mut service_user_names = vec![];
+    for _ in 0..2 {
+        let response = client
+            .create_service_specific_credential()
+            .user_name(USER_NAME)
+            .service_name(SERVICE_NAME)
+            .send()
+            .await
+            .expect("Failed to create service-specific credential");
+        service_user_names.push(
+            response
+                .service_specific_credential()
+                .unwrap()
+                .service_user_name()
+                .to_owned(),
+        );
+    }
+    // the second credential of the user for the same service has a unique service user name
+    assert_eq!(service_user_names, vec!["test-user-1-at-000000000001", "test-user-1+1-at-000000000001"]);
+
+    let result = client
+        .create_service_specific_credential()
+        .user_name(USER_NAME)
+        .service_name(SERVICE_NAME)
+        .send()
+        .await;
+    assert!(result.is_err());
+    assert!(result.unwrap_err().into_service_error().is_limit_exceeded_exception());
+
+    // the limit is applied per service
+    client
+        .create_service_specific_credential()
+        .user_name(USER_NAME)
+        .service_name("cassandra.amazonaws.com")
+        .send()
+        .await
+        .expect("Failed to create service-specific credential");
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn create_service_specific_credential_not_supported_service() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, USER_NAME, "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+
+    let result = client
+        .create_service_specific_credential()
+        .user_name(USER_NAME)
+        .service_name("s3.amazonaws.com")
+        .send()
+        .await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(404u16, sdk_error.raw_response().unwrap().status().as_u16());
+    assert!(sdk_error.into_service_error().is_service_not_supported_exception());
+
+    ctx.stop_server().await;
+}
@@ -44,3 +44,35 @@ async fn create_user() {
 
     ctx.stop_server().await;
 }
+
+#[tokio::test]
+async fn create_user_exceeding_quota() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server_with_low_quotas).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    for user_name in ["user1", "user2"] {
+        super::fixture::create_user(&client, user_name, "/", None, None)
+            .await
+            .expect("Failed to create IAM user");
+    }
+
+    let response = super::fixture::create_user(&client, "user3", "/", None, None).await;
+
+    assert!(response.is_err());
+    let sdk_error = response.unwrap_err();
+    assert_eq!(409u16, sdk_error.raw_response().unwrap().status().as_u16());
+
+    let error = sdk_error.into_service_error();
+    assert!(error.is_limit_exceeded_exception());
+    assert_eq!(error.meta().message().unwrap(), "Cannot exceed quota for Users: 2");
+
+    // duplicates are reported even if the quota is exhausted
+    let response = super::fixture::create_user(&client, "user1", "/", None, None).await;
+    let error = response.unwrap_err().into_service_error();
+    assert!(error.is_entity_already_exists_exception());
+    assert_eq!(error.meta().message().unwrap(), "User with name user1 already exists.");
+
+    ctx.stop_server().await;
+}
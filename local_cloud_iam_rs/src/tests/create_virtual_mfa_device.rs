@@ -1,3 +1,4 @@
+use data_encoding::BASE32_NOPAD;
 use image::ImageFormat;
 
 use crate::tests::fixture::tag;
@@ -29,6 +30,10 @@ async fn create_virtual_mfa_device() {
     assert!(virtual_mfa_device.base32_string_seed().is_some());
     assert!(virtual_mfa_device.qr_code_png.is_some());
 
+    let base32_seed = std::str::from_utf8(virtual_mfa_device.base32_string_seed().unwrap().as_ref()).unwrap();
+    assert_eq!(base32_seed.len(), 32);
+    assert_eq!(BASE32_NOPAD.decode(base32_seed.as_bytes()).unwrap().len(), 20);
+
     let qr_code_image_bytes = virtual_mfa_device.qr_code_png().unwrap().as_ref();
     let image = image::load_from_memory_with_format(&qr_code_image_bytes, ImageFormat::Png);
     assert!(image.is_ok());
@@ -20,12 +20,14 @@ async fn deactivate_mfa_device() {
         .expect("Failed to create Virtual MFA device");
 
     let serial_number = create_mfa_device_output.virtual_mfa_device().unwrap().serial_number();
+    let (code1, code2) =
+        super::fixture::authentication_codes(create_mfa_device_output.virtual_mfa_device().unwrap(), 0);
     client
         .enable_mfa_device()
         .serial_number(serial_number)
         .user_name(USER_NAME)
-        .authentication_code1("012345") // just random code
-        .authentication_code2("234566") // just random code
+        .authentication_code1(code1)
+        .authentication_code2(code2)
         .send()
         .await
         .expect("Failed to enable MFA device for IAM user");
@@ -60,12 +62,14 @@ async fn deactivate_mfa_device_user_not_found() {
         .expect("Failed to create Virtual MFA device");
 
     let serial_number = create_mfa_device_output.virtual_mfa_device().unwrap().serial_number();
+    let (code1, code2) =
+        super::fixture::authentication_codes(create_mfa_device_output.virtual_mfa_device().unwrap(), 0);
     client
         .enable_mfa_device()
         .serial_number(serial_number)
         .user_name(USER_NAME)
-        .authentication_code1("012345") // just random code
-        .authentication_code2("234566") // just random code
+        .authentication_code1(code1)
+        .authentication_code2(code2)
         .send()
         .await
         .expect("Failed to enable MFA device for IAM user");
@@ -149,12 +153,14 @@ async fn deactivate_mfa_device_assigned_to_different_user() {
         .expect("Failed to create Virtual MFA device");
 
     let serial_number = create_mfa_device_output.virtual_mfa_device().unwrap().serial_number();
+    let (code1, code2) =
+        super::fixture::authentication_codes(create_mfa_device_output.virtual_mfa_device().unwrap(), 0);
     client
         .enable_mfa_device()
         .serial_number(serial_number)
         .user_name(USER_NAME)
-        .authentication_code1("012345") // just random code
-        .authentication_code2("234566") // just random code
+        .authentication_code1(code1)
+        .authentication_code2(code2)
         .send()
         .await
         .expect("Failed to enable MFA device for IAM user");
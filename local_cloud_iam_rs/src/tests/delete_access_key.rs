@@ -1 +1,50 @@
+const USER_NAME: &str = "test-user1";
 
+#[tokio::test]
+async fn delete_access_key() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, USER_NAME, "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+
+    let response = client
+        .create_access_key()
+        .user_name(USER_NAME)
+        .send()
+        .await
+        .expect("Failed to create access key");
+
+    let access_key_id = response.access_key().unwrap().access_key_id();
+
+    client
+        .delete_access_key()
+        .user_name(USER_NAME)
+        .access_key_id(access_key_id)
+        .send()
+        .await
+        .expect("Failed to delete access key");
+
+    let response = client
+        .list_access_keys()
+        .user_name(USER_NAME)
+        .send()
+        .await
+        .expect("Failed to get a list of access keys");
+    assert!(response.access_key_metadata().is_empty());
+
+    let result = client
+        .delete_access_key()
+        .user_name(USER_NAME)
+        .access_key_id(access_key_id)
+        .send()
+        .await;
+
+    assert!(result.is_err());
+    assert!(result.unwrap_err().into_service_error().is_no_such_entity_exception());
+
+    ctx.stop_server().await;
+}
@@ -1 +1,57 @@
+#[tokio::test]
+async fn delete_account_alias() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
 
+    client
+        .create_account_alias()
+        .account_alias("home-cloud")
+        .send()
+        .await
+        .expect("Failed to create account alias");
+    client
+        .delete_account_alias()
+        .account_alias("home-cloud")
+        .send()
+        .await
+        .expect("Failed to delete account alias");
+
+    let response = client
+        .list_account_aliases()
+        .send()
+        .await
+        .expect("Failed to list account aliases");
+    assert!(response.account_aliases().is_empty());
+
+    // the alias could be assigned again once it is deleted
+    client
+        .create_account_alias()
+        .account_alias("other-cloud")
+        .send()
+        .await
+        .expect("Failed to create account alias");
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn delete_account_alias_does_not_exist() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let response = client.delete_account_alias().account_alias("home-cloud").send().await;
+
+    assert!(response.is_err());
+    let sdk_error = response.unwrap_err();
+    assert_eq!(404u16, sdk_error.raw_response().unwrap().status().as_u16());
+
+    let error = sdk_error.into_service_error();
+    assert!(error.is_no_such_entity_exception());
+    assert_eq!(error.meta().message().unwrap(), "The account alias home-cloud cannot be found.");
+
+    ctx.stop_server().await;
+}
@@ -1 +1,38 @@
+#[tokio::test]
+async fn delete_account_password_policy() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
 
+    client
+        .update_account_password_policy()
+        .minimum_password_length(20)
+        .send()
+        .await
+        .expect("Failed to update account password policy");
+
+    client
+        .delete_account_password_policy()
+        .send()
+        .await
+        .expect("Failed to delete account password policy");
+
+    // the policy is not enforced anymore
+    super::fixture::create_user(&client, "SuperUser", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    client
+        .create_login_profile()
+        .user_name("SuperUser")
+        .password("short")
+        .send()
+        .await
+        .expect("Failed to create login profile");
+
+    let response = client.delete_account_password_policy().send().await;
+    assert!(response.is_err());
+    let sdk_error = response.unwrap_err();
+    assert_eq!(404u16, sdk_error.raw_response().unwrap().status().as_u16());
+    ctx.stop_server().await;
+}
@@ -1 +1,141 @@
+use crate::tests::fixture::CREATE_USER_PERMISSIONS_BOUNDARY;
 
+#[tokio::test]
+async fn delete_group() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    client
+        .create_group()
+        .group_name("group1")
+        .send()
+        .await
+        .expect("Failed to create IAM group");
+
+    client
+        .delete_group()
+        .group_name("group1")
+        .send()
+        .await
+        .expect("Failed to delete IAM group");
+
+    let result = client.delete_group().group_name("group1").send().await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 404);
+    assert!(sdk_error.into_service_error().is_no_such_entity_exception());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn delete_group_with_users() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, "user1", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    client
+        .create_group()
+        .group_name("group1")
+        .send()
+        .await
+        .expect("Failed to create IAM group");
+    client
+        .add_user_to_group()
+        .group_name("group1")
+        .user_name("user1")
+        .send()
+        .await
+        .expect("Failed to add IAM user to group");
+
+    let result = client.delete_group().group_name("group1").send().await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 409);
+    let error = sdk_error.into_service_error();
+    assert!(error.is_delete_conflict_exception());
+    assert_eq!(error.meta().message().unwrap(), "Cannot delete entity, must remove users from group first.");
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn delete_group_after_removing_dependents() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, "user1", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    client
+        .create_group()
+        .group_name("group1")
+        .send()
+        .await
+        .expect("Failed to create IAM group");
+    client
+        .add_user_to_group()
+        .group_name("group1")
+        .user_name("user1")
+        .send()
+        .await
+        .expect("Failed to add IAM user to group");
+    client
+        .put_group_policy()
+        .group_name("group1")
+        .policy_name("inline-policy")
+        .policy_document(CREATE_USER_PERMISSIONS_BOUNDARY)
+        .send()
+        .await
+        .expect("Failed to put IAM group policy");
+    let policy = super::fixture::create_policy(&client, "test-policy", "", "/", CREATE_USER_PERMISSIONS_BOUNDARY, None)
+        .await
+        .expect("Failed to create IAM policy");
+    let policy_arn = policy.policy().unwrap().arn().unwrap();
+    client
+        .attach_group_policy()
+        .group_name("group1")
+        .policy_arn(policy_arn)
+        .send()
+        .await
+        .expect("Failed to attach IAM group policy");
+
+    client
+        .remove_user_from_group()
+        .group_name("group1")
+        .user_name("user1")
+        .send()
+        .await
+        .expect("Failed to remove IAM user from group");
+    client
+        .delete_group_policy()
+        .group_name("group1")
+        .policy_name("inline-policy")
+        .send()
+        .await
+        .expect("Failed to delete IAM group policy");
+    client
+        .detach_group_policy()
+        .group_name("group1")
+        .policy_arn(policy_arn)
+        .send()
+        .await
+        .expect("Failed to detach IAM group policy");
+
+    client
+        .delete_group()
+        .group_name("group1")
+        .send()
+        .await
+        .expect("Failed to delete IAM group");
+
+    ctx.stop_server().await;
+}
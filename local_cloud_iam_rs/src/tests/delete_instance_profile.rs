@@ -1 +1,136 @@
+use crate::tests::fixture::{tag, CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY};
 
+#[tokio::test]
+async fn delete_instance_profile() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    client
+        .create_instance_profile()
+        .instance_profile_name("Webserver")
+        .tags(tag("key1", "value1"))
+        .send()
+        .await
+        .expect("Failed to create IAM instance profile");
+
+    client
+        .delete_instance_profile()
+        .instance_profile_name("Webserver")
+        .send()
+        .await
+        .expect("Failed to delete IAM instance profile");
+
+    let result = client
+        .delete_instance_profile()
+        .instance_profile_name("Webserver")
+        .send()
+        .await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 404);
+    assert!(sdk_error.into_service_error().is_no_such_entity_exception());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn delete_instance_profile_with_roles() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    client
+        .create_instance_profile()
+        .instance_profile_name("Webserver")
+        .send()
+        .await
+        .expect("Failed to create IAM instance profile");
+    client
+        .create_role()
+        .role_name("Test-Role")
+        .assume_role_policy_document(CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY)
+        .send()
+        .await
+        .expect("Failed to create IAM role");
+    client
+        .add_role_to_instance_profile()
+        .instance_profile_name("Webserver")
+        .role_name("Test-Role")
+        .send()
+        .await
+        .expect("Failed to add IAM role to instance profile");
+
+    let result = client
+        .delete_instance_profile()
+        .instance_profile_name("Webserver")
+        .send()
+        .await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 409);
+    let error = sdk_error.into_service_error();
+    assert!(error.is_delete_conflict_exception());
+    assert_eq!(error.meta().message().unwrap(), "Cannot delete entity, must remove roles from instance profile first.");
+
+    // the role could not be deleted while it is added to the instance profile as well
+    let result = client.delete_role().role_name("Test-Role").send().await;
+    assert!(result.is_err());
+    assert!(result.unwrap_err().into_service_error().is_delete_conflict_exception());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn delete_instance_profile_after_removing_roles() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    client
+        .create_instance_profile()
+        .instance_profile_name("Webserver")
+        .send()
+        .await
+        .expect("Failed to create IAM instance profile");
+    client
+        .create_role()
+        .role_name("Test-Role")
+        .assume_role_policy_document(CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY)
+        .send()
+        .await
+        .expect("Failed to create IAM role");
+    client
+        .add_role_to_instance_profile()
+        .instance_profile_name("Webserver")
+        .role_name("Test-Role")
+        .send()
+        .await
+        .expect("Failed to add IAM role to instance profile");
+
+    client
+        .remove_role_from_instance_profile()
+        .instance_profile_name("Webserver")
+        .role_name("Test-Role")
+        .send()
+        .await
+        .expect("Failed to remove IAM role from instance profile");
+
+    client
+        .delete_instance_profile()
+        .instance_profile_name("Webserver")
+        .send()
+        .await
+        .expect("Failed to delete IAM instance profile");
+    client
+        .delete_role()
+        .role_name("Test-Role")
+        .send()
+        .await
+        .expect("Failed to delete IAM role");
+
+    ctx.stop_server().await;
+}
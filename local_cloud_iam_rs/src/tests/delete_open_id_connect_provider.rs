@@ -1 +1,39 @@
+use crate::tests::fixture::tag;
 
+#[tokio::test]
+async fn delete_open_id_connect_provider() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let response = client
+        .create_open_id_connect_provider()
+        .url("https://server.example.com")
+        .thumbprint_list("c3768084dfb3d2b68b7897bf5f565da8eEXAMPLE")
+        .client_id_list("my-application-ID")
+        .tags(tag("key1", "value1"))
+        .send()
+        .await
+        .expect("Failed to create OpenID connect provider");
+    let provider_arn = response.open_id_connect_provider_arn().unwrap();
+
+    client
+        .delete_open_id_connect_provider()
+        .open_id_connect_provider_arn(provider_arn)
+        .send()
+        .await
+        .expect("Failed to delete OpenID connect provider");
+
+    let result = client
+        .delete_open_id_connect_provider()
+        .open_id_connect_provider_arn(provider_arn)
+        .send()
+        .await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 404);
+    assert!(sdk_error.into_service_error().is_no_such_entity_exception());
+
+    ctx.stop_server().await;
+}
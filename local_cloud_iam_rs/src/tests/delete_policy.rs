@@ -1 +1,135 @@
+use crate::tests::fixture::{tag, CREATE_USER_PERMISSIONS_BOUNDARY};
 
+#[tokio::test]
+async fn delete_policy() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let policy_output = super::fixture::create_policy(
+        &client,
+        "test-policy",
+        "",
+        "/",
+        CREATE_USER_PERMISSIONS_BOUNDARY,
+        Some(vec![tag("key1", "value1")]),
+    )
+    .await
+    .expect("Failed to create IAM policy");
+    let policy_arn = policy_output.policy().unwrap().arn().unwrap();
+
+    client
+        .delete_policy()
+        .policy_arn(policy_arn)
+        .send()
+        .await
+        .expect("Failed to delete IAM policy");
+
+    let result = client.delete_policy().policy_arn(policy_arn).send().await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 404);
+    assert!(sdk_error.into_service_error().is_no_such_entity_exception());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn delete_attached_policy() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let policy_output =
+        super::fixture::create_policy(&client, "test-policy", "", "/", CREATE_USER_PERMISSIONS_BOUNDARY, None)
+            .await
+            .expect("Failed to create IAM policy");
+    let policy_arn = policy_output.policy().unwrap().arn().unwrap();
+    super::fixture::create_user(&client, "user1", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    client
+        .attach_user_policy()
+        .user_name("user1")
+        .policy_arn(policy_arn)
+        .send()
+        .await
+        .expect("Failed to attach IAM user policy");
+
+    let result = client.delete_policy().policy_arn(policy_arn).send().await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 409);
+    let error = sdk_error.into_service_error();
+    assert!(error.is_delete_conflict_exception());
+    assert_eq!(error.meta().message().unwrap(), "Cannot delete a policy attached to entities.");
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn delete_policy_with_versions() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let policy_output =
+        super::fixture::create_policy(&client, "test-policy", "", "/", CREATE_USER_PERMISSIONS_BOUNDARY, None)
+            .await
+            .expect("Failed to create IAM policy");
+    let policy_arn = policy_output.policy().unwrap().arn().unwrap();
+    client
+        .create_policy_version()
+        .policy_arn(policy_arn)
+        .policy_document(CREATE_USER_PERMISSIONS_BOUNDARY)
+        .set_as_default(false)
+        .send()
+        .await
+        .expect("Failed to create IAM policy version");
+
+    let result = client.delete_policy().policy_arn(policy_arn).send().await;
+    assert!(result.is_err());
+    assert!(result.unwrap_err().into_service_error().is_delete_conflict_exception());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn delete_aws_managed_policy() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let result = client
+        .delete_policy()
+        .policy_arn("arn:aws:iam::aws:policy/AdministratorAccess")
+        .send()
+        .await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 403);
+    assert_eq!(sdk_error.into_service_error().meta().code().unwrap(), "AccessDenied");
+
+    let result = client
+        .create_policy_version()
+        .policy_arn("arn:aws:iam::aws:policy/AdministratorAccess")
+        .policy_document(CREATE_USER_PERMISSIONS_BOUNDARY)
+        .send()
+        .await;
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().raw_response().unwrap().status().as_u16(), 403);
+
+    // the policy is still available
+    client
+        .get_policy()
+        .policy_arn("arn:aws:iam::aws:policy/AdministratorAccess")
+        .send()
+        .await
+        .expect("Failed to get AWS managed policy");
+
+    ctx.stop_server().await;
+}
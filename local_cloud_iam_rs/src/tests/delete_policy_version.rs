@@ -1 +1,73 @@
+use crate::tests::fixture::CREATE_USER_PERMISSIONS_BOUNDARY;
 
+#[tokio::test]
+async fn delete_policy_version() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let policy_output =
+        super::fixture::create_policy(&client, "test-policy", "", "/", CREATE_USER_PERMISSIONS_BOUNDARY, None)
+            .await
+            .expect("Failed to create IAM policy");
+    let policy_arn = policy_output.policy().unwrap().arn().unwrap();
+    let version_output = client
+        .create_policy_version()
+        .policy_arn(policy_arn)
+        .policy_document(CREATE_USER_PERMISSIONS_BOUNDARY)
+        .set_as_default(false)
+        .send()
+        .await
+        .expect("Failed to create IAM policy version");
+    let version_id = version_output.policy_version().unwrap().version_id().unwrap();
+
+    client
+        .delete_policy_version()
+        .policy_arn(policy_arn)
+        .version_id(version_id)
+        .send()
+        .await
+        .expect("Failed to delete IAM policy version");
+
+    let result = client
+        .delete_policy_version()
+        .policy_arn(policy_arn)
+        .version_id(version_id)
+        .send()
+        .await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 404);
+    assert!(sdk_error.into_service_error().is_no_such_entity_exception());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn delete_default_policy_version() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let policy_output =
+        super::fixture::create_policy(&client, "test-policy", "", "/", CREATE_USER_PERMISSIONS_BOUNDARY, None)
+            .await
+            .expect("Failed to create IAM policy");
+
+    let result = client
+        .delete_policy_version()
+        .policy_arn(policy_output.policy().unwrap().arn().unwrap())
+        .version_id("v1")
+        .send()
+        .await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 409);
+    let error = sdk_error.into_service_error();
+    assert!(error.is_delete_conflict_exception());
+    assert_eq!(error.meta().message().unwrap(), "Cannot delete the default version of a policy.");
+
+    ctx.stop_server().await;
+}
@@ -1 +1,149 @@
+use crate::tests::fixture::{tag, CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY, CREATE_USER_PERMISSIONS_BOUNDARY};
 
+#[tokio::test]
+async fn delete_role() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    client
+        .create_role()
+        .role_name("Test-Role")
+        .assume_role_policy_document(CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY)
+        .tags(tag("key1", "value1"))
+        .send()
+        .await
+        .expect("Failed to create IAM role");
+
+    client
+        .delete_role()
+        .role_name("Test-Role")
+        .send()
+        .await
+        .expect("Failed to delete IAM role");
+
+    let result = client.delete_role().role_name("Test-Role").send().await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 404);
+    assert!(sdk_error.into_service_error().is_no_such_entity_exception());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn delete_role_with_attached_policies() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    client
+        .create_role()
+        .role_name("Test-Role")
+        .assume_role_policy_document(CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY)
+        .send()
+        .await
+        .expect("Failed to create IAM role");
+    let policy_output =
+        super::fixture::create_policy(&client, "test-policy", "", "/", CREATE_USER_PERMISSIONS_BOUNDARY, None)
+            .await
+            .expect("Failed to create IAM policy");
+    client
+        .attach_role_policy()
+        .role_name("Test-Role")
+        .policy_arn(policy_output.policy().unwrap().arn().unwrap())
+        .send()
+        .await
+        .expect("Failed to attach IAM role policy");
+
+    let result = client.delete_role().role_name("Test-Role").send().await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 409);
+    let error = sdk_error.into_service_error();
+    assert!(error.is_delete_conflict_exception());
+    assert_eq!(error.meta().message().unwrap(), "Cannot delete entity, must detach all policies first.");
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn delete_role_after_removing_dependents() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    client
+        .create_role()
+        .role_name("Test-Role")
+        .assume_role_policy_document(CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY)
+        .send()
+        .await
+        .expect("Failed to create IAM role");
+    client
+        .create_instance_profile()
+        .instance_profile_name("Webserver")
+        .send()
+        .await
+        .expect("Failed to create IAM instance profile");
+    client
+        .add_role_to_instance_profile()
+        .instance_profile_name("Webserver")
+        .role_name("Test-Role")
+        .send()
+        .await
+        .expect("Failed to add IAM role to instance profile");
+    client
+        .put_role_policy()
+        .role_name("Test-Role")
+        .policy_name("inline-policy")
+        .policy_document(CREATE_USER_PERMISSIONS_BOUNDARY)
+        .send()
+        .await
+        .expect("Failed to put IAM role policy");
+    let policy = super::fixture::create_policy(&client, "test-policy", "", "/", CREATE_USER_PERMISSIONS_BOUNDARY, None)
+        .await
+        .expect("Failed to create IAM policy");
+    let policy_arn = policy.policy().unwrap().arn().unwrap();
+    client
+        .attach_role_policy()
+        .role_name("Test-Role")
+        .policy_arn(policy_arn)
+        .send()
+        .await
+        .expect("Failed to attach IAM role policy");
+
+    client
+        .remove_role_from_instance_profile()
+        .instance_profile_name("Webserver")
+        .role_name("Test-Role")
+        .send()
+        .await
+        .expect("Failed to remove IAM role from instance profile");
+    client
+        .delete_role_policy()
+        .role_name("Test-Role")
+        .policy_name("inline-policy")
+        .send()
+        .await
+        .expect("Failed to delete IAM role policy");
+    client
+        .detach_role_policy()
+        .role_name("Test-Role")
+        .policy_arn(policy_arn)
+        .send()
+        .await
+        .expect("Failed to detach IAM role policy");
+
+    client
+        .delete_role()
+        .role_name("Test-Role")
+        .send()
+        .await
+        .expect("Failed to delete IAM role");
+
+    ctx.stop_server().await;
+}
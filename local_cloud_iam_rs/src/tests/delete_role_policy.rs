@@ -1 +1,48 @@
+use crate::tests::fixture::{CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY, CREATE_USER_PERMISSIONS_BOUNDARY};
 
+#[tokio::test]
+async fn delete_role_policy() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    client
+        .create_role()
+        .role_name("Test-Role")
+        .assume_role_policy_document(CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY)
+        .send()
+        .await
+        .expect("Failed to create IAM role");
+    client
+        .put_role_policy()
+        .role_name("Test-Role")
+        .policy_name("test_policy_1")
+        .policy_document(CREATE_USER_PERMISSIONS_BOUNDARY)
+        .send()
+        .await
+        .expect("Failed to put IAM role policy");
+
+    client
+        .delete_role_policy()
+        .role_name("Test-Role")
+        .policy_name("test_policy_1")
+        .send()
+        .await
+        .expect("Failed to delete IAM role policy");
+
+    let result = client
+        .delete_role_policy()
+        .role_name("Test-Role")
+        .policy_name("test_policy_1")
+        .send()
+        .await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 404);
+    let error = sdk_error.into_service_error();
+    assert!(error.is_no_such_entity_exception());
+    assert_eq!(error.meta().message().unwrap(), "The role policy with name test_policy_1 cannot be found.");
+
+    ctx.stop_server().await;
+}
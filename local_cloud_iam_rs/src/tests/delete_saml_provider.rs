@@ -1 +1,40 @@
+use data_encoding::BASE64;
 
+use crate::tests::fixture::tag;
+
+#[tokio::test]
+async fn delete_saml_provider() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let response = client
+        .create_saml_provider()
+        .saml_metadata_document(BASE64.encode(include_str!("resources/saml-metadata.xml").as_bytes()))
+        .name("MyUniversity")
+        .tags(tag("key1", "value1"))
+        .send()
+        .await
+        .expect("Failed to create IAM SAML provider");
+    let provider_arn = response.saml_provider_arn().unwrap();
+
+    client
+        .delete_saml_provider()
+        .saml_provider_arn(provider_arn)
+        .send()
+        .await
+        .expect("Failed to delete IAM SAML provider");
+
+    let result = client
+        .delete_saml_provider()
+        .saml_provider_arn(provider_arn)
+        .send()
+        .await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 404);
+    assert!(sdk_error.into_service_error().is_no_such_entity_exception());
+
+    ctx.stop_server().await;
+}
@@ -1 +1,38 @@
+use crate::tests::fixture::tag;
 
+#[tokio::test]
+async fn delete_server_certificate() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    client
+        .upload_server_certificate()
+        .server_certificate_name("TestCertificate1")
+        .certificate_body(include_str!("resources/cert.pem"))
+        .private_key(include_str!("resources/key.pem"))
+        .tags(tag("key-1", "value-1"))
+        .send()
+        .await
+        .expect("Failed to upload server certificate");
+
+    client
+        .delete_server_certificate()
+        .server_certificate_name("TestCertificate1")
+        .send()
+        .await
+        .expect("Failed to delete server certificate");
+
+    let result = client
+        .delete_server_certificate()
+        .server_certificate_name("TestCertificate1")
+        .send()
+        .await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 404);
+    assert!(sdk_error.into_service_error().is_no_such_entity_exception());
+
+    ctx.stop_server().await;
+}
@@ -1 +1,204 @@
+use aws_sdk_iam::types::DeletionTaskStatusType;
 
+use crate::tests::fixture::CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY;
+
+const ROLE_NAME: &str = "AWSServiceRoleForAmazonElasticsearchService";
+
+#[tokio::test]
+async fn delete_service_linked_role() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    client
+        .create_service_linked_role()
+        .aws_service_name("es.amazonaws.com")
+        .send()
+        .await
+        .expect("Failed to create service-linked role");
+
+    let response = client
+        .delete_service_linked_role()
+        .role_name(ROLE_NAME)
+        .send()
+        .await
+        .expect("Failed to delete service-linked role");
+    let deletion_task_id = response.deletion_task_id();
+    assert!(deletion_task_id.starts_with(&format!("task/aws-service-role/es.amazonaws.com/{ROLE_NAME}/")));
+
+    let status = super::fixture::wait_for_deletion_task(&client, deletion_task_id).await;
+    assert_eq!(status, DeletionTaskStatusType::Succeeded);
+
+    let result = client.get_role().role_name(ROLE_NAME).send().await;
+    assert!(result.is_err());
+    assert!(result.unwrap_err().into_service_error().is_no_such_entity_exception());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn delete_service_linked_role_in_use() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    client
+        .create_service_linked_role()
+        .aws_service_name("es.amazonaws.com")
+        .send()
+        .await
+        .expect("Failed to create service-linked role");
+    client
+        .create_instance_profile()
+        .instance_profile_name("test-profile")
+        .send()
+        .await
+        .expect("Failed to create instance profile");
+    client
+        .add_role_to_instance_profile()
+        .instance_profile_name("test-profile")
+        .role_name(ROLE_NAME)
+        .send()
+        .await
+        .expect("Failed to add role to instance profile");
+
+    let response = client
+        .delete_service_linked_role()
+        .role_name(ROLE_NAME)
+        .send()
+        .await
+        .expect("Failed to delete service-linked role");
+
+    let status = super::fixture::wait_for_deletion_task(&client, response.deletion_task_id()).await;
+    assert_eq!(status, DeletionTaskStatusType::Failed);
+
+    let response = client
+        .get_service_linked_role_deletion_status()
+        .deletion_task_id(response.deletion_task_id())
+        .send()
+        .await
+        .expect("Failed to get service-linked role deletion status");
+    assert!(response.reason().unwrap().reason().is_some());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn delete_service_linked_role_not_service_linked() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    client
+        .create_role()
+        .role_name("Test-Role")
+        .assume_role_policy_document(CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY)
+        .send()
+        .await
+        .expect("Failed to create IAM role");
+
+    let result = client.delete_service_linked_role().role_name("Test-Role").send().await;
+    assert!(result.is_err());
+    let error = result.unwrap_err().into_service_error();
+    assert_eq!(error.meta().code(), Some("InvalidInput"));
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn delete_role_protects_service_linked_role() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    client
+        .create_service_linked_role()
+        .aws_service_name("es.amazonaws.com")
+        .send()
+        .await
+        .expect("Failed to create service-linked role");
+
+    let result = client.delete_role().role_name(ROLE_NAME).send().await;
+    assert!(result.is_err());
+    let error = result.unwrap_err().into_service_error();
+    assert!(error.is_unmodifiable_entity_exception());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn policy_operations_protect_service_linked_role() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    client
+        .create_service_linked_role()
+        .aws_service_name("es.amazonaws.com")
+        .send()
+        .await
+        .expect("Failed to create service-linked role");
+    let policy_arn = "arn:aws:iam::aws:policy/ReadOnlyAccess";
+
+    let result = client
+        .put_role_policy()
+        .role_name(ROLE_NAME)
+        .policy_name("inline")
+        .policy_document(
+            r#"{"Version": "2012-10-17", "Statement": [{"Effect": "Allow", "Action": "*", "Resource": "*"}]}"#,
+        )
+        .send()
+        .await;
+    assert!(result
+        .unwrap_err()
+        .into_service_error()
+        .is_unmodifiable_entity_exception());
+
+    let result = client
+        .attach_role_policy()
+        .role_name(ROLE_NAME)
+        .policy_arn(policy_arn)
+        .send()
+        .await;
+    assert!(result
+        .unwrap_err()
+        .into_service_error()
+        .is_unmodifiable_entity_exception());
+
+    let result = client
+        .put_role_permissions_boundary()
+        .role_name(ROLE_NAME)
+        .permissions_boundary(policy_arn)
+        .send()
+        .await;
+    assert!(result
+        .unwrap_err()
+        .into_service_error()
+        .is_unmodifiable_entity_exception());
+
+    let result = client
+        .update_role()
+        .role_name(ROLE_NAME)
+        .max_session_duration(7200)
+        .send()
+        .await;
+    assert!(result
+        .unwrap_err()
+        .into_service_error()
+        .is_unmodifiable_entity_exception());
+
+    let result = client
+        .get_role_policy()
+        .role_name(ROLE_NAME)
+        .policy_name("inline")
+        .send()
+        .await;
+    assert!(result.unwrap_err().into_service_error().is_no_such_entity_exception());
+
+    ctx.stop_server().await;
+}
@@ -1 +1,55 @@
+const USER_NAME: &str = "test-user-1";
 
+#[tokio::test]
+async fn delete_service_specific_credential() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, USER_NAME, "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    let response = client
+        .create_service_specific_credential()
+        .user_name(USER_NAME)
+        .service_name("codecommit.amazonaws.com")
+        .send()
+        .await
+        .expect("Failed to create service-specific credential");
+    let credential_id = response
+        .service_specific_credential()
+        .unwrap()
+        .service_specific_credential_id();
+
+    // the user can't be deleted while it has service-specific credentials
+    let result = client.delete_user().user_name(USER_NAME).send().await;
+    assert!(result.is_err());
+    assert!(result.unwrap_err().into_service_error().is_delete_conflict_exception());
+
+    client
+        .delete_service_specific_credential()
+        .user_name(USER_NAME)
+        .service_specific_credential_id(credential_id)
+        .send()
+        .await
+        .expect("Failed to delete service-specific credential");
+
+    let result = client
+        .delete_service_specific_credential()
+        .user_name(USER_NAME)
+        .service_specific_credential_id(credential_id)
+        .send()
+        .await;
+    assert!(result.is_err());
+    assert!(result.unwrap_err().into_service_error().is_no_such_entity_exception());
+
+    client
+        .delete_user()
+        .user_name(USER_NAME)
+        .send()
+        .await
+        .expect("Failed to delete IAM user");
+
+    ctx.stop_server().await;
+}
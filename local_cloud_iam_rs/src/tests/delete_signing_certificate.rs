@@ -1 +1,42 @@
+#[tokio::test]
+async fn delete_signing_certificate() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
 
+    super::fixture::create_user(&client, "TestUser1", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    let response = client
+        .upload_signing_certificate()
+        .certificate_body(include_str!("resources/cert.pem").trim())
+        .user_name("TestUser1")
+        .send()
+        .await
+        .expect("Failed to upload signing certificate");
+    let certificate_id = response.certificate().unwrap().certificate_id();
+
+    client
+        .delete_signing_certificate()
+        .user_name("TestUser1")
+        .certificate_id(certificate_id)
+        .send()
+        .await
+        .expect("Failed to delete signing certificate");
+
+    let result = client
+        .delete_signing_certificate()
+        .user_name("TestUser1")
+        .certificate_id(certificate_id)
+        .send()
+        .await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 404);
+    let error = sdk_error.into_service_error();
+    assert!(error.is_no_such_entity_exception());
+    assert_eq!(error.meta().message().unwrap(), format!("The Certificate with id {certificate_id} cannot be found."));
+
+    ctx.stop_server().await;
+}
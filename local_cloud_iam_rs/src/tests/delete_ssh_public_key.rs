@@ -1 +1,42 @@
+#[tokio::test]
+async fn delete_ssh_public_key() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
 
+    super::fixture::create_user(&client, "test-user1", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    let response = client
+        .upload_ssh_public_key()
+        .user_name("test-user1")
+        .ssh_public_key_body(include_str!("./resources/id_ed25519.pub"))
+        .send()
+        .await
+        .expect("Failed to upload SSH public key");
+    let key_id = response.ssh_public_key().unwrap().ssh_public_key_id();
+
+    client
+        .delete_ssh_public_key()
+        .user_name("test-user1")
+        .ssh_public_key_id(key_id)
+        .send()
+        .await
+        .expect("Failed to delete SSH public key");
+
+    let result = client
+        .delete_ssh_public_key()
+        .user_name("test-user1")
+        .ssh_public_key_id(key_id)
+        .send()
+        .await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 404);
+    let error = sdk_error.into_service_error();
+    assert!(error.is_no_such_entity_exception());
+    assert_eq!(error.meta().message().unwrap(), format!("The Public Key with id {key_id} cannot be found."));
+
+    ctx.stop_server().await;
+}
@@ -1 +1,250 @@
+use crate::tests::fixture::{tag, CREATE_USER_PERMISSIONS_BOUNDARY};
 
+#[tokio::test]
+async fn delete_user() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, "user1", "/", None, Some(vec![tag("key1", "value1")]))
+        .await
+        .expect("Failed to create IAM user");
+
+    client
+        .delete_user()
+        .user_name("user1")
+        .send()
+        .await
+        .expect("Failed to delete IAM user");
+
+    let result = client.delete_user().user_name("user1").send().await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 404);
+    assert!(sdk_error.into_service_error().is_no_such_entity_exception());
+
+    // the name could be reused once the user is deleted
+    super::fixture::create_user(&client, "user1", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn delete_user_with_access_keys() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, "user1", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    client
+        .create_access_key()
+        .user_name("user1")
+        .send()
+        .await
+        .expect("Failed to create access key");
+
+    let result = client.delete_user().user_name("user1").send().await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 409);
+    let error = sdk_error.into_service_error();
+    assert!(error.is_delete_conflict_exception());
+    assert_eq!(error.meta().message().unwrap(), "Cannot delete entity, must delete access keys first.");
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn delete_user_in_group() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, "user1", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    client
+        .create_group()
+        .group_name("group1")
+        .send()
+        .await
+        .expect("Failed to create IAM group");
+    client
+        .add_user_to_group()
+        .group_name("group1")
+        .user_name("user1")
+        .send()
+        .await
+        .expect("Failed to add IAM user to group");
+
+    let result = client.delete_user().user_name("user1").send().await;
+    assert!(result.is_err());
+    let error = result.unwrap_err().into_service_error();
+    assert!(error.is_delete_conflict_exception());
+    assert_eq!(error.meta().message().unwrap(), "Cannot delete entity, must remove user from all groups first.");
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn delete_user_after_removing_dependents() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, "user1", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    client
+        .create_login_profile()
+        .user_name("user1")
+        .password("fvrmweioyt43y8989JK9*(&#@*(")
+        .send()
+        .await
+        .expect("Failed to create login profile");
+    let access_key = client
+        .create_access_key()
+        .user_name("user1")
+        .send()
+        .await
+        .expect("Failed to create access key");
+    let certificate = client
+        .upload_signing_certificate()
+        .user_name("user1")
+        .certificate_body(include_str!("resources/cert.pem").trim())
+        .send()
+        .await
+        .expect("Failed to upload signing certificate");
+    let ssh_public_key = client
+        .upload_ssh_public_key()
+        .user_name("user1")
+        .ssh_public_key_body(include_str!("./resources/id_ed25519.pub"))
+        .send()
+        .await
+        .expect("Failed to upload SSH public key");
+    let credential = client
+        .create_service_specific_credential()
+        .user_name("user1")
+        .service_name("codecommit.amazonaws.com")
+        .send()
+        .await
+        .expect("Failed to create service specific credential");
+    let serial_number = super::fixture::create_enabled_mfa_device(&client, "device1", "user1").await;
+    client
+        .put_user_policy()
+        .user_name("user1")
+        .policy_name("inline-policy")
+        .policy_document(CREATE_USER_PERMISSIONS_BOUNDARY)
+        .send()
+        .await
+        .expect("Failed to put IAM user policy");
+    let policy = super::fixture::create_policy(&client, "test-policy", "", "/", CREATE_USER_PERMISSIONS_BOUNDARY, None)
+        .await
+        .expect("Failed to create IAM policy");
+    let policy_arn = policy.policy().unwrap().arn().unwrap();
+    client
+        .attach_user_policy()
+        .user_name("user1")
+        .policy_arn(policy_arn)
+        .send()
+        .await
+        .expect("Failed to attach IAM user policy");
+    client
+        .create_group()
+        .group_name("group1")
+        .send()
+        .await
+        .expect("Failed to create IAM group");
+    client
+        .add_user_to_group()
+        .group_name("group1")
+        .user_name("user1")
+        .send()
+        .await
+        .expect("Failed to add IAM user to group");
+
+    client
+        .delete_login_profile()
+        .user_name("user1")
+        .send()
+        .await
+        .expect("Failed to delete login profile");
+    client
+        .delete_access_key()
+        .user_name("user1")
+        .access_key_id(access_key.access_key().unwrap().access_key_id())
+        .send()
+        .await
+        .expect("Failed to delete access key");
+    client
+        .delete_signing_certificate()
+        .user_name("user1")
+        .certificate_id(certificate.certificate().unwrap().certificate_id())
+        .send()
+        .await
+        .expect("Failed to delete signing certificate");
+    client
+        .delete_ssh_public_key()
+        .user_name("user1")
+        .ssh_public_key_id(ssh_public_key.ssh_public_key().unwrap().ssh_public_key_id())
+        .send()
+        .await
+        .expect("Failed to delete SSH public key");
+    client
+        .delete_service_specific_credential()
+        .user_name("user1")
+        .service_specific_credential_id(
+            credential
+                .service_specific_credential()
+                .unwrap()
+                .service_specific_credential_id(),
+        )
+        .send()
+        .await
+        .expect("Failed to delete service specific credential");
+    client
+        .deactivate_mfa_device()
+        .user_name("user1")
+        .serial_number(serial_number)
+        .send()
+        .await
+        .expect("Failed to deactivate MFA device");
+    client
+        .delete_user_policy()
+        .user_name("user1")
+        .policy_name("inline-policy")
+        .send()
+        .await
+        .expect("Failed to delete IAM user policy");
+    client
+        .detach_user_policy()
+        .user_name("user1")
+        .policy_arn(policy_arn)
+        .send()
+        .await
+        .expect("Failed to detach IAM user policy");
+    client
+        .remove_user_from_group()
+        .group_name("group1")
+        .user_name("user1")
+        .send()
+        .await
+        .expect("Failed to remove IAM user from group");
+
+    client
+        .delete_user()
+        .user_name("user1")
+        .send()
+        .await
+        .expect("Failed to delete IAM user");
+
+    ctx.stop_server().await;
+}
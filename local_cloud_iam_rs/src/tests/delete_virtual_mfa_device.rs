@@ -1 +1,97 @@
+use crate::tests::fixture::tag;
 
+const USER_NAME: &str = "test-user-1";
+
+#[tokio::test]
+async fn delete_virtual_mfa_device() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let output = client
+        .create_virtual_mfa_device()
+        .virtual_mfa_device_name("device-1")
+        .tags(tag("key-1", "value-1"))
+        .send()
+        .await
+        .expect("Failed to create Virtual MFA device");
+    let serial_number = output.virtual_mfa_device().unwrap().serial_number();
+
+    client
+        .delete_virtual_mfa_device()
+        .serial_number(serial_number)
+        .send()
+        .await
+        .expect("Failed to delete Virtual MFA device");
+
+    let result = client
+        .list_virtual_mfa_devices()
+        .send()
+        .await
+        .expect("Failed to get a list of IAM virtual MFA devices");
+    assert!(result.virtual_mfa_devices().is_empty());
+
+    let result = client.list_mfa_device_tags().serial_number(serial_number).send().await;
+    assert!(result.is_err());
+    assert!(result.unwrap_err().into_service_error().is_no_such_entity_exception());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn delete_virtual_mfa_device_enabled() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, USER_NAME, "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    let serial_number = super::fixture::create_enabled_mfa_device(&client, "device-1", USER_NAME).await;
+
+    let result = client
+        .delete_virtual_mfa_device()
+        .serial_number(&serial_number)
+        .send()
+        .await;
+    assert!(result.is_err());
+    let error = result.unwrap_err().into_service_error();
+    assert!(error.is_delete_conflict_exception());
+
+    client
+        .deactivate_mfa_device()
+        .serial_number(&serial_number)
+        .user_name(USER_NAME)
+        .send()
+        .await
+        .expect("Failed to deactivate MFA device");
+    client
+        .delete_virtual_mfa_device()
+        .serial_number(&serial_number)
+        .send()
+        .await
+        .expect("Failed to delete Virtual MFA device");
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn delete_virtual_mfa_device_not_found() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let result = client
+        .delete_virtual_mfa_device()
+        .serial_number("arn:aws:iam::000000000001:mfa/unknown")
+        .send()
+        .await;
+    assert!(result.is_err());
+    let error = result.unwrap_err().into_service_error();
+    assert!(error.is_no_such_entity_exception());
+
+    ctx.stop_server().await;
+}
@@ -1 +1,73 @@
+use crate::tests::fixture::CREATE_USER_PERMISSIONS_BOUNDARY;
 
+#[tokio::test]
+async fn detach_user_policy() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, "user1", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    let policy_output =
+        super::fixture::create_policy(&client, "test-policy", "", "/", CREATE_USER_PERMISSIONS_BOUNDARY, None)
+            .await
+            .expect("Failed to create IAM policy");
+    let policy_arn = policy_output.policy().unwrap().arn().unwrap();
+    client
+        .attach_user_policy()
+        .user_name("user1")
+        .policy_arn(policy_arn)
+        .send()
+        .await
+        .expect("Failed to attach IAM user policy");
+
+    let response = client
+        .get_policy()
+        .policy_arn(policy_arn)
+        .send()
+        .await
+        .expect("Failed to get IAM policy");
+    assert_eq!(response.policy().unwrap().attachment_count().unwrap(), 1);
+
+    client
+        .detach_user_policy()
+        .user_name("user1")
+        .policy_arn(policy_arn)
+        .send()
+        .await
+        .expect("Failed to detach IAM user policy");
+
+    let response = client
+        .list_attached_user_policies()
+        .user_name("user1")
+        .send()
+        .await
+        .expect("Failed to list attached user policies");
+    assert!(response.attached_policies().is_empty());
+
+    let response = client
+        .get_policy()
+        .policy_arn(policy_arn)
+        .send()
+        .await
+        .expect("Failed to get IAM policy");
+    assert_eq!(response.policy().unwrap().attachment_count().unwrap(), 0);
+
+    // the policy is not attached anymore
+    let result = client
+        .detach_user_policy()
+        .user_name("user1")
+        .policy_arn(policy_arn)
+        .send()
+        .await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 404);
+    let error = sdk_error.into_service_error();
+    assert!(error.is_no_such_entity_exception());
+    assert_eq!(error.meta().message().unwrap(), format!("Policy {policy_arn} was not found."));
+
+    ctx.stop_server().await;
+}
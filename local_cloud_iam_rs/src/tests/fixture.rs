@@ -1,8 +1,12 @@
+use std::time::Duration;
+
+use aws_credential_types::Credentials;
 use aws_sdk_iam::operation::create_policy::{CreatePolicyError, CreatePolicyOutput};
 use aws_sdk_iam::operation::create_user::{CreateUserError, CreateUserOutput};
-use aws_sdk_iam::types::Tag;
+use aws_sdk_iam::types::{DeletionTaskStatusType, JobStatusType, ReportStateType, Tag, VirtualMfaDevice};
 use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
 use aws_smithy_runtime_api::client::result::SdkError;
+use data_encoding::BASE32_NOPAD;
 
 pub(crate) const CREATE_USER_PERMISSIONS_BOUNDARY: &str =
     include_str!("resources/create_user__permissions_boundary.json");
@@ -41,3 +45,110 @@ pub(crate) async fn create_user(
         .send()
         .await
 }
+
+/// Generates two consecutive codes of a virtual MFA device which clock is shifted by `time_offset` time steps.
+pub(crate) fn authentication_codes(device: &VirtualMfaDevice, time_offset: i64) -> (String, String) {
+    let seed = BASE32_NOPAD
+        .decode(device.base32_string_seed().unwrap().as_ref())
+        .unwrap();
+    let time_step = local_cloud_common::totp::time_step(chrono::Utc::now().timestamp()) + time_offset;
+    (local_cloud_common::totp::generate(&seed, time_step), local_cloud_common::totp::generate(&seed, time_step + 1))
+}
+
+/// Creates a virtual MFA device and enables it for the user. Returns serial number of the device.
+pub(crate) async fn create_enabled_mfa_device(
+    client: &aws_sdk_iam::Client, device_name: &str, user_name: &str,
+) -> String {
+    let output = client
+        .create_virtual_mfa_device()
+        .virtual_mfa_device_name(device_name)
+        .send()
+        .await
+        .expect("Failed to create Virtual MFA device");
+    let device = output.virtual_mfa_device().unwrap();
+
+    let (code1, code2) = authentication_codes(device, 0);
+    client
+        .enable_mfa_device()
+        .serial_number(device.serial_number())
+        .user_name(user_name)
+        .authentication_code1(code1)
+        .authentication_code2(code2)
+        .send()
+        .await
+        .expect("Failed to enable MFA device for IAM user");
+    device.serial_number().to_owned()
+}
+
+/// Requests a credential report and waits until the report is generated.
+pub(crate) async fn generate_credential_report(client: &aws_sdk_iam::Client) {
+    for _ in 0..50 {
+        let response = client
+            .generate_credential_report()
+            .send()
+            .await
+            .expect("Failed to generate credential report");
+        if response.state() == Some(&ReportStateType::Complete) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("Credential report has not been generated in time");
+}
+
+/// Waits until the deletion task of a service-linked role is finished and returns its final status.
+pub(crate) async fn wait_for_deletion_task(
+    client: &aws_sdk_iam::Client, deletion_task_id: &str,
+) -> DeletionTaskStatusType {
+    for _ in 0..50 {
+        let response = client
+            .get_service_linked_role_deletion_status()
+            .deletion_task_id(deletion_task_id)
+            .send()
+            .await
+            .expect("Failed to get service-linked role deletion status");
+        match response.status() {
+            DeletionTaskStatusType::NotStarted | DeletionTaskStatusType::InProgress => {
+                tokio::time::sleep(Duration::from_millis(100)).await
+            }
+            status => return status.clone(),
+        }
+    }
+    panic!("Service-linked role has not been deleted in time");
+}
+
+/// Creates an access key for the IAM user and returns a client signing requests with it.
+pub(crate) async fn create_user_client(
+    client: &aws_sdk_iam::Client, port: u16, user_name: &str,
+) -> (aws_sdk_iam::Client, String) {
+    let response = client
+        .create_access_key()
+        .user_name(user_name)
+        .send()
+        .await
+        .expect("Failed to create access key");
+    let access_key = response.access_key().unwrap();
+    let credentials =
+        Credentials::new(access_key.access_key_id(), access_key.secret_access_key(), None, None, "provider_name");
+    let user_client = aws_sdk_iam::Client::new(&super::aws_config_with_credentials(port, credentials));
+    (user_client, access_key.access_key_id().to_owned())
+}
+
+/// Waits until the service last accessed details job is finished and returns its final status.
+pub(crate) async fn wait_for_service_last_accessed_details(
+    client: &aws_sdk_iam::Client, job_id: &str,
+) -> JobStatusType {
+    for _ in 0..50 {
+        let response = client
+            .get_service_last_accessed_details()
+            .job_id(job_id)
+            .send()
+            .await
+            .expect("Failed to get service last accessed details");
+        match response.job_status() {
+            JobStatusType::InProgress => tokio::time::sleep(Duration::from_millis(100)).await,
+            status => return status.clone(),
+        }
+    }
+    panic!("Service last accessed details have not been generated in time");
+}
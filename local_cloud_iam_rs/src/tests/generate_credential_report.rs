@@ -1 +1,28 @@
+use aws_sdk_iam::types::ReportStateType;
 
+#[tokio::test]
+async fn generate_credential_report() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let response = client
+        .generate_credential_report()
+        .send()
+        .await
+        .expect("Failed to generate credential report");
+    assert_eq!(Some(&ReportStateType::Started), response.state());
+    assert!(response.description().is_some());
+
+    super::fixture::generate_credential_report(&client).await;
+
+    // the report is not regenerated until it is expired
+    let response = client
+        .generate_credential_report()
+        .send()
+        .await
+        .expect("Failed to generate credential report");
+    assert_eq!(Some(&ReportStateType::Complete), response.state());
+    ctx.stop_server().await;
+}
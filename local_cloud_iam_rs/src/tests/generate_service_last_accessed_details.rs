@@ -1 +1,60 @@
+use aws_sdk_iam::types::AccessAdvisorUsageGranularityType;
 
+#[tokio::test]
+async fn generate_service_last_accessed_details() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let user = super::fixture::create_user(&client, "user1", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+
+    let response = client
+        .generate_service_last_accessed_details()
+        .arn(user.user().unwrap().arn())
+        .granularity(AccessAdvisorUsageGranularityType::ActionLevel)
+        .send()
+        .await
+        .expect("Failed to generate service last accessed details");
+
+    assert!(response.job_id().is_some());
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn generate_service_last_accessed_details_unknown_entity() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let response = client
+        .generate_service_last_accessed_details()
+        .arn("arn:aws:iam::000000000001:user/unknown-user")
+        .send()
+        .await;
+
+    assert!(response.is_err());
+    assert_eq!("NoSuchEntity", response.unwrap_err().into_service_error().meta().code().unwrap());
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn generate_service_last_accessed_details_invalid_arn() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let response = client
+        .generate_service_last_accessed_details()
+        .arn("arn:aws:iam::000000000001:server-certificate/cert1")
+        .send()
+        .await;
+
+    assert!(response.is_err());
+    assert_eq!("InvalidInput", response.unwrap_err().into_service_error().meta().code().unwrap());
+    ctx.stop_server().await;
+}
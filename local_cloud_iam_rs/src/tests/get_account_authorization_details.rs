@@ -1 +1,292 @@
+use aws_sdk_iam::types::EntityType;
 
+use crate::tests::fixture::{
+    create_policy, create_user, tag, CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY, CREATE_USER_PERMISSIONS_BOUNDARY,
+};
+
+#[tokio::test]
+async fn get_account_authorization_details() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let policy_output =
+        create_policy(&client, "detailed_policy", "policy description", "/", CREATE_USER_PERMISSIONS_BOUNDARY, None)
+            .await
+            .expect("Failed to create IAM policy");
+    let policy_arn = policy_output.policy().unwrap().arn().unwrap();
+
+    create_user(&client, "detailed_user", "/", None, Some(vec![tag("key1", "value1")]))
+        .await
+        .expect("Failed to create IAM user");
+    client
+        .create_group()
+        .group_name("detailed_group")
+        .send()
+        .await
+        .expect("Failed to create IAM group");
+    client
+        .add_user_to_group()
+        .group_name("detailed_group")
+        .user_name("detailed_user")
+        .send()
+        .await
+        .expect("Failed to add IAM user to group");
+    client
+        .put_user_policy()
+        .user_name("detailed_user")
+        .policy_name("inline_user_policy")
+        .policy_document(CREATE_USER_PERMISSIONS_BOUNDARY)
+        .send()
+        .await
+        .expect("Failed to put IAM user policy");
+    client
+        .attach_user_policy()
+        .user_name("detailed_user")
+        .policy_arn(policy_arn)
+        .send()
+        .await
+        .expect("Failed to attach IAM user policy");
+
+    client
+        .create_role()
+        .role_name("detailed_role")
+        .assume_role_policy_document(CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY)
+        .send()
+        .await
+        .expect("Failed to create IAM role");
+    client
+        .put_role_policy()
+        .role_name("detailed_role")
+        .policy_name("inline_role_policy")
+        .policy_document(CREATE_USER_PERMISSIONS_BOUNDARY)
+        .send()
+        .await
+        .expect("Failed to put IAM role policy");
+    client
+        .create_instance_profile()
+        .instance_profile_name("detailed_instance_profile")
+        .send()
+        .await
+        .expect("Failed to create IAM instance profile");
+    client
+        .add_role_to_instance_profile()
+        .instance_profile_name("detailed_instance_profile")
+        .role_name("detailed_role")
+        .send()
+        .await
+        .expect("Failed to add IAM role to instance profile");
+
+    let response = client
+        .get_account_authorization_details()
+        .send()
+        .await
+        .expect("Failed to get account authorization details");
+
+    assert!(!response.is_truncated());
+    assert!(response.marker().is_none());
+
+    let user = response
+        .user_detail_list()
+        .iter()
+        .find(|user| user.user_name() == Some("detailed_user"))
+        .expect("User is not found in authorization details");
+    assert_eq!(user.group_list(), &["detailed_group".to_owned()]);
+    assert_eq!(user.user_policy_list().len(), 1);
+    assert_eq!(user.user_policy_list()[0].policy_name(), Some("inline_user_policy"));
+    let user_policy_document = urlencoding::decode(user.user_policy_list()[0].policy_document().unwrap()).unwrap();
+    assert!(user_policy_document.contains("\"s3:*\""));
+    assert_eq!(user.attached_managed_policies().len(), 1);
+    assert_eq!(user.attached_managed_policies()[0].policy_arn(), Some(policy_arn));
+    assert_eq!(user.tags().len(), 1);
+
+    assert_eq!(response.group_detail_list().len(), 1);
+    assert_eq!(response.group_detail_list()[0].group_name(), Some("detailed_group"));
+
+    assert_eq!(response.role_detail_list().len(), 1);
+    let role = &response.role_detail_list()[0];
+    assert_eq!(role.role_name(), Some("detailed_role"));
+    assert_eq!(role.role_policy_list().len(), 1);
+    assert_eq!(role.instance_profile_list().len(), 1);
+    assert_eq!(role.instance_profile_list()[0].roles().len(), 1);
+
+    assert_eq!(response.policies().len(), 1);
+    let policy = &response.policies()[0];
+    assert_eq!(policy.arn(), Some(policy_arn));
+    assert_eq!(policy.default_version_id(), Some("v1"));
+    assert_eq!(policy.attachment_count(), Some(1));
+    assert_eq!(policy.policy_version_list().len(), 1);
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn get_account_authorization_details_with_permissions_boundary() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let policy_output = create_policy(&client, "boundary", "", "/", CREATE_USER_PERMISSIONS_BOUNDARY, None)
+        .await
+        .expect("Failed to create IAM policy");
+    let policy_arn = policy_output.policy().unwrap().arn().unwrap();
+    create_user(&client, "bounded_user", "/", Some(policy_arn), None)
+        .await
+        .expect("Failed to create IAM user");
+    create_user(&client, "unbounded_user", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+
+    let response = client
+        .get_account_authorization_details()
+        .filter(EntityType::User)
+        .send()
+        .await
+        .expect("Failed to get account authorization details");
+
+    let users = response.user_detail_list();
+    assert_eq!(users.len(), 2);
+    assert_eq!(users[0].user_name(), Some("bounded_user"));
+    let boundary = users[0]
+        .permissions_boundary()
+        .expect("Permissions boundary is missing");
+    assert_eq!(boundary.permissions_boundary_arn(), Some(policy_arn));
+    assert_eq!(users[1].user_name(), Some("unbounded_user"));
+    assert!(users[1].permissions_boundary().is_none());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn get_account_authorization_details_with_filter() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    create_user(&client, "filtered_user", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    client
+        .create_role()
+        .role_name("filtered_role")
+        .assume_role_policy_document(CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY)
+        .send()
+        .await
+        .expect("Failed to create IAM role");
+
+    let response = client
+        .get_account_authorization_details()
+        .filter(EntityType::Role)
+        .send()
+        .await
+        .expect("Failed to get account authorization details");
+
+    assert_eq!(response.user_detail_list().len(), 0);
+    assert_eq!(response.group_detail_list().len(), 0);
+    assert_eq!(response.policies().len(), 0);
+    assert_eq!(response.role_detail_list().len(), 1);
+    assert_eq!(response.role_detail_list()[0].role_name(), Some("filtered_role"));
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn get_account_authorization_details_with_pagination() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    for i in 0..3 {
+        client
+            .create_group()
+            .group_name(format!("paged_group_{i}"))
+            .send()
+            .await
+            .expect("Failed to create IAM group");
+    }
+
+    let response = client
+        .get_account_authorization_details()
+        .filter(EntityType::Group)
+        .max_items(2)
+        .send()
+        .await
+        .expect("Failed to get account authorization details");
+
+    assert_eq!(response.group_detail_list().len(), 2);
+    assert!(response.is_truncated());
+    assert!(response.marker().is_some());
+
+    let response = client
+        .get_account_authorization_details()
+        .filter(EntityType::Group)
+        .max_items(2)
+        .marker(response.marker().unwrap())
+        .send()
+        .await
+        .expect("Failed to get account authorization details");
+
+    assert_eq!(response.group_detail_list().len(), 1);
+    assert!(!response.is_truncated());
+    assert!(response.marker().is_none());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn get_account_authorization_details_with_pagination_across_entity_types() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    for i in 0..2 {
+        create_user(&client, &format!("paged_user_{i}"), "/", None, None)
+            .await
+            .expect("Failed to create IAM user");
+        client
+            .create_group()
+            .group_name(format!("paged_group_{i}"))
+            .send()
+            .await
+            .expect("Failed to create IAM group");
+    }
+
+    // users and groups are listed one after another
+    let response = client
+        .get_account_authorization_details()
+        .filter(EntityType::User)
+        .filter(EntityType::Group)
+        .max_items(3)
+        .send()
+        .await
+        .expect("Failed to get account authorization details");
+
+    assert_eq!(response.user_detail_list().len(), 2);
+    assert_eq!(response.user_detail_list()[0].user_name(), Some("paged_user_0"));
+    assert_eq!(response.group_detail_list().len(), 1);
+    assert_eq!(response.group_detail_list()[0].group_name(), Some("paged_group_0"));
+    assert!(response.is_truncated());
+
+    let response = client
+        .get_account_authorization_details()
+        .filter(EntityType::User)
+        .filter(EntityType::Group)
+        .max_items(3)
+        .marker(response.marker().unwrap())
+        .send()
+        .await
+        .expect("Failed to get account authorization details");
+
+    assert_eq!(response.user_detail_list().len(), 0);
+    assert_eq!(response.group_detail_list().len(), 1);
+    assert_eq!(response.group_detail_list()[0].group_name(), Some("paged_group_1"));
+    assert!(!response.is_truncated());
+    assert!(response.marker().is_none());
+
+    ctx.stop_server().await;
+}
@@ -1 +1,49 @@
+#[tokio::test]
+async fn get_account_password_policy() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
 
+    client
+        .update_account_password_policy()
+        .minimum_password_length(8)
+        .require_lowercase_characters(true)
+        .allow_users_to_change_password(true)
+        .max_password_age(90)
+        .hard_expiry(true)
+        .send()
+        .await
+        .expect("Failed to update account password policy");
+
+    let response = client
+        .get_account_password_policy()
+        .send()
+        .await
+        .expect("Failed to get account password policy");
+
+    let policy = response.password_policy().unwrap();
+    assert_eq!(Some(8), policy.minimum_password_length());
+    assert!(policy.require_lowercase_characters());
+    assert!(!policy.require_uppercase_characters());
+    assert!(policy.allow_users_to_change_password());
+    assert!(policy.expire_passwords());
+    assert_eq!(Some(90), policy.max_password_age());
+    assert_eq!(Some(true), policy.hard_expiry());
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn get_account_password_policy_not_found() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let response = client.get_account_password_policy().send().await;
+
+    assert!(response.is_err());
+    let sdk_error = response.unwrap_err();
+    assert_eq!(404u16, sdk_error.raw_response().unwrap().status().as_u16());
+    ctx.stop_server().await;
+}
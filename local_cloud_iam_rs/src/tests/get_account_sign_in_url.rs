@@ -0,0 +1,66 @@
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Sends an unsigned GET request to the local endpoint. Returns the status code and the JSON body of the response.
+async fn get(port: u16, path: &str) -> (u16, serde_json::Value) {
+    let mut stream = tokio::net::TcpStream::connect(("127.0.0.1", port))
+        .await
+        .expect("Failed to connect to the server");
+    let request = format!("GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n");
+    stream
+        .write_all(request.as_bytes())
+        .await
+        .expect("Failed to send the request");
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .await
+        .expect("Failed to read the response");
+
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status_code = head.split(' ').nth(1).unwrap().parse().unwrap();
+    (status_code, serde_json::from_str(body).unwrap())
+}
+
+#[tokio::test]
+async fn get_account_sign_in_url() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let (status_code, body) = get(port, "/iam/accounts/000000000001/sign-in-url").await;
+    assert_eq!(status_code, 200);
+    assert_eq!(body["AccountId"], "000000000001");
+    assert!(body.get("AccountAlias").is_none());
+    assert_eq!(body["SignInUrl"], "https://000000000001.signin.aws.amazon.com/console");
+
+    client
+        .create_account_alias()
+        .account_alias("home-cloud")
+        .send()
+        .await
+        .expect("Failed to create account alias");
+
+    let (status_code, body) = get(port, "/iam/accounts/000000000001/sign-in-url").await;
+    assert_eq!(status_code, 200);
+    assert_eq!(body["AccountAlias"], "home-cloud");
+    assert_eq!(body["SignInUrl"], "https://home-cloud.signin.aws.amazon.com/console");
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn get_account_sign_in_url_unknown_account() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+
+    let (status_code, body) = get(port, "/iam/accounts/000000000999/sign-in-url").await;
+    assert_eq!(status_code, 404);
+    assert_eq!(body["Code"], "NoSuchEntity");
+
+    let (status_code, body) = get(port, "/iam/accounts/home-cloud/sign-in-url").await;
+    assert_eq!(status_code, 400);
+    assert_eq!(body["Code"], "ValidationError");
+
+    ctx.stop_server().await;
+}
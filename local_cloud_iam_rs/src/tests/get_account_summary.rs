@@ -1 +1,102 @@
+use aws_sdk_iam::types::SummaryKeyType;
 
+use crate::tests::fixture::CREATE_USER_PERMISSIONS_BOUNDARY;
+
+#[tokio::test]
+async fn get_account_summary() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, "user1", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    let policy_output = super::fixture::create_policy(
+        &client,
+        "policy1",
+        "policy description",
+        "/",
+        CREATE_USER_PERMISSIONS_BOUNDARY,
+        None,
+    )
+    .await
+    .expect("Failed to create IAM policy");
+    client
+        .attach_user_policy()
+        .user_name("user1")
+        .policy_arn(policy_output.policy().unwrap().arn().unwrap())
+        .send()
+        .await
+        .expect("Failed to attach IAM user policy");
+
+    let response = client
+        .get_account_summary()
+        .send()
+        .await
+        .expect("Failed to get account summary");
+
+    let summary_map = response.summary_map().unwrap();
+    assert_eq!(summary_map.get(&SummaryKeyType::Users), Some(&1));
+    assert_eq!(summary_map.get(&SummaryKeyType::Groups), Some(&0));
+    // AWS managed policies are not owned by the account
+    assert_eq!(summary_map.get(&SummaryKeyType::Policies), Some(&1));
+    assert_eq!(summary_map.get(&SummaryKeyType::PolicyVersionsInUse), Some(&1));
+    assert_eq!(summary_map.get(&SummaryKeyType::from("Roles")), Some(&0));
+    assert_eq!(summary_map.get(&SummaryKeyType::AccountAccessKeysPresent), Some(&1));
+    assert_eq!(summary_map.get(&SummaryKeyType::AccountMfaEnabled), Some(&0));
+    assert_eq!(summary_map.get(&SummaryKeyType::UsersQuota), Some(&5000));
+    assert_eq!(summary_map.get(&SummaryKeyType::AccessKeysPerUserQuota), Some(&2));
+    assert_eq!(summary_map.get(&SummaryKeyType::from("RolesQuota")), Some(&1000));
+    assert_eq!(summary_map.get(&SummaryKeyType::from("AccountAliases")), Some(&0));
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn get_account_summary_with_account_alias() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    client
+        .create_account_alias()
+        .account_alias("home-cloud")
+        .send()
+        .await
+        .expect("Failed to create account alias");
+
+    let response = client
+        .get_account_summary()
+        .send()
+        .await
+        .expect("Failed to get account summary");
+
+    let summary_map = response.summary_map().unwrap();
+    assert_eq!(summary_map.get(&SummaryKeyType::from("AccountAliases")), Some(&1));
+    assert_eq!(summary_map.get(&SummaryKeyType::from("AccountAliasesPerAccountQuota")), Some(&1));
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn get_account_summary_with_configured_quotas() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server_with_low_quotas).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let response = client
+        .get_account_summary()
+        .send()
+        .await
+        .expect("Failed to get account summary");
+
+    let summary_map = response.summary_map().unwrap();
+    assert_eq!(summary_map.get(&SummaryKeyType::UsersQuota), Some(&2));
+    assert_eq!(summary_map.get(&SummaryKeyType::AttachedPoliciesPerUserQuota), Some(&1));
+    assert_eq!(summary_map.get(&SummaryKeyType::GroupsQuota), Some(&300));
+
+    ctx.stop_server().await;
+}
@@ -1 +1,104 @@
+const TAGGED_POLICY: &str = r#"{
+    "Version": "2012-10-17",
+    "Statement": [
+        {
+            "Effect": "Allow",
+            "Action": "ec2:RunInstances",
+            "Resource": "*",
+            "Condition": {
+                "ForAnyValue:StringLike": {"aws:TagKeys": ["env*"]},
+                "StringEqualsIfExists": {"ec2:InstanceType": "t3.micro"}
+            }
+        }
+    ]
+}"#;
 
+const SECURE_TRANSPORT_POLICY: &str = r#"{
+    "Version": "2012-10-17",
+    "Statement": [
+        {
+            "Effect": "Deny",
+            "Action": "*",
+            "Resource": "*",
+            "Condition": {
+                "Bool": {"aws:SecureTransport": "false"},
+                "ForAllValues:StringEquals": {"aws:TagKeys": ["env", "team"]}
+            }
+        }
+    ]
+}"#;
+
+const LOWERCASE_TAG_KEYS_POLICY: &str = r#"{
+    "Version": "2012-10-17",
+    "Statement": [
+        {
+            "Effect": "Allow",
+            "Action": "ec2:CreateTags",
+            "Resource": "*",
+            "Condition": {"ForAllValues:StringEquals": {"AWS:tagkeys": ["env"]}}
+        }
+    ]
+}"#;
+
+#[tokio::test]
+async fn get_context_keys_for_custom_policy() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let result = client
+        .get_context_keys_for_custom_policy()
+        .policy_input_list(TAGGED_POLICY)
+        .policy_input_list(SECURE_TRANSPORT_POLICY)
+        .send()
+        .await
+        .expect("Failed to get context keys for custom policy");
+
+    assert_eq!(result.context_key_names(), &["aws:SecureTransport", "aws:TagKeys", "ec2:InstanceType"]);
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn get_context_keys_for_custom_policy_ignore_case() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let result = client
+        .get_context_keys_for_custom_policy()
+        .policy_input_list(TAGGED_POLICY)
+        .policy_input_list(LOWERCASE_TAG_KEYS_POLICY)
+        .send()
+        .await
+        .expect("Failed to get context keys for custom policy");
+
+    assert_eq!(result.context_key_names(), &["aws:TagKeys", "ec2:InstanceType"]);
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn get_context_keys_for_custom_policy_malformed() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let result = client
+        .get_context_keys_for_custom_policy()
+        .policy_input_list(TAGGED_POLICY)
+        .policy_input_list(r#"{"Version": "2012-10-17", "Statement": "#)
+        .send()
+        .await;
+
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 400);
+    let error = sdk_error.into_service_error();
+    assert!(error.is_invalid_input_exception());
+
+    ctx.stop_server().await;
+}
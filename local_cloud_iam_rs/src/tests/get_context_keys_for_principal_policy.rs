@@ -1 +1,137 @@
+const USER_POLICY: &str = r#"{
+    "Version": "2012-10-17",
+    "Statement": [
+        {
+            "Effect": "Allow",
+            "Action": "s3:ListBucket",
+            "Resource": "*",
+            "Condition": {"StringLikeIfExists": {"s3:prefix": "home/${aws:username}/*"}}
+        }
+    ]
+}"#;
 
+const GROUP_POLICY: &str = r#"{
+    "Version": "2012-10-17",
+    "Statement": [
+        {
+            "Effect": "Deny",
+            "Action": "*",
+            "Resource": "*",
+            "Condition": {"Bool": {"aws:MultiFactorAuthPresent": "false"}}
+        }
+    ]
+}"#;
+
+const MANAGED_POLICY: &str = r#"{
+    "Version": "2012-10-17",
+    "Statement": [
+        {
+            "Effect": "Allow",
+            "Action": "ec2:*",
+            "Resource": "*",
+            "Condition": {
+                "ForAnyValue:StringEquals": {"aws:TagKeys": ["env"]},
+                "Bool": {"aws:MultiFactorAuthPresent": "true"}
+            }
+        }
+    ]
+}"#;
+
+const INPUT_POLICY: &str = r#"{
+    "Version": "2012-10-17",
+    "Statement": [
+        {
+            "Effect": "Allow",
+            "Action": "sqs:*",
+            "Resource": "*",
+            "Condition": {"IpAddress": {"aws:SourceIp": "10.0.0.0/8"}}
+        }
+    ]
+}"#;
+
+#[tokio::test]
+async fn get_context_keys_for_principal_policy() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let user = super::fixture::create_user(&client, "user1", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    let user_arn = user.user().unwrap().arn();
+
+    client
+        .put_user_policy()
+        .user_name("user1")
+        .policy_name("user-policy")
+        .policy_document(USER_POLICY)
+        .send()
+        .await
+        .expect("Failed to put IAM user policy");
+    client
+        .create_group()
+        .group_name("group1")
+        .send()
+        .await
+        .expect("Failed to create IAM group");
+    client
+        .add_user_to_group()
+        .group_name("group1")
+        .user_name("user1")
+        .send()
+        .await
+        .expect("Failed to add IAM user to group");
+    client
+        .put_group_policy()
+        .group_name("group1")
+        .policy_name("group-policy")
+        .policy_document(GROUP_POLICY)
+        .send()
+        .await
+        .expect("Failed to put IAM group policy");
+    let policy_output = super::fixture::create_policy(&client, "managed-policy", "", "/", MANAGED_POLICY, None)
+        .await
+        .expect("Failed to create IAM policy");
+    client
+        .attach_user_policy()
+        .user_name("user1")
+        .policy_arn(policy_output.policy().unwrap().arn().unwrap())
+        .send()
+        .await
+        .expect("Failed to attach IAM user policy");
+
+    let result = client
+        .get_context_keys_for_principal_policy()
+        .policy_source_arn(user_arn)
+        .policy_input_list(INPUT_POLICY)
+        .send()
+        .await
+        .expect("Failed to get context keys for principal policy");
+
+    assert_eq!(result.context_key_names(), &["aws:MultiFactorAuthPresent", "aws:SourceIp", "aws:TagKeys", "s3:prefix"]);
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn get_context_keys_for_unknown_principal_policy() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let result = client
+        .get_context_keys_for_principal_policy()
+        .policy_source_arn("arn:aws:iam::000000000001:role/unknown")
+        .send()
+        .await;
+
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 404);
+    let error = sdk_error.into_service_error();
+    assert!(error.is_no_such_entity_exception());
+
+    ctx.stop_server().await;
+}
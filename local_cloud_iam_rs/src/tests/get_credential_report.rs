@@ -1 +1,78 @@
+use aws_sdk_iam::types::ReportFormatType;
 
+#[tokio::test]
+async fn get_credential_report() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, "SuperUser", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    client
+        .create_login_profile()
+        .user_name("SuperUser")
+        .password("fvrmweioyt43y8989JK9*(&#@*(")
+        .send()
+        .await
+        .expect("Failed to create login profile");
+    client
+        .create_access_key()
+        .user_name("SuperUser")
+        .send()
+        .await
+        .expect("Failed to create access key");
+
+    super::fixture::generate_credential_report(&client).await;
+
+    let response = client
+        .get_credential_report()
+        .send()
+        .await
+        .expect("Failed to get credential report");
+    assert_eq!(Some(&ReportFormatType::TextCsv), response.report_format());
+    assert!(response.generated_time().is_some());
+
+    let content = String::from_utf8(response.content().unwrap().as_ref().to_vec()).unwrap();
+    let lines: Vec<&str> = content.lines().collect();
+    let header: Vec<&str> = lines[0].split(',').collect();
+    assert_eq!(22, header.len());
+    assert_eq!("user", header[0]);
+
+    let root_row: Vec<&str> = lines[1].split(',').collect();
+    assert_eq!("<root_account>", root_row[0]);
+    assert_eq!("arn:aws:iam::000000000001:root", root_row[1]);
+
+    let user_row: Vec<&str> = lines
+        .iter()
+        .find(|line| line.starts_with("SuperUser,"))
+        .unwrap()
+        .split(',')
+        .collect();
+    assert_eq!(22, user_row.len());
+    assert_eq!("arn:aws:iam::000000000001:user/SuperUser", user_row[1]);
+    assert_eq!("true", user_row[3]); // password_enabled
+    assert_eq!("false", user_row[7]); // mfa_active
+    assert_eq!("true", user_row[8]); // access_key_1_active
+    assert_eq!("N/A", user_row[10]); // access_key_1_last_used_date
+    assert_eq!("false", user_row[13]); // access_key_2_active
+    assert_eq!("N/A", user_row[14]); // access_key_2_last_rotated
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn get_credential_report_not_present() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let response = client.get_credential_report().send().await;
+
+    assert!(response.is_err());
+    let sdk_error = response.unwrap_err();
+    assert_eq!(410u16, sdk_error.raw_response().unwrap().status().as_u16());
+    assert_eq!("CredentialReportNotPresent", sdk_error.into_service_error().meta().code().unwrap());
+    ctx.stop_server().await;
+}
@@ -1 +1,50 @@
+#[tokio::test]
+async fn get_login_profile() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
 
+    super::fixture::create_user(&client, "SuperUser", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    client
+        .create_login_profile()
+        .password_reset_required(true)
+        .user_name("SuperUser")
+        .password("fvrmweioyt43y8989JK9*(&#@*(")
+        .send()
+        .await
+        .expect("Failed to create login profile");
+
+    let response = client
+        .get_login_profile()
+        .user_name("SuperUser")
+        .send()
+        .await
+        .expect("Failed to get login profile");
+
+    let login_profile = response.login_profile().unwrap();
+    assert_eq!("SuperUser", login_profile.user_name());
+    assert!(login_profile.password_reset_required());
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn get_login_profile_not_found() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, "SuperUser", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+
+    let response = client.get_login_profile().user_name("SuperUser").send().await;
+
+    assert!(response.is_err());
+    let sdk_error = response.unwrap_err();
+    assert_eq!(404u16, sdk_error.raw_response().unwrap().status().as_u16());
+    ctx.stop_server().await;
+}
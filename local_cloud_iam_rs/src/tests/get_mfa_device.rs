@@ -51,12 +51,15 @@ async fn get_mfa_device_with_attached_user() {
 
     let serial_number = create_mfa_device_output.virtual_mfa_device().unwrap().serial_number();
 
+    let (code1, code2) =
+        super::fixture::authentication_codes(create_mfa_device_output.virtual_mfa_device().unwrap(), 0);
+
     client
         .enable_mfa_device()
         .serial_number(serial_number)
         .user_name(USER_NAME)
-        .authentication_code1("012345") // just random code
-        .authentication_code2("234566") // just random code
+        .authentication_code1(code1)
+        .authentication_code2(code2)
         .send()
         .await
         .expect("Failed to enable MFA device for IAM user");
@@ -48,7 +48,13 @@ async fn get_role() {
 
     let role = result.role().unwrap();
 
-    assert!(role.tags().is_empty());
+    assert_eq!(3, role.tags().len());
+    assert!(role.role_last_used().is_none());
+    let trust_policy = urlencoding::decode(role.assume_role_policy_document().unwrap()).unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY).unwrap(),
+        serde_json::from_str::<serde_json::Value>(&trust_policy).unwrap()
+    );
     assert_not_empty(role.role_name());
     assert_not_empty(role.role_id());
     assert_eq!(role.max_session_duration().unwrap(), 3600);
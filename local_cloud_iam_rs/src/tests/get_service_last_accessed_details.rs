@@ -1 +1,176 @@
+use aws_sdk_iam::types::{AccessAdvisorUsageGranularityType, JobStatusType};
 
+const ALLOW_IAM_AND_S3_POLICY: &str = r#"{
+    "Version": "2012-10-17",
+    "Statement": [{"Effect": "Allow", "Action": ["iam:ListUsers", "iam:GetUser", "s3:GetObject"], "Resource": "*"}]
+}"#;
+
+async fn create_used_user(client: &aws_sdk_iam::Client, port: u16) -> String {
+    let user = super::fixture::create_user(client, "user1", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    client
+        .put_user_policy()
+        .user_name("user1")
+        .policy_name("test-policy")
+        .policy_document(ALLOW_IAM_AND_S3_POLICY)
+        .send()
+        .await
+        .expect("Failed to put IAM user policy");
+    let (user_client, _access_key_id) = super::fixture::create_user_client(client, port, "user1").await;
+    user_client.list_users().send().await.expect("Failed to list IAM users");
+    user.user().unwrap().arn().to_owned()
+}
+
+#[tokio::test]
+async fn get_service_last_accessed_details() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+    let user_arn = create_used_user(&client, port).await;
+
+    let job_id = client
+        .generate_service_last_accessed_details()
+        .arn(&user_arn)
+        .send()
+        .await
+        .expect("Failed to generate service last accessed details")
+        .job_id()
+        .unwrap()
+        .to_owned();
+    let status = super::fixture::wait_for_service_last_accessed_details(&client, &job_id).await;
+    assert_eq!(JobStatusType::Completed, status);
+
+    let response = client
+        .get_service_last_accessed_details()
+        .job_id(&job_id)
+        .send()
+        .await
+        .expect("Failed to get service last accessed details");
+
+    assert_eq!(Some(&AccessAdvisorUsageGranularityType::ServiceLevel), response.job_type());
+    assert!(response.error().is_none());
+    let services = response.services_last_accessed();
+    assert_eq!(2, services.len());
+
+    let iam = services
+        .iter()
+        .find(|service| service.service_namespace() == "iam")
+        .unwrap();
+    assert_eq!("AWS Identity and Access Management", iam.service_name());
+    assert!(iam.last_authenticated().is_some());
+    assert_eq!(Some(user_arn.as_str()), iam.last_authenticated_entity());
+    assert_eq!(Some("eu-local-1"), iam.last_authenticated_region());
+    assert_eq!(Some(1), iam.total_authenticated_entities());
+    assert!(iam.tracked_actions_last_accessed().is_empty());
+
+    let s3 = services
+        .iter()
+        .find(|service| service.service_namespace() == "s3")
+        .unwrap();
+    assert!(s3.last_authenticated().is_none());
+    assert_eq!(Some(0), s3.total_authenticated_entities());
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn get_service_last_accessed_details_action_level() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+    let user_arn = create_used_user(&client, port).await;
+
+    let job_id = client
+        .generate_service_last_accessed_details()
+        .arn(&user_arn)
+        .granularity(AccessAdvisorUsageGranularityType::ActionLevel)
+        .send()
+        .await
+        .expect("Failed to generate service last accessed details")
+        .job_id()
+        .unwrap()
+        .to_owned();
+    super::fixture::wait_for_service_last_accessed_details(&client, &job_id).await;
+
+    let response = client
+        .get_service_last_accessed_details()
+        .job_id(&job_id)
+        .send()
+        .await
+        .expect("Failed to get service last accessed details");
+
+    assert_eq!(Some(&AccessAdvisorUsageGranularityType::ActionLevel), response.job_type());
+    let iam = response
+        .services_last_accessed()
+        .iter()
+        .find(|service| service.service_namespace() == "iam")
+        .unwrap();
+    let actions = iam.tracked_actions_last_accessed();
+    assert_eq!(1, actions.len());
+    assert_eq!(Some("ListUsers"), actions[0].action_name());
+    assert_eq!(Some(user_arn.as_str()), actions[0].last_accessed_entity());
+    assert_eq!(Some("eu-local-1"), actions[0].last_accessed_region());
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn get_service_last_accessed_details_paginated() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+    let user_arn = create_used_user(&client, port).await;
+
+    let job_id = client
+        .generate_service_last_accessed_details()
+        .arn(&user_arn)
+        .send()
+        .await
+        .expect("Failed to generate service last accessed details")
+        .job_id()
+        .unwrap()
+        .to_owned();
+    super::fixture::wait_for_service_last_accessed_details(&client, &job_id).await;
+
+    let response = client
+        .get_service_last_accessed_details()
+        .job_id(&job_id)
+        .max_items(1)
+        .send()
+        .await
+        .expect("Failed to get service last accessed details");
+    assert_eq!(1, response.services_last_accessed().len());
+    assert!(response.is_truncated());
+
+    let response = client
+        .get_service_last_accessed_details()
+        .job_id(&job_id)
+        .max_items(1)
+        .marker(response.marker().unwrap())
+        .send()
+        .await
+        .expect("Failed to get service last accessed details");
+    assert_eq!(1, response.services_last_accessed().len());
+    assert!(!response.is_truncated());
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn get_service_last_accessed_details_not_found() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let response = client
+        .get_service_last_accessed_details()
+        .job_id("00000000-0000-0000-0000-000000000000")
+        .send()
+        .await;
+
+    assert!(response.is_err());
+    assert_eq!("NoSuchEntity", response.unwrap_err().into_service_error().meta().code().unwrap());
+    ctx.stop_server().await;
+}
@@ -1 +1,98 @@
+use aws_sdk_iam::types::{JobStatusType, PolicyOwnerEntityType};
 
+#[tokio::test]
+async fn get_service_last_accessed_details_with_entities() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let group = client
+        .create_group()
+        .group_name("group1")
+        .send()
+        .await
+        .expect("Failed to create IAM group");
+    client
+        .put_group_policy()
+        .group_name("group1")
+        .policy_name("test-policy")
+        .policy_document(
+            r#"{"Version": "2012-10-17", "Statement": [{"Effect": "Allow", "Action": "iam:*", "Resource": "*"}]}"#,
+        )
+        .send()
+        .await
+        .expect("Failed to put IAM group policy");
+    for user_name in ["user1", "user2"] {
+        super::fixture::create_user(&client, user_name, "/", None, None)
+            .await
+            .expect("Failed to create IAM user");
+        client
+            .add_user_to_group()
+            .group_name("group1")
+            .user_name(user_name)
+            .send()
+            .await
+            .expect("Failed to add IAM user to group");
+    }
+    let (user_client, _access_key_id) = super::fixture::create_user_client(&client, port, "user1").await;
+    user_client.list_users().send().await.expect("Failed to list IAM users");
+
+    let job_id = client
+        .generate_service_last_accessed_details()
+        .arn(group.group().unwrap().arn())
+        .send()
+        .await
+        .expect("Failed to generate service last accessed details")
+        .job_id()
+        .unwrap()
+        .to_owned();
+    let status = super::fixture::wait_for_service_last_accessed_details(&client, &job_id).await;
+    assert_eq!(JobStatusType::Completed, status);
+
+    let response = client
+        .get_service_last_accessed_details_with_entities()
+        .job_id(&job_id)
+        .service_namespace("iam")
+        .send()
+        .await
+        .expect("Failed to get service last accessed details with entities");
+
+    assert_eq!(&JobStatusType::Completed, response.job_status());
+    assert!(!response.is_truncated());
+    let entities = response.entity_details_list();
+    assert_eq!(2, entities.len());
+
+    let user1 = entities
+        .iter()
+        .find(|entity| entity.entity_info().unwrap().name() == "user1")
+        .unwrap();
+    assert_eq!(&PolicyOwnerEntityType::User, user1.entity_info().unwrap().r#type());
+    assert!(user1.last_authenticated().is_some());
+
+    let user2 = entities
+        .iter()
+        .find(|entity| entity.entity_info().unwrap().name() == "user2")
+        .unwrap();
+    assert!(user2.last_authenticated().is_none());
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn get_service_last_accessed_details_with_entities_not_found() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let response = client
+        .get_service_last_accessed_details_with_entities()
+        .job_id("00000000-0000-0000-0000-000000000000")
+        .service_namespace("iam")
+        .send()
+        .await;
+
+    assert!(response.is_err());
+    assert_eq!("NoSuchEntity", response.unwrap_err().into_service_error().meta().code().unwrap());
+    ctx.stop_server().await;
+}
@@ -1 +1,17 @@
+#[tokio::test]
+async fn get_service_linked_role_deletion_status_not_found() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
 
+    let result = client
+        .get_service_linked_role_deletion_status()
+        .deletion_task_id("task/aws-service-role/ecs.amazonaws.com/AWSServiceRoleForECS/unknown")
+        .send()
+        .await;
+    assert!(result.is_err());
+    assert!(result.unwrap_err().into_service_error().is_no_such_entity_exception());
+
+    ctx.stop_server().await;
+}
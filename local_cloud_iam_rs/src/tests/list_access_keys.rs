@@ -1 +1,79 @@
+const USER_NAME: &str = "test-user1";
 
+#[tokio::test]
+async fn list_access_keys_empty() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, USER_NAME, "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+
+    let response = client
+        .list_access_keys()
+        .user_name(USER_NAME)
+        .send()
+        .await
+        .expect("Failed to get a list of access keys");
+
+    assert!(response.access_key_metadata().is_empty());
+    assert!(!response.is_truncated());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn list_access_keys() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, USER_NAME, "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+
+    for _i in 0..2 {
+        client
+            .create_access_key()
+            .user_name(USER_NAME)
+            .send()
+            .await
+            .expect("Failed to create access key");
+    }
+
+    let response = client
+        .list_access_keys()
+        .user_name(USER_NAME)
+        .max_items(1)
+        .send()
+        .await
+        .expect("Failed to get a list of access keys");
+
+    assert_eq!(response.access_key_metadata().len(), 1);
+    assert!(response.is_truncated());
+    assert!(response.marker().is_some());
+
+    let first_key_id = response.access_key_metadata()[0].access_key_id().unwrap();
+
+    let response = client
+        .list_access_keys()
+        .user_name(USER_NAME)
+        .max_items(1)
+        .marker(response.marker().unwrap())
+        .send()
+        .await
+        .expect("Failed to get a list of access keys");
+
+    assert_eq!(response.access_key_metadata().len(), 1);
+    assert!(!response.is_truncated());
+    assert!(response.marker().is_none());
+
+    let access_key = &response.access_key_metadata()[0];
+    assert_ne!(access_key.access_key_id().unwrap(), first_key_id);
+    assert_eq!(access_key.user_name().unwrap(), USER_NAME);
+
+    ctx.stop_server().await;
+}
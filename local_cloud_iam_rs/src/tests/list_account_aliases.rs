@@ -1 +1,19 @@
+#[tokio::test]
+async fn list_account_aliases_empty() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
 
+    let response = client
+        .list_account_aliases()
+        .send()
+        .await
+        .expect("Failed to list account aliases");
+
+    assert!(response.account_aliases().is_empty());
+    assert!(!response.is_truncated());
+    assert!(response.marker().is_none());
+
+    ctx.stop_server().await;
+}
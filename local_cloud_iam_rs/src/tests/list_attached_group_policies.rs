@@ -1 +1,56 @@
+use crate::tests::fixture::CREATE_USER_PERMISSIONS_BOUNDARY;
 
+#[tokio::test]
+async fn list_attached_group_policies() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    client
+        .create_group()
+        .group_name("group1")
+        .send()
+        .await
+        .expect("Failed to create IAM group");
+    let policy_output = super::fixture::create_policy(
+        &client,
+        "test-policy",
+        "",
+        "/division_abc/",
+        CREATE_USER_PERMISSIONS_BOUNDARY,
+        None,
+    )
+    .await
+    .expect("Failed to create IAM policy");
+    let policy_arn = policy_output.policy().unwrap().arn().unwrap();
+    client
+        .attach_group_policy()
+        .group_name("group1")
+        .policy_arn(policy_arn)
+        .send()
+        .await
+        .expect("Failed to attach IAM group policy");
+
+    let response = client
+        .list_attached_group_policies()
+        .group_name("group1")
+        .send()
+        .await
+        .expect("Failed to list attached group policies");
+    assert_eq!(response.attached_policies().len(), 1);
+    assert_eq!(response.attached_policies()[0].policy_name().unwrap(), "test-policy");
+    assert_eq!(response.attached_policies()[0].policy_arn().unwrap(), policy_arn);
+    assert!(!response.is_truncated());
+
+    let response = client
+        .list_attached_group_policies()
+        .group_name("group1")
+        .path_prefix("/division_xyz/")
+        .send()
+        .await
+        .expect("Failed to list attached group policies");
+    assert!(response.attached_policies().is_empty());
+
+    ctx.stop_server().await;
+}
@@ -1 +1,44 @@
+use crate::tests::fixture::{CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY, CREATE_USER_PERMISSIONS_BOUNDARY};
 
+#[tokio::test]
+async fn list_attached_role_policies() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    client
+        .create_role()
+        .role_name("Test-Role")
+        .assume_role_policy_document(CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY)
+        .send()
+        .await
+        .expect("Failed to create IAM role");
+    for policy_name in ["policy-1", "policy-2"] {
+        let policy_output =
+            super::fixture::create_policy(&client, policy_name, "", "/", CREATE_USER_PERMISSIONS_BOUNDARY, None)
+                .await
+                .expect("Failed to create IAM policy");
+        client
+            .attach_role_policy()
+            .role_name("Test-Role")
+            .policy_arn(policy_output.policy().unwrap().arn().unwrap())
+            .send()
+            .await
+            .expect("Failed to attach IAM role policy");
+    }
+
+    let response = client
+        .list_attached_role_policies()
+        .role_name("Test-Role")
+        .send()
+        .await
+        .expect("Failed to list attached role policies");
+    assert_eq!(response.attached_policies().len(), 2);
+    assert_eq!(response.attached_policies()[0].policy_name().unwrap(), "policy-1");
+    assert_eq!(response.attached_policies()[1].policy_name().unwrap(), "policy-2");
+    assert!(!response.is_truncated());
+    assert!(response.marker().is_none());
+
+    ctx.stop_server().await;
+}
@@ -1 +1,85 @@
+use crate::tests::fixture::CREATE_USER_PERMISSIONS_BOUNDARY;
 
+#[tokio::test]
+async fn list_attached_user_policies() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, "user1", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    for (policy_name, path) in [
+        ("policy-1", "/"),
+        ("policy-2", "/division_abc/"),
+        ("policy-3", "/division_abc/"),
+    ] {
+        let policy_output =
+            super::fixture::create_policy(&client, policy_name, "", path, CREATE_USER_PERMISSIONS_BOUNDARY, None)
+                .await
+                .expect("Failed to create IAM policy");
+        client
+            .attach_user_policy()
+            .user_name("user1")
+            .policy_arn(policy_output.policy().unwrap().arn().unwrap())
+            .send()
+            .await
+            .expect("Failed to attach IAM user policy");
+    }
+
+    let response = client
+        .list_attached_user_policies()
+        .user_name("user1")
+        .max_items(2)
+        .send()
+        .await
+        .expect("Failed to list attached user policies");
+    assert_eq!(response.attached_policies().len(), 2);
+    assert_eq!(response.attached_policies()[0].policy_name().unwrap(), "policy-1");
+    assert_eq!(response.attached_policies()[0].policy_arn().unwrap(), "arn:aws:iam::000000000001:policy/policy-1");
+    assert!(response.is_truncated());
+    assert!(response.marker().is_some());
+
+    let response = client
+        .list_attached_user_policies()
+        .user_name("user1")
+        .max_items(2)
+        .marker(response.marker().unwrap())
+        .send()
+        .await
+        .expect("Failed to list attached user policies");
+    assert_eq!(response.attached_policies().len(), 1);
+    assert_eq!(response.attached_policies()[0].policy_name().unwrap(), "policy-3");
+    assert!(!response.is_truncated());
+    assert!(response.marker().is_none());
+
+    let response = client
+        .list_attached_user_policies()
+        .user_name("user1")
+        .path_prefix("/division_abc/")
+        .send()
+        .await
+        .expect("Failed to list attached user policies");
+    assert_eq!(response.attached_policies().len(), 2);
+    assert_eq!(response.attached_policies()[0].policy_name().unwrap(), "policy-2");
+    assert_eq!(response.attached_policies()[1].policy_name().unwrap(), "policy-3");
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn list_attached_user_policies_unknown_user() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let result = client.list_attached_user_policies().user_name("unknown").send().await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 404);
+    assert!(sdk_error.into_service_error().is_no_such_entity_exception());
+
+    ctx.stop_server().await;
+}
@@ -1 +1,164 @@
+use aws_sdk_iam::types::{EntityType, PolicyUsageType};
 
+use crate::tests::fixture::{CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY, CREATE_USER_PERMISSIONS_BOUNDARY};
+
+#[tokio::test]
+async fn list_entities_for_policy() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let policy_output =
+        super::fixture::create_policy(&client, "test-policy", "", "/", CREATE_USER_PERMISSIONS_BOUNDARY, None)
+            .await
+            .expect("Failed to create IAM policy");
+    let policy_arn = policy_output.policy().unwrap().arn().unwrap();
+
+    // user1 has the policy attached, user2 uses it as a permissions boundary
+    super::fixture::create_user(&client, "user1", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    client
+        .attach_user_policy()
+        .user_name("user1")
+        .policy_arn(policy_arn)
+        .send()
+        .await
+        .expect("Failed to attach IAM user policy");
+    super::fixture::create_user(&client, "user2", "/division_abc/", Some(policy_arn), None)
+        .await
+        .expect("Failed to create IAM user");
+
+    client
+        .create_group()
+        .group_name("group1")
+        .send()
+        .await
+        .expect("Failed to create IAM group");
+    client
+        .attach_group_policy()
+        .group_name("group1")
+        .policy_arn(policy_arn)
+        .send()
+        .await
+        .expect("Failed to attach IAM group policy");
+
+    client
+        .create_role()
+        .role_name("Test-Role")
+        .assume_role_policy_document(CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY)
+        .permissions_boundary(policy_arn)
+        .send()
+        .await
+        .expect("Failed to create IAM role");
+
+    let response = client
+        .list_entities_for_policy()
+        .policy_arn(policy_arn)
+        .send()
+        .await
+        .expect("Failed to list entities for policy");
+    assert_eq!(response.policy_users().len(), 2);
+    assert_eq!(response.policy_users()[0].user_name().unwrap(), "user1");
+    assert!(response.policy_users()[0].user_id().is_some());
+    assert_eq!(response.policy_users()[1].user_name().unwrap(), "user2");
+    assert_eq!(response.policy_groups().len(), 1);
+    assert_eq!(response.policy_groups()[0].group_name().unwrap(), "group1");
+    assert_eq!(response.policy_roles().len(), 1);
+    assert_eq!(response.policy_roles()[0].role_name().unwrap(), "Test-Role");
+    assert!(!response.is_truncated());
+
+    let response = client
+        .list_entities_for_policy()
+        .policy_arn(policy_arn)
+        .policy_usage_filter(PolicyUsageType::PermissionsPolicy)
+        .send()
+        .await
+        .expect("Failed to list entities for policy");
+    assert_eq!(response.policy_users().len(), 1);
+    assert_eq!(response.policy_users()[0].user_name().unwrap(), "user1");
+    assert_eq!(response.policy_groups().len(), 1);
+    assert!(response.policy_roles().is_empty());
+
+    let response = client
+        .list_entities_for_policy()
+        .policy_arn(policy_arn)
+        .policy_usage_filter(PolicyUsageType::PermissionsBoundary)
+        .send()
+        .await
+        .expect("Failed to list entities for policy");
+    assert_eq!(response.policy_users().len(), 1);
+    assert_eq!(response.policy_users()[0].user_name().unwrap(), "user2");
+    assert!(response.policy_groups().is_empty());
+    assert_eq!(response.policy_roles().len(), 1);
+
+    let response = client
+        .list_entities_for_policy()
+        .policy_arn(policy_arn)
+        .entity_filter(EntityType::User)
+        .path_prefix("/division_abc/")
+        .send()
+        .await
+        .expect("Failed to list entities for policy");
+    assert_eq!(response.policy_users().len(), 1);
+    assert_eq!(response.policy_users()[0].user_name().unwrap(), "user2");
+    assert!(response.policy_groups().is_empty());
+    assert!(response.policy_roles().is_empty());
+
+    let response = client
+        .list_entities_for_policy()
+        .policy_arn(policy_arn)
+        .entity_filter(EntityType::AwsManagedPolicy)
+        .send()
+        .await
+        .expect("Failed to list entities for policy");
+    assert!(response.policy_users().is_empty());
+    assert!(response.policy_groups().is_empty());
+    assert!(response.policy_roles().is_empty());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn list_entities_for_policy_paginated() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let policy_output =
+        super::fixture::create_policy(&client, "test-policy", "", "/", CREATE_USER_PERMISSIONS_BOUNDARY, None)
+            .await
+            .expect("Failed to create IAM policy");
+    let policy_arn = policy_output.policy().unwrap().arn().unwrap();
+    for user_name in ["user1", "user2", "user3"] {
+        super::fixture::create_user(&client, user_name, "/", Some(policy_arn), None)
+            .await
+            .expect("Failed to create IAM user");
+    }
+
+    let response = client
+        .list_entities_for_policy()
+        .policy_arn(policy_arn)
+        .max_items(2)
+        .send()
+        .await
+        .expect("Failed to list entities for policy");
+    assert_eq!(response.policy_users().len(), 2);
+    assert!(response.is_truncated());
+
+    let response = client
+        .list_entities_for_policy()
+        .policy_arn(policy_arn)
+        .max_items(2)
+        .marker(response.marker().unwrap())
+        .send()
+        .await
+        .expect("Failed to list entities for policy");
+    assert_eq!(response.policy_users().len(), 1);
+    assert_eq!(response.policy_users()[0].user_name().unwrap(), "user3");
+    assert!(!response.is_truncated());
+
+    ctx.stop_server().await;
+}
@@ -1 +1,96 @@
+const USER_NAME: &str = "test-user-1";
 
+#[tokio::test]
+async fn list_mfa_devices() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, USER_NAME, "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    let serial_number_1 = super::fixture::create_enabled_mfa_device(&client, "device-1", USER_NAME).await;
+    let serial_number_2 = super::fixture::create_enabled_mfa_device(&client, "device-2", USER_NAME).await;
+    // unassigned devices are not listed
+    client
+        .create_virtual_mfa_device()
+        .virtual_mfa_device_name("device-3")
+        .send()
+        .await
+        .expect("Failed to create Virtual MFA device");
+
+    let result = client
+        .list_mfa_devices()
+        .user_name(USER_NAME)
+        .max_items(1)
+        .send()
+        .await
+        .expect("Failed to list MFA devices");
+    assert_eq!(result.mfa_devices().len(), 1);
+    assert_eq!(result.mfa_devices()[0].user_name(), USER_NAME);
+    assert_eq!(result.mfa_devices()[0].serial_number(), serial_number_1);
+    assert!(result.is_truncated());
+    assert!(result.marker().is_some());
+
+    let result = client
+        .list_mfa_devices()
+        .user_name(USER_NAME)
+        .marker(result.marker().unwrap())
+        .send()
+        .await
+        .expect("Failed to list MFA devices");
+    assert_eq!(result.mfa_devices().len(), 1);
+    assert_eq!(result.mfa_devices()[0].serial_number(), serial_number_2);
+    assert!(!result.is_truncated());
+    assert!(result.marker().is_none());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn list_mfa_devices_of_caller() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, USER_NAME, "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    super::fixture::create_enabled_mfa_device(&client, "device-1", USER_NAME).await;
+
+    let result = client
+        .list_mfa_devices()
+        .send()
+        .await
+        .expect("Failed to list MFA devices");
+    assert!(result.mfa_devices().is_empty());
+
+    let serial_number = super::fixture::create_enabled_mfa_device(&client, "device-2", "Root").await;
+    let result = client
+        .list_mfa_devices()
+        .send()
+        .await
+        .expect("Failed to list MFA devices");
+    assert_eq!(result.mfa_devices().len(), 1);
+    assert_eq!(result.mfa_devices()[0].serial_number(), serial_number);
+    assert_eq!(result.mfa_devices()[0].user_name(), "Root");
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn list_mfa_devices_no_such_user() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let result = client.list_mfa_devices().user_name(USER_NAME).send().await;
+    assert!(result.is_err());
+    let error = result.unwrap_err().into_service_error();
+    assert!(error.is_no_such_entity_exception());
+
+    ctx.stop_server().await;
+}
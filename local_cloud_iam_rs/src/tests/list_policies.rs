@@ -1,3 +1,5 @@
+use aws_sdk_iam::types::PolicyScopeType;
+
 use crate::tests::fixture::{tag, CREATE_USER_PERMISSIONS_BOUNDARY};
 use local_cloud_testing::assertions::assert_not_empty;
 
@@ -10,6 +12,7 @@ async fn list_policies_empty() {
 
     let result = client
         .list_policies()
+        .scope(PolicyScopeType::Local)
         .max_items(10)
         .send()
         .await
@@ -41,6 +44,7 @@ async fn list_policies_no_marker() {
 
     let result = client
         .list_policies()
+        .scope(PolicyScopeType::Local)
         .max_items(10)
         .send()
         .await
@@ -101,6 +105,7 @@ async fn list_policies_with_marker() {
 
     let result = client
         .list_policies()
+        .scope(PolicyScopeType::Local)
         .max_items(1)
         .send()
         .await
@@ -114,6 +119,7 @@ async fn list_policies_with_marker() {
     // requesting second page
     let result = client
         .list_policies()
+        .scope(PolicyScopeType::Local)
         .max_items(1)
         .marker(result.marker().unwrap())
         .send()
@@ -126,3 +132,49 @@ async fn list_policies_with_marker() {
     assert!(result.marker().is_none());
     ctx.stop_server().await;
 }
+
+#[tokio::test]
+async fn list_policies_aws_managed() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_policy(&client, "some-policy-name", "", "/", CREATE_USER_PERMISSIONS_BOUNDARY, None)
+        .await
+        .unwrap();
+
+    let result = client
+        .list_policies()
+        .scope(PolicyScopeType::Aws)
+        .max_items(100)
+        .send()
+        .await
+        .expect("Failed to get a list of IAM policies");
+
+    assert!(!result.policies().is_empty());
+    assert!(result
+        .policies()
+        .iter()
+        .all(|policy| policy.arn().unwrap().starts_with("arn:aws:iam::aws:policy/")));
+    let policy = result
+        .policies()
+        .iter()
+        .find(|policy| policy.policy_name() == Some("ReadOnlyAccess"))
+        .expect("ReadOnlyAccess policy is not found");
+    assert_eq!(policy.arn().unwrap(), "arn:aws:iam::aws:policy/ReadOnlyAccess");
+    assert_eq!(policy.default_version_id().unwrap(), "v1");
+    assert!(policy.is_attachable());
+
+    let result = client
+        .list_policies()
+        .scope(PolicyScopeType::Aws)
+        .path_prefix("/service-role/")
+        .send()
+        .await
+        .expect("Failed to get a list of IAM policies");
+    assert_eq!(result.policies().len(), 1);
+    assert_eq!(result.policies()[0].arn().unwrap(), "arn:aws:iam::aws:policy/service-role/AWSLambdaBasicExecutionRole");
+
+    ctx.stop_server().await;
+}
@@ -67,6 +67,11 @@ async fn list_roles() {
         .expect("Failed to get a list of IAM roles");
 
     assert_eq!(roles.roles().len(), 2);
+    let trust_policy = urlencoding::decode(roles.roles()[0].assume_role_policy_document().unwrap()).unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY).unwrap(),
+        serde_json::from_str::<serde_json::Value>(&trust_policy).unwrap()
+    );
 
     ctx.stop_server().await;
 }
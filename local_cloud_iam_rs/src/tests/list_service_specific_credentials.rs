@@ -1 +1,66 @@
+const USER_NAME: &str = "test-user-1";
 
+#[tokio::test]
+async fn list_service_specific_credentials() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, USER_NAME, "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    for service_name in [
+        "codecommit.amazonaws.com",
+        "cassandra.amazonaws.com",
+        "bedrock.amazonaws.com",
+    ] {
+        client
+            .create_service_specific_credential()
+            .user_name(USER_NAME)
+            .service_name(service_name)
+            .send()
+            .await
+            .expect("Failed to create service-specific credential");
+    }
+
+    let response = client
+        .list_service_specific_credentials()
+        .user_name(USER_NAME)
+        .send()
+        .await
+        .expect("Failed to list service-specific credentials");
+    assert_eq!(response.service_specific_credentials().len(), 3);
+
+    let response = client
+        .list_service_specific_credentials()
+        .user_name(USER_NAME)
+        .service_name("bedrock.amazonaws.com")
+        .send()
+        .await
+        .expect("Failed to list service-specific credentials");
+    assert_eq!(response.service_specific_credentials().len(), 1);
+    let credential = &response.service_specific_credentials()[0];
+    assert_eq!(credential.user_name(), USER_NAME);
+    assert_eq!(credential.service_name(), "bedrock.amazonaws.com");
+    assert!(credential.service_user_name().starts_with("BedrockAPIKey-"));
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn list_service_specific_credentials_empty() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let response = client
+        .list_service_specific_credentials()
+        .send()
+        .await
+        .expect("Failed to list service-specific credentials");
+    assert!(response.service_specific_credentials().is_empty());
+
+    ctx.stop_server().await;
+}
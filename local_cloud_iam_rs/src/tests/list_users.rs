@@ -15,3 +15,29 @@ async fn list_users_empty() {
     assert!(result.users().is_empty());
     ctx.stop_server().await;
 }
+
+#[tokio::test]
+async fn list_users() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    // users which are not members of any group are listed as well
+    for i in 0..2 {
+        super::fixture::create_user(&client, &format!("listed_user_{i}"), "/", None, None)
+            .await
+            .expect("Failed to create IAM user");
+    }
+
+    let result = client
+        .list_users()
+        .max_items(10)
+        .send()
+        .await
+        .expect("Failed to get a list of IAM users");
+
+    let user_names: Vec<&str> = result.users().iter().map(|user| user.user_name()).collect();
+    assert_eq!(user_names, vec!["listed_user_0", "listed_user_1"]);
+    ctx.stop_server().await;
+}
@@ -1,3 +1,4 @@
+use aws_sdk_iam::types::AssignmentStatusType;
 use local_cloud_testing::assertions::assert_not_empty;
 
 const USER_NAME: &str = "test-user-1";
@@ -39,12 +40,15 @@ async fn list_virtual_mfa_devices_with_attached_user() {
         .await
         .expect("Failed to create Virtual MFA device");
 
+    let (code1, code2) =
+        super::fixture::authentication_codes(create_mfa_device_output.virtual_mfa_device().unwrap(), 0);
+
     client
         .enable_mfa_device()
         .serial_number(create_mfa_device_output.virtual_mfa_device().unwrap().serial_number())
         .user_name(USER_NAME)
-        .authentication_code1("012349") // just random code
-        .authentication_code2("234566") // just random code
+        .authentication_code1(code1)
+        .authentication_code2(code2)
         .send()
         .await
         .expect("Failed to enable MFA device for IAM user");
@@ -71,3 +75,52 @@ async fn list_virtual_mfa_devices_with_attached_user() {
 
     ctx.stop_server().await;
 }
+
+#[tokio::test]
+async fn list_virtual_mfa_devices_by_assignment_status() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, USER_NAME, "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    let assigned_serial_number = super::fixture::create_enabled_mfa_device(&client, "device-1", USER_NAME).await;
+    let output = client
+        .create_virtual_mfa_device()
+        .virtual_mfa_device_name("device-2")
+        .send()
+        .await
+        .expect("Failed to create Virtual MFA device");
+    let unassigned_serial_number = output.virtual_mfa_device().unwrap().serial_number();
+
+    let result = client
+        .list_virtual_mfa_devices()
+        .assignment_status(AssignmentStatusType::Assigned)
+        .send()
+        .await
+        .expect("Failed to get a list of IAM virtual MFA devices");
+    assert_eq!(result.virtual_mfa_devices().len(), 1);
+    assert_eq!(result.virtual_mfa_devices()[0].serial_number(), assigned_serial_number);
+
+    let result = client
+        .list_virtual_mfa_devices()
+        .assignment_status(AssignmentStatusType::Unassigned)
+        .send()
+        .await
+        .expect("Failed to get a list of IAM virtual MFA devices");
+    assert_eq!(result.virtual_mfa_devices().len(), 1);
+    assert_eq!(result.virtual_mfa_devices()[0].serial_number(), unassigned_serial_number);
+    assert!(result.virtual_mfa_devices()[0].user().is_none());
+
+    let result = client
+        .list_virtual_mfa_devices()
+        .assignment_status(AssignmentStatusType::Any)
+        .send()
+        .await
+        .expect("Failed to get a list of IAM virtual MFA devices");
+    assert_eq!(result.virtual_mfa_devices().len(), 2);
+
+    ctx.stop_server().await;
+}
@@ -11,6 +11,8 @@ mod add_user_to_group;
 mod attach_group_policy;
 mod attach_role_policy;
 mod attach_user_policy;
+mod authentication;
+mod authorization;
 mod change_password;
 mod create_access_key;
 mod create_account_alias;
@@ -61,6 +63,7 @@ mod generate_service_last_accessed_details;
 mod get_access_key_last_used;
 mod get_account_authorization_details;
 mod get_account_password_policy;
+mod get_account_sign_in_url;
 mod get_account_summary;
 mod get_context_keys_for_custom_policy;
 mod get_context_keys_for_principal_policy;
@@ -170,10 +173,14 @@ pub fn credentials_provider() -> impl ProvideCredentials {
 }
 
 pub fn aws_config(port: u16) -> SdkConfig {
+    aws_config_with_credentials(port, credentials_provider())
+}
+
+pub fn aws_config_with_credentials(port: u16, credentials_provider: impl ProvideCredentials + 'static) -> SdkConfig {
     SdkConfig::builder()
         .region(Some(Region::new("eu-local-1")))
         .endpoint_url(format!("http://localhost:{}/iam", port))
-        .credentials_provider(SharedCredentialsProvider::new(credentials_provider()))
+        .credentials_provider(SharedCredentialsProvider::new(credentials_provider))
         .behavior_version(BehaviorVersion::latest())
         .build()
 }
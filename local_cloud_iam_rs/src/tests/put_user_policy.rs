@@ -23,3 +23,87 @@ async fn put_user_policy() {
 
     ctx.stop_server().await;
 }
+
+#[tokio::test]
+async fn put_user_policy_with_principal() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let config = super::aws_config(ctx.port);
+    let client = aws_sdk_iam::Client::new(&config);
+    super::fixture::create_user(&client, "user1", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+
+    let response = client
+        .put_user_policy()
+        .user_name("user1")
+        .policy_name("test-policy")
+        .policy_document(
+            r#"{"Version": "2012-10-17", "Statement": [
+                {"Effect": "Allow", "Principal": "*", "Action": "s3:*", "Resource": "*"}
+            ]}"#,
+        )
+        .send()
+        .await;
+
+    assert!(response.is_err());
+    let error = response.unwrap_err().into_service_error();
+    assert!(error.is_malformed_policy_document_exception());
+    assert_eq!(
+        error.meta().message().unwrap(),
+        "Statement[0].Principal: Policy document should not specify a principal."
+    );
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn put_user_policy_malformed_resource() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let config = super::aws_config(ctx.port);
+    let client = aws_sdk_iam::Client::new(&config);
+    super::fixture::create_user(&client, "user1", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+
+    let response = client
+        .put_user_policy()
+        .user_name("user1")
+        .policy_name("test-policy")
+        .policy_document(
+            r#"{"Version": "2012-10-17", "Statement": [{"Effect": "Allow", "Action": "s3:*", "Resource": "bucket"}]}"#,
+        )
+        .send()
+        .await;
+
+    assert!(response.is_err());
+    let error = response.unwrap_err().into_service_error();
+    assert!(error.is_malformed_policy_document_exception());
+    assert_eq!(
+        error.meta().message().unwrap(),
+        r#"Statement[0].Resource: Resource bucket must be in ARN format or "*"."#
+    );
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn put_user_policy_too_large_request() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let config = super::aws_config(ctx.port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let response = client
+        .put_user_policy()
+        .user_name("user1")
+        .policy_name("test-policy")
+        .policy_document("a".repeat(3 * 1024 * 1024))
+        .send()
+        .await;
+
+    assert!(response.is_err());
+    let sdk_error = response.unwrap_err();
+    assert_eq!(400u16, sdk_error.raw_response().unwrap().status().as_u16());
+    let error = sdk_error.into_service_error();
+    assert_eq!(error.meta().code().unwrap(), "InvalidInput");
+    assert_eq!(error.meta().message().unwrap(), "Request body must not be larger than 2097152 bytes.");
+
+    ctx.stop_server().await;
+}
@@ -1 +1,53 @@
+use crate::tests::fixture::CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY;
 
+#[tokio::test]
+async fn remove_role_from_instance_profile() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    client
+        .create_instance_profile()
+        .instance_profile_name("Webserver")
+        .send()
+        .await
+        .expect("Failed to create IAM instance profile");
+    client
+        .create_role()
+        .role_name("Test-Role")
+        .assume_role_policy_document(CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY)
+        .send()
+        .await
+        .expect("Failed to create IAM role");
+    client
+        .add_role_to_instance_profile()
+        .instance_profile_name("Webserver")
+        .role_name("Test-Role")
+        .send()
+        .await
+        .expect("Failed to add IAM role to instance profile");
+
+    client
+        .remove_role_from_instance_profile()
+        .instance_profile_name("Webserver")
+        .role_name("Test-Role")
+        .send()
+        .await
+        .expect("Failed to remove IAM role from instance profile");
+
+    let result = client
+        .remove_role_from_instance_profile()
+        .instance_profile_name("Webserver")
+        .role_name("Test-Role")
+        .send()
+        .await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 404);
+    let error = sdk_error.into_service_error();
+    assert!(error.is_no_such_entity_exception());
+    assert_eq!(error.meta().message().unwrap(), "Role Test-Role in Instance Profile Webserver cannot be found.");
+
+    ctx.stop_server().await;
+}
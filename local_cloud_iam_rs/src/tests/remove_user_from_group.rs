@@ -1 +1,47 @@
+#[tokio::test]
+async fn remove_user_from_group() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
 
+    super::fixture::create_user(&client, "user1", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    client
+        .create_group()
+        .group_name("group1")
+        .send()
+        .await
+        .expect("Failed to create IAM group");
+    client
+        .add_user_to_group()
+        .group_name("group1")
+        .user_name("user1")
+        .send()
+        .await
+        .expect("Failed to add IAM user to group");
+
+    client
+        .remove_user_from_group()
+        .group_name("group1")
+        .user_name("user1")
+        .send()
+        .await
+        .expect("Failed to remove IAM user from group");
+
+    let result = client
+        .remove_user_from_group()
+        .group_name("group1")
+        .user_name("user1")
+        .send()
+        .await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 404);
+    let error = sdk_error.into_service_error();
+    assert!(error.is_no_such_entity_exception());
+    assert_eq!(error.meta().message().unwrap(), "User user1 is not a member of group group1.");
+
+    ctx.stop_server().await;
+}
@@ -1 +1,35 @@
+const USER_NAME: &str = "test-user-1";
 
+#[tokio::test]
+async fn reset_service_specific_credential() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, USER_NAME, "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    let response = client
+        .create_service_specific_credential()
+        .user_name(USER_NAME)
+        .service_name("codecommit.amazonaws.com")
+        .send()
+        .await
+        .expect("Failed to create service-specific credential");
+    let created = response.service_specific_credential().unwrap();
+
+    let response = client
+        .reset_service_specific_credential()
+        .user_name(USER_NAME)
+        .service_specific_credential_id(created.service_specific_credential_id())
+        .send()
+        .await
+        .expect("Failed to reset service-specific credential");
+    let reset = response.service_specific_credential().unwrap();
+    assert_eq!(reset.service_specific_credential_id(), created.service_specific_credential_id());
+    assert_eq!(reset.service_user_name(), created.service_user_name());
+    assert_ne!(reset.service_password(), created.service_password());
+
+    ctx.stop_server().await;
+}
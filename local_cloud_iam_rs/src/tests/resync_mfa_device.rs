@@ -1 +1,119 @@
+const DEVICE_NAME: &str = "TestMFADevice";
+const USER_NAME: &str = "user1";
 
+#[tokio::test]
+async fn resync_mfa_device() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, USER_NAME, "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+
+    let create_mfa_device_output = client
+        .create_virtual_mfa_device()
+        .virtual_mfa_device_name(DEVICE_NAME)
+        .send()
+        .await
+        .expect("Failed to create Virtual MFA device");
+    let device = create_mfa_device_output.virtual_mfa_device().unwrap();
+
+    let (code1, code2) = super::fixture::authentication_codes(device, 0);
+    client
+        .enable_mfa_device()
+        .serial_number(device.serial_number())
+        .user_name(USER_NAME)
+        .authentication_code1(code1)
+        .authentication_code2(code2)
+        .send()
+        .await
+        .expect("Failed to enable MFA device for IAM user");
+
+    // clock of the device has drifted by 5 minutes
+    let (code1, code2) = super::fixture::authentication_codes(device, 10);
+    client
+        .resync_mfa_device()
+        .serial_number(device.serial_number())
+        .user_name(USER_NAME)
+        .authentication_code1(code1)
+        .authentication_code2(code2)
+        .send()
+        .await
+        .expect("Failed to resync MFA device");
+
+    // the drift is taken into account by subsequent verifications
+    let (code1, code2) = super::fixture::authentication_codes(device, 12);
+    client
+        .resync_mfa_device()
+        .serial_number(device.serial_number())
+        .user_name(USER_NAME)
+        .authentication_code1(code1)
+        .authentication_code2(code2)
+        .send()
+        .await
+        .expect("Failed to resync MFA device");
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn resync_mfa_device_invalid_codes() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, USER_NAME, "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+
+    let create_mfa_device_output = client
+        .create_virtual_mfa_device()
+        .virtual_mfa_device_name(DEVICE_NAME)
+        .send()
+        .await
+        .expect("Failed to create Virtual MFA device");
+    let device = create_mfa_device_output.virtual_mfa_device().unwrap();
+
+    let (code1, code2) = super::fixture::authentication_codes(device, 0);
+    let result = client
+        .resync_mfa_device()
+        .serial_number(device.serial_number())
+        .user_name(USER_NAME)
+        .authentication_code1(&code1)
+        .authentication_code2(&code2)
+        .send()
+        .await;
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().raw_response().unwrap().status().as_u16(), 404);
+
+    client
+        .enable_mfa_device()
+        .serial_number(device.serial_number())
+        .user_name(USER_NAME)
+        .authentication_code1(code1)
+        .authentication_code2(code2)
+        .send()
+        .await
+        .expect("Failed to enable MFA device for IAM user");
+
+    let (code1, code2) = super::fixture::authentication_codes(device, 60);
+    let result = client
+        .resync_mfa_device()
+        .serial_number(device.serial_number())
+        .user_name(USER_NAME)
+        .authentication_code1(code1)
+        .authentication_code2(code2)
+        .send()
+        .await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 403);
+    assert!(sdk_error
+        .into_service_error()
+        .is_invalid_authentication_code_exception());
+
+    ctx.stop_server().await;
+}
@@ -1 +1,103 @@
+use crate::tests::fixture::CREATE_USER_PERMISSIONS_BOUNDARY;
 
+#[tokio::test]
+async fn set_default_policy_version() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let policy_output =
+        super::fixture::create_policy(&client, "test-policy", "", "/", CREATE_USER_PERMISSIONS_BOUNDARY, None)
+            .await
+            .expect("Failed to create IAM policy");
+    let policy_arn = policy_output.policy().unwrap().arn().unwrap();
+    for _i in 0..2 {
+        client
+            .create_policy_version()
+            .policy_arn(policy_arn)
+            .policy_document(CREATE_USER_PERMISSIONS_BOUNDARY)
+            .set_as_default(true)
+            .send()
+            .await
+            .expect("Failed to create IAM policy version");
+    }
+
+    let response = client
+        .get_policy()
+        .policy_arn(policy_arn)
+        .send()
+        .await
+        .expect("Failed to get IAM policy");
+    assert_eq!(response.policy().unwrap().default_version_id().unwrap(), "v3");
+
+    client
+        .set_default_policy_version()
+        .policy_arn(policy_arn)
+        .version_id("v1")
+        .send()
+        .await
+        .expect("Failed to set default IAM policy version");
+
+    let response = client
+        .get_policy()
+        .policy_arn(policy_arn)
+        .send()
+        .await
+        .expect("Failed to get IAM policy");
+    assert_eq!(response.policy().unwrap().default_version_id().unwrap(), "v1");
+
+    // there is a single default version
+    let response = client
+        .list_policy_versions()
+        .policy_arn(policy_arn)
+        .send()
+        .await
+        .expect("Failed to list IAM policy versions");
+    assert_eq!(response.versions().len(), 3);
+    let default_versions: Vec<&str> = response
+        .versions()
+        .iter()
+        .filter(|version| version.is_default_version())
+        .map(|version| version.version_id().unwrap())
+        .collect();
+    assert_eq!(default_versions, vec!["v1"]);
+
+    // the version which is not default anymore could be deleted
+    client
+        .delete_policy_version()
+        .policy_arn(policy_arn)
+        .version_id("v3")
+        .send()
+        .await
+        .expect("Failed to delete IAM policy version");
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn set_default_policy_version_does_not_exist() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let policy_output =
+        super::fixture::create_policy(&client, "test-policy", "", "/", CREATE_USER_PERMISSIONS_BOUNDARY, None)
+            .await
+            .expect("Failed to create IAM policy");
+    let policy_arn = policy_output.policy().unwrap().arn().unwrap();
+
+    let result = client
+        .set_default_policy_version()
+        .policy_arn(policy_arn)
+        .version_id("v2")
+        .send()
+        .await;
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 404);
+    assert!(sdk_error.into_service_error().is_no_such_entity_exception());
+
+    ctx.stop_server().await;
+}
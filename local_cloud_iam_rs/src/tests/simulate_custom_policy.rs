@@ -1 +1,147 @@
+use aws_sdk_iam::types::{ContextEntry, ContextKeyTypeEnum, PolicyEvaluationDecisionType};
 
+const S3_READ_POLICY: &str = r#"{
+    "Version": "2012-10-17",
+    "Statement": [
+        {
+            "Sid": "ReadBucket",
+            "Effect": "Allow",
+            "Action": ["s3:GetObject", "s3:ListBucket"],
+            "Resource": "arn:aws:s3:::bucket-a/*"
+        },
+        {
+            "Effect": "Deny",
+            "Action": "s3:GetObject",
+            "Resource": "arn:aws:s3:::bucket-a/secret/*"
+        }
+    ]
+}"#;
+
+const CONDITIONAL_POLICY: &str = r#"{
+    "Version": "2012-10-17",
+    "Statement": [
+        {
+            "Effect": "Allow",
+            "Action": "ec2:StartInstances",
+            "Resource": "*",
+            "Condition": {"StringEquals": {"aws:RequestedRegion": "eu-west-1"}}
+        }
+    ]
+}"#;
+
+#[tokio::test]
+async fn simulate_custom_policy() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let result = client
+        .simulate_custom_policy()
+        .policy_input_list(S3_READ_POLICY)
+        .action_names("s3:GetObject")
+        .action_names("s3:PutObject")
+        .resource_arns("arn:aws:s3:::bucket-a/public/file.txt")
+        .resource_arns("arn:aws:s3:::bucket-a/secret/file.txt")
+        .send()
+        .await
+        .expect("Failed to simulate custom policy");
+
+    let evaluation_results = result.evaluation_results();
+    assert_eq!(evaluation_results.len(), 2);
+
+    let get_object = &evaluation_results[0];
+    assert_eq!(get_object.eval_action_name(), "s3:GetObject");
+    assert_eq!(get_object.eval_decision(), &PolicyEvaluationDecisionType::ExplicitDeny);
+    let resource_results = get_object.resource_specific_results();
+    assert_eq!(resource_results.len(), 2);
+    assert_eq!(resource_results[0].eval_resource_name(), "arn:aws:s3:::bucket-a/public/file.txt");
+    assert_eq!(resource_results[0].eval_resource_decision(), &PolicyEvaluationDecisionType::Allowed);
+    assert_eq!(resource_results[0].matched_statements().len(), 1);
+    assert_eq!(resource_results[0].matched_statements()[0].source_policy_id(), Some("PolicyInputList.1"));
+    assert_eq!(resource_results[1].eval_resource_decision(), &PolicyEvaluationDecisionType::ExplicitDeny);
+
+    let put_object = &evaluation_results[1];
+    assert_eq!(put_object.eval_action_name(), "s3:PutObject");
+    assert_eq!(put_object.eval_decision(), &PolicyEvaluationDecisionType::ImplicitDeny);
+    assert!(put_object.matched_statements().is_empty());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn simulate_custom_policy_with_context_entries() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let result = client
+        .simulate_custom_policy()
+        .policy_input_list(CONDITIONAL_POLICY)
+        .action_names("ec2:StartInstances")
+        .send()
+        .await
+        .expect("Failed to simulate custom policy");
+    let evaluation_result = &result.evaluation_results()[0];
+    assert_eq!(evaluation_result.eval_resource_name(), Some("*"));
+    assert_eq!(evaluation_result.eval_decision(), &PolicyEvaluationDecisionType::ImplicitDeny);
+    assert_eq!(evaluation_result.missing_context_values(), &["aws:RequestedRegion".to_owned()]);
+
+    let context_entry = ContextEntry::builder()
+        .context_key_name("aws:RequestedRegion")
+        .context_key_type(ContextKeyTypeEnum::String)
+        .context_key_values("eu-west-1")
+        .build();
+    let result = client
+        .simulate_custom_policy()
+        .policy_input_list(CONDITIONAL_POLICY)
+        .action_names("ec2:StartInstances")
+        .context_entries(context_entry)
+        .send()
+        .await
+        .expect("Failed to simulate custom policy");
+    let evaluation_result = &result.evaluation_results()[0];
+    assert_eq!(evaluation_result.eval_decision(), &PolicyEvaluationDecisionType::Allowed);
+    assert!(evaluation_result.missing_context_values().is_empty());
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn simulate_custom_policy_with_pagination() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let result = client
+        .simulate_custom_policy()
+        .policy_input_list(S3_READ_POLICY)
+        .action_names("s3:GetObject")
+        .action_names("s3:ListBucket")
+        .action_names("s3:PutObject")
+        .max_items(2)
+        .send()
+        .await
+        .expect("Failed to simulate custom policy");
+    assert_eq!(result.evaluation_results().len(), 2);
+    assert!(result.is_truncated());
+
+    let result = client
+        .simulate_custom_policy()
+        .policy_input_list(S3_READ_POLICY)
+        .action_names("s3:GetObject")
+        .action_names("s3:ListBucket")
+        .action_names("s3:PutObject")
+        .max_items(2)
+        .marker(result.marker().unwrap())
+        .send()
+        .await
+        .expect("Failed to simulate custom policy");
+    assert_eq!(result.evaluation_results().len(), 1);
+    assert_eq!(result.evaluation_results()[0].eval_action_name(), "s3:PutObject");
+    assert!(!result.is_truncated());
+
+    ctx.stop_server().await;
+}
@@ -1 +1,219 @@
+use aws_sdk_iam::types::{PolicyEvaluationDecisionType, PolicySourceType};
 
+const ALLOW_S3_POLICY: &str = r#"{
+    "Version": "2012-10-17",
+    "Statement": [{"Effect": "Allow", "Action": "s3:*", "Resource": "*"}]
+}"#;
+
+const DENY_DELETE_POLICY: &str = r#"{
+    "Version": "2012-10-17",
+    "Statement": [{"Effect": "Deny", "Action": "s3:DeleteObject", "Resource": "*"}]
+}"#;
+
+const ALLOW_READ_BOUNDARY: &str = r#"{
+    "Version": "2012-10-17",
+    "Statement": [{"Effect": "Allow", "Action": ["s3:Get*", "s3:DeleteObject"], "Resource": "*"}]
+}"#;
+
+#[tokio::test]
+async fn simulate_principal_policy() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let user = super::fixture::create_user(&client, "user1", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    let user_arn = user.user().unwrap().arn();
+
+    client
+        .put_user_policy()
+        .user_name("user1")
+        .policy_name("allow-s3")
+        .policy_document(ALLOW_S3_POLICY)
+        .send()
+        .await
+        .expect("Failed to put IAM user policy");
+
+    client
+        .create_group()
+        .group_name("group1")
+        .send()
+        .await
+        .expect("Failed to create IAM group");
+    client
+        .add_user_to_group()
+        .group_name("group1")
+        .user_name("user1")
+        .send()
+        .await
+        .expect("Failed to add IAM user to group");
+    let policy_output = super::fixture::create_policy(&client, "deny-delete", "", "/", DENY_DELETE_POLICY, None)
+        .await
+        .expect("Failed to create IAM policy");
+    client
+        .attach_group_policy()
+        .group_name("group1")
+        .policy_arn(policy_output.policy().unwrap().arn().unwrap())
+        .send()
+        .await
+        .expect("Failed to attach IAM group policy");
+
+    let result = client
+        .simulate_principal_policy()
+        .policy_source_arn(user_arn)
+        .action_names("s3:GetObject")
+        .action_names("s3:DeleteObject")
+        .action_names("ec2:RunInstances")
+        .send()
+        .await
+        .expect("Failed to simulate principal policy");
+
+    let evaluation_results = result.evaluation_results();
+    assert_eq!(evaluation_results.len(), 3);
+
+    assert_eq!(evaluation_results[0].eval_decision(), &PolicyEvaluationDecisionType::Allowed);
+    let statement = &evaluation_results[0].matched_statements()[0];
+    assert_eq!(statement.source_policy_id(), Some("user_user1_allow-s3"));
+    assert_eq!(statement.source_policy_type(), Some(&PolicySourceType::User));
+
+    assert_eq!(evaluation_results[1].eval_decision(), &PolicyEvaluationDecisionType::ExplicitDeny);
+    let statement = evaluation_results[1]
+        .matched_statements()
+        .iter()
+        .find(|statement| statement.source_policy_id() == Some("deny-delete"))
+        .expect("Deny statement of the group policy is not matched");
+    assert_eq!(statement.source_policy_type(), Some(&PolicySourceType::UserManaged));
+
+    assert_eq!(evaluation_results[2].eval_decision(), &PolicyEvaluationDecisionType::ImplicitDeny);
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn simulate_principal_policy_with_same_policy_names() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let user = super::fixture::create_user(&client, "user1", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+
+    // customer managed policy which has the same name as the AWS managed one
+    let policy_output = super::fixture::create_policy(&client, "ReadOnlyAccess", "", "/", DENY_DELETE_POLICY, None)
+        .await
+        .expect("Failed to create IAM policy");
+    client
+        .attach_user_policy()
+        .user_name("user1")
+        .policy_arn(policy_output.policy().unwrap().arn().unwrap())
+        .send()
+        .await
+        .expect("Failed to attach IAM user policy");
+    client
+        .attach_user_policy()
+        .user_name("user1")
+        .policy_arn("arn:aws:iam::aws:policy/ReadOnlyAccess")
+        .send()
+        .await
+        .expect("Failed to attach AWS managed policy");
+
+    let result = client
+        .simulate_principal_policy()
+        .policy_source_arn(user.user().unwrap().arn())
+        .action_names("s3:GetObject")
+        .action_names("s3:DeleteObject")
+        .send()
+        .await
+        .expect("Failed to simulate principal policy");
+
+    let evaluation_results = result.evaluation_results();
+    assert_eq!(evaluation_results[0].eval_decision(), &PolicyEvaluationDecisionType::Allowed);
+    let statement = &evaluation_results[0].matched_statements()[0];
+    assert_eq!(statement.source_policy_id(), Some("ReadOnlyAccess"));
+    assert_eq!(statement.source_policy_type(), Some(&PolicySourceType::AwsManaged));
+
+    assert_eq!(evaluation_results[1].eval_decision(), &PolicyEvaluationDecisionType::ExplicitDeny);
+    let statement = &evaluation_results[1].matched_statements()[0];
+    assert_eq!(statement.source_policy_id(), Some("ReadOnlyAccess"));
+    assert_eq!(statement.source_policy_type(), Some(&PolicySourceType::UserManaged));
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn simulate_principal_policy_with_permissions_boundary() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let boundary = super::fixture::create_policy(&client, "read-boundary", "", "/", ALLOW_READ_BOUNDARY, None)
+        .await
+        .expect("Failed to create IAM policy");
+    let boundary_arn = boundary.policy().unwrap().arn().unwrap();
+    let user = super::fixture::create_user(&client, "user1", "/", Some(boundary_arn), None)
+        .await
+        .expect("Failed to create IAM user");
+
+    client
+        .put_user_policy()
+        .user_name("user1")
+        .policy_name("allow-s3")
+        .policy_document(ALLOW_S3_POLICY)
+        .send()
+        .await
+        .expect("Failed to put IAM user policy");
+
+    let result = client
+        .simulate_principal_policy()
+        .policy_source_arn(user.user().unwrap().arn())
+        .action_names("s3:GetObject")
+        .action_names("s3:PutObject")
+        .send()
+        .await
+        .expect("Failed to simulate principal policy");
+
+    let evaluation_results = result.evaluation_results();
+    assert_eq!(evaluation_results[0].eval_decision(), &PolicyEvaluationDecisionType::Allowed);
+    assert_eq!(evaluation_results[1].eval_decision(), &PolicyEvaluationDecisionType::ImplicitDeny);
+    let boundary_detail = evaluation_results[1].permissions_boundary_decision_detail().unwrap();
+    assert!(!boundary_detail.allowed_by_permissions_boundary());
+
+    // boundaries from the request replace the attached one
+    let result = client
+        .simulate_principal_policy()
+        .policy_source_arn(user.user().unwrap().arn())
+        .action_names("s3:PutObject")
+        .permissions_boundary_policy_input_list(ALLOW_S3_POLICY)
+        .send()
+        .await
+        .expect("Failed to simulate principal policy");
+    assert_eq!(result.evaluation_results()[0].eval_decision(), &PolicyEvaluationDecisionType::Allowed);
+
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn simulate_principal_policy_unknown_principal() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let result = client
+        .simulate_principal_policy()
+        .policy_source_arn("arn:aws:iam::000000000001:role/unknown-role")
+        .action_names("s3:GetObject")
+        .send()
+        .await;
+
+    assert!(result.is_err());
+    let sdk_error = result.unwrap_err();
+    assert_eq!(sdk_error.raw_response().unwrap().status().as_u16(), 404);
+
+    ctx.stop_server().await;
+}
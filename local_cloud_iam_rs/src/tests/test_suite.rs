@@ -2,13 +2,63 @@ use uuid::Uuid;
 
 use crate::config::AppConfig;
 
+pub(crate) const ROOT_SECRET_ACCESS_KEY: &str = "configured_secret_access_key";
+
 pub(crate) async fn start_server(port: u16) -> std::io::Result<axum::Router> {
+    start_server_with_auth(port, false, false).await
+}
+
+pub(crate) async fn start_server_trusting_any_key(port: u16) -> std::io::Result<axum::Router> {
+    start_server_with_auth(port, true, false).await
+}
+
+pub(crate) async fn start_server_enforcing_policies(port: u16) -> std::io::Result<axum::Router> {
+    start_server_with_auth(port, false, true).await
+}
+
+pub(crate) async fn start_server_trusting_any_key_enforcing_policies(port: u16) -> std::io::Result<axum::Router> {
+    start_server_with_auth(port, true, true).await
+}
+
+/// Starts the server with low quotas to test quota exhaustion.
+pub(crate) async fn start_server_with_low_quotas(port: u16) -> std::io::Result<axum::Router> {
+    let quotas = vec![
+        ("UsersQuota".to_owned(), 2),
+        ("AttachedPoliciesPerUserQuota".to_owned(), 1),
+    ];
+    start_server_with_config(port, false, false, quotas).await
+}
+
+/// Starts the server with a configured secret of the bootstrap access key of the `Root` user.
+pub(crate) async fn start_server_with_root_secret(port: u16) -> std::io::Result<axum::Router> {
+    let mut app_config = app_config(port, false, false, vec![]);
+    app_config.root_secret_access_key = ROOT_SECRET_ACCESS_KEY.to_owned();
+    crate::http::server::router(&app_config).await
+}
+
+async fn start_server_with_auth(
+    port: u16, trust_any_access_key: bool, enforce_policies: bool,
+) -> std::io::Result<axum::Router> {
+    start_server_with_config(port, trust_any_access_key, enforce_policies, vec![]).await
+}
+
+async fn start_server_with_config(
+    port: u16, trust_any_access_key: bool, enforce_policies: bool, quotas: Vec<(String, i32)>,
+) -> std::io::Result<axum::Router> {
+    crate::http::server::router(&app_config(port, trust_any_access_key, enforce_policies, quotas)).await
+}
+
+fn app_config(port: u16, trust_any_access_key: bool, enforce_policies: bool, quotas: Vec<(String, i32)>) -> AppConfig {
     let db_file_name = Uuid::new_v4();
-    let app_config = AppConfig {
+    AppConfig {
         database_url: format!("file:{}?mode=memory&cache=shared", db_file_name),
         etcd_enabled: false,
         etcd_endpoints: None,
         service_port: port,
-    };
-    crate::http::server::router(&app_config).await
+        trust_any_access_key,
+        enforce_policies,
+        quotas,
+        mfa_clock_skew_steps: 1,
+        root_secret_access_key: "secret_access_key".to_owned(),
+    }
 }
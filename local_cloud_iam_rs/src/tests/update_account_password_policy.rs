@@ -1 +1,87 @@
+#[tokio::test]
+async fn update_account_password_policy() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
 
+    client
+        .update_account_password_policy()
+        .minimum_password_length(12)
+        .require_numbers(true)
+        .password_reuse_prevention(3)
+        .send()
+        .await
+        .expect("Failed to update account password policy");
+
+    // parameters which are not specified are reverted to default values
+    client
+        .update_account_password_policy()
+        .minimum_password_length(10)
+        .require_symbols(true)
+        .send()
+        .await
+        .expect("Failed to update account password policy");
+
+    let response = client
+        .get_account_password_policy()
+        .send()
+        .await
+        .expect("Failed to get account password policy");
+    let policy = response.password_policy().unwrap();
+    assert_eq!(Some(10), policy.minimum_password_length());
+    assert!(policy.require_symbols());
+    assert!(!policy.require_numbers());
+    assert!(policy.password_reuse_prevention().is_none());
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn update_account_password_policy_enforced() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    client
+        .update_account_password_policy()
+        .minimum_password_length(10)
+        .require_uppercase_characters(true)
+        .require_numbers(true)
+        .send()
+        .await
+        .expect("Failed to update account password policy");
+    super::fixture::create_user(&client, "SuperUser", "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+
+    let response = client
+        .create_login_profile()
+        .user_name("SuperUser")
+        .password("password_without_uppercase_1")
+        .send()
+        .await;
+    assert!(response.is_err());
+    let sdk_error = response.unwrap_err();
+    assert_eq!(400u16, sdk_error.raw_response().unwrap().status().as_u16());
+    assert_eq!("PasswordPolicyViolation", sdk_error.into_service_error().meta().code().unwrap());
+
+    client
+        .create_login_profile()
+        .user_name("SuperUser")
+        .password("Password_With_Uppercase_1")
+        .send()
+        .await
+        .expect("Failed to create login profile");
+
+    let response = client
+        .update_login_profile()
+        .user_name("SuperUser")
+        .password("Short_1")
+        .send()
+        .await;
+    assert!(response.is_err());
+    let sdk_error = response.unwrap_err();
+    assert_eq!("PasswordPolicyViolation", sdk_error.into_service_error().meta().code().unwrap());
+    ctx.stop_server().await;
+}
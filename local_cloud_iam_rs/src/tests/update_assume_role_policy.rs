@@ -1 +1,113 @@
+use crate::tests::fixture::CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY;
 
+const ROLE_NAME: &str = "Test-Role";
+
+const EC2_TRUST_POLICY: &str = r#"{
+    "Version": "2012-10-17",
+    "Statement": [{"Effect": "Allow", "Principal": {"Service": "ec2.amazonaws.com"}, "Action": "sts:AssumeRole"}]
+}"#;
+
+async fn create_role(client: &aws_sdk_iam::Client) {
+    client
+        .create_role()
+        .role_name(ROLE_NAME)
+        .assume_role_policy_document(CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY)
+        .send()
+        .await
+        .expect("Failed to create IAM role");
+}
+
+#[tokio::test]
+async fn update_assume_role_policy() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let config = super::aws_config(ctx.port);
+    let client = aws_sdk_iam::Client::new(&config);
+    create_role(&client).await;
+
+    client
+        .update_assume_role_policy()
+        .role_name(ROLE_NAME)
+        .policy_document(EC2_TRUST_POLICY)
+        .send()
+        .await
+        .expect("Failed to update assume role policy");
+
+    let response = client
+        .get_role()
+        .role_name(ROLE_NAME)
+        .send()
+        .await
+        .expect("Failed to get IAM role");
+    let trust_policy = urlencoding::decode(response.role().unwrap().assume_role_policy_document().unwrap()).unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(EC2_TRUST_POLICY).unwrap(),
+        serde_json::from_str::<serde_json::Value>(&trust_policy).unwrap()
+    );
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn update_assume_role_policy_without_principal() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let config = super::aws_config(ctx.port);
+    let client = aws_sdk_iam::Client::new(&config);
+    create_role(&client).await;
+
+    let response = client
+        .update_assume_role_policy()
+        .role_name(ROLE_NAME)
+        .policy_document(r#"{"Version": "2012-10-17", "Statement": [{"Effect": "Allow", "Action": "sts:AssumeRole"}]}"#)
+        .send()
+        .await;
+
+    assert!(response.is_err());
+    let error = response.unwrap_err().into_service_error();
+    assert!(error.is_malformed_policy_document_exception());
+    assert_eq!(error.meta().message().unwrap(), "Statement[0].Principal: Missing required field Principal.");
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn update_assume_role_policy_of_service_linked_role() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let config = super::aws_config(ctx.port);
+    let client = aws_sdk_iam::Client::new(&config);
+    let response = client
+        .create_service_linked_role()
+        .aws_service_name("ecs.amazonaws.com")
+        .send()
+        .await
+        .expect("Failed to create service-linked role");
+    let role_name = response.role().unwrap().role_name();
+
+    let response = client
+        .update_assume_role_policy()
+        .role_name(role_name)
+        .policy_document(EC2_TRUST_POLICY)
+        .send()
+        .await;
+
+    assert!(response.is_err());
+    let error = response.unwrap_err().into_service_error();
+    assert!(error.is_unmodifiable_entity_exception());
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn update_assume_role_policy_does_not_exist() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let config = super::aws_config(ctx.port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let response = client
+        .update_assume_role_policy()
+        .role_name(ROLE_NAME)
+        .policy_document(EC2_TRUST_POLICY)
+        .send()
+        .await;
+
+    assert!(response.is_err());
+    let error = response.unwrap_err().into_service_error();
+    assert!(error.is_no_such_entity_exception());
+    ctx.stop_server().await;
+}
@@ -1 +1,109 @@
+use crate::tests::fixture::CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY;
 
+const ROLE_NAME: &str = "Test-Role";
+
+async fn create_role(client: &aws_sdk_iam::Client) {
+    client
+        .create_role()
+        .role_name(ROLE_NAME)
+        .description("Initial description")
+        .assume_role_policy_document(CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY)
+        .send()
+        .await
+        .expect("Failed to create IAM role");
+}
+
+#[tokio::test]
+async fn update_role() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let config = super::aws_config(ctx.port);
+    let client = aws_sdk_iam::Client::new(&config);
+    create_role(&client).await;
+
+    client
+        .update_role()
+        .role_name(ROLE_NAME)
+        .description("Updated description")
+        .max_session_duration(7200)
+        .send()
+        .await
+        .expect("Failed to update IAM role");
+
+    let response = client
+        .get_role()
+        .role_name(ROLE_NAME)
+        .send()
+        .await
+        .expect("Failed to get IAM role");
+    let role = response.role().unwrap();
+    assert_eq!(Some("Updated description"), role.description());
+    assert_eq!(Some(7200), role.max_session_duration());
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn update_role_keeps_unspecified_fields() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let config = super::aws_config(ctx.port);
+    let client = aws_sdk_iam::Client::new(&config);
+    create_role(&client).await;
+
+    client
+        .update_role()
+        .role_name(ROLE_NAME)
+        .max_session_duration(43200)
+        .send()
+        .await
+        .expect("Failed to update IAM role");
+
+    let response = client
+        .get_role()
+        .role_name(ROLE_NAME)
+        .send()
+        .await
+        .expect("Failed to get IAM role");
+    let role = response.role().unwrap();
+    assert_eq!(Some("Initial description"), role.description());
+    assert_eq!(Some(43200), role.max_session_duration());
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn update_role_max_session_duration_out_of_range() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let config = super::aws_config(ctx.port);
+    let client = aws_sdk_iam::Client::new(&config);
+    create_role(&client).await;
+
+    let response = client
+        .update_role()
+        .role_name(ROLE_NAME)
+        .max_session_duration(43201)
+        .send()
+        .await;
+
+    assert!(response.is_err());
+    let sdk_error = response.unwrap_err();
+    assert_eq!(400u16, sdk_error.raw_response().unwrap().status().as_u16());
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn update_role_does_not_exist() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let config = super::aws_config(ctx.port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let response = client
+        .update_role()
+        .role_name(ROLE_NAME)
+        .description("Updated description")
+        .send()
+        .await;
+
+    assert!(response.is_err());
+    let error = response.unwrap_err().into_service_error();
+    assert!(error.is_no_such_entity_exception());
+    assert_eq!(error.meta().message().unwrap(), "IAM role with name 'Test-Role' doesn't exist.");
+    ctx.stop_server().await;
+}
@@ -1 +1,53 @@
+use crate::tests::fixture::{tag, CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY};
 
+const ROLE_NAME: &str = "Test-Role";
+
+#[tokio::test]
+async fn update_role_description() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let config = super::aws_config(ctx.port);
+    let client = aws_sdk_iam::Client::new(&config);
+    client
+        .create_role()
+        .role_name(ROLE_NAME)
+        .description("Initial description")
+        .assume_role_policy_document(CREATE_ROLE_ASSUME_ROLE_PERMISSIONS_BOUNDARY)
+        .tags(tag("key1", "value1"))
+        .send()
+        .await
+        .expect("Failed to create IAM role");
+
+    let response = client
+        .update_role_description()
+        .role_name(ROLE_NAME)
+        .description("Updated description")
+        .send()
+        .await
+        .expect("Failed to update IAM role description");
+
+    let role = response.role().unwrap();
+    assert_eq!(ROLE_NAME, role.role_name());
+    assert_eq!(Some("Updated description"), role.description());
+    assert_eq!(Some(3600), role.max_session_duration());
+    assert_eq!(1, role.tags().len());
+    ctx.stop_server().await;
+}
+
+#[tokio::test]
+async fn update_role_description_does_not_exist() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let config = super::aws_config(ctx.port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    let response = client
+        .update_role_description()
+        .role_name(ROLE_NAME)
+        .description("Updated description")
+        .send()
+        .await;
+
+    assert!(response.is_err());
+    let error = response.unwrap_err().into_service_error();
+    assert!(error.is_no_such_entity_exception());
+    ctx.stop_server().await;
+}
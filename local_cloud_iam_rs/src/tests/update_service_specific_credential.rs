@@ -1 +1,55 @@
+use aws_sdk_iam::types::StatusType;
 
+const USER_NAME: &str = "test-user-1";
+
+#[tokio::test]
+async fn update_service_specific_credential() {
+    let ctx = local_cloud_testing::axum_suite::create_test_ctx(super::test_suite::start_server).await;
+    let port = ctx.port;
+    let config = super::aws_config(port);
+    let client = aws_sdk_iam::Client::new(&config);
+
+    super::fixture::create_user(&client, USER_NAME, "/", None, None)
+        .await
+        .expect("Failed to create IAM user");
+    let response = client
+        .create_service_specific_credential()
+        .user_name(USER_NAME)
+        .service_name("codecommit.amazonaws.com")
+        .send()
+        .await
+        .expect("Failed to create service-specific credential");
+    let credential_id = response
+        .service_specific_credential()
+        .unwrap()
+        .service_specific_credential_id();
+
+    client
+        .update_service_specific_credential()
+        .user_name(USER_NAME)
+        .service_specific_credential_id(credential_id)
+        .status(StatusType::Inactive)
+        .send()
+        .await
+        .expect("Failed to update service-specific credential");
+
+    let response = client
+        .list_service_specific_credentials()
+        .user_name(USER_NAME)
+        .send()
+        .await
+        .expect("Failed to list service-specific credentials");
+    assert_eq!(response.service_specific_credentials()[0].status(), &StatusType::Inactive);
+
+    let result = client
+        .update_service_specific_credential()
+        .user_name(USER_NAME)
+        .service_specific_credential_id("ACCAUNKNOWNCREDENTIAL")
+        .status(StatusType::Active)
+        .send()
+        .await;
+    assert!(result.is_err());
+    assert!(result.unwrap_err().into_service_error().is_no_such_entity_exception());
+
+    ctx.stop_server().await;
+}
@@ -0,0 +1 @@
+ALTER TABLE credentials ADD COLUMN principal_arn VARCHAR2(2048);
@@ -0,0 +1 @@
+ALTER TABLE credentials ADD COLUMN principal_id VARCHAR2(128);
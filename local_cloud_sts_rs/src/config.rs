@@ -2,14 +2,36 @@ use local_cloud_common::config;
 
 const ENV_DATABASE_URL: &str = "STS_DATABASE_URL";
 const ENV_SERVICE_PORT: &str = "STS_SERVICE_PORT";
+const ENV_IAM_DATABASE_URL: &str = "STS_IAM_DATABASE_URL";
+const ENV_TRUST_ANY_ACCESS_KEY: &str = "STS_TRUST_ANY_ACCESS_KEY";
+const ENV_ENFORCE_IAM_POLICIES: &str = "STS_ENFORCE_IAM_POLICIES";
+const ENV_IAM_ENDPOINT_URL: &str = "STS_IAM_ENDPOINT_URL";
+const ENV_IAM_ACCESS_KEY_ID: &str = "STS_IAM_ACCESS_KEY_ID";
+const ENV_IAM_SECRET_ACCESS_KEY: &str = "STS_IAM_SECRET_ACCESS_KEY";
 
 const DEFAULT_DATABASE_URL: &str = "sqlite://sts.db";
+const DEFAULT_IAM_DATABASE_URL: &str = "sqlite://iam.db";
 const DEFAULT_SERVICE_PORT: u16 = 4502;
+const DEFAULT_IAM_ENDPOINT_URL: &str = "http://localhost:4502/iam";
+const DEFAULT_IAM_ACCESS_KEY_ID: &str = "AKIAIOSFODNN201ADMIN";
+/// Publicly known secret of the bootstrap access key of the IAM `Root` user.
+pub(crate) const DEFAULT_IAM_SECRET_ACCESS_KEY: &str = "secret_access_key";
 
 #[derive(Debug)]
 pub struct AppConfig {
     pub database_url: String,
     pub service_port: u16,
+    /// Database of the IAM service which is used to verify requests signed with long-term access keys of IAM users.
+    pub iam_database_url: String,
+    /// Skip SigV4 signature verification and accept requests signed with unknown access keys.
+    pub trust_any_access_key: bool,
+    /// Authorize every action against the IAM policies of the caller. Policies are evaluated by the IAM service.
+    pub enforce_iam_policies: bool,
+    /// Endpoint of the IAM service used to evaluate policies.
+    pub iam_endpoint_url: String,
+    /// Credentials used to sign requests sent to the IAM service.
+    pub iam_access_key_id: String,
+    pub iam_secret_access_key: String,
 }
 
 impl AppConfig {
@@ -18,17 +40,39 @@ impl AppConfig {
 
         let database_url = config::get_string_env_with_default(ENV_DATABASE_URL, DEFAULT_DATABASE_URL).into();
         let service_port = config::get_u16_env_with_default(ENV_SERVICE_PORT, DEFAULT_SERVICE_PORT);
+        let iam_database_url =
+            config::get_string_env_with_default(ENV_IAM_DATABASE_URL, DEFAULT_IAM_DATABASE_URL).into();
+        let trust_any_access_key = config::get_bool_env_with_default(ENV_TRUST_ANY_ACCESS_KEY, false).into();
+        let enforce_iam_policies = config::get_bool_env_with_default(ENV_ENFORCE_IAM_POLICIES, false).into();
+        let iam_endpoint_url =
+            config::get_string_env_with_default(ENV_IAM_ENDPOINT_URL, DEFAULT_IAM_ENDPOINT_URL).into();
+        let iam_access_key_id =
+            config::get_string_env_with_default(ENV_IAM_ACCESS_KEY_ID, DEFAULT_IAM_ACCESS_KEY_ID).into();
+        let iam_secret_access_key =
+            config::get_string_env_with_default(ENV_IAM_SECRET_ACCESS_KEY, DEFAULT_IAM_SECRET_ACCESS_KEY).into();
         AppConfig {
             database_url,
             service_port,
+            iam_database_url,
+            trust_any_access_key,
+            enforce_iam_policies,
+            iam_endpoint_url,
+            iam_access_key_id,
+            iam_secret_access_key,
         }
     }
 
-    #[allow(dead_code)]
-    pub fn with_params(database_url: impl Into<String>, port: u16) -> Self {
+    #[cfg(test)]
+    pub fn with_params(database_url: impl Into<String>, iam_database_url: impl Into<String>, port: u16) -> Self {
         AppConfig {
             database_url: database_url.into(),
             service_port: port,
+            iam_database_url: iam_database_url.into(),
+            trust_any_access_key: false,
+            enforce_iam_policies: false,
+            iam_endpoint_url: DEFAULT_IAM_ENDPOINT_URL.to_owned(),
+            iam_access_key_id: DEFAULT_IAM_ACCESS_KEY_ID.to_owned(),
+            iam_secret_access_key: DEFAULT_IAM_SECRET_ACCESS_KEY.to_owned(),
         }
     }
 }
@@ -0,0 +1,62 @@
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use axum::Extension;
+use chrono::Utc;
+
+use local_cloud_axum::local::web::CallerIdentity;
+use local_cloud_common::arn::assumed_role_name;
+use local_cloud_db::LocalDb;
+
+use crate::http::aws::sts::repository;
+use crate::http::aws::sts::request_params::RequestParams;
+use crate::http::aws::sts::types::iam_service_last_accesses::DbIamServiceLastAccess;
+
+/// Middleware which records the service, action, region and time of every authenticated request in the database of
+/// the IAM service, the same way the IAM service records its own requests. Requests are recorded before
+/// authorization, since access attempts which are denied count as well.
+pub(crate) async fn track(
+    State(iam_db): State<LocalDb>, Extension(caller): Extension<CallerIdentity>,
+    Extension(params): Extension<RequestParams>, request: Request, next: Next,
+) -> Response {
+    if let Some(action) = params.action() {
+        if let Err(err) = record_access(&iam_db, &caller, action).await {
+            log::error!("Failed to record access of '{}' to '{}': {}", caller.access_key_id, action, err);
+        }
+    }
+
+    next.run(request).await
+}
+
+async fn record_access(iam_db: &LocalDb, caller: &CallerIdentity, action: &str) -> Result<(), sqlx::Error> {
+    let (principal, scope) = match (&caller.principal, &caller.scope) {
+        (Some(principal), Some(scope)) => (principal, scope),
+        // requests which are not bound to a principal are not tracked
+        _ => return Ok(()),
+    };
+
+    let current_time = Utc::now().timestamp();
+    let access = DbIamServiceLastAccess {
+        account_id: caller.account_id,
+        principal_arn: principal.arn.to_owned(),
+        access_key_id: caller.access_key_id.to_owned(),
+        service_namespace: scope.service.to_owned(),
+        action_name: action.to_owned(),
+        region: scope.region.to_owned(),
+        last_accessed_date: current_time,
+    };
+    let mut tx = iam_db.new_tx().await?;
+    repository::iam_service_last_accesses::record(&mut tx, &access).await?;
+    if let Some(role_name) = assumed_role_name(&principal.arn) {
+        repository::iam_service_last_accesses::record_role_usage(
+            &mut tx,
+            caller.account_id,
+            role_name,
+            &scope.region,
+            current_time,
+        )
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
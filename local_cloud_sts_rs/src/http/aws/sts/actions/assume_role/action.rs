@@ -3,6 +3,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use aws_sdk_sts::operation::assume_role::AssumeRoleOutput;
 use aws_sdk_sts::types::AssumedRoleUser;
 
+use local_cloud_common::arn::assumed_role_arn;
 use local_cloud_db::LocalDb;
 
 use crate::http::aws::sts::actions::assume_role::LocalAssumeRole;
@@ -18,9 +19,17 @@ impl LocalAssumeRole {
     ) -> Result<OutputWrapper<AssumeRoleOutput>, StsApiError> {
         let mut tx = db.new_tx().await.expect("failed to BEGIN a new transaction");
         // todo: get role by ARN from IAM
+        let role_arn = self.role_arn().unwrap();
+        let session_name = self.role_session_name().unwrap_or("unknown");
+        let assumed_role_arn = assumed_role_arn(role_arn, session_name).ok_or_else(|| StsApiError {
+            error_code: StsErrorKind::InvalidInput.status_code(),
+            kind: StsErrorKind::InvalidInput,
+            request_id: aws_request_id.to_owned(),
+            message: format!("{role_arn} is not a valid role ARN."),
+        })?;
 
         let assumed_role_user = AssumedRoleUser::builder()
-            .arn(self.role_arn().unwrap())
+            .arn(&assumed_role_arn)
             .assumed_role_id(format!("{}:{}", "AROA", self.role_session_name().unwrap_or("unknown")))
             .build()
             .unwrap();
@@ -38,6 +47,8 @@ impl LocalAssumeRole {
             // TODO: identify region and account from request
             .account_id(account_id)
             .region_id(1)
+            .principal_arn(assumed_role_user.arn())
+            .principal_id(assumed_role_user.assumed_role_id())
             .build();
 
         repository::credentials::create(&mut tx, &mut credentials)
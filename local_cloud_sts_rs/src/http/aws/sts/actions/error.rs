@@ -7,6 +7,7 @@ use crate::http::aws::sts::constants;
 
 #[derive(Debug, Clone)]
 pub enum StsErrorKind {
+    AccessDenied,
     InvalidInput,
     ServiceFailureException,
 }
@@ -14,6 +15,7 @@ pub enum StsErrorKind {
 impl StsErrorKind {
     pub(crate) fn status_code(&self) -> StatusCode {
         match self {
+            StsErrorKind::AccessDenied => StatusCode::FORBIDDEN,
             StsErrorKind::InvalidInput => StatusCode::BAD_REQUEST,
             StsErrorKind::ServiceFailureException => StatusCode::INTERNAL_SERVER_ERROR,
         }
@@ -23,6 +25,7 @@ impl StsErrorKind {
 impl Into<String> for &StsErrorKind {
     fn into(self) -> String {
         match self {
+            StsErrorKind::AccessDenied => String::from("AccessDenied"),
             StsErrorKind::InvalidInput => String::from("InvalidInput"),
             StsErrorKind::ServiceFailureException => String::from("ServiceFailureException"),
         }
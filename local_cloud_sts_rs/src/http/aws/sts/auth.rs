@@ -0,0 +1,71 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use axum::async_trait;
+
+use local_cloud_axum::local::web::{CallerIdentity, CredentialsResolver, Principal, ResolvedCredentials};
+use local_cloud_db::LocalDb;
+
+use crate::http::aws::sts::repository;
+
+/// Resolves temporary credentials issued by STS and long-term access keys of IAM users.
+#[derive(Debug, Clone)]
+pub(crate) struct StsCredentialsResolver {
+    db: LocalDb,
+    iam_db: LocalDb,
+}
+
+impl StsCredentialsResolver {
+    pub(crate) fn new(db: LocalDb, iam_db: LocalDb) -> Self {
+        StsCredentialsResolver { db, iam_db }
+    }
+
+    async fn resolve_iam_access_key(&self, access_key_id: &str) -> Result<Option<ResolvedCredentials>, sqlx::Error> {
+        let mut connection = self.iam_db.new_connection().await?;
+        let access_key =
+            repository::iam_access_keys::find_active_by_access_key_id(connection.as_mut(), access_key_id).await?;
+
+        Ok(access_key.map(|access_key| ResolvedCredentials {
+            secret_access_key: access_key.secret_access_key,
+            session_token: None,
+            identity: CallerIdentity::new(
+                access_key.access_key_id,
+                access_key.account_id,
+                Some(Principal {
+                    id: access_key.user_id,
+                    arn: access_key.user_arn,
+                }),
+            ),
+        }))
+    }
+}
+
+#[async_trait]
+impl CredentialsResolver for StsCredentialsResolver {
+    type Error = sqlx::Error;
+
+    async fn resolve(&self, access_key_id: &str) -> Result<Option<ResolvedCredentials>, Self::Error> {
+        let current_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time went backwards")
+            .as_secs() as i64;
+        let mut connection = self.db.new_connection().await?;
+        let credentials =
+            repository::credentials::find_active_by_access_key_id(connection.as_mut(), access_key_id, current_time)
+                .await?;
+
+        let credentials = match credentials {
+            Some(credentials) => credentials,
+            // not issued by STS, so it could be a long-term access key of an IAM user
+            None => return self.resolve_iam_access_key(access_key_id).await,
+        };
+        let principal = credentials
+            .principal_arn
+            .zip(credentials.principal_id)
+            .map(|(arn, id)| Principal { id, arn });
+        Ok(Some(ResolvedCredentials {
+            secret_access_key: credentials.secret_access_key,
+            session_token: Some(credentials.session_token),
+            identity: CallerIdentity::new(credentials.access_key_id, credentials.account_id, principal),
+        }))
+    }
+}
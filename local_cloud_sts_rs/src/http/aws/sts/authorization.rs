@@ -0,0 +1,185 @@
+use aws_config::BehaviorVersion;
+use aws_credential_types::Credentials;
+use aws_sdk_iam::config::Region;
+use aws_sdk_iam::types::PolicyEvaluationDecisionType;
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use axum::Extension;
+use uuid::Uuid;
+
+use local_cloud_axum::local::web::{CallerIdentity, Principal};
+use local_cloud_common::arn::assumed_role_name;
+use local_cloud_db::LocalDb;
+
+use crate::config::{AppConfig, DEFAULT_IAM_SECRET_ACCESS_KEY};
+use crate::http::aws::sts::actions::error::{StsApiError, StsErrorKind};
+use crate::http::aws::sts::repository;
+use crate::http::aws::sts::request_params::{error_response, RequestParams};
+const IAM_REGION: &str = "eu-local-1";
+
+/// Evaluates policies of STS callers using the `SimulatePrincipalPolicy` action of the IAM service.
+#[derive(Debug, Clone)]
+pub(crate) struct IamPolicyAuthorizer {
+    client: aws_sdk_iam::Client,
+    iam_db: LocalDb,
+}
+
+impl IamPolicyAuthorizer {
+    /// Warns if requests to IAM are signed with the publicly known secret of the `Root` user, the same way IAM does.
+    pub(crate) fn new(app_config: &AppConfig, iam_db: LocalDb) -> Self {
+        if app_config.iam_secret_access_key == DEFAULT_IAM_SECRET_ACCESS_KEY {
+            log::warn!(
+                "Requests to IAM are signed with the publicly known secret access key of {}. \
+                Set STS_IAM_SECRET_ACCESS_KEY to the secret configured with IAM_ROOT_SECRET_ACCESS_KEY.",
+                app_config.iam_access_key_id
+            );
+        }
+        let credentials = Credentials::new(
+            &app_config.iam_access_key_id,
+            &app_config.iam_secret_access_key,
+            None,
+            None,
+            "local_cloud_sts",
+        );
+        let config = aws_sdk_iam::Config::builder()
+            .region(Region::new(IAM_REGION))
+            .endpoint_url(&app_config.iam_endpoint_url)
+            .credentials_provider(credentials)
+            .behavior_version(BehaviorVersion::latest())
+            .build();
+        IamPolicyAuthorizer {
+            client: aws_sdk_iam::Client::from_conf(config),
+            iam_db,
+        }
+    }
+
+    async fn authorize_action(
+        &self, caller: &CallerIdentity, principal: &Principal, action: &str, params: &RequestParams,
+        aws_request_id: &str,
+    ) -> Result<(), StsApiError> {
+        let sts_action = format!("sts:{action}");
+        let role_arn = params.get("RoleArn");
+        let resource = role_arn.unwrap_or("*");
+        let policy_source_arn = self
+            .find_policy_source_arn(caller.account_id, principal)
+            .await
+            .map_err(|error| {
+                log::error!("Failed to find the role of '{}': {}", &principal.arn, error);
+                evaluation_failure(aws_request_id)
+            })?;
+        let decision = match policy_source_arn {
+            Some(policy_source_arn) => {
+                self.simulate(&policy_source_arn, &sts_action, role_arn, aws_request_id)
+                    .await?
+            }
+            // the role of the session doesn't exist anymore
+            None => PolicyEvaluationDecisionType::ImplicitDeny,
+        };
+
+        let reason = match decision {
+            PolicyEvaluationDecisionType::Allowed => return Ok(()),
+            PolicyEvaluationDecisionType::ExplicitDeny => {
+                "with an explicit deny in an identity-based policy".to_owned()
+            }
+            _ => format!("because no identity-based policy allows the {sts_action} action"),
+        };
+        Err(StsApiError {
+            error_code: StsErrorKind::AccessDenied.status_code(),
+            kind: StsErrorKind::AccessDenied,
+            request_id: aws_request_id.to_owned(),
+            message: format!(
+                "User: {} is not authorized to perform: {sts_action} on resource: {resource} {reason}",
+                &principal.arn
+            ),
+        })
+    }
+
+    /// Role sessions are evaluated against the policies of the role, since `SimulatePrincipalPolicy` accepts ARNs of
+    /// IAM users, groups and roles only. Returns `None` if the role of the session doesn't exist.
+    async fn find_policy_source_arn(
+        &self, account_id: i64, principal: &Principal,
+    ) -> Result<Option<String>, sqlx::Error> {
+        let role_name = match assumed_role_name(&principal.arn) {
+            Some(role_name) => role_name,
+            None => return Ok(Some(principal.arn.to_owned())),
+        };
+        let mut connection = self.iam_db.new_connection().await?;
+        let role = repository::roles::find_by_name(connection.as_mut(), account_id, role_name).await?;
+        Ok(role.map(|role| role.arn))
+    }
+
+    async fn simulate(
+        &self, policy_source_arn: &str, sts_action: &str, role_arn: Option<&str>, aws_request_id: &str,
+    ) -> Result<PolicyEvaluationDecisionType, StsApiError> {
+        let response = self
+            .client
+            .simulate_principal_policy()
+            .policy_source_arn(policy_source_arn)
+            .action_names(sts_action)
+            .set_resource_arns(role_arn.map(|arn| vec![arn.to_owned()]))
+            .send()
+            .await;
+
+        let decision = match response {
+            // a single action is simulated, so there is a single evaluation result
+            Ok(output) => output
+                .evaluation_results()
+                .first()
+                .map(|result| result.eval_decision().clone())
+                .unwrap_or(PolicyEvaluationDecisionType::ImplicitDeny),
+            // credentials were issued for a principal which doesn't exist in IAM
+            Err(error) if error.as_service_error().map(|e| e.is_no_such_entity_exception()) == Some(true) => {
+                PolicyEvaluationDecisionType::ImplicitDeny
+            }
+            Err(error) => {
+                log::error!("Failed to evaluate policies of '{}': {:?}", policy_source_arn, error);
+                return Err(evaluation_failure(aws_request_id));
+            }
+        };
+        Ok(decision)
+    }
+}
+
+fn evaluation_failure(aws_request_id: &str) -> StsApiError {
+    StsApiError {
+        error_code: StsErrorKind::ServiceFailureException.status_code(),
+        kind: StsErrorKind::ServiceFailureException,
+        request_id: aws_request_id.to_owned(),
+        message: "Failed to evaluate IAM policies of the caller.".to_owned(),
+    }
+}
+
+/// Middleware which authorizes the action against the IAM policies of the caller before it is executed.
+/// Callers which are not bound to a principal (e.g. unknown keys accepted in `trust any key` mode) are denied.
+pub(crate) async fn authorize(
+    State(authorizer): State<IamPolicyAuthorizer>, Extension(caller): Extension<CallerIdentity>,
+    Extension(params): Extension<RequestParams>, request: Request, next: Next,
+) -> Response {
+    let aws_request_id = Uuid::new_v4().to_string();
+    let principal = match &caller.principal {
+        None => {
+            return error_response(StsApiError {
+                error_code: StsErrorKind::AccessDenied.status_code(),
+                kind: StsErrorKind::AccessDenied,
+                request_id: aws_request_id,
+                message: format!(
+                    "Access key {} is not bound to an IAM principal, so IAM policies can't be evaluated.",
+                    &caller.access_key_id
+                ),
+            })
+        }
+        Some(principal) => principal,
+    };
+
+    if let Some(action) = params.action() {
+        if let Err(error) = authorizer
+            .authorize_action(&caller, principal, action, &params, &aws_request_id)
+            .await
+        {
+            return error_response(error);
+        }
+    }
+
+    next.run(request).await
+}
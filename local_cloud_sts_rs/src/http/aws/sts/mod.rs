@@ -1,17 +1,22 @@
 use axum::extract::State;
 use axum::http::{Response, StatusCode};
+use axum::Extension;
 use serde::Deserialize;
 use uuid::Uuid;
 
-use local_cloud_axum::local::web::{AwsQueryBody, XmlResponse};
+use local_cloud_axum::local::web::{AwsQueryBody, CallerIdentity, XmlResponse};
 use local_cloud_db::LocalDb;
 
 use crate::http::aws::sts::actions::assume_role::LocalAssumeRole;
 use crate::http::aws::sts::actions::error::StsApiError;
 
+pub(crate) mod access_tracking;
 pub(crate) mod actions;
+pub(crate) mod auth;
+pub(crate) mod authorization;
 pub(crate) mod constants;
 pub(crate) mod repository;
+pub(crate) mod request_params;
 pub(crate) mod types;
 
 #[derive(Deserialize, Debug)]
@@ -24,9 +29,10 @@ pub(crate) enum LocalAwsRequest {
 const CONTENT_TYPE_HEADER: &str = "Content-Type";
 const CONTENT_TYPE_HEADER_VALUE: &str = "text/xml; charset=utf-8";
 
-pub(crate) async fn handle(State(db): State<LocalDb>, aws_query: AwsQueryBody<LocalAwsRequest>) -> Response<String> {
-    // TODO: populate account ID from token
-    let acc_id = 1i64;
+pub(crate) async fn handle(
+    State(db): State<LocalDb>, Extension(caller): Extension<CallerIdentity>, aws_query: AwsQueryBody<LocalAwsRequest>,
+) -> Response<String> {
+    let acc_id = caller.account_id;
     let aws_request = aws_query.into_inner();
     let aws_request_id = Uuid::new_v4().to_string();
     let output: Result<XmlResponse, StsApiError> = (match aws_request {
@@ -1,13 +1,14 @@
 use sqlx::sqlite::SqliteRow;
-use sqlx::{Error, Row, Sqlite, Transaction};
+use sqlx::{Error, Executor, FromRow, Row, Sqlite, Transaction};
 
 use crate::http::aws::sts::types::credentials::DbCredentials;
 
 pub async fn create<'a>(tx: &mut Transaction<'a, Sqlite>, credentials: &mut DbCredentials) -> Result<(), Error> {
     let result = sqlx::query(
         r#"insert into credentials
-            (access_key_id, secret_access_key, session_token, expiration, account_id, region_id)
-            values ($1, $2, $3, $4, $5, $6) returning id"#,
+            (access_key_id, secret_access_key, session_token, expiration, account_id, region_id, principal_arn,
+                principal_id)
+            values ($1, $2, $3, $4, $5, $6, $7, $8) returning id"#,
     )
     .bind(&credentials.access_key_id)
     .bind(&credentials.secret_access_key)
@@ -15,6 +16,8 @@ pub async fn create<'a>(tx: &mut Transaction<'a, Sqlite>, credentials: &mut DbCr
     .bind(credentials.expiration)
     .bind(credentials.account_id)
     .bind(credentials.region_id)
+    .bind(&credentials.principal_arn)
+    .bind(&credentials.principal_id)
     .map(|row: SqliteRow| row.get::<i64, &str>("id"))
     .fetch_one(tx.as_mut())
     .await?;
@@ -22,3 +25,24 @@ pub async fn create<'a>(tx: &mut Transaction<'a, Sqlite>, credentials: &mut DbCr
     credentials.id = Some(result);
     Ok(())
 }
+
+/// Finds credentials which are not expired yet by access key ID.
+pub async fn find_active_by_access_key_id<'a, E>(
+    executor: E, access_key_id: &str, current_time: i64,
+) -> Result<Option<DbCredentials>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"select id, access_key_id, secret_access_key, session_token, expiration, account_id, region_id,
+                principal_arn, principal_id
+            from credentials
+            where access_key_id = $1 and expiration > $2"#,
+    )
+    .bind(access_key_id)
+    .bind(current_time)
+    .map(|row: SqliteRow| DbCredentials::from_row(&row).unwrap())
+    .fetch_optional(executor)
+    .await?;
+    Ok(result)
+}
@@ -0,0 +1,27 @@
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Error, Executor, FromRow, Sqlite};
+
+use crate::http::aws::sts::types::iam_access_keys::DbIamAccessKey;
+
+/// Status of active access keys in the `access_keys` table of the IAM service.
+const ACTIVE_STATUS: i32 = 1;
+
+/// Finds an active access key of an IAM user by access key ID in the database of the IAM service.
+pub async fn find_active_by_access_key_id<'a, E>(
+    executor: E, access_key_id: &str,
+) -> Result<Option<DbIamAccessKey>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query(
+        r#"select ak.access_key_id, ak.secret_access_key, ak.account_id, u.user_id as user_id, u.arn as user_arn
+            from access_keys ak join users u on ak.user_id = u.id
+            where ak.access_key_id = $1 and ak.status = $2"#,
+    )
+    .bind(access_key_id)
+    .bind(ACTIVE_STATUS)
+    .map(|row: SqliteRow| DbIamAccessKey::from_row(&row).unwrap())
+    .fetch_optional(executor)
+    .await?;
+    Ok(result)
+}
@@ -0,0 +1,84 @@
+use sqlx::{Error, Sqlite, Transaction};
+
+use crate::http::aws::sts::types::iam_service_last_accesses::DbIamServiceLastAccess;
+
+/// Records the access of the principal to the service action, and the last usage of the access key and the user in
+/// the database of the IAM service. Regions which are unknown to the `regions` table are not recorded.
+pub async fn record<'a>(tx: &mut Transaction<'a, Sqlite>, access: &DbIamServiceLastAccess) -> Result<(), Error> {
+    sqlx::query(
+        r#"insert into service_last_accesses
+                (account_id, principal_arn, service_namespace, action_name, region_id, last_accessed_date)
+            values ($1, $2, $3, $4, (select id from regions where region = $5), $6)
+            on conflict (principal_arn, service_namespace, action_name) do update set
+                region_id = excluded.region_id,
+                last_accessed_date = excluded.last_accessed_date"#,
+    )
+    .bind(access.account_id)
+    .bind(&access.principal_arn)
+    .bind(&access.service_namespace)
+    .bind(&access.action_name)
+    .bind(&access.region)
+    .bind(access.last_accessed_date)
+    .execute(tx.as_mut())
+    .await?;
+
+    // temporary credentials issued by STS are not stored in the `access_keys` table
+    sqlx::query(
+        r#"update access_keys set
+                last_used_date = $2,
+                last_used_region_id = (select id from regions where region = $3),
+                last_used_service = $4
+            where access_key_id = $1"#,
+    )
+    .bind(&access.access_key_id)
+    .bind(access.last_accessed_date)
+    .bind(&access.region)
+    .bind(&access.service_namespace)
+    .execute(tx.as_mut())
+    .await?;
+
+    sqlx::query("update users set last_used_date = $3 where account_id = $1 and arn = $2")
+        .bind(access.account_id)
+        .bind(&access.principal_arn)
+        .bind(access.last_accessed_date)
+        .execute(tx.as_mut())
+        .await?;
+    Ok(())
+}
+
+/// Records the last usage of the role and instance profiles which contain the role in the database of the IAM
+/// service.
+pub async fn record_role_usage<'a>(
+    tx: &mut Transaction<'a, Sqlite>, account_id: i64, role_name: &str, region: &str, last_used_date: i64,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"update roles set
+                last_used_date = $3,
+                last_used_region_id = (select id from regions where region = $4)
+            where account_id = $1 and unique_role_name = $2"#,
+    )
+    .bind(account_id)
+    .bind(role_name.to_uppercase())
+    .bind(last_used_date)
+    .bind(region)
+    .execute(tx.as_mut())
+    .await?;
+
+    sqlx::query(
+        r#"update instance_profiles set
+                last_used_date = $3,
+                last_used_region_id = (select id from regions where region = $4)
+            where id in (
+                select ipr.instance_profile_id
+                from instance_profile_roles ipr left join roles r on ipr.role_id = r.id
+                where r.account_id = $1 and r.unique_role_name = $2
+            )"#,
+    )
+    .bind(account_id)
+    .bind(role_name.to_uppercase())
+    .bind(last_used_date)
+    .bind(region)
+    .execute(tx.as_mut())
+    .await?;
+    Ok(())
+}
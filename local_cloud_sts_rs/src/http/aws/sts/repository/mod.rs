@@ -1 +1,4 @@
 pub(crate) mod credentials;
+pub(crate) mod iam_access_keys;
+pub(crate) mod iam_service_last_accesses;
+pub(crate) mod roles;
@@ -0,0 +1,18 @@
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Error, Executor, FromRow, Sqlite};
+
+use crate::http::aws::sts::types::roles::DbStsRole;
+
+/// Finds a role by name in the database of the IAM service. Role names are case-insensitive.
+pub async fn find_by_name<'a, E>(executor: E, account_id: i64, role_name: &str) -> Result<Option<DbStsRole>, Error>
+where
+    E: 'a + Executor<'a, Database = Sqlite>,
+{
+    let result = sqlx::query("select arn from roles where account_id = $1 and unique_role_name = $2")
+        .bind(account_id)
+        .bind(role_name.to_uppercase())
+        .map(|row: SqliteRow| DbStsRole::from_row(&row).unwrap())
+        .fetch_optional(executor)
+        .await?;
+    Ok(result)
+}
@@ -10,6 +10,10 @@ pub struct DbCredentials {
     pub expiration: i64, // in seconds
     pub account_id: i64,
     pub region_id: i64,
+    /// ARN of the principal the credentials were issued for, e.g. the assumed role.
+    pub principal_arn: Option<String>,
+    /// Unique ID of the principal the credentials were issued for, e.g. `AROA...:<session name>`.
+    pub principal_id: Option<String>,
 }
 
 impl DbCredentials {
@@ -38,6 +42,8 @@ pub struct DbCredentialsBuilder {
     pub(crate) expiration: Option<i64>,
     pub(crate) account_id: Option<i64>,
     pub(crate) region_id: Option<i64>,
+    pub(crate) principal_arn: Option<String>,
+    pub(crate) principal_id: Option<String>,
 }
 
 impl DbCredentialsBuilder {
@@ -75,6 +81,16 @@ impl DbCredentialsBuilder {
         self
     }
 
+    pub fn principal_arn(mut self, input: impl Into<std::string::String>) -> Self {
+        self.principal_arn = Some(input.into());
+        self
+    }
+
+    pub fn principal_id(mut self, input: impl Into<std::string::String>) -> Self {
+        self.principal_id = Some(input.into());
+        self
+    }
+
     /// Consumes the builder and constructs a [`Credentials`](crate::types::Credentials).
     pub fn build(self) -> DbCredentials {
         DbCredentials {
@@ -85,6 +101,8 @@ impl DbCredentialsBuilder {
             expiration: self.expiration.unwrap(),
             account_id: self.account_id.unwrap(),
             region_id: self.region_id.unwrap(),
+            principal_arn: self.principal_arn,
+            principal_id: self.principal_id,
         }
     }
 }
@@ -0,0 +1,12 @@
+use sqlx::FromRow;
+
+/// Long-term access key of an IAM user stored in the database of the IAM service.
+#[derive(Clone, FromRow, Debug)]
+pub struct DbIamAccessKey {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub account_id: i64,
+    /// Unique ID of the user, e.g. `AIDA...`.
+    pub user_id: String,
+    pub user_arn: String,
+}
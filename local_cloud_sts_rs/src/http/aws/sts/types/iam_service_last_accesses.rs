@@ -0,0 +1,12 @@
+/// Access of a principal to a service action which is recorded in the database of the IAM service, so that it is
+/// reported by `GetAccessKeyLastUsed`, `GetRole` and service last accessed details.
+#[derive(Clone, Debug)]
+pub struct DbIamServiceLastAccess {
+    pub account_id: i64,
+    pub principal_arn: String,
+    pub access_key_id: String,
+    pub service_namespace: String,
+    pub action_name: String,
+    pub region: String,
+    pub last_accessed_date: i64,
+}
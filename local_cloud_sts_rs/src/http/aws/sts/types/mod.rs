@@ -1,4 +1,6 @@
 pub(crate) mod accounts;
 pub(crate) mod credentials;
+pub(crate) mod iam_access_keys;
+pub(crate) mod iam_service_last_accesses;
 pub(crate) mod regions;
 pub(crate) mod roles;
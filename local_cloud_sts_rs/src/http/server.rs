@@ -1,20 +1,53 @@
 use crate::config::AppConfig;
 use axum::routing::post;
-use axum::Router;
+use axum::{middleware, Router};
+
+use local_cloud_axum::local::web::sigv4;
+use local_cloud_axum::local::web::SigV4Auth;
 
 use crate::http::aws;
+use crate::http::aws::sts::access_tracking;
+use crate::http::aws::sts::auth::StsCredentialsResolver;
+use crate::http::aws::sts::authorization;
+use crate::http::aws::sts::authorization::IamPolicyAuthorizer;
+use crate::http::aws::sts::constants;
+use crate::http::aws::sts::request_params;
 
 pub(crate) async fn router(app_config: &AppConfig) -> std::io::Result<Router> {
-    let iam_db = local_cloud_db::LocalDb::new(&app_config.database_url, &sqlx::migrate!())
+    let sts_db = local_cloud_db::LocalDb::new(&app_config.database_url, &sqlx::migrate!())
         .await
         .map_err(|err| {
             log::error!("Failed to setup DB: {}", err);
             err
         })
         .unwrap();
+    // the schema of the IAM database is owned by the IAM service
+    let iam_db = local_cloud_db::LocalDb::connect(&app_config.iam_database_url)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to connect to IAM DB: {}", err);
+            err
+        })
+        .unwrap();
+
+    let auth = SigV4Auth::new(StsCredentialsResolver::new(sts_db.clone(), iam_db.clone()), constants::xml::STS_XMLNS)
+        .trust_any_key(app_config.trust_any_access_key);
 
     // setting up HTTP Router
-    let app = Router::new().route("/sts/", post(aws::sts::handle)).with_state(iam_db);
+    let mut app = Router::new().route("/sts/", post(aws::sts::handle));
+    if app_config.enforce_iam_policies {
+        app = app.route_layer(middleware::from_fn_with_state(
+            IamPolicyAuthorizer::new(app_config, iam_db.clone()),
+            authorization::authorize,
+        ));
+    }
+    // runs before authorization, so that denied requests are tracked as well
+    let app = app
+        .route_layer(middleware::from_fn_with_state(iam_db, access_tracking::track))
+        .route_layer(middleware::from_fn_with_state(auth, sigv4::authenticate::<StsCredentialsResolver>))
+        // runs first: rejects too large requests and parses the parameters once for tracking and authorization
+        .route_layer(middleware::from_fn(request_params::parse))
+        .with_state(sts_db);
 
     Ok(app)
 }
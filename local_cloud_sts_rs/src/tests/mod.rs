@@ -4,7 +4,10 @@ mod test_suite;
 
 #[cfg(test)]
 mod assume_role;
+#[cfg(test)]
+mod authorization;
 
+/// Bootstrap access key of the `Root` IAM user.
 pub fn credentials_provider() -> impl ProvideCredentials {
-    aws_credential_types::Credentials::new("access_key_id", "secret_access_key", None, None, "provider_name")
+    aws_credential_types::Credentials::new("AKIAIOSFODNN201ADMIN", "secret_access_key", None, None, "provider_name")
 }
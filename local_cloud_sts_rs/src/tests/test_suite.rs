@@ -1,12 +1,84 @@
 use uuid::Uuid;
 
+use local_cloud_db::LocalDb;
+
 use crate::config::AppConfig;
 
 pub(crate) async fn start_server(port: u16) -> std::io::Result<axum::Router> {
-    let db_file_name = Uuid::new_v4();
+    let iam_db = create_iam_db().await;
+    start_server_with_iam_db(port, &iam_db).await
+}
+
+/// Starts the server connected to the IAM database which is owned by the test.
+pub(crate) async fn start_server_with_iam_db(port: u16, iam_db: &IamDb) -> std::io::Result<axum::Router> {
+    crate::http::server::router(&test_config(port, iam_db)).await
+}
+
+/// Starts the server which authorizes requests with the IAM service at `iam_endpoint_url`. The IAM database has the
+/// `rd_role` role, so that sessions of the role can be authorized.
+pub(crate) async fn start_server_enforcing_iam_policies(
+    port: u16, iam_endpoint_url: String,
+) -> std::io::Result<axum::Router> {
+    let iam_db = create_iam_db().await;
+    create_role(&iam_db, "rd_role").await;
+    let app_config = AppConfig {
+        enforce_iam_policies: true,
+        iam_endpoint_url,
+        ..test_config(port, &iam_db)
+    };
+    crate::http::server::router(&app_config).await
+}
+
+/// Same as [`start_server_enforcing_iam_policies`] but accepts requests signed with unknown access keys.
+pub(crate) async fn start_server_trusting_any_access_key(
+    port: u16, iam_endpoint_url: String,
+) -> std::io::Result<axum::Router> {
+    let iam_db = create_iam_db().await;
+    create_role(&iam_db, "rd_role").await;
     let app_config = AppConfig {
-        database_url: format!("file:{}?mode=memory&cache=shared", db_file_name),
-        service_port: port,
+        trust_any_access_key: true,
+        enforce_iam_policies: true,
+        iam_endpoint_url,
+        ..test_config(port, &iam_db)
     };
     crate::http::server::router(&app_config).await
 }
+
+/// In-memory database of the IAM service with the `Root` user and its bootstrap access key. The database exists while
+/// it has open connections, so it must not be dropped before the server is connected to it.
+pub(crate) struct IamDb {
+    url: String,
+    pub(crate) db: LocalDb,
+}
+
+pub(crate) async fn create_iam_db() -> IamDb {
+    let url = format!("file:{}?mode=memory&cache=shared", Uuid::new_v4());
+    let db = LocalDb::new(&url, &sqlx::migrate!("../local_cloud_iam_rs/migrations"))
+        .await
+        .expect("failed to setup IAM DB");
+    IamDb { url, db }
+}
+
+fn test_config(port: u16, iam_db: &IamDb) -> AppConfig {
+    let db_file_name = Uuid::new_v4();
+    AppConfig::with_params(format!("file:{}?mode=memory&cache=shared", db_file_name), &iam_db.url, port)
+}
+
+/// Creates a role in the account of the `Root` user in the database of the IAM service and returns its ARN.
+pub(crate) async fn create_role(iam_db: &IamDb, role_name: &str) -> String {
+    let arn = format!("arn:aws:iam::000000000001:role/{role_name}");
+    sqlx::query(
+        r#"insert into roles
+                (account_id, role_name, unique_role_name, max_session_duration, assume_role_policy_document, arn,
+                 path, role_id, create_date)
+            values (1, $1, $2, 3600, '{}', $3, '/', $4, 0)"#,
+    )
+    .bind(role_name)
+    .bind(role_name.to_uppercase())
+    .bind(&arn)
+    .bind(format!("AROA{}", role_name.to_uppercase()))
+    .execute(iam_db.db.new_connection().await.unwrap().as_mut())
+    .await
+    .expect("failed to create IAM role");
+    arn
+}
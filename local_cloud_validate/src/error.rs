@@ -13,6 +13,7 @@ pub enum ValidationErrorKind {
     LengthMax,
     RegExp,
     Allowed,
+    PolicyDocument,
     Other,
 }
 